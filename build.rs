@@ -0,0 +1,64 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Generates `$OUT_DIR/embedded_web_assets.rs`.
+///
+/// With the `embed-web` feature the generated table `include_bytes!`s every
+/// file under `web/dist`; otherwise it is empty and the server keeps serving
+/// the SPA from `STATIC_DIR`.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=CARGO_FEATURE_EMBED_WEB");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    let mut entries = Vec::new();
+    if env::var_os("CARGO_FEATURE_EMBED_WEB").is_some() {
+        let manifest_dir =
+            PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set"));
+        let dist_dir = manifest_dir.join("web").join("dist");
+        println!("cargo:rerun-if-changed={}", dist_dir.display());
+        if !dist_dir.join("index.html").is_file() {
+            panic!(
+                "the embed-web feature requires a built SPA at {}; run the web build first",
+                dist_dir.display()
+            );
+        }
+        collect_assets(&dist_dir, &dist_dir, &mut entries);
+        entries.sort();
+    }
+
+    let mut generated =
+        String::from("pub(crate) static EMBEDDED_WEB_ASSETS: &[(&str, &[u8])] = &[\n");
+    for (key, path) in &entries {
+        generated.push_str(&format!(
+            "    ({key:?}, include_bytes!({:?})),\n",
+            path.display().to_string()
+        ));
+    }
+    generated.push_str("];\n");
+    fs::write(out_dir.join("embedded_web_assets.rs"), generated)
+        .expect("write embedded web asset table");
+}
+
+fn collect_assets(root: &Path, dir: &Path, entries: &mut Vec<(String, PathBuf)>) {
+    let read_dir =
+        fs::read_dir(dir).unwrap_or_else(|err| panic!("failed to read {}: {err}", dir.display()));
+    for entry in read_dir {
+        let path = entry.expect("read web/dist entry").path();
+        if path.is_dir() {
+            collect_assets(root, &path, entries);
+            continue;
+        }
+        println!("cargo:rerun-if-changed={}", path.display());
+        let key = path
+            .strip_prefix(root)
+            .expect("asset is under web/dist")
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        entries.push((key, path));
+    }
+}
@@ -6,7 +6,7 @@ use sqlx::FromRow;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 use tokio::sync::broadcast;
-use tracing::warn;
+use tracing::{debug, warn};
 
 pub(crate) fn align_reporting_bucket_epoch(
     epoch: i64,
@@ -626,6 +626,7 @@ pub(crate) struct ErrorQuery {
 #[derive(serde::Serialize)]
 pub(crate) struct ErrorDistributionItem {
     pub(crate) reason: String,
+    pub(crate) category: ErrorCategory,
     pub(crate) count: i64,
 }
 
@@ -661,6 +662,214 @@ pub(crate) struct OtherErrorsResponse {
     pub(crate) items: Vec<OtherErrorItem>,
 }
 
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ErrorSampleQuery {
+    pub(crate) category: String,
+    pub(crate) range: String,
+    pub(crate) limit: Option<i64>,
+    pub(crate) scope: Option<String>,
+    pub(crate) time_zone: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct ErrorSampleResponse {
+    pub(crate) reason: String,
+    pub(crate) category: ErrorCategory,
+    pub(crate) items: Vec<OtherErrorItem>,
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct ErrorSummaryItem {
+    pub(crate) reason: String,
+    pub(crate) category: ErrorCategory,
+    pub(crate) count: i64,
+    pub(crate) samples: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct ErrorSummaryResponse {
+    pub(crate) range_start: String,
+    pub(crate) range_end: String,
+    pub(crate) items: Vec<ErrorSummaryItem>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelEfficiencyQuery {
+    #[serde(default = "default_range")]
+    pub(crate) range: String,
+    pub(crate) time_zone: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelEfficiencyItem {
+    pub(crate) model: String,
+    pub(crate) total_cost: f64,
+    pub(crate) total_tokens: i64,
+    #[sqlx(skip)]
+    pub(crate) cost_per_1k_tokens: Option<f64>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelEfficiencyResponse {
+    pub(crate) range_start: String,
+    pub(crate) range_end: String,
+    pub(crate) items: Vec<ModelEfficiencyItem>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CacheSavingsQuery {
+    #[serde(default = "default_range")]
+    pub(crate) range: String,
+    pub(crate) time_zone: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CacheSavingsResponse {
+    pub(crate) range_start: String,
+    pub(crate) range_end: String,
+    pub(crate) cached_tokens: i64,
+    pub(crate) total_input_tokens: i64,
+    pub(crate) cache_hit_rate: Option<f64>,
+    pub(crate) cache_discount: f64,
+    pub(crate) estimated_savings: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HourlyProfileItem {
+    pub(crate) hour: u32,
+    pub(crate) avg_count: f64,
+    pub(crate) avg_cost: f64,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HourlyProfileQuery {
+    #[serde(default = "default_range")]
+    pub(crate) range: String,
+    pub(crate) time_zone: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HourlyProfileResponse {
+    pub(crate) range_start: String,
+    pub(crate) range_end: String,
+    pub(crate) active_days: i64,
+    pub(crate) items: Vec<HourlyProfileItem>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StreakRun {
+    /// `success` or `failure`; `None` when the range has no finished invocations.
+    pub(crate) status: Option<&'static str>,
+    pub(crate) count: i64,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StreakQuery {
+    #[serde(default = "default_range")]
+    pub(crate) range: String,
+    pub(crate) time_zone: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StreakResponse {
+    pub(crate) range_start: String,
+    pub(crate) range_end: String,
+    pub(crate) current_streak: StreakRun,
+    pub(crate) longest_success: i64,
+    pub(crate) longest_failure: i64,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScatterQuery {
+    #[serde(default = "default_range")]
+    pub(crate) range: String,
+    pub(crate) time_zone: Option<String>,
+    pub(crate) limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScatterPoint {
+    pub(crate) total_tokens: i64,
+    pub(crate) cost: f64,
+    pub(crate) model: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BudgetQuery {
+    pub(crate) time_zone: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BillingPeriodQuery {
+    pub(crate) time_zone: Option<String>,
+    pub(crate) periods: Option<i64>,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BillingPeriodTotals {
+    pub(crate) period_start: String,
+    pub(crate) period_end: String,
+    pub(crate) total_cost: f64,
+    pub(crate) total_requests: i64,
+    /// `quota_resets` or `calendar`; see `fetch_billing_period_totals`.
+    pub(crate) source: &'static str,
+}
+
+pub(crate) const BILLING_PERIOD_SOURCE_QUOTA_RESETS: &str = "quota_resets";
+pub(crate) const BILLING_PERIOD_SOURCE_CALENDAR: &str = "calendar";
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BudgetResponse {
+    pub(crate) period_start: String,
+    pub(crate) period_end: String,
+    pub(crate) budget: Option<f64>,
+    pub(crate) spent_this_period: f64,
+    pub(crate) remaining: Option<f64>,
+    pub(crate) projected_end_of_period: f64,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StatsComparisonQuery {
+    #[serde(default = "default_range")]
+    pub(crate) range: String,
+    pub(crate) time_zone: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StatsComparisonResponse {
+    pub(crate) range_start: String,
+    pub(crate) range_end: String,
+    pub(crate) previous_range_start: String,
+    pub(crate) previous_range_end: String,
+    pub(crate) current: StatsResponse,
+    pub(crate) previous: StatsResponse,
+    /// Percentage changes versus the previous period; `None` when the previous
+    /// value is zero.
+    pub(crate) count_change_pct: Option<f64>,
+    pub(crate) cost_change_pct: Option<f64>,
+    pub(crate) tokens_change_pct: Option<f64>,
+}
+
 #[derive(serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct FailureSummaryQuery {
@@ -719,13 +928,13 @@ pub(crate) async fn fetch_error_distribution(
     Query(params): Query<ErrorQuery>,
 ) -> Result<Json<ErrorDistributionResponse>, ApiError> {
     let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
-    let range_window = resolve_range_window(&params.range, reporting_tz)?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
     let start_dt = range_window.start;
     let display_end = range_window.display_end;
     let scope = FailureScope::parse(params.scope.as_deref())?;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
     if start_dt < shanghai_retention_cutoff(state.config.invocation_max_days) {
-        let mut counts: HashMap<String, i64> = HashMap::new();
+        let mut counts: HashMap<ErrorCategory, i64> = HashMap::new();
         let range_plan = build_hourly_rollup_exact_range_plan(
             start_dt,
             display_end,
@@ -784,7 +993,9 @@ pub(crate) async fn fetch_error_distribution(
             if !failure_scope_matches(scope, class) {
                 continue;
             }
-            *counts.entry(row.error_category).or_default() += row.failure_count;
+            *counts
+                .entry(ErrorCategory::from_persisted(&row.error_category))
+                .or_default() += row.failure_count;
         }
         for record in exact_records {
             let classification = resolve_failure_classification(
@@ -798,7 +1009,7 @@ pub(crate) async fn fetch_error_distribution(
                 continue;
             }
             let raw = record.error_message.unwrap_or_default();
-            let key = categorize_error(&raw);
+            let key = categorize_error_with_http_status(&raw, record.http_status);
             *counts.entry(key).or_default() += 1;
         }
         if let Some((range_start_epoch, range_end_epoch)) = range_plan.full_hour_range {
@@ -834,17 +1045,21 @@ pub(crate) async fn fetch_error_distribution(
                     continue;
                 }
                 let raw = row.error_message.unwrap_or_default();
-                let key = categorize_error(&raw);
+                let key = categorize_error_with_http_status(&raw, row.http_status);
                 *counts.entry(key).or_default() += 1;
             }
         }
         let mut items: Vec<ErrorDistributionItem> = counts
             .into_iter()
-            .map(|(reason, count)| ErrorDistributionItem { reason, count })
+            .map(|(category, count)| ErrorDistributionItem {
+                reason: category.label(),
+                category,
+                count,
+            })
             .collect();
         items.sort_by_key(|item| std::cmp::Reverse(item.count));
         if let Some(top) = params.top {
-            let limited = top.clamp(1, 50) as usize;
+            let limited = top.clamp(1, state.config.error_top_max as i64) as usize;
             if items.len() > limited {
                 items.truncate(limited);
             }
@@ -860,13 +1075,14 @@ pub(crate) async fn fetch_error_distribution(
     struct RawErr {
         status: Option<String>,
         error_message: Option<String>,
+        http_status: Option<i64>,
         failure_kind: Option<String>,
         failure_class: Option<String>,
         is_actionable: Option<i64>,
     }
 
     let mut query = QueryBuilder::new(
-        "SELECT status, error_message, failure_kind, failure_class, is_actionable FROM codex_invocations WHERE occurred_at >= ",
+        "SELECT status, error_message, http_status, failure_kind, failure_class, is_actionable FROM codex_invocations WHERE occurred_at >= ",
     );
     query.push_bind(db_occurred_at_lower_bound(start_dt));
     if source_scope == InvocationSourceScope::ProxyOnly {
@@ -875,7 +1091,7 @@ pub(crate) async fn fetch_error_distribution(
     query.push(" AND (status IS NULL OR status != 'success')");
     let rows: Vec<RawErr> = query.build_query_as().fetch_all(&state.pool).await?;
 
-    let mut counts: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
+    let mut counts: HashMap<ErrorCategory, i64> = HashMap::new();
     for r in rows {
         let classification = resolve_failure_classification(
             r.status.as_deref(),
@@ -888,17 +1104,21 @@ pub(crate) async fn fetch_error_distribution(
             continue;
         }
         let raw = r.error_message.unwrap_or_default();
-        let key = categorize_error(&raw);
+        let key = categorize_error_with_http_status(&raw, r.http_status);
         *counts.entry(key).or_insert(0) += 1;
     }
 
     let mut items: Vec<ErrorDistributionItem> = counts
         .into_iter()
-        .map(|(reason, count)| ErrorDistributionItem { reason, count })
+        .map(|(category, count)| ErrorDistributionItem {
+            reason: category.label(),
+            category,
+            count,
+        })
         .collect();
     items.sort_by_key(|item| std::cmp::Reverse(item.count));
     if let Some(top) = params.top {
-        let limited = top.clamp(1, 50) as usize;
+        let limited = top.clamp(1, state.config.error_top_max as i64) as usize;
         if items.len() > limited {
             items.truncate(limited);
         }
@@ -911,55 +1131,206 @@ pub(crate) async fn fetch_error_distribution(
     }))
 }
 
+/// Structured error bucket used by the error distribution endpoints.
+///
+/// `code()` is the stable machine key and the value persisted in
+/// `invocation_failure_rollup_hourly.error_category`; `label()` is the legacy
+/// display string, which older rollup rows still carry. Codes derived from
+/// upstream text (`upstream_type:`, `message:`) are prefixed so they never
+/// collide with the fixed category codes, and the label is always rendered from
+/// the parsed code rather than stored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ErrorCategory {
+    HttpServer(u16),
+    HttpClient(u16),
+    RateLimited,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    UsageLimit,
+    UsageNotIncluded,
+    JsonError(String),
+    Message(String),
+    Other,
+}
+
+impl ErrorCategory {
+    /// Stable machine key for clients (i18n, coloring).
+    pub(crate) fn code(&self) -> String {
+        match self {
+            Self::HttpServer(code) | Self::HttpClient(code) => format!("http_{code}"),
+            Self::RateLimited => "rate_limited".to_string(),
+            Self::Unauthorized => "unauthorized".to_string(),
+            Self::Forbidden => "forbidden".to_string(),
+            Self::NotFound => "not_found".to_string(),
+            Self::UsageLimit => "usage_limit_reached".to_string(),
+            Self::UsageNotIncluded => "usage_not_included".to_string(),
+            Self::JsonError(ty) => format!("upstream_type:{ty}"),
+            Self::Message(text) => format!("message:{text}"),
+            Self::Other => "other".to_string(),
+        }
+    }
+
+    /// Inverse of [`ErrorCategory::code`].
+    pub(crate) fn from_code(code: &str) -> Option<Self> {
+        if let Some(ty) = code.strip_prefix("upstream_type:") {
+            return Some(Self::JsonError(ty.to_string()));
+        }
+        if let Some(text) = code.strip_prefix("message:") {
+            return Some(Self::Message(text.to_string()));
+        }
+        if let Some(status) = code
+            .strip_prefix("http_")
+            .and_then(|raw| raw.parse::<u16>().ok())
+        {
+            return Some(if status >= 501 {
+                Self::HttpServer(status)
+            } else {
+                Self::HttpClient(status)
+            });
+        }
+        match code {
+            "rate_limited" => Some(Self::RateLimited),
+            "unauthorized" => Some(Self::Unauthorized),
+            "forbidden" => Some(Self::Forbidden),
+            "not_found" => Some(Self::NotFound),
+            "usage_limit_reached" => Some(Self::UsageLimit),
+            "usage_not_included" => Some(Self::UsageNotIncluded),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+
+    /// Reads a persisted `error_category`: new rows store the code, rows written
+    /// before codes were persisted still hold the label.
+    pub(crate) fn from_persisted(value: &str) -> Self {
+        Self::from_code(value).unwrap_or_else(|| Self::from_label(value))
+    }
+
+    pub(crate) fn label(&self) -> String {
+        match self {
+            Self::HttpServer(code) => format!("HTTP {code}"),
+            Self::HttpClient(429) => "http_429".to_string(),
+            Self::HttpClient(code) => format!("HTTP {code}"),
+            Self::RateLimited => "too_many_requests".to_string(),
+            Self::Unauthorized => "unauthorized".to_string(),
+            Self::Forbidden => "forbidden".to_string(),
+            Self::NotFound => "not_found".to_string(),
+            Self::UsageLimit => "usage_limit_reached".to_string(),
+            Self::UsageNotIncluded => "usage_not_included".to_string(),
+            Self::JsonError(ty) => ty.clone(),
+            Self::Message(text) => text.clone(),
+            Self::Other => "Other".to_string(),
+        }
+    }
+
+    /// Best-effort parse of a legacy label; only used for rollup rows that predate
+    /// persisted codes and for `category` query values that are not codes.
+    pub(crate) fn from_label(label: &str) -> Self {
+        match label {
+            "" | "Other" => return Self::Other,
+            "http_429" => return Self::HttpClient(429),
+            "too_many_requests" => return Self::RateLimited,
+            "unauthorized" => return Self::Unauthorized,
+            "forbidden" => return Self::Forbidden,
+            "not_found" => return Self::NotFound,
+            "usage_limit_reached" => return Self::UsageLimit,
+            "usage_not_included" => return Self::UsageNotIncluded,
+            _ => {}
+        }
+        if let Some(code) = label
+            .strip_prefix("HTTP ")
+            .and_then(|raw| raw.parse::<u16>().ok())
+        {
+            return if code >= 501 {
+                Self::HttpServer(code)
+            } else {
+                Self::HttpClient(code)
+            };
+        }
+        // JSON error types are bare identifiers; normalized messages always contain spaces.
+        if label.chars().any(char::is_whitespace) {
+            Self::Message(label.to_string())
+        } else {
+            Self::JsonError(label.to_string())
+        }
+    }
+}
+
+impl serde::Serialize for ErrorCategory {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("ErrorCategory", 2)?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("label", &self.label())?;
+        state.end()
+    }
+}
+
 // Classify error message by rules:
 // - If contains HTTP code >= 501, group as "HTTP <code>"
 // - If 4xx: try to extract concrete type (json error.type or regex phrases); otherwise "HTTP <code>"
 // - Otherwise: normalize message and if still not matched, return "Other"
-pub(crate) fn categorize_error(input: &str) -> String {
+pub(crate) fn categorize_error(input: &str) -> ErrorCategory {
+    categorize_error_with_http_status(input, None)
+}
+
+/// Like [`categorize_error`], but prefers a stored error status (>= 400) over
+/// re-parsing one out of the message text.
+pub(crate) fn categorize_error_with_http_status(
+    input: &str,
+    http_status: Option<i64>,
+) -> ErrorCategory {
     let s = input.trim();
     if s.is_empty() {
-        return "Other".to_string();
+        return ErrorCategory::Other;
     }
 
-    if let Some(code) = extract_http_code(s) {
+    let stored_code = http_status
+        .and_then(|code| u16::try_from(code).ok())
+        .filter(|code| *code >= 400);
+    if let Some(code) = stored_code.or_else(|| extract_http_code(s)) {
         if code >= 501 {
-            return format!("HTTP {}", code);
+            return ErrorCategory::HttpServer(code);
         }
         if (400..500).contains(&code) {
             if let Some(t) = extract_json_error_type(s) {
-                return t.to_string();
+                return ErrorCategory::JsonError(t);
             }
             if RE_USAGE_NOT_INCLUDED.is_match(s) {
-                return "usage_not_included".to_string();
+                return ErrorCategory::UsageNotIncluded;
             }
             if RE_USAGE_LIMIT_REACHED.is_match(s) {
-                return "usage_limit_reached".to_string();
+                return ErrorCategory::UsageLimit;
             }
             if code == 429 {
                 if RE_TOO_MANY_REQUESTS.is_match(s) {
-                    return "too_many_requests".to_string();
+                    return ErrorCategory::RateLimited;
                 }
-                return "http_429".to_string();
+                return ErrorCategory::HttpClient(429);
             }
             if code == 401 {
-                return "unauthorized".to_string();
+                return ErrorCategory::Unauthorized;
             }
             if code == 403 {
-                return "forbidden".to_string();
+                return ErrorCategory::Forbidden;
             }
             if code == 404 {
-                return "not_found".to_string();
+                return ErrorCategory::NotFound;
             }
-            return format!("HTTP {}", code);
+            return ErrorCategory::HttpClient(code);
         }
     }
 
     // Fallback to normalized text; if empty -> Other
     let norm = normalize_error_reason(s);
     if norm == "Unknown" || norm.is_empty() {
-        "Other".to_string()
+        ErrorCategory::Other
     } else {
-        norm
+        ErrorCategory::Message(norm)
     }
 }
 
@@ -1047,6 +1418,18 @@ pub(crate) fn extract_http_code(s: &str) -> Option<u16> {
         .and_then(|m| m.as_str().parse::<u16>().ok())
 }
 
+/// Status code stored in `codex_invocations.http_status`: the upstream status the
+/// proxy recorded in the payload, falling back to a code parsed from the error.
+pub(crate) fn resolve_invocation_http_status(
+    payload: Option<&str>,
+    error_message: Option<&str>,
+) -> Option<i64> {
+    payload
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|value| value.get("statusCode").and_then(serde_json::Value::as_i64))
+        .or_else(|| error_message.and_then(extract_http_code).map(i64::from))
+}
+
 pub(crate) fn extract_json_error_type(s: &str) -> Option<String> {
     if !s.trim_start().starts_with('{') {
         return None;
@@ -1071,7 +1454,7 @@ pub(crate) async fn fetch_other_errors(
     Query(params): Query<OtherErrorsQuery>,
 ) -> Result<Json<OtherErrorsResponse>, ApiError> {
     let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
-    let range_window = resolve_range_window(&params.range, reporting_tz)?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
     let start_dt = range_window.start;
     let scope = FailureScope::parse(params.scope.as_deref())?;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
@@ -1082,12 +1465,13 @@ pub(crate) async fn fetch_other_errors(
         occurred_at: String,
         status: Option<String>,
         error_message: Option<String>,
+        http_status: Option<i64>,
         failure_kind: Option<String>,
         failure_class: Option<String>,
         is_actionable: Option<i64>,
     }
     let mut query = QueryBuilder::new(
-        "SELECT id, occurred_at, status, error_message, failure_kind, failure_class, is_actionable FROM codex_invocations WHERE occurred_at >= ",
+        "SELECT id, occurred_at, status, error_message, http_status, failure_kind, failure_class, is_actionable FROM codex_invocations WHERE occurred_at >= ",
     );
     query.push_bind(db_occurred_at_lower_bound(start_dt));
     if source_scope == InvocationSourceScope::ProxyOnly {
@@ -1109,14 +1493,16 @@ pub(crate) async fn fetch_other_errors(
             continue;
         }
         let msg = r.error_message.clone().unwrap_or_default();
-        let cat = categorize_error(&msg);
-        if cat == "Other" {
+        if categorize_error_with_http_status(&msg, r.http_status) == ErrorCategory::Other {
             others.push(r);
         }
     }
 
     let total = others.len() as i64;
-    let limit = params.limit.unwrap_or(50).clamp(1, 200);
+    let limit = params
+        .limit
+        .unwrap_or(50)
+        .clamp(1, state.config.other_errors_limit_max as i64);
     let page = params.page.unwrap_or(1).max(1);
     let start = ((page - 1) * limit) as usize;
     let end = (start + limit as usize).min(others.len());
@@ -1143,12 +1529,615 @@ pub(crate) async fn fetch_other_errors(
     }))
 }
 
+/// Returns raw error messages that the current categorization rules map to the
+/// requested distribution label, so rule changes can be checked against history.
+pub(crate) async fn fetch_error_samples(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ErrorSampleQuery>,
+) -> Result<Json<ErrorSampleResponse>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
+    let start_dt = range_window.start;
+    let scope = FailureScope::parse(params.scope.as_deref())?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+    let requested = params.category.trim();
+    if requested.is_empty() {
+        return Err(ApiError::bad_request(anyhow!("category must not be empty")));
+    }
+    let category = ErrorCategory::from_persisted(requested);
+    let limit = params.limit.unwrap_or(20).clamp(1, 200) as usize;
+
+    #[derive(sqlx::FromRow)]
+    struct RowItem {
+        id: i64,
+        occurred_at: String,
+        status: Option<String>,
+        error_message: Option<String>,
+        http_status: Option<i64>,
+        failure_kind: Option<String>,
+        failure_class: Option<String>,
+        is_actionable: Option<i64>,
+    }
+    let mut query = QueryBuilder::new(
+        "SELECT id, occurred_at, status, error_message, http_status, failure_kind, failure_class, is_actionable FROM codex_invocations WHERE occurred_at >= ",
+    );
+    query.push_bind(db_occurred_at_lower_bound(start_dt));
+    if source_scope == InvocationSourceScope::ProxyOnly {
+        query.push(" AND source = ").push_bind(SOURCE_PROXY);
+    }
+    query.push(" AND (status IS NULL OR status != 'success') ORDER BY occurred_at DESC");
+    let rows: Vec<RowItem> = query.build_query_as().fetch_all(&state.pool).await?;
+
+    let mut items = Vec::new();
+    for r in rows {
+        let classification = resolve_failure_classification(
+            r.status.as_deref(),
+            r.error_message.as_deref(),
+            r.failure_kind.as_deref(),
+            r.failure_class.as_deref(),
+            r.is_actionable,
+        );
+        if !failure_scope_matches(scope, classification.failure_class) {
+            continue;
+        }
+        let msg = r.error_message.as_deref().unwrap_or_default();
+        if categorize_error_with_http_status(msg, r.http_status) != category {
+            continue;
+        }
+        items.push(OtherErrorItem {
+            id: r.id,
+            occurred_at: r.occurred_at,
+            error_message: r.error_message,
+        });
+        if items.len() >= limit {
+            break;
+        }
+    }
+
+    Ok(Json(ErrorSampleResponse {
+        reason: category.label(),
+        category,
+        items,
+    }))
+}
+
+const ERROR_SUMMARY_SAMPLES_PER_CATEGORY: usize = 2;
+
+/// Error distribution plus a few recent raw messages per category, so dashboards
+/// do not need a follow-up `errors/sample` call for every row.
+pub(crate) async fn fetch_error_summary(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ErrorQuery>,
+) -> Result<Json<ErrorSummaryResponse>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
+    let scope = FailureScope::parse(params.scope.as_deref())?;
+    let Json(distribution) = fetch_error_distribution(
+        State(state.clone()),
+        Query(ErrorQuery {
+            range: params.range.clone(),
+            top: params.top,
+            scope: params.scope.clone(),
+            time_zone: params.time_zone.clone(),
+        }),
+    )
+    .await?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+
+    let mut samples: HashMap<ErrorCategory, Vec<String>> = distribution
+        .items
+        .iter()
+        .map(|item| (item.category.clone(), Vec::new()))
+        .collect();
+    if !samples.is_empty() {
+        #[derive(sqlx::FromRow)]
+        struct RowItem {
+            status: Option<String>,
+            error_message: Option<String>,
+            http_status: Option<i64>,
+            failure_kind: Option<String>,
+            failure_class: Option<String>,
+            is_actionable: Option<i64>,
+        }
+        let mut query = QueryBuilder::new(
+            "SELECT status, error_message, http_status, failure_kind, failure_class, is_actionable FROM codex_invocations WHERE occurred_at >= ",
+        );
+        query.push_bind(db_occurred_at_lower_bound(range_window.start));
+        if source_scope == InvocationSourceScope::ProxyOnly {
+            query.push(" AND source = ").push_bind(SOURCE_PROXY);
+        }
+        query.push(
+            " AND (status IS NULL OR status != 'success') AND error_message IS NOT NULL AND TRIM(error_message) != '' ORDER BY occurred_at DESC",
+        );
+        let rows: Vec<RowItem> = query.build_query_as().fetch_all(&state.pool).await?;
+
+        let mut pending = samples.len();
+        for r in rows {
+            let classification = resolve_failure_classification(
+                r.status.as_deref(),
+                r.error_message.as_deref(),
+                r.failure_kind.as_deref(),
+                r.failure_class.as_deref(),
+                r.is_actionable,
+            );
+            if !failure_scope_matches(scope, classification.failure_class) {
+                continue;
+            }
+            let Some(msg) = r.error_message else {
+                continue;
+            };
+            let Some(bucket) =
+                samples.get_mut(&categorize_error_with_http_status(&msg, r.http_status))
+            else {
+                continue;
+            };
+            if bucket.len() >= ERROR_SUMMARY_SAMPLES_PER_CATEGORY || bucket.contains(&msg) {
+                continue;
+            }
+            bucket.push(msg);
+            if bucket.len() == ERROR_SUMMARY_SAMPLES_PER_CATEGORY {
+                pending -= 1;
+                if pending == 0 {
+                    break;
+                }
+            }
+        }
+    }
+
+    let items = distribution
+        .items
+        .into_iter()
+        .map(|item| ErrorSummaryItem {
+            samples: samples.remove(&item.category).unwrap_or_default(),
+            reason: item.reason,
+            category: item.category,
+            count: item.count,
+        })
+        .collect();
+    Ok(Json(ErrorSummaryResponse {
+        range_start: distribution.range_start,
+        range_end: distribution.range_end,
+        items,
+    }))
+}
+
+/// Per-model cost per 1k tokens over a range, most expensive first. Models with
+/// no recorded tokens report `costPer1kTokens: null` and sort last.
+pub(crate) async fn fetch_model_efficiency(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ModelEfficiencyQuery>,
+) -> Result<Json<ModelEfficiencyResponse>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+
+    let mut query = QueryBuilder::new(
+        "SELECT COALESCE(NULLIF(TRIM(model_canonical), ''), NULLIF(TRIM(model), ''), 'unknown') \
+         AS model, \
+         CAST(COALESCE(SUM(cost), 0) AS REAL) AS total_cost, \
+         COALESCE(SUM(total_tokens), 0) AS total_tokens \
+         FROM codex_invocations WHERE occurred_at >= ",
+    );
+    query
+        .push_bind(db_occurred_at_lower_bound(range_window.start))
+        .push(" AND occurred_at < ")
+        .push_bind(db_occurred_at_upper_bound(range_window.end));
+    if source_scope == InvocationSourceScope::ProxyOnly {
+        query.push(" AND source = ").push_bind(SOURCE_PROXY);
+    }
+    query.push(" AND LOWER(TRIM(COALESCE(status, ''))) NOT IN ('running', 'pending')");
+    query.push(" GROUP BY 1");
+    let mut items: Vec<ModelEfficiencyItem> = query.build_query_as().fetch_all(&state.pool).await?;
+
+    for item in &mut items {
+        item.cost_per_1k_tokens =
+            (item.total_tokens > 0).then(|| item.total_cost * 1_000.0 / item.total_tokens as f64);
+    }
+    items.sort_by(|a, b| {
+        b.cost_per_1k_tokens
+            .partial_cmp(&a.cost_per_1k_tokens)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.model.cmp(&b.model))
+    });
+
+    Ok(Json(ModelEfficiencyResponse {
+        range_start: format_utc_iso(range_window.start),
+        range_end: format_utc_iso(range_window.display_end),
+        items,
+    }))
+}
+
+pub(crate) async fn fetch_cache_savings(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CacheSavingsQuery>,
+) -> Result<Json<CacheSavingsResponse>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+
+    let mut query = QueryBuilder::new(
+        "SELECT COALESCE(SUM(cache_input_tokens), 0), COALESCE(SUM(input_tokens), 0) \
+         FROM codex_invocations WHERE occurred_at >= ",
+    );
+    query
+        .push_bind(db_occurred_at_lower_bound(range_window.start))
+        .push(" AND occurred_at < ")
+        .push_bind(db_occurred_at_upper_bound(range_window.end));
+    if source_scope == InvocationSourceScope::ProxyOnly {
+        query.push(" AND source = ").push_bind(SOURCE_PROXY);
+    }
+    query.push(" AND LOWER(TRIM(COALESCE(status, ''))) NOT IN ('running', 'pending')");
+    let (cached_tokens, total_input_tokens): (i64, i64) =
+        query.build_query_as().fetch_one(&state.pool).await?;
+
+    // `input_tokens` already includes the cached prefix, so the savings are
+    // expressed in uncached input-token equivalents.
+    let cache_discount = state.config.cache_discount;
+    Ok(Json(CacheSavingsResponse {
+        range_start: format_utc_iso(range_window.start),
+        range_end: format_utc_iso(range_window.display_end),
+        cached_tokens,
+        total_input_tokens,
+        cache_hit_rate: (total_input_tokens > 0)
+            .then(|| cached_tokens as f64 / total_input_tokens as f64),
+        cache_discount,
+        estimated_savings: cached_tokens as f64 * cache_discount,
+    }))
+}
+
+/// Typical day curve: per hour-of-day (in the reporting timezone), requests and
+/// cost averaged over the days in range that saw any traffic.
+pub(crate) async fn fetch_hourly_profile(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HourlyProfileQuery>,
+) -> Result<Json<HourlyProfileResponse>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+
+    let mut query = QueryBuilder::new(
+        "SELECT occurred_at, CAST(COALESCE(cost, 0) AS REAL) FROM codex_invocations \
+         WHERE occurred_at >= ",
+    );
+    query
+        .push_bind(db_occurred_at_lower_bound(range_window.start))
+        .push(" AND occurred_at < ")
+        .push_bind(db_occurred_at_upper_bound(range_window.end));
+    if source_scope == InvocationSourceScope::ProxyOnly {
+        query.push(" AND source = ").push_bind(SOURCE_PROXY);
+    }
+    query.push(" AND LOWER(TRIM(COALESCE(status, ''))) NOT IN ('running', 'pending')");
+    let rows: Vec<(String, f64)> = query.build_query_as().fetch_all(&state.pool).await?;
+
+    let mut totals = [(0_i64, 0.0_f64); 24];
+    let mut active_days = HashSet::new();
+    for (occurred_at, cost) in rows {
+        let Some(occurred_at) = parse_to_utc_datetime(&occurred_at) else {
+            continue;
+        };
+        let local = occurred_at.with_timezone(&reporting_tz);
+        active_days.insert(local.date_naive());
+        let slot = &mut totals[local.hour() as usize];
+        slot.0 += 1;
+        slot.1 += cost;
+    }
+    let day_count = active_days.len() as i64;
+    let items = totals
+        .iter()
+        .enumerate()
+        .map(|(hour, (count, cost))| {
+            if day_count == 0 {
+                return HourlyProfileItem {
+                    hour: hour as u32,
+                    ..HourlyProfileItem::default()
+                };
+            }
+            HourlyProfileItem {
+                hour: hour as u32,
+                avg_count: *count as f64 / day_count as f64,
+                avg_cost: cost / day_count as f64,
+            }
+        })
+        .collect();
+
+    Ok(Json(HourlyProfileResponse {
+        range_start: format_utc_iso(range_window.start),
+        range_end: format_utc_iso(range_window.display_end),
+        active_days: day_count,
+        items,
+    }))
+}
+
+pub(crate) async fn fetch_streak(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<StreakQuery>,
+) -> Result<Json<StreakResponse>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+
+    let mut query = QueryBuilder::new(
+        "SELECT status, error_message FROM codex_invocations WHERE occurred_at >= ",
+    );
+    query
+        .push_bind(db_occurred_at_lower_bound(range_window.start))
+        .push(" AND occurred_at < ")
+        .push_bind(db_occurred_at_upper_bound(range_window.end));
+    if source_scope == InvocationSourceScope::ProxyOnly {
+        query.push(" AND source = ").push_bind(SOURCE_PROXY);
+    }
+    query.push(" AND LOWER(TRIM(COALESCE(status, ''))) NOT IN ('running', 'pending')");
+    query.push(" ORDER BY occurred_at ASC, id ASC");
+    let rows: Vec<(Option<String>, Option<String>)> =
+        query.build_query_as().fetch_all(&state.pool).await?;
+
+    let mut current = StreakRun::default();
+    let mut longest_success = 0_i64;
+    let mut longest_failure = 0_i64;
+    for (status, error_message) in rows {
+        let outcome = if invocation_status_is_success_like(
+            status.as_deref(),
+            error_message.as_deref(),
+            state.config.treat_empty_error_as_success,
+        ) {
+            "success"
+        } else {
+            "failure"
+        };
+        if current.status == Some(outcome) {
+            current.count += 1;
+        } else {
+            current = StreakRun {
+                status: Some(outcome),
+                count: 1,
+            };
+        }
+        let longest = if outcome == "success" {
+            &mut longest_success
+        } else {
+            &mut longest_failure
+        };
+        *longest = (*longest).max(current.count);
+    }
+
+    Ok(Json(StreakResponse {
+        range_start: format_utc_iso(range_window.start),
+        range_end: format_utc_iso(range_window.display_end),
+        current_streak: current,
+        longest_success,
+        longest_failure,
+    }))
+}
+
+pub(crate) async fn fetch_scatter(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ScatterQuery>,
+) -> Result<Json<Vec<ScatterPoint>>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+    let limit = params.limit.unwrap_or(500).clamp(1, 5_000) as usize;
+
+    let mut query = QueryBuilder::new(
+        "SELECT total_tokens, cost, model FROM codex_invocations WHERE occurred_at >= ",
+    );
+    query
+        .push_bind(db_occurred_at_lower_bound(range_window.start))
+        .push(" AND occurred_at < ")
+        .push_bind(db_occurred_at_upper_bound(range_window.end));
+    if source_scope == InvocationSourceScope::ProxyOnly {
+        query.push(" AND source = ").push_bind(SOURCE_PROXY);
+    }
+    query.push(" AND total_tokens IS NOT NULL AND cost IS NOT NULL");
+    query.push(" ORDER BY occurred_at ASC, id ASC");
+    let rows: Vec<ScatterPoint> = query.build_query_as().fetch_all(&state.pool).await?;
+
+    Ok(Json(downsample_evenly(rows, limit)))
+}
+
+/// Keeps `limit` items spread evenly across `items` (always including the
+/// first), so repeated calls over the same data return the same sample.
+pub(crate) fn downsample_evenly<T>(items: Vec<T>, limit: usize) -> Vec<T> {
+    let total = items.len();
+    if total <= limit {
+        return items;
+    }
+    let mut next_pick = 0_usize;
+    items
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            (next_pick < limit && index == next_pick * total / limit).then(|| {
+                next_pick += 1;
+                item
+            })
+        })
+        .collect()
+}
+
+pub(crate) async fn fetch_budget(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<BudgetQuery>,
+) -> Result<Json<BudgetResponse>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+    let now = state.clock.now();
+    let period_start = start_of_local_month(now, reporting_tz);
+    let period_end = start_of_next_month(period_start, reporting_tz);
+
+    let mut query = QueryBuilder::new(
+        "SELECT CAST(COALESCE(SUM(cost), 0) AS REAL) FROM codex_invocations WHERE occurred_at >= ",
+    );
+    query
+        .push_bind(db_occurred_at_lower_bound(period_start))
+        .push(" AND occurred_at < ")
+        .push_bind(db_occurred_at_upper_bound(now));
+    if source_scope == InvocationSourceScope::ProxyOnly {
+        query.push(" AND source = ").push_bind(SOURCE_PROXY);
+    }
+    let (spent_this_period,): (f64,) = query.build_query_as().fetch_one(&state.pool).await?;
+
+    // Extrapolate the month-to-date burn rate linearly over the whole month.
+    let elapsed_secs = (now - period_start).num_seconds();
+    let period_secs = (period_end - period_start).num_seconds();
+    let projected_end_of_period = if elapsed_secs > 0 {
+        spent_this_period * period_secs as f64 / elapsed_secs as f64
+    } else {
+        spent_this_period
+    };
+    let budget = state.config.monthly_budget;
+
+    Ok(Json(BudgetResponse {
+        period_start: format_utc_iso(period_start),
+        period_end: format_utc_iso(period_end),
+        budget,
+        spent_this_period,
+        remaining: budget.map(|budget| budget - spent_this_period),
+        projected_end_of_period,
+    }))
+}
+
+/// Billing periods ending at each distinct `period_reset_time` seen in the
+/// quota snapshot history, oldest first. The earliest period is assumed to
+/// span one month; periods that have not started yet are dropped. When the
+/// latest reset already passed, monthly periods are projected after it until
+/// one covers `now`.
+pub(crate) fn billing_periods_from_resets(
+    mut resets: Vec<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    resets.sort();
+    resets.dedup();
+    let Some(first_start) = resets
+        .first()
+        .and_then(|first| first.checked_sub_months(chrono::Months::new(1)))
+    else {
+        return Vec::new();
+    };
+    let mut periods = std::iter::once(first_start)
+        .chain(resets.iter().copied())
+        .zip(resets.iter().copied())
+        .filter(|(start, _)| *start <= now)
+        .collect::<Vec<_>>();
+    let mut start = resets[resets.len() - 1];
+    while start <= now {
+        let Some(end) = start.checked_add_months(chrono::Months::new(1)) else {
+            break;
+        };
+        periods.push((start, end));
+        start = end;
+    }
+    periods
+}
+
+pub(crate) async fn fetch_billing_period_totals(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<BillingPeriodQuery>,
+) -> Result<Json<Vec<BillingPeriodTotals>>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+    let now = state.clock.now();
+    let limit = params.periods.unwrap_or(6).clamp(1, 24) as usize;
+
+    let reset_times: Vec<String> = sqlx::query_scalar(
+        "SELECT DISTINCT period_reset_time FROM codex_quota_snapshots WHERE period_reset_time IS NOT NULL",
+    )
+    .fetch_all(&state.pool)
+    .await?;
+    let resets = reset_times
+        .iter()
+        .filter_map(|raw| parse_to_utc_datetime(raw))
+        .collect::<Vec<_>>();
+    // Nothing in this tree records `codex_quota_snapshots` any more, so only legacy
+    // databases carry reset times; everyone else gets calendar months, and each
+    // item says which one it is.
+    let mut periods = billing_periods_from_resets(resets, now);
+    let mut source = BILLING_PERIOD_SOURCE_QUOTA_RESETS;
+    if periods.is_empty() {
+        source = BILLING_PERIOD_SOURCE_CALENDAR;
+        let mut start = start_of_local_month(now, reporting_tz);
+        for _ in 0..limit {
+            periods.push((start, start_of_next_month(start, reporting_tz)));
+            start = start_of_local_month(start - ChronoDuration::seconds(1), reporting_tz);
+        }
+        periods.reverse();
+    }
+    let skip = periods.len().saturating_sub(limit);
+
+    let mut items = Vec::with_capacity(periods.len() - skip);
+    for (start, end) in periods.into_iter().skip(skip) {
+        let mut query = QueryBuilder::new(
+            "SELECT CAST(COALESCE(SUM(cost), 0) AS REAL), COUNT(*) FROM codex_invocations WHERE occurred_at >= ",
+        );
+        query
+            .push_bind(db_occurred_at_lower_bound(start))
+            .push(" AND occurred_at < ")
+            .push_bind(db_occurred_at_upper_bound(end));
+        if source_scope == InvocationSourceScope::ProxyOnly {
+            query.push(" AND source = ").push_bind(SOURCE_PROXY);
+        }
+        let (total_cost, total_requests): (f64, i64) =
+            query.build_query_as().fetch_one(&state.pool).await?;
+        items.push(BillingPeriodTotals {
+            period_start: format_utc_iso(start),
+            period_end: format_utc_iso(end),
+            total_cost,
+            total_requests,
+            source,
+        });
+    }
+    Ok(Json(items))
+}
+
+fn change_pct(current: f64, previous: f64) -> Option<f64> {
+    (previous != 0.0).then(|| (current - previous) / previous * 100.0)
+}
+
+pub(crate) async fn fetch_stats_comparison(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<StatsComparisonQuery>,
+) -> Result<Json<StatsComparisonResponse>, ApiError> {
+    let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
+    let source_scope = resolve_default_source_scope(&state.pool).await?;
+    let previous_start = range_window.start - range_window.duration;
+
+    let current = query_combined_totals(
+        &state.pool,
+        StatsFilter::Range(range_window.start, range_window.end),
+        source_scope,
+        state.archive_db_attached,
+        state.config.treat_empty_error_as_success,
+    )
+    .await?;
+    let previous = query_combined_totals(
+        &state.pool,
+        StatsFilter::Range(previous_start, range_window.start),
+        source_scope,
+        state.archive_db_attached,
+        state.config.treat_empty_error_as_success,
+    )
+    .await?;
+
+    Ok(Json(StatsComparisonResponse {
+        range_start: format_utc_iso(range_window.start),
+        range_end: format_utc_iso(range_window.display_end),
+        previous_range_start: format_utc_iso(previous_start),
+        previous_range_end: format_utc_iso(range_window.start),
+        count_change_pct: change_pct(current.total_count as f64, previous.total_count as f64),
+        cost_change_pct: change_pct(current.total_cost, previous.total_cost),
+        tokens_change_pct: change_pct(current.total_tokens as f64, previous.total_tokens as f64),
+        current: current.into_response(),
+        previous: previous.into_response(),
+    }))
+}
+
 pub(crate) async fn fetch_failure_summary(
     State(state): State<Arc<AppState>>,
     Query(params): Query<FailureSummaryQuery>,
 ) -> Result<Json<FailureSummaryResponse>, ApiError> {
     let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
-    let range_window = resolve_range_window(&params.range, reporting_tz)?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
     let start_dt = range_window.start;
     let display_end = range_window.display_end;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
@@ -1388,7 +2377,7 @@ pub(crate) async fn fetch_perf_stats(
     }
 
     let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
-    let range_window = resolve_range_window(&params.range, reporting_tz)?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
     if range_window.start < shanghai_retention_cutoff(state.config.invocation_max_days) {
         let range_plan = build_hourly_rollup_exact_range_plan(
             range_window.start,
@@ -1629,6 +2618,22 @@ pub(crate) async fn latest_quota_snapshot(
     Ok(Json(snapshot))
 }
 
+pub(crate) async fn latest_quota_snapshot_cached(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let Json(snapshot) = latest_quota_snapshot(State(state)).await?;
+    let etag = quota_snapshot_etag(&snapshot.captured_at);
+    let body = serde_json::to_vec(&snapshot)
+        .map_err(|err| ApiError::from(anyhow!("failed to serialize quota snapshot: {err}")))?;
+    conditional_json_response(&headers, &etag, body)
+}
+
+pub(crate) fn quota_snapshot_etag(captured_at: &str) -> String {
+    let digest = Sha256::digest(captured_at.as_bytes());
+    format!("W/\"quota-{digest:x}\"")
+}
+
 pub(crate) async fn broadcast_summary_if_changed(
     broadcaster: &broadcast::Sender<BroadcastPayload>,
     cache: &Mutex<BroadcastStateCache>,
@@ -1694,8 +2699,24 @@ pub(crate) async fn broadcast_quota_if_changed(
 pub(crate) async fn sse_stream(
     state: State<Arc<AppState>>,
     query: Query<SubscriptionStreamQuery>,
-) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>>, ApiError> {
-    topic_sse_stream(state, query).await
+) -> Result<Response, ApiError> {
+    // Best-effort cap: concurrent handshakes may briefly overshoot it, which
+    // is fine for guarding against reconnect storms.
+    if let Some(max_clients) = state.config.max_sse_clients {
+        let live_clients = state.sse_connections.len() as u64;
+        if live_clients >= max_clients {
+            warn!(
+                live_clients,
+                max_clients, "rejecting sse client over MAX_SSE_CLIENTS"
+            );
+            return Ok((
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("too many live event streams (limit {max_clients})"),
+            )
+                .into_response());
+        }
+    }
+    Ok(topic_sse_stream(state, query).await?.into_response())
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1742,6 +2763,8 @@ mod tests {
             live_phase: phase.map(str::to_string),
             error_message: None,
             downstream_status_code: None,
+            http_status: None,
+            label: None,
             failure_kind: None,
             blocked_binding: None,
             blocked_binding_json: None,
@@ -2062,6 +3085,13 @@ pub(crate) async fn get_versions(
     Ok(Json(VersionResponse { backend, frontend }))
 }
 
+/// Same payload as `/api/version`, for `--version`.
+pub(crate) fn render_versions_json(static_dir: Option<&Path>) -> Result<String> {
+    let (backend, frontend) = detect_versions(static_dir);
+    serde_json::to_string(&VersionResponse { backend, frontend })
+        .context("failed to serialize version info")
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct BroadcastStateCache {
     pub(crate) summaries: HashMap<String, StatsResponse>,
@@ -2245,12 +3275,13 @@ pub(crate) fn schedule_dashboard_activity_live_snapshot(state: &AppState) {
                 {
                     Ok(snapshot) => {
                         let revision = snapshot.revision;
-                        if let Err(err) =
-                            broadcaster.send(BroadcastPayload::DashboardActivityLive { snapshot })
+                        if broadcaster
+                            .send(BroadcastPayload::DashboardActivityLive { snapshot })
+                            .is_err()
                         {
-                            warn!(
-                                ?err,
-                                revision, "failed to broadcast dashboard activity live snapshot"
+                            debug!(
+                                revision,
+                                "dashboard activity live snapshot not broadcast; all receivers disconnected"
                             );
                         } else {
                             tracing::debug!(
@@ -2313,6 +3344,13 @@ pub(crate) enum BroadcastPayload {
     Quota {
         snapshot: Box<QuotaSnapshotResponse>,
     },
+    Alert {
+        kind: String,
+        detail: String,
+    },
+    Deleted {
+        id: i64,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, FromRow)]
@@ -2358,6 +3396,11 @@ pub(crate) struct ApiInvocation {
     #[sqlx(default)]
     pub(crate) downstream_status_code: Option<i64>,
     #[sqlx(default)]
+    pub(crate) http_status: Option<i64>,
+    /// Client-set bookkeeping label (`PUT /api/invocations/:id/label`).
+    #[sqlx(default)]
+    pub(crate) label: Option<String>,
+    #[sqlx(default)]
     pub(crate) failure_kind: Option<String>,
     #[sqlx(skip)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -262,160 +262,228 @@ pub(crate) fn runtime_invocation_live_phase(record: &ApiInvocation) -> Option<&'
     }
 }
 
+/// `/api/invocations` select list: each entry pairs the serialized `ApiInvocation`
+/// keys it feeds with its SQL. Entries without keys back non-defaulted `FromRow`
+/// fields and the runtime overlay merge, so every projection keeps them.
+pub(crate) fn invocation_select_columns() -> Vec<(&'static [&'static str], String)> {
+    let failure_class_sql = INVOCATION_RESOLVED_FAILURE_CLASS_SQL;
+    vec![
+        (&[], "id, invoke_id, occurred_at, source".to_string()),
+        (
+            &["proxyDisplayName"],
+            "CASE WHEN json_valid(payload) THEN json_extract(payload, '$.proxyDisplayName') END AS proxy_display_name".to_string(),
+        ),
+        (&[], "model".to_string()),
+        (
+            &["requestModel"],
+            format!("{INVOCATION_REQUEST_MODEL_SQL} AS request_model"),
+        ),
+        (
+            &["responseModel"],
+            format!("{INVOCATION_RESPONSE_MODEL_SQL} AS response_model"),
+        ),
+        (
+            &[],
+            "input_tokens, output_tokens, cache_input_tokens, reasoning_tokens".to_string(),
+        ),
+        (
+            &["reasoningEffort"],
+            format!("{INVOCATION_REASONING_EFFORT_SQL} AS reasoning_effort"),
+        ),
+        (&[], "total_tokens, cost, status".to_string()),
+        (
+            &["livePhase"],
+            format!("{} AS live_phase", invocation_live_phase_sql("codex_invocations")),
+        ),
+        (&[], "error_message".to_string()),
+        (
+            &["downstreamStatusCode"],
+            format!("{INVOCATION_DOWNSTREAM_STATUS_CODE_SQL} AS downstream_status_code"),
+        ),
+        (&["httpStatus"], "http_status".to_string()),
+        (&["label"], "label".to_string()),
+        (
+            &["endpoint"],
+            "CASE WHEN json_valid(payload) THEN json_extract(payload, '$.endpoint') END AS endpoint".to_string(),
+        ),
+        (
+            &["compactionRequestKind"],
+            format!("{INVOCATION_COMPACTION_REQUEST_KIND_SQL} AS compaction_request_kind"),
+        ),
+        (
+            &["compactionResponseKind"],
+            format!("{INVOCATION_COMPACTION_RESPONSE_KIND_SQL} AS compaction_response_kind"),
+        ),
+        (
+            &["imageIntent"],
+            format!("{INVOCATION_IMAGE_INTENT_SQL} AS image_intent"),
+        ),
+        (
+            &["failureKind"],
+            format!("{INVOCATION_FAILURE_KIND_SQL} AS failure_kind"),
+        ),
+        (
+            &["blockedBinding"],
+            format!("{INVOCATION_BLOCKED_BINDING_JSON_SQL} AS blocked_binding_json"),
+        ),
+        (
+            &["streamTerminalEvent"],
+            "CASE WHEN json_valid(payload) THEN json_extract(payload, '$.streamTerminalEvent') END AS stream_terminal_event".to_string(),
+        ),
+        (
+            &["upstreamErrorCode"],
+            "CASE WHEN json_valid(payload) THEN json_extract(payload, '$.upstreamErrorCode') END AS upstream_error_code".to_string(),
+        ),
+        (
+            &["upstreamErrorMessage"],
+            "CASE WHEN json_valid(payload) THEN json_extract(payload, '$.upstreamErrorMessage') END AS upstream_error_message".to_string(),
+        ),
+        (
+            &["downstreamErrorMessage"],
+            format!("{INVOCATION_DOWNSTREAM_ERROR_MESSAGE_SQL} AS downstream_error_message"),
+        ),
+        (
+            &["upstreamRequestId"],
+            "CASE WHEN json_valid(payload) THEN json_extract(payload, '$.upstreamRequestId') END AS upstream_request_id".to_string(),
+        ),
+        (
+            &["failureClass"],
+            format!("{failure_class_sql} AS failure_class"),
+        ),
+        (
+            &["isActionable"],
+            format!(
+                "CASE WHEN {failure_class_sql} = 'service_failure' THEN 1 ELSE 0 END AS is_actionable"
+            ),
+        ),
+        (
+            &["requesterIp"],
+            "CASE WHEN json_valid(payload) THEN json_extract(payload, '$.requesterIp') END AS requester_ip".to_string(),
+        ),
+        (
+            &["promptCacheKey"],
+            format!("{INVOCATION_PROMPT_CACHE_KEY_SQL} AS prompt_cache_key"),
+        ),
+        (
+            &["stickyKey"],
+            format!("{INVOCATION_STICKY_KEY_SQL} AS sticky_key"),
+        ),
+        (
+            &["routeMode"],
+            format!("{INVOCATION_ROUTE_MODE_SQL} AS route_mode"),
+        ),
+        (
+            &["upstreamAccountId"],
+            format!("{INVOCATION_UPSTREAM_ACCOUNT_ID_SQL} AS upstream_account_id"),
+        ),
+        (
+            &["upstreamAccountName"],
+            format!("{INVOCATION_UPSTREAM_ACCOUNT_NAME_SQL} AS upstream_account_name"),
+        ),
+        (
+            &["responseContentEncoding"],
+            format!("{INVOCATION_RESPONSE_CONTENT_ENCODING_SQL} AS response_content_encoding"),
+        ),
+        (
+            &["transport"],
+            format!("{INVOCATION_TRANSPORT_SQL} AS transport"),
+        ),
+        (
+            &["poolAttemptCount"],
+            format!("{INVOCATION_POOL_ATTEMPT_COUNT_SQL} AS pool_attempt_count"),
+        ),
+        (
+            &["poolDistinctAccountCount"],
+            format!("{INVOCATION_POOL_DISTINCT_ACCOUNT_COUNT_SQL} AS pool_distinct_account_count"),
+        ),
+        (
+            &["poolAttemptTerminalReason"],
+            format!("{INVOCATION_POOL_ATTEMPT_TERMINAL_REASON_SQL} AS pool_attempt_terminal_reason"),
+        ),
+        (
+            &["requestedServiceTier"],
+            "CASE \
+               WHEN json_valid(payload) AND json_type(payload, '$.requestedServiceTier') = 'text' \
+                 THEN json_extract(payload, '$.requestedServiceTier') \
+               WHEN json_valid(payload) AND json_type(payload, '$.requested_service_tier') = 'text' \
+                 THEN json_extract(payload, '$.requested_service_tier') END AS requested_service_tier"
+                .to_string(),
+        ),
+        (
+            &["serviceTier"],
+            "CASE \
+               WHEN json_valid(payload) AND json_type(payload, '$.serviceTier') = 'text' \
+                 THEN json_extract(payload, '$.serviceTier') \
+               WHEN json_valid(payload) AND json_type(payload, '$.service_tier') = 'text' \
+                 THEN json_extract(payload, '$.service_tier') END AS service_tier"
+                .to_string(),
+        ),
+        (
+            &["billingServiceTier"],
+            format!("{INVOCATION_BILLING_SERVICE_TIER_SQL} AS billing_service_tier"),
+        ),
+        (
+            &["proxyWeightDelta"],
+            "CASE WHEN json_valid(payload) \
+               AND json_type(payload, '$.proxyWeightDelta') IN ('integer', 'real') \
+               THEN json_extract(payload, '$.proxyWeightDelta') END AS proxy_weight_delta"
+                .to_string(),
+        ),
+        (&["costEstimated"], "cost_estimated".to_string()),
+        (&["priceVersion"], "price_version".to_string()),
+        (&["requestRawPath"], "request_raw_path".to_string()),
+        (&["requestRawSize"], "request_raw_size".to_string()),
+        (&["requestRawTruncated"], "request_raw_truncated".to_string()),
+        (
+            &["requestRawTruncatedReason"],
+            "request_raw_truncated_reason".to_string(),
+        ),
+        (&["responseRawPath"], "response_raw_path".to_string()),
+        (&["responseRawSize"], "response_raw_size".to_string()),
+        (&["responseRawTruncated"], "response_raw_truncated".to_string()),
+        (
+            &["responseRawTruncatedReason"],
+            "response_raw_truncated_reason".to_string(),
+        ),
+        (&[], "detail_level".to_string()),
+        (&["detailPrunedAt"], "detail_pruned_at".to_string()),
+        (&["detailPruneReason"], "detail_prune_reason".to_string()),
+        (&["tTotalMs"], "t_total_ms".to_string()),
+        (&["tReqReadMs"], "t_req_read_ms".to_string()),
+        (&["tReqParseMs"], "t_req_parse_ms".to_string()),
+        (&["tUpstreamConnectMs"], "t_upstream_connect_ms".to_string()),
+        (&["tUpstreamTtfbMs"], "t_upstream_ttfb_ms".to_string()),
+        (&["tUpstreamStreamMs"], "t_upstream_stream_ms".to_string()),
+        (&["tRespParseMs"], "t_resp_parse_ms".to_string()),
+        (&["tPersistMs"], "t_persist_ms".to_string()),
+        (&[], "created_at".to_string()),
+    ]
+}
+
 pub(crate) fn build_invocation_select_query() -> QueryBuilder<'static, Sqlite> {
-    let mut query = QueryBuilder::new(
-        "SELECT id, invoke_id, occurred_at, source, \
-         CASE WHEN json_valid(payload) THEN json_extract(payload, '$.proxyDisplayName') END AS proxy_display_name, \
-         model, \
-         ",
-    );
-    query
-        .push(INVOCATION_REQUEST_MODEL_SQL)
-        .push(
-            " AS request_model, \
-         ",
-        )
-        .push(INVOCATION_RESPONSE_MODEL_SQL)
-        .push(
-            " AS response_model, \
-         input_tokens, output_tokens, \
-         cache_input_tokens, reasoning_tokens, \
-         ",
-        )
-        .push(INVOCATION_REASONING_EFFORT_SQL)
-        .push(
-            " AS reasoning_effort, \
-         total_tokens, cost, status, \
-         ",
-        )
-        .push(invocation_live_phase_sql("codex_invocations"))
-        .push(
-            " AS live_phase, error_message, \
-         ",
-        )
-        .push(INVOCATION_DOWNSTREAM_STATUS_CODE_SQL)
-        .push(
-            " AS downstream_status_code, \
-         CASE WHEN json_valid(payload) THEN json_extract(payload, '$.endpoint') END AS endpoint, \
-         ",
-        )
-        .push(INVOCATION_COMPACTION_REQUEST_KIND_SQL)
-        .push(
-            " AS compaction_request_kind, \
-         ",
-        )
-        .push(INVOCATION_COMPACTION_RESPONSE_KIND_SQL)
-        .push(
-            " AS compaction_response_kind, \
-         ",
-        )
-        .push(INVOCATION_IMAGE_INTENT_SQL)
-        .push(
-            " AS image_intent, \
-         ",
-        )
-        .push(INVOCATION_FAILURE_KIND_SQL)
-        .push(
-            " AS failure_kind, \
-         ",
-        )
-        .push(INVOCATION_BLOCKED_BINDING_JSON_SQL)
-        .push(
-            " AS blocked_binding_json, \
-         CASE WHEN json_valid(payload) THEN json_extract(payload, '$.streamTerminalEvent') END AS stream_terminal_event, \
-         CASE WHEN json_valid(payload) THEN json_extract(payload, '$.upstreamErrorCode') END AS upstream_error_code, \
-         CASE WHEN json_valid(payload) THEN json_extract(payload, '$.upstreamErrorMessage') END AS upstream_error_message, \
-         ",
-        )
-        .push(INVOCATION_DOWNSTREAM_ERROR_MESSAGE_SQL)
-        .push(
-            " AS downstream_error_message, \
-         CASE WHEN json_valid(payload) THEN json_extract(payload, '$.upstreamRequestId') END AS upstream_request_id, ",
-        )
-        .push(INVOCATION_RESOLVED_FAILURE_CLASS_SQL)
-        .push(" AS failure_class, CASE WHEN ")
-        .push(INVOCATION_RESOLVED_FAILURE_CLASS_SQL)
-        .push(
-            " = 'service_failure' THEN 1 ELSE 0 END AS is_actionable, \
-         CASE WHEN json_valid(payload) THEN json_extract(payload, '$.requesterIp') END AS requester_ip, \
-         ",
-        )
-        .push(INVOCATION_PROMPT_CACHE_KEY_SQL)
-        .push(
-            " AS prompt_cache_key, \
-         ",
-        )
-        .push(INVOCATION_STICKY_KEY_SQL)
-        .push(
-            " AS sticky_key, \
-         ",
-        )
-        .push(INVOCATION_ROUTE_MODE_SQL)
-        .push(
-            " AS route_mode, \
-         ",
-        )
-        .push(INVOCATION_UPSTREAM_ACCOUNT_ID_SQL)
-        .push(
-            " AS upstream_account_id, \
-         ",
-        )
-        .push(INVOCATION_UPSTREAM_ACCOUNT_NAME_SQL)
-        .push(
-            " AS upstream_account_name, \
-         ",
-        )
-        .push(INVOCATION_RESPONSE_CONTENT_ENCODING_SQL)
-        .push(
-            " AS response_content_encoding, \
-         ",
-        )
-        .push(INVOCATION_TRANSPORT_SQL)
-        .push(
-            " AS transport, \
-         ",
-        )
-        .push(INVOCATION_POOL_ATTEMPT_COUNT_SQL)
-        .push(
-            " AS pool_attempt_count, \
-         ",
-        )
-        .push(INVOCATION_POOL_DISTINCT_ACCOUNT_COUNT_SQL)
-        .push(
-            " AS pool_distinct_account_count, \
-         ",
-        )
-        .push(INVOCATION_POOL_ATTEMPT_TERMINAL_REASON_SQL)
-        .push(
-            " AS pool_attempt_terminal_reason, \
-         CASE \
-           WHEN json_valid(payload) AND json_type(payload, '$.requestedServiceTier') = 'text' \
-             THEN json_extract(payload, '$.requestedServiceTier') \
-           WHEN json_valid(payload) AND json_type(payload, '$.requested_service_tier') = 'text' \
-             THEN json_extract(payload, '$.requested_service_tier') END AS requested_service_tier, \
-         CASE \
-           WHEN json_valid(payload) AND json_type(payload, '$.serviceTier') = 'text' \
-             THEN json_extract(payload, '$.serviceTier') \
-           WHEN json_valid(payload) AND json_type(payload, '$.service_tier') = 'text' \
-             THEN json_extract(payload, '$.service_tier') END AS service_tier, \
-         ",
-        )
-        .push(INVOCATION_BILLING_SERVICE_TIER_SQL)
-        .push(
-            " AS billing_service_tier, \
-         CASE WHEN json_valid(payload) \
-           AND json_type(payload, '$.proxyWeightDelta') IN ('integer', 'real') \
-           THEN json_extract(payload, '$.proxyWeightDelta') END AS proxy_weight_delta, \
-         cost_estimated, price_version, \
-         request_raw_path, request_raw_size, request_raw_truncated, request_raw_truncated_reason, \
-         response_raw_path, response_raw_size, response_raw_truncated, response_raw_truncated_reason, \
-         detail_level, detail_pruned_at, detail_prune_reason, \
-         t_total_ms, t_req_read_ms, t_req_parse_ms, t_upstream_connect_ms, t_upstream_ttfb_ms, \
-         t_upstream_stream_ms, t_resp_parse_ms, t_persist_ms, \
-         created_at \
-         FROM codex_invocations WHERE 1 = 1",
-        );
-    query
+    build_invocation_projected_select_query(None)
+}
+
+/// Like `build_invocation_select_query`, but with `fields` set only computes the
+/// columns those serialized keys need. `costAudit` is derived from most of the
+/// record, so requesting it keeps the full select list.
+pub(crate) fn build_invocation_projected_select_query(
+    fields: Option<&HashSet<String>>,
+) -> QueryBuilder<'static, Sqlite> {
+    let fields = fields.filter(|fields| !fields.contains("costAudit"));
+    let columns = invocation_select_columns()
+        .into_iter()
+        .filter(|(keys, _)| {
+            fields.is_none_or(|fields| {
+                keys.is_empty() || keys.iter().any(|key| fields.contains(*key))
+            })
+        })
+        .map(|(_, sql)| sql)
+        .collect::<Vec<_>>();
+    QueryBuilder::new(format!(
+        "SELECT {} FROM codex_invocations WHERE 1 = 1",
+        columns.join(", ")
+    ))
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -450,6 +518,19 @@ impl InvocationSortBy {
             Self::Status => INVOCATION_STATUS_NORMALIZED_SQL,
         }
     }
+
+    /// Serialized record key `compare_runtime_invocation_records` reads besides
+    /// the always-selected columns.
+    fn record_field(self) -> &'static str {
+        match self {
+            Self::OccurredAt => "occurredAt",
+            Self::TotalTokens => "totalTokens",
+            Self::Cost => "cost",
+            Self::TotalMs => "tTotalMs",
+            Self::TtfbMs => "tUpstreamTtfbMs",
+            Self::Status => "failureClass",
+        }
+    }
 }
 
 pub(crate) fn invocation_display_status_sql() -> String {
@@ -558,6 +639,10 @@ pub(crate) struct InvocationRecordsFilters {
     pub(crate) max_total_tokens: Option<i64>,
     pub(crate) min_total_ms: Option<f64>,
     pub(crate) max_total_ms: Option<f64>,
+    pub(crate) label: Option<String>,
+    /// Validated JSON path (`$.a.b`) into `payload`, from `payloadKey`.
+    pub(crate) payload_path: Option<String>,
+    pub(crate) payload_val: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -824,6 +909,16 @@ pub(crate) fn build_invocation_filters(
         )));
     }
 
+    let payload_path = normalize_query_text(params.payload_key.as_deref())
+        .map(|key| payload_json_path(&key))
+        .transpose()?;
+    let payload_val = normalize_query_text(params.payload_val.as_deref());
+    if payload_val.is_some() && payload_path.is_none() {
+        return Err(ApiError::bad_request(anyhow!(
+            "payloadVal requires payloadKey"
+        )));
+    }
+
     let model_values = parse_query_text_list(params.models.as_deref());
     let model_target = InvocationModelTarget::parse(params.model_target.as_deref())?;
     let model_rerouted = InvocationModelRerouteFilter::parse(params.model_rerouted.as_deref())?;
@@ -863,9 +958,38 @@ pub(crate) fn build_invocation_filters(
         max_total_tokens: params.max_total_tokens,
         min_total_ms: params.min_total_ms,
         max_total_ms: params.max_total_ms,
+        label: normalize_query_text(params.label.as_deref()),
+        payload_path,
+        payload_val,
     })
 }
 
+/// Turns a dotted `payloadKey` into a JSON path. Only plain identifier segments
+/// are accepted because the path is inlined into SQL rather than bound.
+fn payload_json_path(key: &str) -> Result<String, ApiError> {
+    let valid = key.split('.').all(|segment| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+    });
+    if !valid {
+        return Err(ApiError::bad_request(anyhow!(
+            "invalid payloadKey: {key}; expected dot-separated [A-Za-z0-9_] segments"
+        )));
+    }
+    Ok(format!("$.{key}"))
+}
+
+/// Startup probe for `AppState::sqlite_json1_available`: whether the linked
+/// SQLite exposes the JSON1 functions.
+pub(crate) async fn probe_sqlite_json1(pool: &Pool<Sqlite>) -> bool {
+    sqlx::query_scalar::<_, i64>("SELECT json_extract('{\"a\":1}', '$.a')")
+        .fetch_one(pool)
+        .await
+        .is_ok()
+}
+
 pub(crate) fn build_invocation_list_request(
     params: &ListQuery,
     list_limit_max: i64,
@@ -912,12 +1036,18 @@ async fn resolve_attempt_public_id_to_invoke_id(
     .map_err(ApiError::from)
 }
 
-async fn build_resolved_invocation_list_request(
+pub(crate) async fn build_resolved_invocation_list_request(
     pool: &Pool<Sqlite>,
     params: &ListQuery,
     list_limit_max: i64,
+    sqlite_json1_available: bool,
 ) -> Result<InvocationListRequest, ApiError> {
     let mut request = build_invocation_list_request(params, list_limit_max)?;
+    if request.filters.payload_path.is_some() && !sqlite_json1_available {
+        return Err(ApiError::bad_request(anyhow!(
+            "payloadKey filters need SQLite with JSON1 support"
+        )));
+    }
     let Some(attempt_id) = normalize_query_text(params.attempt_id.as_deref()) else {
         return Ok(request);
     };
@@ -1144,6 +1274,24 @@ pub(crate) fn apply_invocation_records_filters(
         push_exact_text_filter(query, INVOCATION_STICKY_KEY_SQL, sticky_key);
     }
 
+    if let Some(label) = filters.label.as_deref() {
+        push_exact_text_filter(query, "label", label);
+    }
+
+    if let Some(payload_path) = filters.payload_path.as_deref() {
+        let payload_expr = format!(
+            "CASE WHEN json_valid(payload) THEN CAST(json_extract(payload, '{payload_path}') AS TEXT) END"
+        );
+        match filters.payload_val.as_deref() {
+            Some(payload_val) => push_exact_text_filter(query, &payload_expr, payload_val),
+            None => {
+                query.push(" AND ");
+                query.push(payload_expr);
+                query.push(" IS NOT NULL");
+            }
+        }
+    }
+
     if let Some(upstream_scope) = filters.upstream_scope.as_deref() {
         push_exact_text_filter(query, INVOCATION_UPSTREAM_SCOPE_SQL, upstream_scope);
     }
@@ -1283,10 +1431,15 @@ fn usage_breakdown_payload_text(payload: Option<&str>, key: &str) -> Option<Stri
 }
 
 pub(crate) fn normalized_usage_breakdown_model(
+    model_canonical: Option<&str>,
     model: Option<&str>,
     payload: Option<&str>,
 ) -> String {
-    usage_breakdown_payload_text(payload, "responseModel")
+    model_canonical
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or_else(|| usage_breakdown_payload_text(payload, "responseModel"))
         .or_else(|| {
             model
                 .map(str::trim)
@@ -1509,6 +1662,10 @@ pub(crate) fn runtime_record_matches_filters(
     {
         return false;
     }
+    // Labels and payload JSON only exist on persisted rows.
+    if filters.label.is_some() || filters.payload_path.is_some() {
+        return false;
+    }
     if let Some(upstream_scope) = filters.upstream_scope.as_deref()
         && !runtime_text_equals(Some(runtime_upstream_scope(record)), upstream_scope)
     {
@@ -1980,6 +2137,7 @@ pub(crate) fn runtime_overlay_total_delta(
     runtime_records: &[ApiInvocation],
     db_runtime_keys: &HashSet<(String, String)>,
     db_terminal_keys: &HashSet<(String, String)>,
+    treat_empty_error_as_success: bool,
 ) -> (RuntimeSummaryOverlayDelta, usize, usize) {
     if runtime_records.is_empty() {
         return (RuntimeSummaryOverlayDelta::default(), 0, 0);
@@ -2020,7 +2178,7 @@ pub(crate) fn runtime_overlay_total_delta(
         if runtime_record_is_in_flight(record) {
             continue;
         }
-        delta.add_terminal_record(record);
+        delta.add_terminal_record(record, treat_empty_error_as_success);
     }
     (delta, runtime_new_count, stale_db_runtime_count)
 }
@@ -2043,7 +2201,7 @@ pub(crate) struct RuntimeSummaryOverlayDelta {
 }
 
 impl RuntimeSummaryOverlayDelta {
-    fn add_terminal_record(&mut self, record: &ApiInvocation) {
+    fn add_terminal_record(&mut self, record: &ApiInvocation, treat_empty_error_as_success: bool) {
         self.total_tokens += record.total_tokens.unwrap_or_default();
         self.total_cost += record.cost.unwrap_or_default();
         self.cache_write_tokens +=
@@ -2051,7 +2209,9 @@ impl RuntimeSummaryOverlayDelta {
         self.cache_input_tokens += record.cache_input_tokens.unwrap_or_default();
         self.output_tokens += record.output_tokens.unwrap_or_default();
         let failure_class = normalized_runtime_text(record.failure_class.as_deref());
-        if failure_class == "none" && runtime_record_is_success_for_summary(record) {
+        if failure_class == "none"
+            && runtime_record_is_success_for_summary(record, treat_empty_error_as_success)
+        {
             self.success_count += 1;
         }
         match failure_class.as_str() {
@@ -2089,13 +2249,15 @@ impl RuntimeSummaryOverlayDelta {
     }
 }
 
-pub(crate) fn runtime_record_is_success_for_summary(record: &ApiInvocation) -> bool {
-    let status = normalized_runtime_text(record.status.as_deref());
-    status == "success"
-        || status == "completed"
-        || status == INVOCATION_STATUS_WARNING_SUCCESS
-        || (status == "http_200"
-            && normalized_runtime_text(record.error_message.as_deref()).is_empty())
+pub(crate) fn runtime_record_is_success_for_summary(
+    record: &ApiInvocation,
+    treat_empty_error_as_success: bool,
+) -> bool {
+    crate::invocation_status_is_success_like(
+        record.status.as_deref(),
+        record.error_message.as_deref(),
+        treat_empty_error_as_success,
+    )
 }
 
 pub(crate) async fn query_invocation_network_summary(
@@ -2562,6 +2724,9 @@ pub(crate) fn is_legacy_invocation_stream_query(params: &ListQuery) -> bool {
         && params.max_total_tokens.is_none()
         && params.min_total_ms.is_none()
         && params.max_total_ms.is_none()
+        && params.label.is_none()
+        && params.payload_key.is_none()
+        && params.payload_val.is_none()
 }
 
 pub(crate) async fn query_invocation_exception_summary(
@@ -2607,23 +2772,158 @@ pub(crate) async fn query_invocation_exception_summary(
     })
 }
 
+/// Serialized `ApiInvocation` keys that `GET /api/invocations?fields=` may select.
+pub(crate) const INVOCATION_LIST_FIELDS: &[&str] = &[
+    "id",
+    "invokeId",
+    "occurredAt",
+    "source",
+    "proxyDisplayName",
+    "model",
+    "requestModel",
+    "responseModel",
+    "inputTokens",
+    "outputTokens",
+    "cacheInputTokens",
+    "reasoningTokens",
+    "reasoningEffort",
+    "totalTokens",
+    "cost",
+    "costInput",
+    "costCacheWrite",
+    "costCacheRead",
+    "costOutput",
+    "costReasoning",
+    "cacheWriteTokens",
+    "status",
+    "livePhase",
+    "errorMessage",
+    "downstreamStatusCode",
+    "httpStatus",
+    "label",
+    "failureKind",
+    "blockedBinding",
+    "streamTerminalEvent",
+    "upstreamErrorCode",
+    "upstreamErrorMessage",
+    "downstreamErrorMessage",
+    "upstreamRequestId",
+    "failureClass",
+    "isActionable",
+    "endpoint",
+    "compactionRequestKind",
+    "compactionResponseKind",
+    "imageIntent",
+    "requesterIp",
+    "promptCacheKey",
+    "routeMode",
+    "upstreamAccountId",
+    "upstreamAccountName",
+    "responseContentEncoding",
+    "transport",
+    "poolAttemptCount",
+    "poolDistinctAccountCount",
+    "poolAttemptTerminalReason",
+    "requestedServiceTier",
+    "serviceTier",
+    "billingServiceTier",
+    "proxyWeightDelta",
+    "costEstimated",
+    "priceVersion",
+    "costAudit",
+    "requestRawPath",
+    "requestRawSize",
+    "requestRawTruncated",
+    "requestRawTruncatedReason",
+    "responseRawPath",
+    "responseRawSize",
+    "responseRawTruncated",
+    "responseRawTruncatedReason",
+    "detailLevel",
+    "detailPrunedAt",
+    "detailPruneReason",
+    "tTotalMs",
+    "tReqReadMs",
+    "tReqParseMs",
+    "tUpstreamConnectMs",
+    "tUpstreamTtfbMs",
+    "tUpstreamStreamMs",
+    "tRespParseMs",
+    "tPersistMs",
+    "createdAt",
+];
+
+pub(crate) fn parse_invocation_list_fields(
+    raw: Option<&str>,
+) -> Result<Option<HashSet<String>>, ApiError> {
+    let Some(raw) = raw.map(str::trim).filter(|raw| !raw.is_empty()) else {
+        return Ok(None);
+    };
+    let fields = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(str::to_string)
+        .collect::<HashSet<_>>();
+    let mut unknown = fields
+        .iter()
+        .filter(|field| !INVOCATION_LIST_FIELDS.contains(&field.as_str()))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    if !unknown.is_empty() {
+        unknown.sort_unstable();
+        return Err(ApiError::bad_request(anyhow!(
+            "unknown invocation fields: {}",
+            unknown.join(", ")
+        )));
+    }
+    Ok(Some(fields))
+}
+
+/// Route handler for `/api/invocations`: `list_invocations` with the optional
+/// `fields` projection pushed into the select list and applied to each
+/// serialized record.
+pub(crate) async fn list_invocations_with_fields(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListQuery>,
+) -> Result<Response, ApiError> {
+    let fields = parse_invocation_list_fields(params.fields.as_deref())?;
+    let runtime_overlay = load_invocation_anchor_runtime_records(&params)?;
+    let Json(response) =
+        list_invocations_with_runtime_overlay(state, params, runtime_overlay, fields.as_ref())
+            .await?;
+    let Some(fields) = fields else {
+        return Ok(Json(response).into_response());
+    };
+    let mut body = serde_json::to_value(&response)
+        .map_err(|err| ApiError::from(anyhow!("failed to serialize invocations: {err}")))?;
+    if let Some(records) = body.get_mut("records").and_then(Value::as_array_mut) {
+        for record in records.iter_mut().filter_map(Value::as_object_mut) {
+            record.retain(|key, _| fields.contains(key));
+        }
+    }
+    Ok(Json(body).into_response())
+}
+
 pub(crate) async fn list_invocations(
     State(state): State<Arc<AppState>>,
     Query(params): Query<ListQuery>,
 ) -> Result<Json<ListResponse>, ApiError> {
     let runtime_overlay = load_invocation_anchor_runtime_records(&params)?;
-    list_invocations_with_runtime_overlay(state, params, runtime_overlay).await
+    list_invocations_with_runtime_overlay(state, params, runtime_overlay, None).await
 }
 
 async fn list_invocations_with_runtime_overlay(
     state: Arc<AppState>,
     params: ListQuery,
     runtime_overlay_override: Option<Vec<ApiInvocation>>,
+    fields: Option<&HashSet<String>>,
 ) -> Result<Json<ListResponse>, ApiError> {
     let request = build_resolved_invocation_list_request(
         &state.pool,
         &params,
         state.config.list_limit_max as i64,
+        state.sqlite_json1_available,
     )
     .await?;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
@@ -2634,6 +2934,12 @@ async fn list_invocations_with_runtime_overlay(
         }
         None => Vec::new(),
     };
+    // Runtime overlay merges re-sort the page in memory, so keep the sort column.
+    let projection = fields.map(|fields| {
+        let mut projection = fields.clone();
+        projection.insert(request.sort_by.record_field().to_string());
+        projection
+    });
     if is_legacy_invocation_stream_query(&params) {
         let db_terminal_keys = if runtime_overlay_records.is_empty() {
             HashSet::new()
@@ -2641,7 +2947,7 @@ async fn list_invocations_with_runtime_overlay(
             query_terminal_db_keys_for_runtime_records(&state.pool, &runtime_overlay_records, None)
                 .await?
         };
-        let mut query = build_invocation_select_query();
+        let mut query = build_invocation_projected_select_query(projection.as_ref());
         apply_invocation_records_filters(&mut query, &request.filters, source_scope, None);
         append_invocation_order_clause(&mut query, request.sort_by, request.sort_order);
         query.push(" LIMIT ").push_bind(request.page_size);
@@ -2782,7 +3088,7 @@ async fn list_invocations_with_runtime_overlay(
         }));
     }
 
-    let mut query = build_invocation_select_query();
+    let mut query = build_invocation_projected_select_query(projection.as_ref());
     apply_invocation_records_filters(
         &mut query,
         &request.filters,
@@ -3075,6 +3381,7 @@ pub(crate) async fn locate_invocation_page(
                 ..Default::default()
             },
             Some(anchor_runtime_records.clone()),
+            None,
         )
         .await?;
         if let Some(target_index) = response.records.iter().position(|record| {
@@ -5091,6 +5398,128 @@ fn build_response_body_response(
     }
 }
 
+pub(crate) async fn fetch_invocation_by_id(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+) -> Result<axum::response::Response, ApiError> {
+    match load_persisted_api_invocation_by_id(&state.pool, id).await? {
+        Some(record) => Ok(Json(record).into_response()),
+        None => Ok((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "code": "invocation_not_found",
+                "message": "invocation record not found",
+                "id": id,
+            })),
+        )
+            .into_response()),
+    }
+}
+
+pub(crate) async fn delete_invocation_by_id(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+) -> Result<axum::response::Response, (StatusCode, String)> {
+    authenticate_external_api_key(state.as_ref(), &headers).await?;
+    let deleted =
+        delete_persisted_invocation(&state.pool, id, state.config.treat_empty_error_as_success)
+            .await
+            .map_err(internal_error_tuple)?;
+    if !deleted {
+        return Ok((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "code": "invocation_not_found",
+                "message": "invocation record not found",
+                "id": id,
+            })),
+        )
+            .into_response());
+    }
+    state.sqlite_batch_writer.bump_write_generation();
+    info!(id, "deleted invocation record");
+    let _ = state.broadcaster.send(BroadcastPayload::Deleted { id });
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct InvocationLabelRequest {
+    pub(crate) label: Option<String>,
+}
+
+/// Sets or clears (`null`/blank) the free-form bookkeeping label of one
+/// invocation. Labels are client metadata only; capture never writes them.
+pub(crate) async fn update_invocation_label(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    axum::extract::Path(id): axum::extract::Path<i64>,
+    Json(request): Json<InvocationLabelRequest>,
+) -> Result<axum::response::Response, (StatusCode, String)> {
+    authenticate_external_api_key(state.as_ref(), &headers).await?;
+    let label = normalize_query_text(request.label.as_deref());
+    let updated = sqlx::query("UPDATE codex_invocations SET label = ?1 WHERE id = ?2")
+        .bind(label.as_deref())
+        .bind(id)
+        .execute(&state.pool)
+        .await
+        .map_err(internal_error_tuple)?
+        .rows_affected();
+    if updated == 0 {
+        return Ok((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "code": "invocation_not_found",
+                "message": "invocation record not found",
+                "id": id,
+            })),
+        )
+            .into_response());
+    }
+    state.sqlite_batch_writer.bump_write_generation();
+    Ok(Json(json!({ "id": id, "label": label })).into_response())
+}
+
+/// Removes one live invocation row and rebuilds the hourly rollups of its bucket
+/// so aggregates stop counting it. Returns `false` when no such row exists.
+pub(crate) async fn delete_persisted_invocation(
+    pool: &Pool<Sqlite>,
+    id: i64,
+    treat_empty_error_as_success: bool,
+) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+    let Some(occurred_at) = sqlx::query_scalar::<_, String>(
+        "DELETE FROM codex_invocations WHERE id = ?1 RETURNING occurred_at",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(false);
+    };
+    // Catch the live rollup cursor up before rebuilding the bucket from every live row;
+    // otherwise the next replay would count the bucket's rows past the cursor again.
+    let max_live_id =
+        sqlx::query_scalar::<_, i64>("SELECT COALESCE(MAX(id), 0) FROM codex_invocations")
+            .fetch_one(&mut *tx)
+            .await?;
+    replay_live_invocation_hourly_rollups_until_tx(
+        tx.as_mut(),
+        max_live_id,
+        treat_empty_error_as_success,
+    )
+    .await?;
+    let bucket_epoch = invocation_bucket_start_epoch(&occurred_at)?;
+    recompute_invocation_hourly_rollups_for_bucket_epochs_tx(
+        tx.as_mut(),
+        &[bucket_epoch],
+        treat_empty_error_as_success,
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
 pub(crate) async fn fetch_invocation_record_detail(
     State(state): State<Arc<AppState>>,
     axum::extract::Path(id): axum::extract::Path<i64>,
@@ -5161,6 +5590,7 @@ pub(crate) async fn fetch_invocation_summary(
         &state.pool,
         &params,
         state.config.list_limit_max as i64,
+        state.sqlite_json1_available,
     )
     .await?;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
@@ -5237,6 +5667,7 @@ pub(crate) async fn fetch_invocation_summary(
             &runtime_records,
             &db_runtime_keys,
             &db_terminal_keys,
+            state.config.treat_empty_error_as_success,
         );
         if runtime_new_count > 0 || stale_db_runtime_count > 0 {
             debug!(
@@ -5330,6 +5761,7 @@ pub(crate) async fn fetch_invocation_new_records_count(
         &state.pool,
         &params,
         state.config.list_limit_max as i64,
+        state.sqlite_json1_available,
     )
     .await?;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
@@ -5359,6 +5791,7 @@ pub(crate) async fn fetch_invocation_suggestions(
         &state.pool,
         &params,
         state.config.list_limit_max as i64,
+        state.sqlite_json1_available,
     )
     .await?;
     let filters = request.filters;
@@ -5576,7 +6009,14 @@ pub(crate) async fn fetch_stats(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<StatsResponse>, ApiError> {
     let source_scope = resolve_default_source_scope(&state.pool).await?;
-    let totals = query_combined_totals(&state.pool, StatsFilter::All, source_scope).await?;
+    let totals = query_combined_totals(
+        &state.pool,
+        StatsFilter::All,
+        source_scope,
+        state.archive_db_attached,
+        state.config.treat_empty_error_as_success,
+    )
+    .await?;
     let mut response = totals.into_response();
     response.non_success_cost = Some(totals.non_success_cost);
     let augmentation = load_summary_live_augmentation(
@@ -5593,9 +6033,111 @@ pub(crate) async fn fetch_stats(
     .await?;
     apply_summary_live_augmentation(&mut response, augmentation);
     response.maintenance = Some(load_stats_maintenance_response(state.as_ref()).await?);
+    response.refunds = Some(
+        query_all_time_refund_invocation_count(
+            &state.pool,
+            source_scope,
+            state.archive_db_attached,
+        )
+        .await?,
+    );
     Ok(Json(response))
 }
 
+pub(crate) async fn fetch_stats_cached(
+    State(state): State<Arc<AppState>>,
+    Query(units): Query<TokenUnitsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let units = TokenUnits::parse(units.units.as_deref())?;
+    let Json(stats) = fetch_stats(State(state)).await?;
+    let body = serialize_with_token_units(&stats, units)
+        .map_err(|err| ApiError::from(anyhow!("failed to serialize stats: {err}")))?;
+    let digest = Sha256::digest(&body);
+    let etag = format!("\"stats-{digest:x}\"");
+    conditional_json_response(&headers, &etag, body)
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct TokenUnitsQuery {
+    pub(crate) units: Option<String>,
+}
+
+/// Optional `?units=k|m` pre-scaling of token counts for display-only clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenUnits {
+    Raw,
+    Thousands,
+    Millions,
+}
+
+impl TokenUnits {
+    pub(crate) fn parse(raw: Option<&str>) -> Result<Self, ApiError> {
+        match raw.map(str::trim).unwrap_or_default() {
+            "" => Ok(Self::Raw),
+            "k" | "K" => Ok(Self::Thousands),
+            "m" | "M" => Ok(Self::Millions),
+            other => Err(ApiError::bad_request(anyhow!(
+                "unsupported units: {other}; expected k|m"
+            ))),
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Raw => "",
+            Self::Thousands => "k",
+            Self::Millions => "m",
+        }
+    }
+
+    fn divisor(self) -> Option<f64> {
+        match self {
+            Self::Raw => None,
+            Self::Thousands => Some(1e3),
+            Self::Millions => Some(1e6),
+        }
+    }
+}
+
+/// Divides every numeric `*Tokens` field (at any depth) by the unit, turning
+/// the raw integers into floats. `TokenUnits::Raw` leaves the value untouched.
+pub(crate) fn scale_token_fields(value: &mut Value, units: TokenUnits) {
+    let Some(divisor) = units.divisor() else {
+        return;
+    };
+    match value {
+        Value::Object(object) => {
+            for (key, field) in object.iter_mut() {
+                match field.as_f64() {
+                    Some(raw) if key.ends_with("Tokens") || key == "avgTokensPerRequest" => {
+                        *field = Value::from(raw / divisor)
+                    }
+                    _ => scale_token_fields(field, units),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                scale_token_fields(item, units);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn serialize_with_token_units<T: Serialize>(
+    response: &T,
+    units: TokenUnits,
+) -> Result<Vec<u8>, serde_json::Error> {
+    if units == TokenUnits::Raw {
+        return serde_json::to_vec(response);
+    }
+    let mut value = serde_json::to_value(response)?;
+    scale_token_fields(&mut value, units);
+    serde_json::to_vec(&value)
+}
+
 pub(crate) async fn load_in_progress_conversation_count(
     state: &AppState,
     source_scope: InvocationSourceScope,
@@ -5860,8 +6402,8 @@ pub(crate) fn summary_window_range(
         SummaryWindow::All | SummaryWindow::Current(_) => Ok(None),
         SummaryWindow::Duration(duration) => Ok(Some((now - *duration, now))),
         SummaryWindow::Calendar(spec) => {
-            let range =
-                resolve_range_window(spec.as_str(), reporting_tz).map_err(ApiError::from)?;
+            let range = resolve_range_window_at(spec.as_str(), reporting_tz, now)
+                .map_err(ApiError::from)?;
             Ok(Some((range.start, range.end)))
         }
         SummaryWindow::PreviousFullDays(day_count) => {
@@ -6385,6 +6927,8 @@ pub(crate) async fn build_empty_summary_response(
         failure_count: 0,
         total_cost: 0.0,
         total_tokens: 0,
+        avg_cost_per_request: 0.0,
+        avg_tokens_per_request: 0.0,
         usage_breakdown: None,
         in_progress_conversation_count: None,
         in_progress_retry_conversation_count: None,
@@ -6393,6 +6937,7 @@ pub(crate) async fn build_empty_summary_response(
         non_success_cost: Some(0.0),
         non_success_tokens: None,
         maintenance: Some(load_stats_maintenance_response(state).await?),
+        refunds: None,
     };
     let augmentation = load_summary_live_augmentation(
         state,
@@ -6737,8 +7282,13 @@ pub(crate) struct DashboardActivityCurrentMinuteAccumulator {
 }
 
 impl DashboardActivityCurrentMinuteAccumulator {
-    fn add_row(&mut self, row: &UpstreamAccountInvocationPreviewRow) {
-        self.total_cost += row.cost.unwrap_or_default().max(0.0);
+    fn add_row(
+        &mut self,
+        row: &UpstreamAccountInvocationPreviewRow,
+        treat_empty_error_as_success: bool,
+    ) {
+        // Refunds net out here exactly as they do in the range totals.
+        self.total_cost += row.cost.unwrap_or_default();
 
         let classification = resolve_failure_classification(
             Some(row.status.as_str()),
@@ -6751,6 +7301,7 @@ impl DashboardActivityCurrentMinuteAccumulator {
             prompt_cache_and_timeseries_shared::prompt_invocation_status_is_success_like(
                 Some(row.status.as_str()),
                 row.error_message.as_deref(),
+                treat_empty_error_as_success,
             ) && classification.failure_class == FailureClass::None;
         let is_qualified_tpm = is_success && row.cost.is_some();
 
@@ -8280,6 +8831,7 @@ async fn query_upstream_account_usage_breakdown_rows_from_executor<'e, E>(
     source_scope: InvocationSourceScope,
     range: ExactUtcRange,
     has_cost_breakdown_columns: bool,
+    has_model_canonical_column: bool,
     use_attempt_fallback: bool,
     exclude_invocation_ids: DashboardActivityExcludedInvocationIdsFilter<'_>,
     start_after_id: Option<i64>,
@@ -8294,9 +8846,14 @@ where
     } else {
         INVOCATION_UPSTREAM_ACCOUNT_ID_SQL.to_string()
     };
+    let model_canonical_sql = if has_model_canonical_column {
+        "NULLIF(TRIM(model_canonical), ''), "
+    } else {
+        ""
+    };
     let model_sql = format!(
-        "COALESCE(NULLIF(TRIM({}), ''), NULLIF(TRIM(model), ''), 'unknown')",
-        INVOCATION_RESPONSE_MODEL_SQL
+        "COALESCE({}NULLIF(TRIM({}), ''), NULLIF(TRIM(model), ''), 'unknown')",
+        model_canonical_sql, INVOCATION_RESPONSE_MODEL_SQL
     );
     let reasoning_effort_sql = format!("NULLIF(TRIM({}), '')", INVOCATION_REASONING_EFFORT_SQL);
     let cost_complete_sql = if has_cost_breakdown_columns {
@@ -8415,6 +8972,7 @@ async fn query_live_upstream_account_usage_breakdown_rows(
         source_scope,
         range,
         has_cost_breakdown_columns,
+        true,
         use_attempt_fallback,
         exclude_invocation_ids,
         None,
@@ -8454,6 +9012,7 @@ async fn query_live_upstream_account_usage_breakdown_rows_tx(
         source_scope,
         range,
         has_cost_breakdown_columns,
+        true,
         use_attempt_fallback,
         exclude_invocation_ids,
         start_after_id,
@@ -8476,7 +9035,7 @@ async fn query_live_model_performance_duration_overrides(
         INVOCATION_UPSTREAM_ACCOUNT_ID_SQL.to_string()
     };
     let model_sql = format!(
-        "COALESCE(NULLIF(TRIM({}), ''), NULLIF(TRIM(model), ''), 'unknown')",
+        "COALESCE(NULLIF(TRIM(model_canonical), ''), NULLIF(TRIM({}), ''), NULLIF(TRIM(model), ''), 'unknown')",
         INVOCATION_RESPONSE_MODEL_SQL
     );
     let reasoning_effort_sql = format!("NULLIF(TRIM({}), '')", INVOCATION_REASONING_EFFORT_SQL);
@@ -9491,6 +10050,7 @@ async fn query_unmaterialized_archive_usage_breakdown_rows(
     ranges: &[UsageBreakdownArchiveFallbackRange],
     upstream_account_id: Option<i64>,
     exclude_invocation_ids: Option<&HashSet<i64>>,
+    treat_empty_error_as_success: bool,
 ) -> Result<
     (
         Vec<UpstreamAccountUsageBreakdownAggregateRow>,
@@ -9551,14 +10111,23 @@ async fn query_unmaterialized_archive_usage_breakdown_rows(
             fallback_reason = "archive_batch_unavailable";
             continue;
         };
-        if crate::maintenance::invocation_archive_has_pruned_success_details_in_db(&archive_pool)
-            .await?
+        if crate::maintenance::invocation_archive_has_pruned_success_details_in_db(
+            &archive_pool,
+            treat_empty_error_as_success,
+        )
+        .await?
         {
             legacy_pruned_payload_mode = "structured_rollup_unknown_reasoning";
         }
         let has_cost_breakdown_columns =
             crate::stats::sqlite_table_has_column(&archive_pool, "codex_invocations", "cost_input")
                 .await?;
+        let has_model_canonical_column = crate::stats::sqlite_table_has_column(
+            &archive_pool,
+            "codex_invocations",
+            "model_canonical",
+        )
+        .await?;
         let exclude_filter = prepare_dashboard_activity_excluded_invocation_ids_filter(
             &archive_pool,
             exclude_invocation_ids,
@@ -9570,6 +10139,7 @@ async fn query_unmaterialized_archive_usage_breakdown_rows(
                 source_scope,
                 fallback_range.range,
                 has_cost_breakdown_columns,
+                has_model_canonical_column,
                 false,
                 exclude_filter,
                 (fallback_range.skip_replayed_prefix && start_after_id > 0)
@@ -9778,6 +10348,7 @@ async fn load_usage_breakdown_rows_for_range(
                 &archive_fallback_ranges,
                 upstream_account_id,
                 (!archive_overlap_ids.is_empty()).then_some(&archive_overlap_ids),
+                state.config.treat_empty_error_as_success,
             )
             .await?
         };
@@ -10194,6 +10765,8 @@ impl DashboardActivitySnapshot {
                     failure_count: 0,
                     total_cost: 0.0,
                     total_tokens: 0,
+                    avg_cost_per_request: 0.0,
+                    avg_tokens_per_request: 0.0,
                     usage_breakdown: None,
                     in_progress_conversation_count: None,
                     in_progress_retry_conversation_count: None,
@@ -10202,6 +10775,7 @@ impl DashboardActivitySnapshot {
                     non_success_cost: None,
                     non_success_tokens: None,
                     maintenance: None,
+                    refunds: None,
                 },
                 tokens_per_minute: None,
                 spend_rate: None,
@@ -10295,19 +10869,23 @@ fn dashboard_activity_stats_totals_subtract(left: StatsTotals, right: StatsTotal
             .failure_count
             .saturating_sub(right.failure_count)
             .max(0),
-        total_cost: (left.total_cost - right.total_cost).max(0.0),
+        total_cost: left.total_cost - right.total_cost,
         total_tokens: left.total_tokens.saturating_sub(right.total_tokens).max(0),
-        non_success_cost: (left.non_success_cost - right.non_success_cost).max(0.0),
+        non_success_cost: left.non_success_cost - right.non_success_cost,
     }
 }
 
+/// Signed cost residuals below this are float noise from archive-minus-materialized
+/// subtraction, not a real charge or refund.
+const DASHBOARD_ACTIVITY_COST_RESIDUAL_EPSILON_USD: f64 = 0.000001;
+
 fn dashboard_activity_stats_totals_has_values(totals: StatsTotals) -> bool {
     totals.total_count > 0
         || totals.success_count > 0
         || totals.failure_count > 0
-        || totals.total_cost > 0.0
+        || totals.total_cost.abs() > DASHBOARD_ACTIVITY_COST_RESIDUAL_EPSILON_USD
         || totals.total_tokens > 0
-        || totals.non_success_cost > 0.0
+        || totals.non_success_cost.abs() > DASHBOARD_ACTIVITY_COST_RESIDUAL_EPSILON_USD
 }
 
 async fn dashboard_activity_materialized_archive_fallback_totals(
@@ -10399,7 +10977,7 @@ async fn dashboard_activity_historical_live_gap_totals(
     Ok(totals)
 }
 
-fn dashboard_activity_apply_materialized_archive_fallback_to_stats(
+pub(crate) fn dashboard_activity_apply_materialized_archive_fallback_to_stats(
     stats: &mut StatsResponse,
     fallback_totals: StatsTotals,
 ) {
@@ -10414,6 +10992,9 @@ fn dashboard_activity_apply_materialized_archive_fallback_to_stats(
     stats.total_tokens += fallback_totals.total_tokens;
     stats.non_success_cost =
         Some(stats.non_success_cost.unwrap_or_default() + fallback_totals.non_success_cost);
+    stats.avg_cost_per_request = per_request_average(stats.total_cost, stats.total_count);
+    stats.avg_tokens_per_request =
+        per_request_average(stats.total_tokens as f64, stats.total_count);
     // Materialized invocation rollups do not retain model/cost-breakdown or non-success-token
     // detail, so omit those partial fields when they would no longer align with top-level totals.
     stats.usage_breakdown = None;
@@ -10839,8 +11420,10 @@ fn dashboard_activity_snapshot_selection_anchor(
 fn resolve_dashboard_activity_exact_range(
     range_name: &str,
     reporting_tz: Tz,
+    now: DateTime<Utc>,
 ) -> Result<ExactUtcRange, ApiError> {
-    let range_window = resolve_range_window(range_name, reporting_tz).map_err(ApiError::from)?;
+    let range_window =
+        resolve_range_window_at(range_name, reporting_tz, now).map_err(ApiError::from)?;
     Ok(ExactUtcRange {
         start: range_window.start,
         end: range_window.end,
@@ -10850,8 +11433,9 @@ fn resolve_dashboard_activity_exact_range(
 pub(crate) fn resolve_dashboard_activity_cached_range(
     range_name: &str,
     reporting_tz: Tz,
+    now: DateTime<Utc>,
 ) -> Result<ExactUtcRange, ApiError> {
-    resolve_dashboard_activity_exact_range(range_name, reporting_tz)
+    resolve_dashboard_activity_exact_range(range_name, reporting_tz, now)
 }
 
 fn dashboard_activity_full_hour_exact_range(
@@ -11468,7 +12052,7 @@ async fn load_dashboard_activity_current_minute_accumulators_by_account(
         accumulators
             .entry(row.upstream_account_id)
             .or_default()
-            .add_row(&row);
+            .add_row(&row, state.config.treat_empty_error_as_success);
     }
     Ok(accumulators)
 }
@@ -11514,12 +12098,17 @@ pub(crate) fn build_dashboard_activity_summary(
     for account in accounts {
         usage_breakdown.merge_response(&account.usage_breakdown);
     }
+    let total_count = accounts.iter().map(|account| account.request_count).sum();
+    let total_cost = accounts.iter().map(|account| account.total_cost).sum();
+    let total_tokens = accounts.iter().map(|account| account.total_tokens).sum();
     let stats = StatsResponse {
-        total_count: accounts.iter().map(|account| account.request_count).sum(),
+        total_count,
         success_count: accounts.iter().map(|account| account.success_count).sum(),
         failure_count: accounts.iter().map(|account| account.failure_count).sum(),
-        total_cost: accounts.iter().map(|account| account.total_cost).sum(),
-        total_tokens: accounts.iter().map(|account| account.total_tokens).sum(),
+        total_cost,
+        total_tokens,
+        avg_cost_per_request: per_request_average(total_cost, total_count),
+        avg_tokens_per_request: per_request_average(total_tokens as f64, total_count),
         usage_breakdown: Some(usage_breakdown.into_response()),
         in_progress_conversation_count: include_live_counts.then(|| {
             accounts
@@ -11560,6 +12149,7 @@ pub(crate) fn build_dashboard_activity_summary(
                 .sum(),
         ),
         maintenance: None,
+        refunds: None,
     };
 
     DashboardActivitySummaryResponse {
@@ -11889,7 +12479,8 @@ pub(crate) async fn load_dashboard_activity_snapshot(
     include_recent: bool,
     in_progress_counts_override: Option<HashMap<Option<i64>, UpstreamAccountInProgressSummary>>,
 ) -> Result<DashboardActivitySnapshot, ApiError> {
-    let range = resolve_dashboard_activity_exact_range(range_name, reporting_tz)?;
+    let range =
+        resolve_dashboard_activity_exact_range(range_name, reporting_tz, state.clock.now())?;
     load_dashboard_activity_snapshot_for_range(
         state,
         range_name,
@@ -12535,7 +13126,8 @@ async fn load_dashboard_activity_snapshot_cached(
 > {
     if range_name == "yesterday" {
         let started_at = Instant::now();
-        let range = resolve_dashboard_activity_exact_range(range_name, reporting_tz)?;
+        let range =
+            resolve_dashboard_activity_exact_range(range_name, reporting_tz, state.clock.now())?;
         let snapshot = load_dashboard_activity_snapshot_for_range(
             state,
             range_name,
@@ -12565,7 +13157,8 @@ async fn load_dashboard_activity_snapshot_cached(
     }
 
     let source_scope = resolve_default_source_scope(&state.pool).await?;
-    let range = resolve_dashboard_activity_cached_range(range_name, reporting_tz)?;
+    let range =
+        resolve_dashboard_activity_cached_range(range_name, reporting_tz, state.clock.now())?;
     let selection = build_dashboard_activity_snapshot_selection(
         range_name,
         range,
@@ -12787,8 +13380,11 @@ pub(crate) async fn fetch_dashboard_activity(
         .as_ref()
         .and_then(|snapshot| snapshot.network_realtime_rate.clone());
     let include_recent = params.include_recent.unwrap_or(true);
-    let request_range =
-        resolve_dashboard_activity_exact_range(params.range.as_str(), reporting_tz)?;
+    let request_range = resolve_dashboard_activity_exact_range(
+        params.range.as_str(),
+        reporting_tz,
+        state.clock.now(),
+    )?;
     let (mut snapshot, cache_outcome) = load_dashboard_activity_snapshot_cached(
         state.as_ref(),
         params.range.as_str(),
@@ -13461,7 +14057,8 @@ pub(crate) async fn fetch_dashboard_network_timeseries(
     validate_dashboard_network_timeseries_range(params.range.as_str())?;
     let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
     let range_window =
-        resolve_range_window(params.range.as_str(), reporting_tz).map_err(ApiError::from)?;
+        resolve_range_window_at(params.range.as_str(), reporting_tz, state.clock.now())
+            .map_err(ApiError::from)?;
     let range = ExactUtcRange {
         start: range_window.start,
         end: range_window.end,
@@ -13615,7 +14212,11 @@ pub(crate) async fn fetch_upstream_account_activity(
         params.recent_limit,
     )?;
     let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
-    let range = resolve_dashboard_activity_exact_range(params.range.as_str(), reporting_tz)?;
+    let range = resolve_dashboard_activity_exact_range(
+        params.range.as_str(),
+        reporting_tz,
+        state.clock.now(),
+    )?;
     let build = load_dashboard_activity_account_build_result(
         state.as_ref(),
         params.range.as_str(),
@@ -13827,6 +14428,30 @@ mod upstream_account_activity_rate_tests {
     }
 }
 
+#[cfg(test)]
+mod dashboard_activity_fallback_totals_tests {
+    use super::*;
+
+    fn cost_totals(total_cost: f64) -> StatsTotals {
+        StatsTotals {
+            total_cost,
+            ..StatsTotals::default()
+        }
+    }
+
+    #[test]
+    fn cost_float_residue_does_not_count_as_fallback_values() {
+        let residual =
+            dashboard_activity_stats_totals_subtract(cost_totals(0.1 + 0.2), cost_totals(0.3));
+        assert_ne!(residual.total_cost, 0.0);
+        assert!(!dashboard_activity_stats_totals_has_values(residual));
+
+        let refund = dashboard_activity_stats_totals_subtract(cost_totals(0.1), cost_totals(0.3));
+        assert!((refund.total_cost + 0.2).abs() < 1e-9);
+        assert!(dashboard_activity_stats_totals_has_values(refund));
+    }
+}
+
 #[cfg(test)]
 mod dashboard_network_timeseries_tests {
     use super::*;
@@ -14267,7 +14892,7 @@ pub(crate) async fn load_summary_response_from_query(
     let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
     let upstream_account_id = params.upstream_account_id;
-    let now = Utc::now();
+    let now = state.clock.now();
 
     if summary_range_telemetry.closed_window {
         let summary_delivery_mode = route.closed_window_delivery_mode();
@@ -14309,7 +14934,14 @@ pub(crate) async fn load_summary_response_from_query(
                 )
                 .await?
             } else {
-                query_combined_totals(&state.pool, StatsFilter::All, source_scope).await?
+                query_combined_totals(
+                    &state.pool,
+                    StatsFilter::All,
+                    source_scope,
+                    state.archive_db_attached,
+                    state.config.treat_empty_error_as_success,
+                )
+                .await?
             }
         }
         SummaryWindow::Current(limit) => {
@@ -14320,12 +14952,19 @@ pub(crate) async fn load_summary_response_from_query(
                         StatsFilter::RecentLimit(limit),
                         source_scope,
                         upstream_account_id,
+                        state.config.treat_empty_error_as_success,
                     )
                     .await?,
                 )
             } else {
-                query_combined_totals(&state.pool, StatsFilter::RecentLimit(limit), source_scope)
-                    .await?
+                query_combined_totals(
+                    &state.pool,
+                    StatsFilter::RecentLimit(limit),
+                    source_scope,
+                    state.archive_db_attached,
+                    state.config.treat_empty_error_as_success,
+                )
+                .await?
             }
         }
         SummaryWindow::Duration(duration) => {
@@ -14344,8 +14983,8 @@ pub(crate) async fn load_summary_response_from_query(
             }
         }
         SummaryWindow::Calendar(ref spec) => {
-            let range_window =
-                resolve_range_window(spec.as_str(), reporting_tz).map_err(ApiError::from)?;
+            let range_window = resolve_range_window_at(spec.as_str(), reporting_tz, now)
+                .map_err(ApiError::from)?;
             if range_window.start >= range_window.end {
                 return build_empty_summary_response(state, source_scope, upstream_account_id)
                     .await;
@@ -14428,6 +15067,23 @@ pub(crate) async fn fetch_summary(
     ))
 }
 
+pub(crate) async fn fetch_summary_in_units(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SummaryQuery>,
+    Query(units): Query<TokenUnitsQuery>,
+) -> Result<Response, ApiError> {
+    let units = TokenUnits::parse(units.units.as_deref())?;
+    let Json(summary) = fetch_summary(State(state), Query(params)).await?;
+    let body = serialize_with_token_units(&summary, units)
+        .map_err(|err| ApiError::from(anyhow!("failed to serialize summary: {err}")))?;
+    Ok((
+        StatusCode::OK,
+        [(axum::http::header::CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response())
+}
+
 pub(crate) async fn load_stats_maintenance_response(
     state: &AppState,
 ) -> Result<StatsMaintenanceResponse, ApiError> {
@@ -14496,6 +15152,8 @@ pub(crate) struct InvocationAggregateRecord {
     pub(crate) cache_input_tokens: Option<i64>,
     pub(crate) cost: Option<f64>,
     pub(crate) error_message: Option<String>,
+    #[sqlx(default)]
+    pub(crate) http_status: Option<i64>,
     pub(crate) failure_kind: Option<String>,
     pub(crate) failure_class: Option<String>,
     pub(crate) is_actionable: Option<i64>,
@@ -14594,6 +15252,8 @@ mod invocation_cost_audit_tests {
             live_phase: None,
             error_message: None,
             downstream_status_code: Some(200),
+            http_status: None,
+            label: None,
             failure_kind: None,
             blocked_binding: None,
             blocked_binding_json: None,
@@ -14,7 +14,7 @@ pub(crate) async fn fetch_forward_proxy_timeseries(
     Query(params): Query<TimeseriesQuery>,
 ) -> Result<Json<ForwardProxyTimeseriesResponse>, ApiError> {
     let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
-    let range_window = resolve_range_window(&params.range, reporting_tz)?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
     ensure_forward_proxy_hourly_tz_supported(reporting_tz, &range_window)?;
     let bucket_spec = params.bucket.as_deref().unwrap_or("1h");
     if bucket_seconds_from_spec(bucket_spec) != Some(3_600) {
@@ -0,0 +1,169 @@
+use super::*;
+
+/// Metric names offered to Grafana's SimpleJSON datasource, one per
+/// `TimeseriesPoint` field.
+pub(crate) const GRAFANA_METRICS: [&str; 5] = [
+    "request_count",
+    "success_count",
+    "failure_count",
+    "total_tokens",
+    "total_cost",
+];
+
+const GRAFANA_BUCKET_SPECS: [(&str, i64); 8] = [
+    ("1m", 60),
+    ("5m", 300),
+    ("15m", 900),
+    ("30m", 1_800),
+    ("1h", 3_600),
+    ("6h", 21_600),
+    ("12h", 43_200),
+    ("1d", 86_400),
+];
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct GrafanaSearchRequest {
+    #[serde(default)]
+    pub(crate) target: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GrafanaQueryRequest {
+    pub(crate) range: GrafanaTimeRange,
+    pub(crate) interval_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) targets: Vec<GrafanaQueryTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct GrafanaTimeRange {
+    pub(crate) from: String,
+    pub(crate) to: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct GrafanaQueryTarget {
+    pub(crate) target: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct GrafanaTimeseries {
+    pub(crate) target: String,
+    /// `[value, epoch_ms]` pairs, oldest first.
+    pub(crate) datapoints: Vec<(f64, i64)>,
+}
+
+pub(crate) async fn grafana_health(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<StatusCode, (StatusCode, String)> {
+    authenticate_external_api_key(state.as_ref(), &headers).await?;
+    Ok(StatusCode::OK)
+}
+
+pub(crate) async fn grafana_search(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<GrafanaSearchRequest>,
+) -> Result<Json<Vec<&'static str>>, (StatusCode, String)> {
+    authenticate_external_api_key(state.as_ref(), &headers).await?;
+    let needle = request.target.trim();
+    Ok(Json(
+        GRAFANA_METRICS
+            .into_iter()
+            .filter(|metric| metric.contains(needle))
+            .collect(),
+    ))
+}
+
+pub(crate) async fn grafana_query(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<GrafanaQueryRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    authenticate_external_api_key(state.as_ref(), &headers).await?;
+    let parse_bound = |raw: &str| {
+        DateTime::parse_from_rfc3339(raw)
+            .map(|value| value.with_timezone(&Utc))
+            .map_err(|err| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("invalid grafana range bound {raw}: {err}"),
+                )
+            })
+    };
+    let from = parse_bound(&request.range.from)?;
+    let to = parse_bound(&request.range.to)?;
+    if let Some(target) = request
+        .targets
+        .iter()
+        .find(|target| !GRAFANA_METRICS.contains(&target.target.as_str()))
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("unknown grafana metric: {}", target.target),
+        ));
+    }
+
+    // The timeseries endpoint only speaks lookback ranges, so ask for enough
+    // minutes to reach `from` and trim the buckets to the dashboard window.
+    let lookback_minutes = (state.clock.now() - from).num_minutes().max(0) + 1;
+    let bucket = request.interval_ms.and_then(|interval_ms| {
+        GRAFANA_BUCKET_SPECS
+            .iter()
+            .find(|(_, seconds)| seconds * 1_000 >= interval_ms)
+            .map(|(spec, _)| spec.to_string())
+    });
+    let timeseries = fetch_timeseries(
+        State(state.clone()),
+        Query(TimeseriesQuery {
+            range: format!("{lookback_minutes}m"),
+            bucket,
+            settlement_hour: None,
+            time_zone: Some("UTC".to_string()),
+            upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: Some(true),
+            ts: None,
+            resolution: None,
+        }),
+    )
+    .await;
+    let Json(timeseries) = match timeseries {
+        Ok(response) => response,
+        Err(err) => return Ok(err.into_response()),
+    };
+
+    let points = timeseries
+        .points
+        .iter()
+        .filter_map(|point| {
+            let start = DateTime::parse_from_rfc3339(&point.bucket_start).ok()?;
+            let end = DateTime::parse_from_rfc3339(&point.bucket_end).ok()?;
+            (end > from && start < to).then_some((start.timestamp_millis(), point))
+        })
+        .collect::<Vec<_>>();
+    let series = request
+        .targets
+        .iter()
+        .map(|target| GrafanaTimeseries {
+            target: target.target.clone(),
+            datapoints: points
+                .iter()
+                .map(|(epoch_ms, point)| {
+                    let value = match target.target.as_str() {
+                        "request_count" => point.total_count as f64,
+                        "success_count" => point.success_count as f64,
+                        "failure_count" => point.failure_count as f64,
+                        "total_tokens" => point.total_tokens as f64,
+                        _ => point.total_cost,
+                    };
+                    (value, *epoch_ms)
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    Ok(Json(series).into_response())
+}
@@ -22,3 +22,12 @@ pub(crate) use prompt_cache_conversations::*;
 )]
 pub(crate) mod prompt_cache_and_timeseries_timeseries;
 pub(crate) use prompt_cache_and_timeseries_timeseries::*;
+#[path = "timeseries_cache.rs"]
+mod prompt_cache_and_timeseries_timeseries_cache;
+pub(crate) use prompt_cache_and_timeseries_timeseries_cache::*;
+#[path = "timeseries_svg.rs"]
+mod prompt_cache_and_timeseries_timeseries_svg;
+pub(crate) use prompt_cache_and_timeseries_timeseries_svg::*;
+#[path = "grafana.rs"]
+mod prompt_cache_and_timeseries_grafana;
+pub(crate) use prompt_cache_and_timeseries_grafana::*;
@@ -462,13 +462,18 @@ pub(crate) async fn query_prompt_cache_conversation_encrypted_owner_summaries_at
     source_scope: InvocationSourceScope,
     selected_keys: &[String],
     snapshot: &PromptCacheConversationHydrationSnapshot<'_>,
+    treat_empty_error_as_success: bool,
 ) -> Result<Vec<PromptCacheConversationEncryptedOwnerSummaryRow>> {
     if selected_keys.is_empty() {
         return Ok(Vec::new());
     }
 
     const KEY_EXPR: &str = "CASE WHEN json_valid(payload) THEN TRIM(CAST(json_extract(payload, '$.promptCacheKey') AS TEXT)) END";
-    let success_like_sql = "(LOWER(TRIM(COALESCE(status, ''))) IN ('success', 'completed', 'warning_success') OR (LOWER(TRIM(COALESCE(status, ''))) = 'http_200' AND TRIM(COALESCE(error_message, '')) = ''))";
+    let success_like_sql = invocation_status_is_success_like_sql(
+        "status",
+        "error_message",
+        treat_empty_error_as_success,
+    );
     let mut query = QueryBuilder::<Sqlite>::new("WITH ranked AS (SELECT ");
     query
         .push(KEY_EXPR)
@@ -519,7 +524,7 @@ pub(crate) async fn query_prompt_cache_conversation_encrypted_owner_summaries_at
         .push(" IS NOT NULL AND ")
         .push(INVOCATION_RESOLVED_FAILURE_CLASS_SQL)
         .push(" = 'none' AND ")
-        .push(success_like_sql)
+        .push(success_like_sql.as_str())
         .push(
             " AND (\
                 (json_valid(payload) AND json_extract(payload, '$.requestContainsEncryptedContent') = 1) \
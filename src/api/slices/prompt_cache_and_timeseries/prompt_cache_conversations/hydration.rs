@@ -160,6 +160,7 @@ pub(crate) async fn hydrate_prompt_cache_conversations(
                 source_scope,
                 &selected_keys,
                 snapshot,
+                state.config.treat_empty_error_as_success,
             )
             .await?
         } else {
@@ -188,6 +189,7 @@ pub(crate) async fn hydrate_prompt_cache_conversations(
             row.downstream_error_message.as_deref(),
             row.failure_kind.as_deref(),
             row.failure_class.as_deref(),
+            state.config.treat_empty_error_as_success,
         )
         .to_string();
         let request_tokens = row.request_tokens.max(0);
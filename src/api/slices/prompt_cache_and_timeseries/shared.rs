@@ -92,14 +92,9 @@ pub(super) fn resolve_timeseries_fill_end_epoch(
 pub(crate) fn prompt_invocation_status_is_success_like(
     status: Option<&str>,
     error_message: Option<&str>,
+    treat_empty_error_as_success: bool,
 ) -> bool {
-    let normalized_status = status.map(str::trim).unwrap_or_default();
-    let error_message_empty = error_message.map(str::trim).is_none_or(str::is_empty);
-
-    normalized_status.eq_ignore_ascii_case("success")
-        || normalized_status.eq_ignore_ascii_case("completed")
-        || normalized_status.eq_ignore_ascii_case(INVOCATION_STATUS_WARNING_SUCCESS)
-        || (normalized_status.eq_ignore_ascii_case("http_200") && error_message_empty)
+    invocation_status_is_success_like(status, error_message, treat_empty_error_as_success)
 }
 
 pub(crate) fn prompt_invocation_status_counts_toward_terminal_totals(status: Option<&str>) -> bool {
@@ -143,8 +138,9 @@ pub(crate) fn invocation_point_is_success(
     status: Option<&str>,
     error_message: Option<&str>,
     failure_class: Option<&str>,
+    treat_empty_error_as_success: bool,
 ) -> bool {
-    prompt_invocation_status_is_success_like(status, error_message)
+    prompt_invocation_status_is_success_like(status, error_message, treat_empty_error_as_success)
         && failure_class
             .map(str::trim)
             .unwrap_or_default()
@@ -157,8 +153,14 @@ pub(super) fn invocation_point_outcome(
     downstream_error_message: Option<&str>,
     failure_kind: Option<&str>,
     failure_class: Option<&str>,
+    treat_empty_error_as_success: bool,
 ) -> &'static str {
-    if invocation_point_is_success(status, error_message, failure_class) {
+    if invocation_point_is_success(
+        status,
+        error_message,
+        failure_class,
+        treat_empty_error_as_success,
+    ) {
         return "success";
     }
     if invocation_point_has_explicit_failure_metadata(
@@ -301,6 +303,31 @@ pub(crate) async fn query_pool_attempt_records_from_live(
     Ok(records)
 }
 
+/// Optional per-invocation usage floor for timeseries record queries. Zero values
+/// leave the query untouched so keep-alive pings are only dropped on request.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct TimeseriesUsageFloor {
+    pub(crate) min_cost: f64,
+    pub(crate) min_tokens: i64,
+}
+
+impl TimeseriesUsageFloor {
+    pub(crate) fn is_active(&self) -> bool {
+        self.min_cost > 0.0 || self.min_tokens > 0
+    }
+
+    pub(crate) fn push_filters(&self, query: &mut QueryBuilder<'_, Sqlite>) {
+        if self.min_cost > 0.0 {
+            query.push(" AND cost >= ").push_bind(self.min_cost);
+        }
+        if self.min_tokens > 0 {
+            query
+                .push(" AND total_tokens >= ")
+                .push_bind(self.min_tokens);
+        }
+    }
+}
+
 pub(crate) async fn query_invocation_aggregate_records_from_live_range_executor<'e, E>(
     executor: E,
     range: ExactUtcRange,
@@ -308,13 +335,14 @@ pub(crate) async fn query_invocation_aggregate_records_from_live_range_executor<
     start_after_id: Option<i64>,
     snapshot_id: Option<i64>,
     upstream_account_id: Option<i64>,
+    usage_floor: TimeseriesUsageFloor,
 ) -> Result<Vec<InvocationAggregateRecord>, ApiError>
 where
     E: sqlx::Executor<'e, Database = Sqlite>,
 {
     let mut query = QueryBuilder::<Sqlite>::new(
         "SELECT \
-            id, invoke_id, occurred_at, status, total_tokens, cache_input_tokens, cost, error_message, ",
+            id, invoke_id, occurred_at, status, total_tokens, cache_input_tokens, cost, error_message, http_status, ",
     );
     query
         .push(INVOCATION_FAILURE_KIND_SQL)
@@ -358,6 +386,7 @@ where
             .push(" = ")
             .push_bind(upstream_account_id);
     }
+    usage_floor.push_filters(&mut query);
     query.push(" ORDER BY occurred_at ASC, id ASC");
     query
         .build_query_as::<InvocationAggregateRecord>()
@@ -372,6 +401,25 @@ pub(super) async fn query_invocation_aggregate_records_from_live_range(
     source_scope: InvocationSourceScope,
     start_after_id: Option<i64>,
     snapshot_id: Option<i64>,
+) -> Result<Vec<InvocationAggregateRecord>, ApiError> {
+    query_invocation_aggregate_records_from_live_range_with_floor(
+        pool,
+        range,
+        source_scope,
+        start_after_id,
+        snapshot_id,
+        TimeseriesUsageFloor::default(),
+    )
+    .await
+}
+
+pub(super) async fn query_invocation_aggregate_records_from_live_range_with_floor(
+    pool: &Pool<Sqlite>,
+    range: ExactUtcRange,
+    source_scope: InvocationSourceScope,
+    start_after_id: Option<i64>,
+    snapshot_id: Option<i64>,
+    usage_floor: TimeseriesUsageFloor,
 ) -> Result<Vec<InvocationAggregateRecord>, ApiError> {
     query_invocation_aggregate_records_from_live_range_executor(
         pool,
@@ -380,10 +428,43 @@ pub(super) async fn query_invocation_aggregate_records_from_live_range(
         start_after_id,
         snapshot_id,
         None,
+        usage_floor,
     )
     .await
 }
 
+pub(super) async fn query_invocation_aggregate_records_from_archive_db(
+    pool: &Pool<Sqlite>,
+    range: ExactUtcRange,
+    source_scope: InvocationSourceScope,
+    usage_floor: TimeseriesUsageFloor,
+) -> Result<Vec<InvocationAggregateRecord>, ApiError> {
+    let mut query = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT \
+            0 AS id, '' AS invoke_id, occurred_at, status, total_tokens, \
+            NULL AS cache_input_tokens, cost, error_message, failure_kind, failure_class, \
+            NULL AS is_actionable, NULL AS live_phase, \
+            NULL AS t_total_ms, NULL AS t_req_read_ms, NULL AS t_req_parse_ms, \
+            NULL AS t_upstream_connect_ms, NULL AS t_upstream_ttfb_ms, NULL AS t_upstream_stream_ms, \
+            NULL AS t_resp_parse_ms, NULL AS t_persist_ms \
+         FROM ({})",
+        crate::stats::archive_db_invocations_sql()
+    ));
+    crate::stats::push_archive_db_occurred_at_filters(
+        &mut query,
+        Some(range.start),
+        Some(range.end),
+        source_scope,
+    );
+    usage_floor.push_filters(&mut query);
+    query.push(" ORDER BY occurred_at ASC");
+    query
+        .build_query_as::<InvocationAggregateRecord>()
+        .fetch_all(pool)
+        .await
+        .map_err(Into::into)
+}
+
 pub(super) async fn query_invocation_aggregate_records_from_live_range_for_account(
     pool: &Pool<Sqlite>,
     range: ExactUtcRange,
@@ -399,6 +480,7 @@ pub(super) async fn query_invocation_aggregate_records_from_live_range_for_accou
         start_after_id,
         snapshot_id,
         Some(upstream_account_id),
+        TimeseriesUsageFloor::default(),
     )
     .await
 }
@@ -417,6 +499,7 @@ pub(crate) async fn query_invocation_aggregate_records_from_live_range_tx(
         start_after_id,
         snapshot_id,
         None,
+        TimeseriesUsageFloor::default(),
     )
     .await
 }
@@ -436,6 +519,7 @@ pub(crate) async fn query_invocation_aggregate_records_from_live_range_tx_for_ac
         start_after_id,
         snapshot_id,
         Some(upstream_account_id),
+        TimeseriesUsageFloor::default(),
     )
     .await
 }
@@ -990,6 +1074,7 @@ pub(crate) async fn sqlite_table_has_column_tx(
 pub(super) fn add_invocation_record_to_summary_totals(
     totals: &mut StatsTotals,
     record: &InvocationAggregateRecord,
+    treat_empty_error_as_success: bool,
 ) {
     totals.total_count += 1;
     let classification = resolve_failure_classification(
@@ -1002,6 +1087,7 @@ pub(super) fn add_invocation_record_to_summary_totals(
     if prompt_invocation_status_is_success_like(
         record.status.as_deref(),
         record.error_message.as_deref(),
+        treat_empty_error_as_success,
     ) && classification.failure_class == FailureClass::None
     {
         totals.success_count += 1;
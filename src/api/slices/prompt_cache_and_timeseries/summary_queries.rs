@@ -1,28 +1,13 @@
 use super::*;
 use anyhow::anyhow;
 
-pub(crate) async fn query_hourly_backed_summary_since_with_config(
-    pool: &Pool<Sqlite>,
-    invocation_max_days: u64,
-    start: DateTime<Utc>,
-    source_scope: InvocationSourceScope,
-) -> Result<StatsTotals, ApiError> {
-    query_hourly_backed_summary_range_with_config(
-        pool,
-        invocation_max_days,
-        start,
-        Utc::now(),
-        source_scope,
-    )
-    .await
-}
-
 pub(crate) async fn query_hourly_backed_summary_range_with_config(
     pool: &Pool<Sqlite>,
     invocation_max_days: u64,
     start: DateTime<Utc>,
     end: DateTime<Utc>,
     source_scope: InvocationSourceScope,
+    treat_empty_error_as_success: bool,
 ) -> Result<StatsTotals, ApiError> {
     if start >= end {
         return Ok(StatsTotals::default());
@@ -78,7 +63,11 @@ pub(crate) async fn query_hourly_backed_summary_range_with_config(
                 .collect::<HashSet<_>>();
             exact_records.extend(tail_records);
             for record in &exact_records {
-                add_invocation_record_to_summary_totals(&mut totals, record);
+                add_invocation_record_to_summary_totals(
+                    &mut totals,
+                    record,
+                    treat_empty_error_as_success,
+                );
             }
             archive_overlap_ids
         };
@@ -96,6 +85,7 @@ pub(crate) async fn query_hourly_backed_summary_range_with_config(
                 source_scope,
                 Some((archived_start, archived_end)),
                 Some(&archive_overlap_ids),
+                treat_empty_error_as_success,
             )
             .await?,
         );
@@ -104,7 +94,11 @@ pub(crate) async fn query_hourly_backed_summary_range_with_config(
         let exact_records =
             query_invocation_exact_records(pool, &range_plan, source_scope, snapshot_id).await?;
         for record in &exact_records {
-            add_invocation_record_to_summary_totals(&mut totals, record);
+            add_invocation_record_to_summary_totals(
+                &mut totals,
+                record,
+                treat_empty_error_as_success,
+            );
         }
     }
     Ok(totals)
@@ -117,6 +111,7 @@ pub(crate) async fn query_hourly_backed_summary_range_for_account_with_config(
     end: DateTime<Utc>,
     source_scope: InvocationSourceScope,
     upstream_account_id: i64,
+    treat_empty_error_as_success: bool,
 ) -> Result<StatsTotals, ApiError> {
     if start >= end {
         return Ok(StatsTotals::default());
@@ -173,7 +168,11 @@ pub(crate) async fn query_hourly_backed_summary_range_for_account_with_config(
                 .collect::<HashSet<_>>();
             exact_records.extend(tail_records);
             for record in &exact_records {
-                add_invocation_record_to_summary_totals(&mut totals, record);
+                add_invocation_record_to_summary_totals(
+                    &mut totals,
+                    record,
+                    treat_empty_error_as_success,
+                );
             }
             archive_overlap_ids
         } else {
@@ -190,7 +189,11 @@ pub(crate) async fn query_hourly_backed_summary_range_for_account_with_config(
                 Vec::new()
             };
             for record in &exact_records {
-                add_invocation_record_to_summary_totals(&mut totals, record);
+                add_invocation_record_to_summary_totals(
+                    &mut totals,
+                    record,
+                    treat_empty_error_as_success,
+                );
             }
             HashSet::new()
         };
@@ -218,6 +221,7 @@ pub(crate) async fn query_hourly_backed_summary_range_for_account_with_config(
                 Some((archived_start, archived_end)),
                 Some(&archive_overlap_ids),
                 upstream_account_id,
+                treat_empty_error_as_success,
             )
             .await?,
         );
@@ -239,6 +243,7 @@ pub(crate) async fn query_hourly_backed_summary_range_for_account(
         end,
         source_scope,
         upstream_account_id,
+        state.config.treat_empty_error_as_success,
     )
     .await
 }
@@ -248,13 +253,7 @@ pub(crate) async fn query_hourly_backed_summary_since(
     start: DateTime<Utc>,
     source_scope: InvocationSourceScope,
 ) -> Result<StatsTotals, ApiError> {
-    query_hourly_backed_summary_since_with_config(
-        &state.pool,
-        state.config.invocation_max_days,
-        start,
-        source_scope,
-    )
-    .await
+    query_hourly_backed_summary_range(state, start, state.clock.now(), source_scope).await
 }
 
 pub(crate) async fn query_hourly_backed_summary_range(
@@ -269,6 +268,7 @@ pub(crate) async fn query_hourly_backed_summary_range(
         start,
         end,
         source_scope,
+        state.config.treat_empty_error_as_success,
     )
     .await
 }
@@ -11,13 +11,20 @@ pub(crate) async fn fetch_timeseries(
     let reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
     let snapshot_id = resolve_invocation_snapshot_id(&state.pool, source_scope).await?;
-    let range_window = resolve_range_window(&params.range, reporting_tz)?;
+    let range_window = resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?;
     let bucket_selection = resolve_timeseries_bucket_selection(
         &params,
         &range_window,
         state.config.invocation_max_days,
+        state.config.max_timeseries_points,
     )?;
+    let usage_floor = resolve_timeseries_usage_floor(&params)?;
     if let Some(upstream_account_id) = params.upstream_account_id {
+        if usage_floor.is_active() {
+            return Err(ApiError::bad_request(anyhow!(
+                "minCost/minTokens are not supported together with upstreamAccountId"
+            )));
+        }
         return fetch_timeseries_for_account(
             state,
             reporting_tz,
@@ -40,6 +47,14 @@ pub(crate) async fn fetch_timeseries(
                     "unsupported timeZone for historical hourly timeseries: {reporting_tz}; historical hourly buckets require whole-hour UTC offsets"
                 )));
             }
+        } else if usage_floor.is_active() {
+            // Hourly rollups are pre-aggregated, so a usage floor can only be
+            // honoured while the raw invocation rows are still retained.
+            if needs_historical_rollups {
+                return Err(ApiError::bad_request(anyhow!(
+                    "minCost/minTokens require a range within the retained invocation window"
+                )));
+            }
         } else {
             return fetch_timeseries_from_hourly_rollups(
                 state,
@@ -57,32 +72,45 @@ pub(crate) async fn fetch_timeseries(
     let start_dt = range_window.start;
     let start_str_iso = format_utc_iso(start_dt);
 
-    let records = query_invocation_aggregate_records_from_live_range(
+    let range = ExactUtcRange {
+        start: start_dt,
+        end: end_dt,
+    };
+    let mut records = query_invocation_aggregate_records_from_live_range_with_floor(
         &state.pool,
-        ExactUtcRange {
-            start: start_dt,
-            end: end_dt,
-        },
+        range,
         source_scope,
         None,
         Some(snapshot_id),
+        usage_floor,
     )
     .await?;
     let db_runtime_records = collect_in_flight_aggregate_records(&records);
+    if state.archive_db_attached {
+        records.extend(
+            query_invocation_aggregate_records_from_archive_db(
+                &state.pool,
+                range,
+                source_scope,
+                usage_floor,
+            )
+            .await?,
+        );
+    }
 
     let mut aggregates: BTreeMap<i64, BucketAggregate> = BTreeMap::new();
 
     let start_epoch = start_dt.timestamp();
 
     for record in records {
-        let naive = NaiveDateTime::parse_from_str(&record.occurred_at, "%Y-%m-%d %H:%M:%S")
-            .map_err(|err| anyhow!("failed to parse occurred_at: {err}"))?;
-        // Interpret stored naive time as local Asia/Shanghai and convert to UTC epoch
-        let epoch = Shanghai
-            .from_local_datetime(&naive)
-            .single()
-            .map(|dt| dt.with_timezone(&Utc).timestamp())
-            .unwrap_or_else(|| naive.and_utc().timestamp());
+        let epoch = match NaiveDateTime::parse_from_str(&record.occurred_at, "%Y-%m-%d %H:%M:%S") {
+            // Interpret stored naive time as local Asia/Shanghai and convert to UTC epoch
+            Ok(naive) => local_naive_to_utc(naive, Shanghai).timestamp(),
+            // Some providers report RFC3339 request times that are stored verbatim.
+            Err(err) => parse_to_utc_datetime(&record.occurred_at)
+                .map(|dt| dt.timestamp())
+                .ok_or_else(|| anyhow!("failed to parse occurred_at: {err}"))?,
+        };
         let bucket_epoch = align_reporting_bucket_epoch(epoch, bucket_seconds, reporting_tz)?;
         let entry = aggregates.entry(bucket_epoch).or_default();
         entry.total_count += 1;
@@ -96,6 +124,7 @@ pub(crate) async fn fetch_timeseries(
         let is_success_like = prompt_shared::prompt_invocation_status_is_success_like(
             record.status.as_deref(),
             record.error_message.as_deref(),
+            state.config.treat_empty_error_as_success,
         ) && classification.failure_class == FailureClass::None;
         if is_success_like {
             entry.success_count += 1;
@@ -118,7 +147,11 @@ pub(crate) async fn fetch_timeseries(
         if !prompt_shared::invocation_status_is_in_flight(record.status.as_deref()) {
             entry.record_total_latency_sample(record.t_total_ms);
         }
-        entry.record_ttfb_sample(latency_status, record.t_upstream_ttfb_ms);
+        entry.record_ttfb_sample(
+            latency_status,
+            record.t_upstream_ttfb_ms,
+            state.config.treat_empty_error_as_success,
+        );
         entry.record_first_response_byte_total_sample(
             record.t_req_read_ms,
             record.t_req_parse_ms,
@@ -150,17 +183,20 @@ pub(crate) async fn fetch_timeseries(
         aggregates.entry(bucket_cursor).or_default();
         bucket_cursor = next_reporting_bucket_epoch(bucket_cursor, bucket_seconds, reporting_tz)?;
     }
-    overlay_runtime_timeseries_in_flight(
-        state.as_ref(),
-        &mut aggregates,
-        source_scope,
-        None,
-        start_dt,
-        end_dt,
-        bucket_seconds,
-        reporting_tz,
-        &db_runtime_records,
-    )?;
+    // In-flight runtime rows carry no usage yet, so they never clear an active floor.
+    if !usage_floor.is_active() {
+        overlay_runtime_timeseries_in_flight(
+            state.as_ref(),
+            &mut aggregates,
+            source_scope,
+            None,
+            start_dt,
+            end_dt,
+            bucket_seconds,
+            reporting_tz,
+            &db_runtime_records,
+        )?;
+    }
 
     let mut points = Vec::with_capacity(aggregates.len());
     for (bucket_epoch, agg) in aggregates {
@@ -196,6 +232,79 @@ pub(crate) async fn fetch_timeseries(
     Ok(Json(response))
 }
 
+fn resolve_timeseries_usage_floor(
+    params: &TimeseriesQuery,
+) -> Result<TimeseriesUsageFloor, ApiError> {
+    let min_cost = params.min_cost.unwrap_or(0.0);
+    if !min_cost.is_finite() || min_cost < 0.0 {
+        return Err(ApiError::bad_request(anyhow!(
+            "minCost must be a non-negative number"
+        )));
+    }
+    let min_tokens = params.min_tokens.unwrap_or(0);
+    if min_tokens < 0 {
+        return Err(ApiError::bad_request(anyhow!(
+            "minTokens must be a non-negative integer"
+        )));
+    }
+    Ok(TimeseriesUsageFloor {
+        min_cost,
+        min_tokens,
+    })
+}
+
+pub(crate) async fn fetch_timeseries_peak(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TimeseriesPeakQuery>,
+) -> Result<Json<TimeseriesPeakResponse>, ApiError> {
+    let Json(timeseries) = fetch_timeseries(
+        State(state),
+        Query(TimeseriesQuery {
+            range: params.range,
+            bucket: params.bucket,
+            settlement_hour: None,
+            time_zone: params.time_zone,
+            upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
+        }),
+    )
+    .await?;
+
+    // Ties keep the earliest bucket; an empty range reports no peak.
+    let peak = timeseries
+        .points
+        .iter()
+        .filter(|point| point.total_count > 0)
+        .fold(None::<&TimeseriesPoint>, |best, point| match best {
+            Some(best) if best.total_count >= point.total_count => Some(best),
+            _ => Some(point),
+        });
+    let total_count: i64 = timeseries
+        .points
+        .iter()
+        .map(|point| point.total_count)
+        .sum();
+    let average_count = if timeseries.points.is_empty() {
+        0.0
+    } else {
+        total_count as f64 / timeseries.points.len() as f64
+    };
+
+    Ok(Json(TimeseriesPeakResponse {
+        range_start: timeseries.range_start,
+        range_end: timeseries.range_end,
+        bucket_seconds: timeseries.bucket_seconds,
+        effective_bucket: timeseries.effective_bucket,
+        peak_bucket_start: peak.map(|point| point.bucket_start.clone()),
+        peak_count: peak.map_or(0, |point| point.total_count),
+        average_count,
+    }))
+}
+
 pub(crate) async fn fetch_timeseries_for_account(
     state: Arc<AppState>,
     reporting_tz: Tz,
@@ -317,6 +426,7 @@ pub(crate) async fn fetch_timeseries_for_account(
             exact_records,
             bucket_seconds,
             reporting_tz,
+            state.config.treat_empty_error_as_success,
         )?;
     }
 
@@ -342,6 +452,7 @@ pub(crate) async fn fetch_timeseries_for_account(
             tail_records,
             bucket_seconds,
             reporting_tz,
+            state.config.treat_empty_error_as_success,
         )?;
     }
     if bucket_seconds >= 3_600
@@ -367,6 +478,7 @@ pub(crate) async fn fetch_timeseries_for_account(
                 Some((archived_start, archived_end)),
                 Some(&archive_overlap_ids),
                 upstream_account_id,
+                state.config.treat_empty_error_as_success,
             )
             .await?;
         for row in archived_rows {
@@ -493,6 +605,7 @@ pub(crate) fn add_exact_records_to_timeseries_aggregates(
     records: Vec<InvocationAggregateRecord>,
     bucket_seconds: i64,
     reporting_tz: Tz,
+    treat_empty_error_as_success: bool,
 ) -> Result<(), ApiError> {
     for record in records {
         let Some(occurred_utc) = parse_to_utc_datetime(&record.occurred_at) else {
@@ -501,7 +614,7 @@ pub(crate) fn add_exact_records_to_timeseries_aggregates(
         let bucket_epoch =
             align_reporting_bucket_epoch(occurred_utc.timestamp(), bucket_seconds, reporting_tz)?;
         if let Some(entry) = aggregates.get_mut(&bucket_epoch) {
-            add_exact_record_to_timeseries_aggregate(entry, &record);
+            add_exact_record_to_timeseries_aggregate(entry, &record, treat_empty_error_as_success);
         }
     }
     Ok(())
@@ -510,6 +623,7 @@ pub(crate) fn add_exact_records_to_timeseries_aggregates(
 pub(crate) fn add_exact_record_to_timeseries_aggregate(
     entry: &mut BucketAggregate,
     record: &InvocationAggregateRecord,
+    treat_empty_error_as_success: bool,
 ) {
     entry.total_count += 1;
     let classification = resolve_failure_classification(
@@ -522,6 +636,7 @@ pub(crate) fn add_exact_record_to_timeseries_aggregate(
     let is_success_like = prompt_shared::prompt_invocation_status_is_success_like(
         record.status.as_deref(),
         record.error_message.as_deref(),
+        treat_empty_error_as_success,
     ) && classification.failure_class == FailureClass::None;
     if is_success_like {
         entry.success_count += 1;
@@ -544,7 +659,11 @@ pub(crate) fn add_exact_record_to_timeseries_aggregate(
     if !prompt_shared::invocation_status_is_in_flight(record.status.as_deref()) {
         entry.record_total_latency_sample(record.t_total_ms);
     }
-    entry.record_exact_ttfb_sample(latency_status, record.t_upstream_ttfb_ms);
+    entry.record_exact_ttfb_sample(
+        latency_status,
+        record.t_upstream_ttfb_ms,
+        treat_empty_error_as_success,
+    );
     entry.record_exact_first_response_byte_total_sample(
         record.t_req_read_ms,
         record.t_req_parse_ms,
@@ -682,7 +801,11 @@ pub(crate) fn overlay_runtime_timeseries_in_flight(
         entry
             .in_flight_phase_counts
             .increment_phase_name(runtime_phase);
-        entry.record_ttfb_sample(record.status.as_deref(), record.t_upstream_ttfb_ms);
+        entry.record_ttfb_sample(
+            record.status.as_deref(),
+            record.t_upstream_ttfb_ms,
+            state.config.treat_empty_error_as_success,
+        );
         entry.record_first_response_byte_total_sample(
             record.t_req_read_ms,
             record.t_req_parse_ms,
@@ -848,7 +971,17 @@ pub(crate) async fn fetch_parallel_work_stats_cached(
     let body = serde_json::to_vec(&response)
         .map_err(|err| ApiError::from(anyhow!("failed to serialize parallel-work stats: {err}")))?;
     let etag = parallel_work_stats_etag(&body);
-    let mut response = if request_etag_matches(&headers, &etag) {
+    conditional_json_response(&headers, &etag, body)
+}
+
+/// Answers `If-None-Match` revalidation: returns `304` without a body when the
+/// client already holds `etag`, otherwise the serialized JSON body.
+pub(crate) fn conditional_json_response(
+    headers: &HeaderMap,
+    etag: &str,
+    body: Vec<u8>,
+) -> Result<Response, ApiError> {
+    let mut response = if request_etag_matches(headers, etag) {
         StatusCode::NOT_MODIFIED.into_response()
     } else {
         (
@@ -858,8 +991,8 @@ pub(crate) async fn fetch_parallel_work_stats_cached(
         )
             .into_response()
     };
-    let etag_value = HeaderValue::from_str(&etag)
-        .map_err(|err| ApiError::from(anyhow!("invalid parallel-work etag: {err}")))?;
+    let etag_value = HeaderValue::from_str(etag)
+        .map_err(|err| ApiError::from(anyhow!("invalid etag {etag}: {err}")))?;
     response
         .headers_mut()
         .insert(axum::http::header::ETAG, etag_value);
@@ -894,18 +1027,26 @@ pub(crate) async fn load_parallel_work_stats_response(
     let requested_reporting_tz = parse_reporting_tz(params.time_zone.as_deref())?;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
     let upstream_account_id = params.upstream_account_id;
-    let requested_range_window = resolve_range_window(&params.range, requested_reporting_tz)?;
+    let requested_range_window =
+        resolve_range_window_at(&params.range, requested_reporting_tz, state.clock.now())?;
     let bucket_params = TimeseriesQuery {
         range: params.range.clone(),
         bucket: params.bucket.clone(),
         settlement_hour: None,
         time_zone: params.time_zone.clone(),
         upstream_account_id,
+        min_cost: None,
+        min_tokens: None,
+        // Parallel-work keeps coarsening oversized ranges rather than rejecting them.
+        force: Some(true),
+        ts: None,
+        resolution: None,
     };
     let bucket_selection = resolve_timeseries_bucket_selection(
         &bucket_params,
         &requested_range_window,
         state.config.invocation_max_days,
+        state.config.max_timeseries_points,
     )?;
     let bucket_seconds = bucket_selection.bucket_seconds;
     let (reporting_tz, time_zone_fallback) = if bucket_seconds >= 3_600 {
@@ -914,7 +1055,7 @@ pub(crate) async fn load_parallel_work_stats_response(
         (requested_reporting_tz, false)
     };
     let range_window = if time_zone_fallback {
-        resolve_range_window(&params.range, reporting_tz)?
+        resolve_range_window_at(&params.range, reporting_tz, state.clock.now())?
     } else {
         requested_range_window
     };
@@ -1201,13 +1342,31 @@ pub(crate) async fn fetch_timeseries_from_hourly_rollups(
             source_scope,
             Some((archived_start, archived_end)),
             Some(&archive_overlap_ids),
+            state.config.treat_empty_error_as_success,
         )
         .await?
     } else {
         Vec::new()
     };
 
-    for row in hourly_rows.into_iter().chain(archived_hourly_rows) {
+    let archive_db_hourly_rows = if state.archive_db_attached {
+        crate::stats::query_archive_db_hourly_rollup_records(
+            &state.pool,
+            range_window.start,
+            range_window.end,
+            source_scope,
+            state.config.treat_empty_error_as_success,
+        )
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    for row in hourly_rows
+        .into_iter()
+        .chain(archived_hourly_rows)
+        .chain(archive_db_hourly_rows)
+    {
         let bucket_epoch =
             align_reporting_bucket_epoch(row.bucket_start_epoch, bucket_seconds, reporting_tz)?;
         let entry = aggregates.entry(bucket_epoch).or_default();
@@ -1265,6 +1424,7 @@ pub(crate) async fn fetch_timeseries_from_hourly_rollups(
             let is_success_like = prompt_shared::prompt_invocation_status_is_success_like(
                 record.status.as_deref(),
                 record.error_message.as_deref(),
+                state.config.treat_empty_error_as_success,
             ) && classification.failure_class == FailureClass::None;
             if is_success_like {
                 entry.success_count += 1;
@@ -1284,7 +1444,11 @@ pub(crate) async fn fetch_timeseries_from_hourly_rollups(
             } else {
                 record.status.as_deref()
             };
-            entry.record_exact_ttfb_sample(latency_status, record.t_upstream_ttfb_ms);
+            entry.record_exact_ttfb_sample(
+                latency_status,
+                record.t_upstream_ttfb_ms,
+                state.config.treat_empty_error_as_success,
+            );
             entry.record_exact_first_response_byte_total_sample(
                 record.t_req_read_ms,
                 record.t_req_parse_ms,
@@ -0,0 +1,217 @@
+use super::*;
+
+/// Serialized `/api/stats/timeseries` bodies keyed by the normalized query.
+///
+/// Entries expire after one poll interval and are dropped as soon as the
+/// SQLite batch writer commits new invocation rows (its write generation moves
+/// past the generation the entry was computed at). At most
+/// `TIMESERIES_RESPONSE_CACHE_CAPACITY` bodies are kept; inserting past that
+/// evicts the least recently used one. Hit and miss counts are reported under
+/// `timeseriesCache` in `/api/admin/stats`, since the service has no `/metrics`
+/// endpoint.
+#[derive(Debug, Default)]
+pub(crate) struct TimeseriesResponseCache {
+    entries: std::sync::Mutex<HashMap<String, TimeseriesResponseCacheEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    // Monotonic recency stamp; `Instant` can tie for back-to-back accesses.
+    uses: AtomicU64,
+}
+
+#[derive(Debug)]
+struct TimeseriesResponseCacheEntry {
+    generation: u64,
+    cached_at: Instant,
+    last_used: u64,
+    body: Arc<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TimeseriesResponseCacheStats {
+    pub(crate) hits: u64,
+    pub(crate) misses: u64,
+    pub(crate) entries: u64,
+}
+
+impl TimeseriesResponseCache {
+    pub(crate) fn get(&self, key: &str, generation: u64, ttl: Duration) -> Option<Arc<Vec<u8>>> {
+        let mut entries = self.entries.lock().ok()?;
+        let body = match entries.get_mut(key) {
+            Some(entry) if entry.generation == generation && entry.cached_at.elapsed() < ttl => {
+                entry.last_used = self.uses.fetch_add(1, Ordering::Relaxed);
+                Some(entry.body.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        };
+        let counter = if body.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        body
+    }
+
+    pub(crate) fn insert(&self, key: String, generation: u64, body: Arc<Vec<u8>>) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if !entries.contains_key(&key) && entries.len() >= TIMESERIES_RESPONSE_CACHE_CAPACITY {
+            let least_recently_used = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(least_recently_used) = least_recently_used {
+                entries.remove(&least_recently_used);
+            }
+        }
+        entries.insert(
+            key,
+            TimeseriesResponseCacheEntry {
+                generation,
+                cached_at: Instant::now(),
+                last_used: self.uses.fetch_add(1, Ordering::Relaxed),
+                body,
+            },
+        );
+    }
+
+    pub(crate) fn stats(&self) -> TimeseriesResponseCacheStats {
+        TimeseriesResponseCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self
+                .entries
+                .lock()
+                .map(|entries| entries.len() as u64)
+                .unwrap_or(0),
+        }
+    }
+}
+
+pub(crate) fn timeseries_response_cache_key(params: &TimeseriesQuery) -> String {
+    let normalize = |value: Option<&str>| {
+        value
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or("")
+            .to_string()
+    };
+    format!(
+        "range={}|bucket={}|settlement={}|tz={}|account={}|minCost={}|minTokens={}|force={}|ts={}|resolution={}",
+        params.range.trim(),
+        normalize(params.bucket.as_deref()),
+        params
+            .settlement_hour
+            .map(|v| v.to_string())
+            .unwrap_or_default(),
+        normalize(params.time_zone.as_deref()),
+        params
+            .upstream_account_id
+            .map(|v| v.to_string())
+            .unwrap_or_default(),
+        params.min_cost.map(|v| v.to_string()).unwrap_or_default(),
+        params.min_tokens.map(|v| v.to_string()).unwrap_or_default(),
+        params.force.unwrap_or(false),
+        normalize(params.ts.as_deref()),
+        normalize(params.resolution.as_deref()),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TimeseriesTimestampFormat {
+    Iso,
+    EpochMs,
+}
+
+impl TimeseriesTimestampFormat {
+    pub(crate) fn parse(raw: Option<&str>) -> Result<Self, ApiError> {
+        match raw.map(str::trim).unwrap_or_default() {
+            "" | "iso" => Ok(Self::Iso),
+            "epoch_ms" => Ok(Self::EpochMs),
+            other => Err(ApiError::bad_request(anyhow!(
+                "unsupported ts format: {other}; expected iso|epoch_ms"
+            ))),
+        }
+    }
+}
+
+/// Replaces the ISO `rangeStart`/`rangeEnd` and per-point `bucketStart`/`bucketEnd`
+/// strings with epoch milliseconds.
+pub(crate) fn rewrite_timeseries_timestamps_as_epoch_ms(value: &mut Value) {
+    fn rewrite(object: &mut serde_json::Map<String, Value>, keys: &[&str]) {
+        for key in keys {
+            let Some(slot) = object.get_mut(*key) else {
+                continue;
+            };
+            if let Some(millis) = slot
+                .as_str()
+                .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+                .map(|dt| dt.timestamp_millis())
+            {
+                *slot = Value::from(millis);
+            }
+        }
+    }
+    let Some(object) = value.as_object_mut() else {
+        return;
+    };
+    rewrite(object, &["rangeStart", "rangeEnd"]);
+    if let Some(points) = object.get_mut("points").and_then(Value::as_array_mut) {
+        for point in points.iter_mut().filter_map(Value::as_object_mut) {
+            rewrite(point, &["bucketStart", "bucketEnd"]);
+        }
+    }
+}
+
+pub(crate) async fn fetch_timeseries_cached(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TimeseriesQuery>,
+    Query(units): Query<TokenUnitsQuery>,
+) -> Result<Response, ApiError> {
+    let timestamp_format = TimeseriesTimestampFormat::parse(params.ts.as_deref())?;
+    let units = TokenUnits::parse(units.units.as_deref())?;
+    let mut key = timeseries_response_cache_key(&params);
+    if units != TokenUnits::Raw {
+        key.push_str("|units=");
+        key.push_str(units.as_str());
+    }
+    // Read the generation before computing so rows committed mid-query leave
+    // the entry already stale instead of caching a partial view.
+    let generation = state.sqlite_batch_writer.write_generation();
+    let ttl = state.config.poll_interval;
+    let body = match state.timeseries_response_cache.get(&key, generation, ttl) {
+        Some(body) => body,
+        None => {
+            let Json(response) = fetch_timeseries(State(state.clone()), Query(params)).await?;
+            let serialized = match timestamp_format {
+                TimeseriesTimestampFormat::Iso => serialize_with_token_units(&response, units),
+                TimeseriesTimestampFormat::EpochMs => serde_json::to_value(&response)
+                    .map(|mut value| {
+                        rewrite_timeseries_timestamps_as_epoch_ms(&mut value);
+                        scale_token_fields(&mut value, units);
+                        value
+                    })
+                    .and_then(|value| serde_json::to_vec(&value)),
+            };
+            let body = Arc::new(serialized.map_err(|err| {
+                ApiError::from(anyhow!("failed to serialize timeseries response: {err}"))
+            })?);
+            state
+                .timeseries_response_cache
+                .insert(key, generation, body.clone());
+            body
+        }
+    };
+    Ok((
+        StatusCode::OK,
+        [(axum::http::header::CONTENT_TYPE, "application/json")],
+        body.as_ref().clone(),
+    )
+        .into_response())
+}
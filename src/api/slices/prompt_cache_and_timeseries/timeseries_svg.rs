@@ -0,0 +1,80 @@
+use super::*;
+use plotters::prelude::*;
+
+const SVG_WIDTH: u32 = 800;
+const SVG_HEIGHT: u32 = 320;
+const SVG_BAR_COLOR: RGBColor = RGBColor(0x3b, 0x82, 0xf6);
+
+/// `GET /api/stats/timeseries.svg`: the request-count series of
+/// `/api/stats/timeseries` rendered as a bar chart for sharing in reports.
+pub(crate) async fn fetch_timeseries_svg(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TimeseriesQuery>,
+) -> Result<Response, ApiError> {
+    let range = params.range.clone();
+    let Json(response) = fetch_timeseries(State(state), Query(params)).await?;
+    let svg =
+        render_timeseries_svg(&response, &range).context("failed to render timeseries svg")?;
+    Ok((
+        StatusCode::OK,
+        [(axum::http::header::CONTENT_TYPE, "image/svg+xml")],
+        svg,
+    )
+        .into_response())
+}
+
+/// Renders the title, both axes, and one bar per bucket with plotters' SVG backend.
+pub(crate) fn render_timeseries_svg(response: &TimeseriesResponse, range: &str) -> Result<String> {
+    let bucket_count = response.points.len().max(1) as u32;
+    let max_count = response
+        .points
+        .iter()
+        .map(|point| point.total_count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, (SVG_WIDTH, SVG_HEIGHT)).into_drawing_area();
+        root.fill(&WHITE)?;
+        let mut chart = ChartBuilder::on(&root)
+            .caption(format!("Requests ({range})"), ("sans-serif", 16))
+            .margin(12)
+            .x_label_area_size(28)
+            .y_label_area_size(48)
+            .build_cartesian_2d((0..bucket_count).into_segmented(), 0..max_count)?;
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_labels(6)
+            .x_label_formatter(&|value| {
+                let index = match value {
+                    SegmentValue::Exact(index) | SegmentValue::CenterOf(index) => *index,
+                    SegmentValue::Last => return String::new(),
+                };
+                response
+                    .points
+                    .get(index as usize)
+                    .map(|point| point.bucket_start.clone())
+                    .unwrap_or_default()
+            })
+            .y_desc("requests")
+            .draw()?;
+        chart.draw_series(
+            Histogram::vertical(&chart)
+                .style(SVG_BAR_COLOR.filled())
+                .margin(2)
+                .data(
+                    response
+                        .points
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, point)| point.total_count > 0)
+                        .map(|(index, point)| (index as u32, point.total_count)),
+                ),
+        )?;
+        root.present()?;
+    }
+    Ok(svg)
+}
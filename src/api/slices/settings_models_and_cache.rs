@@ -208,6 +208,10 @@ pub(crate) struct StatsResponse {
     pub(crate) failure_count: i64,
     pub(crate) total_cost: f64,
     pub(crate) total_tokens: i64,
+    /// `total_cost / total_count`, `0` when the window has no requests.
+    pub(crate) avg_cost_per_request: f64,
+    /// `total_tokens / total_count`, `0` when the window has no requests.
+    pub(crate) avg_tokens_per_request: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) usage_breakdown: Option<UsageBreakdownResponse>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -224,6 +228,9 @@ pub(crate) struct StatsResponse {
     pub(crate) non_success_tokens: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) maintenance: Option<StatsMaintenanceResponse>,
+    /// Live records with a negative `cost` (provider credits); only `/api/stats` fills it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) refunds: Option<i64>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize)]
@@ -365,6 +372,8 @@ impl StatsTotals {
             failure_count: self.failure_count,
             total_cost: self.total_cost,
             total_tokens: self.total_tokens,
+            avg_cost_per_request: per_request_average(self.total_cost, self.total_count),
+            avg_tokens_per_request: per_request_average(self.total_tokens as f64, self.total_count),
             usage_breakdown: None,
             in_progress_conversation_count: None,
             in_progress_retry_conversation_count: None,
@@ -373,6 +382,7 @@ impl StatsTotals {
             non_success_cost: None,
             non_success_tokens: None,
             maintenance: None,
+            refunds: None,
         }
     }
 }
@@ -390,6 +400,14 @@ impl From<StatsRow> for StatsTotals {
     }
 }
 
+pub(crate) fn per_request_average(total: f64, total_count: i64) -> f64 {
+    if total_count > 0 {
+        total / total_count as f64
+    } else {
+        0.0
+    }
+}
+
 impl From<StatsRow> for StatsResponse {
     fn from(value: StatsRow) -> Self {
         StatsTotals::from(value).into_response()
@@ -452,6 +470,46 @@ pub(crate) struct ParallelWorkConversation {
     pub(crate) request_count: i64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TimeseriesResolution {
+    Fine,
+    Normal,
+    Coarse,
+}
+
+impl TimeseriesResolution {
+    pub(crate) fn parse(raw: Option<&str>) -> Result<Self, ApiError> {
+        match raw.map(str::trim).unwrap_or_default() {
+            "" | "normal" => Ok(Self::Normal),
+            "fine" => Ok(Self::Fine),
+            "coarse" => Ok(Self::Coarse),
+            other => Err(ApiError::bad_request(anyhow!(
+                "unsupported resolution: {other}; expected fine|normal|coarse"
+            ))),
+        }
+    }
+
+    /// Moves the default bucket to the neighbouring supported bucket spec,
+    /// staying put at either end of the ladder.
+    pub(crate) fn scale_default_bucket(self, bucket_seconds: i64) -> i64 {
+        let ladder = available_timeseries_bucket_specs(true)
+            .iter()
+            .filter_map(|spec| bucket_seconds_from_spec(spec))
+            .collect::<Vec<_>>();
+        let Some(index) = ladder.iter().position(|seconds| *seconds == bucket_seconds) else {
+            return bucket_seconds;
+        };
+        let scaled = match self {
+            Self::Fine => index.checked_sub(1),
+            Self::Normal => Some(index),
+            Self::Coarse => Some(index + 1),
+        };
+        scaled
+            .and_then(|index| ladder.get(index).copied())
+            .unwrap_or(bucket_seconds)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct TimeseriesBucketSelection {
     pub(crate) bucket_seconds: i64,
@@ -464,12 +522,14 @@ pub(crate) fn resolve_timeseries_bucket_selection(
     params: &TimeseriesQuery,
     range_window: &RangeWindow,
     invocation_max_days: u64,
+    max_points: u64,
 ) -> Result<TimeseriesBucketSelection, ApiError> {
+    let resolution = TimeseriesResolution::parse(params.resolution.as_deref())?;
     let mut bucket_seconds = if let Some(spec) = params.bucket.as_deref() {
         bucket_seconds_from_spec(spec)
             .ok_or_else(|| anyhow!("unsupported bucket specification: {spec}"))?
     } else {
-        default_bucket_seconds(range_window.duration)
+        resolution.scale_default_bucket(default_bucket_seconds(range_window.duration))
     };
 
     if bucket_seconds <= 0 {
@@ -479,9 +539,23 @@ pub(crate) fn resolve_timeseries_bucket_selection(
     }
 
     let range_seconds = range_window.duration.num_seconds();
-    if range_seconds / bucket_seconds > 10_000 {
+    let max_points = i64::try_from(max_points).unwrap_or(i64::MAX).max(1);
+    let point_count = range_seconds / bucket_seconds;
+    if point_count > max_points {
+        if !params.force.unwrap_or(false) {
+            let suggested_bucket = available_timeseries_bucket_specs(true)
+                .into_iter()
+                .find(|spec| {
+                    bucket_seconds_from_spec(spec)
+                        .is_some_and(|seconds| range_seconds / seconds <= max_points)
+                })
+                .unwrap_or_else(|| format!("{}s", (range_seconds + max_points - 1) / max_points));
+            return Err(ApiError::bad_request(anyhow!(
+                "timeseries would return {point_count} points, exceeding the limit of {max_points}; use bucket={suggested_bucket} or larger, or pass force=true to coarsen automatically"
+            )));
+        }
         // avoid accidentally returning extremely large payloads
-        bucket_seconds = range_seconds / 10_000;
+        bucket_seconds = range_seconds / max_points;
     }
 
     let subhour_supported = range_window.start >= shanghai_retention_cutoff(invocation_max_days);
@@ -1288,6 +1362,16 @@ impl ProxyCostBreakdown {
     pub(crate) fn total(self) -> f64 {
         self.input + self.cache_write + self.cache_read + self.output + self.reasoning
     }
+
+    pub(crate) fn rounded(self, precision: u32) -> Self {
+        Self {
+            input: round_cost_to_precision(self.input, precision),
+            cache_write: round_cost_to_precision(self.cache_write, precision),
+            cache_read: round_cost_to_precision(self.cache_read, precision),
+            output: round_cost_to_precision(self.output, precision),
+            reasoning: round_cost_to_precision(self.reasoning, precision),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -1359,6 +1443,8 @@ pub(crate) struct ProxyCaptureRecord {
     pub(crate) invoke_id: String,
     pub(crate) occurred_at: String,
     pub(crate) model: Option<String>,
+    /// `model` after applying `MODEL_ALIASES`; filled in just before persisting.
+    pub(crate) model_canonical: Option<String>,
     pub(crate) usage: ParsedUsage,
     pub(crate) cost: Option<f64>,
     pub(crate) cost_breakdown: Option<ProxyCostBreakdown>,
@@ -1811,8 +1897,18 @@ pub(crate) struct ListQuery {
     pub(crate) max_total_tokens: Option<i64>,
     pub(crate) min_total_ms: Option<f64>,
     pub(crate) max_total_ms: Option<f64>,
+    /// Exact match on the client-supplied label set via `PUT /api/invocations/:id/label`.
+    pub(crate) label: Option<String>,
+    /// Dotted key inside the stored `payload` JSON, e.g. `model` or `usage.source`.
+    #[serde(alias = "payload_key")]
+    pub(crate) payload_key: Option<String>,
+    /// Exact value for `payload_key`; without it the key only has to be present.
+    #[serde(alias = "payload_val")]
+    pub(crate) payload_val: Option<String>,
     pub(crate) suggest_field: Option<String>,
     pub(crate) suggest_query: Option<String>,
+    /// Comma-separated record keys to keep in `/api/invocations` responses.
+    pub(crate) fields: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1859,6 +1955,38 @@ pub(crate) struct TimeseriesQuery {
     pub(crate) settlement_hour: Option<u8>,
     pub(crate) time_zone: Option<String>,
     pub(crate) upstream_account_id: Option<i64>,
+    #[serde(alias = "min_cost")]
+    pub(crate) min_cost: Option<f64>,
+    #[serde(alias = "min_tokens")]
+    pub(crate) min_tokens: Option<i64>,
+    /// Coarsen the bucket instead of rejecting requests above the point cap.
+    pub(crate) force: Option<bool>,
+    /// `epoch_ms` switches response timestamps from ISO strings to epoch millis.
+    pub(crate) ts: Option<String>,
+    /// `fine`/`coarse` shift the range-derived default bucket one step; ignored
+    /// when `bucket` is given.
+    pub(crate) resolution: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TimeseriesPeakQuery {
+    #[serde(default = "default_range")]
+    pub(crate) range: String,
+    pub(crate) bucket: Option<String>,
+    pub(crate) time_zone: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TimeseriesPeakResponse {
+    pub(crate) range_start: String,
+    pub(crate) range_end: String,
+    pub(crate) bucket_seconds: i64,
+    pub(crate) effective_bucket: String,
+    pub(crate) peak_bucket_start: Option<String>,
+    pub(crate) peak_count: i64,
+    pub(crate) average_count: f64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -13,6 +13,19 @@ const SUBSCRIPTION_DEFAULT_PROMPT_CACHE_RECENT_LIMIT: i64 = 16;
 const SUBSCRIPTION_DEFAULT_WORKING_CONVERSATIONS_ACTIVITY_MINUTES: i64 = 5;
 const SUBSCRIPTION_DEFAULT_WORKING_CONVERSATIONS_PAGE_SIZE: i64 = 20;
 const SUBSCRIPTION_DEFAULT_INVOCATION_LIMIT: i64 = 20;
+const SUBSCRIPTION_SEED_MAX_ATTEMPTS: u32 = 3;
+const SUBSCRIPTION_SEED_RETRY_DELAY: Duration = Duration::from_millis(50);
+/// `type` tags of `BroadcastPayload` accepted by the `types` stream filter.
+const SUBSCRIPTION_PAYLOAD_TYPES: &[&str] = &[
+    "version",
+    "records",
+    "dashboardActivityLive",
+    "pool_attempts",
+    "summary",
+    "quota",
+    "alert",
+    "deleted",
+];
 #[cfg(not(test))]
 const DASHBOARD_NETWORK_RECENT_TOPIC_PUSH_INTERVAL: Duration = Duration::from_secs(1);
 #[cfg(test)]
@@ -85,6 +98,10 @@ pub(crate) enum SubscriptionEventEnvelope {
         cursor: u64,
         payload: Value,
     },
+    #[serde(rename = "seed_incomplete")]
+    SeedIncomplete {
+        topics: Vec<SubscriptionTopicDescriptor>,
+    },
 }
 
 #[derive(Debug, Deserialize)]
@@ -92,6 +109,7 @@ pub(crate) enum SubscriptionEventEnvelope {
 pub(crate) struct SubscriptionStreamQuery {
     pub(crate) topics: Option<String>,
     pub(crate) resume: Option<String>,
+    pub(crate) types: Option<String>,
     pub(crate) attempt: Option<u64>,
     pub(crate) reason: Option<String>,
 }
@@ -109,6 +127,8 @@ pub(crate) struct SubscriptionDispatchEvent {
 pub(crate) struct SubscriptionHub {
     state: Mutex<SubscriptionHubState>,
     broadcaster: broadcast::Sender<SubscriptionDispatchEvent>,
+    /// Largest `AppState::broadcaster` backlog seen by the mutation listener.
+    broadcast_queued_max: AtomicUsize,
 }
 
 #[derive(Debug, Default)]
@@ -185,6 +205,7 @@ pub(crate) enum TopicInitDisposition {
     ResumeCaughtUp,
     SnapshotNoResume,
     SnapshotResumeMiss,
+    SnapshotFailed,
 }
 
 impl TopicInitDisposition {
@@ -194,6 +215,7 @@ impl TopicInitDisposition {
             Self::ResumeCaughtUp => "resume_caught_up",
             Self::SnapshotNoResume => "snapshot_no_resume",
             Self::SnapshotResumeMiss => "snapshot_resume_miss",
+            Self::SnapshotFailed => "snapshot_failed",
         }
     }
 }
@@ -275,6 +297,7 @@ enum SubscriptionTopic {
     InvocationPoolAttempts {
         invoke_id: String,
     },
+    AlertsLive,
 }
 
 impl SubscriptionHub {
@@ -283,6 +306,7 @@ impl SubscriptionHub {
         Self {
             state: Mutex::new(SubscriptionHubState::default()),
             broadcaster,
+            broadcast_queued_max: AtomicUsize::new(0),
         }
     }
 
@@ -290,6 +314,15 @@ impl SubscriptionHub {
         self.broadcaster.subscribe()
     }
 
+    pub(crate) fn observe_broadcast_queued(&self, queued: usize) {
+        self.broadcast_queued_max
+            .fetch_max(queued, Ordering::Relaxed);
+    }
+
+    pub(crate) fn broadcast_queued_max(&self) -> usize {
+        self.broadcast_queued_max.load(Ordering::Relaxed)
+    }
+
     async fn register_server_push_topics(
         self: &Arc<Self>,
         state: Arc<AppState>,
@@ -375,13 +408,30 @@ impl SubscriptionHub {
         let mut initial = Vec::new();
         let mut last_sent_cursors = HashMap::new();
         let mut outcomes = Vec::new();
+        let mut failed_topics = Vec::new();
 
         for descriptor in descriptors {
             let topic = SubscriptionTopic::from_descriptor(&descriptor)?;
-            let cached = self
-                .ensure_cached_topic(state.clone(), topic.clone())
-                .await?;
             let topic_key = topic.cache_key()?;
+            // A failed snapshot must not take the whole connection down: the
+            // client is told via `seed_incomplete` to fetch that state over REST,
+            // and live updates for the topic still flow from cursor 0.
+            let cached = match self.seed_cached_topic(state.clone(), topic.clone()).await {
+                Ok(cached) => cached,
+                Err(err) => {
+                    warn!(?err, topic_key, "subscription snapshot seed failed");
+                    failed_topics.push(topic.descriptor());
+                    outcomes.push(TopicInitOutcome {
+                        topic_key,
+                        disposition: TopicInitDisposition::SnapshotFailed,
+                        replay_event_count: 0,
+                        replay_bytes: 0,
+                        cursor: 0,
+                        miss_reason: None,
+                    });
+                    continue;
+                }
+            };
             let resume_cursor = resume_by_topic_key.get(&topic_key);
             let replay_attempt = self
                 .replay_events_for_resume(&topic_key, topic.schema_epoch(), resume_cursor)
@@ -471,6 +521,12 @@ impl SubscriptionHub {
             }
         }
 
+        if !failed_topics.is_empty() {
+            initial.push(SubscriptionEventEnvelope::SeedIncomplete {
+                topics: failed_topics,
+            });
+        }
+
         Ok(PreparedSubscriptionConnection {
             initial,
             last_sent_cursors,
@@ -538,6 +594,27 @@ impl SubscriptionHub {
         Ok(Some(gap))
     }
 
+    /// Seeds one topic for a new connection, retrying a few times so a transient
+    /// database error does not cost the client its initial snapshot.
+    async fn seed_cached_topic(
+        &self,
+        state: Arc<AppState>,
+        topic: SubscriptionTopic,
+    ) -> Result<CachedSubscriptionTopic, ApiError> {
+        let mut attempt = 1_u32;
+        loop {
+            match self.ensure_cached_topic(state.clone(), topic.clone()).await {
+                Ok(cached) => return Ok(cached),
+                Err(err) if attempt < SUBSCRIPTION_SEED_MAX_ATTEMPTS => {
+                    debug!(?err, attempt, "retrying subscription snapshot seed");
+                    tokio::time::sleep(SUBSCRIPTION_SEED_RETRY_DELAY * attempt).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
     async fn ensure_cached_topic(
         &self,
         state: Arc<AppState>,
@@ -555,12 +632,23 @@ impl SubscriptionHub {
         state: Arc<AppState>,
         topic: SubscriptionTopic,
         emit_live: bool,
+    ) -> Result<CachedSubscriptionTopic, ApiError> {
+        let started = Instant::now();
+        let payload = topic.build_payload(state.clone()).await?;
+        self.store_topic_payload(topic, payload, emit_live, started)
+            .await
+    }
+
+    async fn store_topic_payload(
+        &self,
+        topic: SubscriptionTopic,
+        payload: Value,
+        emit_live: bool,
+        started: Instant,
     ) -> Result<CachedSubscriptionTopic, ApiError> {
         let topic_key = topic.cache_key()?;
         let schema_epoch = topic.schema_epoch();
         let descriptor = topic.descriptor();
-        let started = Instant::now();
-        let payload = topic.build_payload(state.clone()).await?;
         let payload_bytes = serialized_len(&payload)?;
 
         let (cached, dispatch) = {
@@ -646,6 +734,20 @@ impl SubscriptionHub {
         };
 
         for cached in affected {
+            if matches!(&payload, BroadcastPayload::Alert { .. }) {
+                let stored = match serde_json::to_value(&payload) {
+                    Ok(alert) => {
+                        self.store_topic_payload(cached.topic.clone(), alert, true, Instant::now())
+                            .await
+                    }
+                    Err(err) => Err(ApiError::from(err)),
+                };
+                if let Err(err) = stored {
+                    warn!(?err, topic = %cached.topic.name(), "failed to publish alert");
+                }
+                continue;
+            }
+
             if cached.topic.uses_dashboard_activity_live_overlay()
                 && let BroadcastPayload::DashboardActivityLive { snapshot } = &payload
                 && let Err(err) = self
@@ -892,7 +994,8 @@ async fn dashboard_activity_snapshot_selection_for_topic(
         Some(*recent_limit),
     )?;
     let reporting_tz = parse_reporting_tz(Some(time_zone))?;
-    let exact_range = resolve_dashboard_activity_cached_range(range, reporting_tz)?;
+    let exact_range =
+        resolve_dashboard_activity_cached_range(range, reporting_tz, state.clock.now())?;
     let source_scope = resolve_default_source_scope(&state.pool).await?;
 
     Ok(Some(build_dashboard_activity_snapshot_selection(
@@ -1174,7 +1277,10 @@ pub(crate) fn spawn_subscription_broadcast_listener(state: Arc<AppState>) {
                 _ = shutdown.cancelled() => return,
                 item = receiver.recv() => {
                     match item {
-                        Ok(payload) => hub.handle_internal_broadcast(state.clone(), payload).await,
+                        Ok(payload) => {
+                            hub.observe_broadcast_queued(state.broadcaster.len());
+                            hub.handle_internal_broadcast(state.clone(), payload).await
+                        }
                         Err(broadcast::error::RecvError::Lagged(skipped)) => {
                             warn!(skipped, "subscription mutation listener lagged");
                         }
@@ -1193,7 +1299,10 @@ pub(crate) async fn topic_sse_stream(
     let descriptors = decode_topics_query(query.topics.as_deref())?;
     let resume = decode_resume_query(query.resume.as_deref(), &descriptors)?;
     let resume_count = resume.len();
+    let descriptors =
+        filter_topics_by_payload_types(descriptors, decode_types_query(query.types.as_deref())?)?;
     let mut live_receiver = state.subscription_hub.subscribe();
+    let connection = state.sse_connections.register();
     let selected_topics = descriptors
         .iter()
         .map(SubscriptionTopic::from_descriptor)
@@ -1268,10 +1377,106 @@ pub(crate) async fn topic_sse_stream(
         }
     };
 
-    let merged = initial_stream.chain(live_stream);
+    let merged = initial_stream.chain(live_stream).map(move |event| {
+        connection.mark_event_sent();
+        event
+    });
     Ok(Sse::new(merged).keep_alive(KeepAlive::new().interval(Duration::from_secs(15))))
 }
 
+/// Open `/events` connections for `/api/admin/connections`.
+#[derive(Debug, Default)]
+pub(crate) struct SseConnectionRegistry {
+    next_id: AtomicU64,
+    connections: std::sync::Mutex<BTreeMap<u64, SseConnectionEntry>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SseConnectionEntry {
+    connected_at: DateTime<Utc>,
+    last_event_sent_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SseConnectionInfo {
+    pub(crate) id: u64,
+    pub(crate) connected_at: String,
+    pub(crate) last_event_sent_at: Option<String>,
+}
+
+impl SseConnectionRegistry {
+    pub(crate) fn register(self: &Arc<Self>) -> SseConnectionGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.connections
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(
+                id,
+                SseConnectionEntry {
+                    connected_at: Utc::now(),
+                    last_event_sent_at: None,
+                },
+            );
+        SseConnectionGuard {
+            registry: self.clone(),
+            id,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.connections
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<SseConnectionInfo> {
+        self.connections
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .map(|(id, entry)| SseConnectionInfo {
+                id: *id,
+                connected_at: format_utc_iso(entry.connected_at),
+                last_event_sent_at: entry.last_event_sent_at.map(format_utc_iso),
+            })
+            .collect()
+    }
+}
+
+/// Keeps one connection registered for as long as its SSE stream is alive;
+/// axum drops the stream (and this guard) when the client disconnects.
+#[derive(Debug)]
+pub(crate) struct SseConnectionGuard {
+    registry: Arc<SseConnectionRegistry>,
+    id: u64,
+}
+
+impl SseConnectionGuard {
+    pub(crate) fn mark_event_sent(&self) {
+        if let Some(entry) = self
+            .registry
+            .connections
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get_mut(&self.id)
+        {
+            entry.last_event_sent_at = Some(Utc::now());
+        }
+    }
+}
+
+impl Drop for SseConnectionGuard {
+    fn drop(&mut self) {
+        self.registry
+            .connections
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(&self.id);
+    }
+}
+
 impl SubscriptionTopic {
     fn uses_server_push_cadence(&self) -> bool {
         matches!(self, Self::DashboardNetworkRecentCurrent)
@@ -1380,6 +1585,7 @@ impl SubscriptionTopic {
             "invocation.pool-attempts" => Ok(Self::InvocationPoolAttempts {
                 invoke_id: parse_required_text_param(params, "invokeId")?,
             }),
+            "alerts.live" => Ok(Self::AlertsLive),
             _ => Err(ApiError::bad_request(anyhow!(
                 "unsupported subscription topic: {topic}"
             ))),
@@ -1597,6 +1803,10 @@ impl SubscriptionTopic {
                 topic: self.name().to_string(),
                 params: BTreeMap::from([("invokeId".to_string(), invoke_id.clone())]),
             },
+            Self::AlertsLive => SubscriptionTopicDescriptor {
+                topic: self.name().to_string(),
+                params: BTreeMap::new(),
+            },
         }
     }
 
@@ -1618,6 +1828,7 @@ impl SubscriptionTopic {
             Self::ParallelWorkCurrent { .. } => "stats.parallel-work.current",
             Self::ForwardProxyLive => "forward-proxy.live",
             Self::InvocationPoolAttempts { .. } => "invocation.pool-attempts",
+            Self::AlertsLive => "alerts.live",
         }
     }
 
@@ -1643,6 +1854,7 @@ impl SubscriptionTopic {
             Self::ParallelWorkCurrent { .. } => "stats.parallel-work.current/v1".to_string(),
             Self::ForwardProxyLive => "forward-proxy.live/v1".to_string(),
             Self::InvocationPoolAttempts { .. } => "invocation.pool-attempts/v1".to_string(),
+            Self::AlertsLive => "alerts.live/v1".to_string(),
         }
     }
 
@@ -1650,9 +1862,33 @@ impl SubscriptionTopic {
         serde_json::to_string(&self.descriptor()).map_err(ApiError::from)
     }
 
+    /// `BroadcastPayload` types whose arrival refreshes this topic; kept in
+    /// step with `is_affected_by`.
+    fn payload_types(&self) -> &'static [&'static str] {
+        match self {
+            Self::AppVersion => &["version"],
+            Self::QuotaCurrent => &["quota"],
+            Self::DashboardActivityCurrent { .. }
+            | Self::DashboardNetworkTimeseriesWindow { .. }
+            | Self::DashboardNetworkRecentCurrent => {
+                &["records", "deleted", "dashboardActivityLive"]
+            }
+            Self::SummaryCurrent { .. } => &["records", "deleted", "summary"],
+            Self::DashboardWorkingConversationsCurrent { .. }
+            | Self::InvocationWindow { .. }
+            | Self::PromptCacheWindow { .. }
+            | Self::PromptCacheStickyWindow { .. }
+            | Self::TimeseriesOpenWindow { .. }
+            | Self::ParallelWorkCurrent { .. }
+            | Self::ForwardProxyLive => &["records", "deleted"],
+            Self::InvocationPoolAttempts { .. } => &["pool_attempts"],
+            Self::AlertsLive => &["alert"],
+        }
+    }
+
     fn is_affected_by(&self, payload: &BroadcastPayload) -> bool {
         match payload {
-            BroadcastPayload::Records { .. } => matches!(
+            BroadcastPayload::Records { .. } | BroadcastPayload::Deleted { .. } => matches!(
                 self,
                 Self::DashboardActivityCurrent { .. }
                     | Self::DashboardNetworkTimeseriesWindow { .. }
@@ -1681,6 +1917,7 @@ impl SubscriptionTopic {
             BroadcastPayload::Summary { .. } => matches!(self, Self::SummaryCurrent { .. }),
             BroadcastPayload::Quota { .. } => matches!(self, Self::QuotaCurrent),
             BroadcastPayload::Version { .. } => matches!(self, Self::AppVersion),
+            BroadcastPayload::Alert { .. } => matches!(self, Self::AlertsLive),
         }
     }
 
@@ -1885,6 +2122,11 @@ impl SubscriptionTopic {
                         settlement_hour: *settlement_hour,
                         time_zone: Some(time_zone.clone()),
                         upstream_account_id: *upstream_account_id,
+                        min_cost: None,
+                        min_tokens: None,
+                        force: None,
+                        ts: None,
+                        resolution: None,
                     }),
                 )
                 .await?;
@@ -1918,6 +2160,9 @@ impl SubscriptionTopic {
                         .await?;
                 Ok(serde_json::to_value(response)?)
             }
+            // Alerts are one-shot events published straight from the broadcast,
+            // so a fresh subscription starts from an empty snapshot.
+            Self::AlertsLive => Ok(Value::Null),
         }
     }
 }
@@ -1929,6 +2174,47 @@ fn decode_topics_query(raw: Option<&str>) -> Result<Vec<SubscriptionTopicDescrip
     decode_query_json(raw, "topics")
 }
 
+fn decode_types_query(raw: Option<&str>) -> Result<Option<HashSet<&'static str>>, ApiError> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    raw.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            SUBSCRIPTION_PAYLOAD_TYPES
+                .iter()
+                .copied()
+                .find(|known| *known == name)
+                .ok_or_else(|| {
+                    ApiError::bad_request(anyhow!("unsupported subscription payload type: {name}"))
+                })
+        })
+        .collect::<Result<HashSet<_>, _>>()
+        .map(Some)
+}
+
+fn filter_topics_by_payload_types(
+    descriptors: Vec<SubscriptionTopicDescriptor>,
+    types: Option<HashSet<&'static str>>,
+) -> Result<Vec<SubscriptionTopicDescriptor>, ApiError> {
+    let Some(types) = types else {
+        return Ok(descriptors);
+    };
+    let mut kept = Vec::with_capacity(descriptors.len());
+    for descriptor in descriptors {
+        let topic = SubscriptionTopic::from_descriptor(&descriptor)?;
+        if topic
+            .payload_types()
+            .iter()
+            .any(|name| types.contains(name))
+        {
+            kept.push(descriptor);
+        }
+    }
+    Ok(kept)
+}
+
 fn decode_resume_query(
     raw: Option<&str>,
     descriptors: &[SubscriptionTopicDescriptor],
@@ -2518,6 +2804,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn filter_topics_by_payload_types_keeps_only_matching_topics() {
+        let descriptors = vec![
+            SubscriptionTopic::QuotaCurrent.descriptor(),
+            summary_topic().descriptor(),
+            SubscriptionTopic::AppVersion.descriptor(),
+        ];
+
+        let unfiltered = filter_topics_by_payload_types(
+            descriptors.clone(),
+            decode_types_query(None).expect("decode absent types"),
+        )
+        .expect("filter without types");
+        assert_eq!(unfiltered, descriptors);
+
+        let filtered = filter_topics_by_payload_types(
+            descriptors.clone(),
+            decode_types_query(Some("quota, records,")).expect("decode known types"),
+        )
+        .expect("filter with types");
+        assert_eq!(filtered, descriptors[..2].to_vec());
+
+        let none = filter_topics_by_payload_types(
+            descriptors,
+            decode_types_query(Some("pool_attempts")).expect("decode unmatched type"),
+        )
+        .expect("filter with unmatched types");
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn decode_types_query_rejects_unknown_types_by_name() {
+        match decode_types_query(Some("records,typo")) {
+            Err(ApiError::BadRequest(err)) => {
+                assert!(err.to_string().contains("typo"), "unexpected error: {err}");
+            }
+            other => panic!("unknown type should be a bad request: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn every_filterable_payload_type_selects_some_topic() {
+        let topics = [
+            SubscriptionTopic::AppVersion,
+            SubscriptionTopic::QuotaCurrent,
+            summary_topic(),
+            SubscriptionTopic::DashboardNetworkRecentCurrent,
+            SubscriptionTopic::InvocationPoolAttempts {
+                invoke_id: "invoke-1".to_string(),
+            },
+            SubscriptionTopic::AlertsLive,
+        ];
+        for payload_type in SUBSCRIPTION_PAYLOAD_TYPES {
+            assert!(
+                topics
+                    .iter()
+                    .any(|topic| topic.payload_types().contains(payload_type)),
+                "no topic is refreshed by `{payload_type}`"
+            );
+        }
+
+        let alerts = filter_topics_by_payload_types(
+            topics.iter().map(SubscriptionTopic::descriptor).collect(),
+            decode_types_query(Some("alert")).expect("decode alert type"),
+        )
+        .expect("filter alert type");
+        assert_eq!(alerts, vec![SubscriptionTopic::AlertsLive.descriptor()]);
+    }
+
     #[tokio::test]
     async fn replay_returns_gap_when_cursor_is_within_window() {
         let hub = SubscriptionHub::new();
@@ -2784,6 +3139,159 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sse_connection_registry_tracks_events_and_drops_on_disconnect() {
+        let registry = Arc::new(SseConnectionRegistry::default());
+        let first = registry.register();
+        let second = registry.register();
+
+        assert_eq!(registry.len(), 2);
+        let snapshot = registry.snapshot();
+        assert_eq!(
+            snapshot.iter().map(|item| item.id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(
+            snapshot
+                .iter()
+                .all(|item| item.last_event_sent_at.is_none())
+        );
+
+        second.mark_event_sent();
+        let snapshot = registry.snapshot();
+        assert!(snapshot[0].last_event_sent_at.is_none());
+        assert!(snapshot[1].last_event_sent_at.is_some());
+
+        drop(first);
+        let snapshot = registry.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].id, 2);
+        drop(second);
+        assert!(registry.snapshot().is_empty());
+        assert_eq!(registry.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn prepare_connection_reports_seed_incomplete_when_snapshot_fails() {
+        let state =
+            crate::tests::test_state_with_openai_base(Url::parse("http://127.0.0.1:9").unwrap())
+                .await;
+        let hub = SubscriptionHub::new();
+        let seeded = summary_topic();
+        let seeded_key = seeded.cache_key().expect("seeded topic key");
+        hub.state.lock().await.topics.insert(
+            seeded_key.clone(),
+            seeded_cached_topic(seeded.clone(), &[1, 2], Utc::now()),
+        );
+        let failing = SubscriptionTopic::QuotaCurrent;
+        let failing_key = failing.cache_key().expect("failing topic key");
+        state.pool.close().await;
+
+        let prepared = hub
+            .prepare_connection(
+                state,
+                vec![seeded.descriptor(), failing.descriptor()],
+                Vec::new(),
+            )
+            .await
+            .expect("connection should survive a failed snapshot");
+
+        assert_eq!(prepared.initial.len(), 2);
+        assert!(matches!(
+            prepared.initial[0],
+            SubscriptionEventEnvelope::Snapshot { .. }
+        ));
+        assert_eq!(
+            serde_json::to_value(&prepared.initial[1]).expect("serialize seed_incomplete"),
+            json!({
+                "type": "seed_incomplete",
+                "topics": [{ "topic": "quota.current" }],
+            })
+        );
+        assert_eq!(prepared.outcomes[1].topic_key, failing_key);
+        assert_eq!(
+            prepared.outcomes[1].disposition,
+            TopicInitDisposition::SnapshotFailed
+        );
+        assert!(!prepared.last_sent_cursors.contains_key(&failing_key));
+        assert_eq!(prepared.last_sent_cursors.get(&seeded_key), Some(&2));
+    }
+
+    #[tokio::test]
+    async fn prepare_connection_reports_seed_incomplete_when_all_summary_seed_fails() {
+        let state =
+            crate::tests::test_state_with_openai_base(Url::parse("http://127.0.0.1:9").unwrap())
+                .await;
+        let hub = SubscriptionHub::new();
+        let failing = SubscriptionTopic::SummaryCurrent {
+            window: "all".to_string(),
+            time_zone: SUBSCRIPTION_DEFAULT_TIME_ZONE.to_string(),
+            limit: None,
+            upstream_account_id: None,
+        };
+        let failing_key = failing.cache_key().expect("failing topic key");
+        state.pool.close().await;
+
+        let prepared = hub
+            .prepare_connection(state, vec![failing.descriptor()], Vec::new())
+            .await
+            .expect("connection should survive a failed summary snapshot");
+
+        assert_eq!(prepared.initial.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&prepared.initial[0]).expect("serialize seed_incomplete"),
+            json!({
+                "type": "seed_incomplete",
+                "topics": [failing.descriptor()],
+            })
+        );
+        assert_eq!(prepared.outcomes[0].topic_key, failing_key);
+        assert_eq!(
+            prepared.outcomes[0].disposition,
+            TopicInitDisposition::SnapshotFailed
+        );
+    }
+
+    #[tokio::test]
+    async fn prepare_connection_retries_a_transient_seed_failure() {
+        let state =
+            crate::tests::test_state_with_openai_base(Url::parse("http://127.0.0.1:9").unwrap())
+                .await;
+        let hub = SubscriptionHub::new();
+        sqlx::query("ALTER TABLE codex_quota_snapshots RENAME TO codex_quota_snapshots_hidden")
+            .execute(&state.pool)
+            .await
+            .expect("hide quota snapshots");
+        let pool = state.pool.clone();
+        let restore = tokio::spawn(async move {
+            tokio::time::sleep(SUBSCRIPTION_SEED_RETRY_DELAY / 2).await;
+            sqlx::query("ALTER TABLE codex_quota_snapshots_hidden RENAME TO codex_quota_snapshots")
+                .execute(&pool)
+                .await
+                .expect("restore quota snapshots");
+        });
+
+        let prepared = hub
+            .prepare_connection(
+                state,
+                vec![SubscriptionTopic::QuotaCurrent.descriptor()],
+                Vec::new(),
+            )
+            .await
+            .expect("connection should seed after a retry");
+        restore.await.expect("restore task");
+
+        assert_eq!(prepared.initial.len(), 1);
+        assert!(matches!(
+            prepared.initial[0],
+            SubscriptionEventEnvelope::Snapshot { .. }
+        ));
+        assert_ne!(
+            prepared.outcomes[0].disposition,
+            TopicInitDisposition::SnapshotFailed
+        );
+    }
+
     #[tokio::test]
     async fn invalidate_dashboard_activity_snapshot_cache_only_removes_selected_entry() {
         let cache = Arc::new(Mutex::new(DashboardActivitySnapshotCacheState::default()));
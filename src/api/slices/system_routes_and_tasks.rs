@@ -10,6 +10,7 @@ use std::{
 };
 
 pub(crate) const SYSTEM_STATUS_CACHE_TTL_SECS: u64 = 10;
+const BOOTSTRAP_RECENT_INVOCATION_LIMIT: i64 = 20;
 
 #[derive(Debug, Clone, Default, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -34,6 +35,46 @@ pub(crate) struct SystemStatusResponse {
     pub(crate) refreshed_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AdminStatsResponse {
+    pub(crate) invocation_count: u64,
+    pub(crate) snapshot_count: u64,
+    pub(crate) db_file_bytes: Option<u64>,
+    #[serde(serialize_with = "serialize_opt_local_or_utc_to_utc_iso")]
+    pub(crate) oldest_invocation: Option<String>,
+    #[serde(serialize_with = "serialize_opt_local_or_utc_to_utc_iso")]
+    pub(crate) newest_invocation: Option<String>,
+    pub(crate) timeseries_cache: TimeseriesResponseCacheStats,
+    /// Messages still queued in the internal broadcast channel for its slowest receiver.
+    pub(crate) broadcast_queued: usize,
+    /// Highest `broadcast_queued` observed since startup.
+    pub(crate) broadcast_queued_max: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AdminLogsResponse {
+    pub(crate) lines: Vec<LogBufferLine>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AdminRefreshSummariesResponse {
+    pub(crate) summaries: Vec<SummaryPublish>,
+    pub(crate) broadcast_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BootstrapResponse {
+    pub(crate) version: VersionResponse,
+    pub(crate) config: SettingsResponse,
+    pub(crate) quota: QuotaSnapshotResponse,
+    pub(crate) summaries: Vec<SummaryPublish>,
+    pub(crate) recent_invocations: Vec<ApiInvocation>,
+}
+
 #[derive(Debug, Clone, Serialize, FromRow)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct SystemTaskRunResponse {
@@ -105,6 +146,13 @@ pub(crate) struct SystemInvocationStatusAggRow {
     non_success_count: Option<i64>,
 }
 
+#[derive(Debug, Default, FromRow)]
+pub(crate) struct AdminInvocationStatsRow {
+    invocation_count: Option<i64>,
+    oldest_invocation: Option<String>,
+    newest_invocation: Option<String>,
+}
+
 #[derive(Debug, Default, FromRow)]
 pub(crate) struct SystemArchiveAggRow {
     completed_archive_batches_count: Option<i64>,
@@ -366,6 +414,47 @@ pub(crate) async fn load_system_status_uncached(state: &AppState) -> Result<Syst
     })
 }
 
+pub(crate) fn admin_stats_database_file_bytes(db_path: &Path) -> Option<u64> {
+    let raw = db_path.to_string_lossy();
+    if raw == ":memory:" || raw.starts_with("file::memory:") {
+        return None;
+    }
+    fs::metadata(db_path).ok().map(|meta| meta.len())
+}
+
+pub(crate) async fn load_admin_stats(state: &AppState) -> Result<AdminStatsResponse> {
+    let invocations = sqlx::query_as::<_, AdminInvocationStatsRow>(
+        r#"
+        SELECT
+            COUNT(*) AS invocation_count,
+            MIN(occurred_at) AS oldest_invocation,
+            MAX(occurred_at) AS newest_invocation
+        FROM codex_invocations
+        "#,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+    let snapshot_count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM codex_quota_snapshots")
+        .fetch_one(&state.pool)
+        .await?;
+
+    let broadcast_queued = state.broadcaster.len();
+    state
+        .subscription_hub
+        .observe_broadcast_queued(broadcast_queued);
+
+    Ok(AdminStatsResponse {
+        invocation_count: invocations.invocation_count.unwrap_or(0).max(0) as u64,
+        snapshot_count: snapshot_count.max(0) as u64,
+        db_file_bytes: admin_stats_database_file_bytes(&state.config.database_path),
+        oldest_invocation: invocations.oldest_invocation,
+        newest_invocation: invocations.newest_invocation,
+        timeseries_cache: state.timeseries_response_cache.stats(),
+        broadcast_queued,
+        broadcast_queued_max: state.subscription_hub.broadcast_queued_max(),
+    })
+}
+
 pub(crate) async fn load_system_status_cached(state: &AppState) -> Result<SystemStatusResponse> {
     {
         let cache = state.system_status_cache.lock().await;
@@ -509,6 +598,127 @@ pub(crate) async fn fetch_system_status(
     Ok(Json(load_system_status_cached(state.as_ref()).await?))
 }
 
+pub(crate) async fn fetch_admin_stats(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<AdminStatsResponse>, (StatusCode, String)> {
+    authenticate_external_api_key(state.as_ref(), &headers).await?;
+    let stats = load_admin_stats(state.as_ref())
+        .await
+        .map_err(internal_error_tuple_local)?;
+    Ok(Json(stats))
+}
+
+pub(crate) async fn fetch_admin_logs(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<AdminLogsResponse>, (StatusCode, String)> {
+    authenticate_external_api_key(state.as_ref(), &headers).await?;
+    Ok(Json(AdminLogsResponse {
+        lines: state.log_buffer.snapshot(),
+    }))
+}
+
+pub(crate) async fn fetch_admin_connections(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<SseConnectionInfo>>, (StatusCode, String)> {
+    authenticate_external_api_key(state.as_ref(), &headers).await?;
+    Ok(Json(state.sse_connections.snapshot()))
+}
+
+pub(crate) async fn refresh_admin_summaries(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<AdminRefreshSummariesResponse>, (StatusCode, String)> {
+    authenticate_external_api_key(state.as_ref(), &headers).await?;
+    let summaries = collect_summary_snapshots(
+        &state.pool,
+        state.clock.as_ref(),
+        state.archive_db_attached,
+        state.config.invocation_max_days,
+        state.config.treat_empty_error_as_success,
+    )
+    .await
+    .map_err(internal_error_tuple_local)?;
+
+    // Only windows whose numbers actually moved are pushed, matching the
+    // regular follow-up broadcasts so dashboards never see duplicate frames.
+    let mut broadcast_count = 0;
+    for summary in &summaries {
+        match broadcast_summary_if_changed(
+            &state.broadcaster,
+            &state.broadcast_state_cache,
+            &summary.window,
+            summary.summary.clone(),
+        )
+        .await
+        {
+            Ok(true) => broadcast_count += 1,
+            Ok(false) => {}
+            Err(err) => {
+                warn!(?err, window = %summary.window, "failed to rebroadcast summary");
+            }
+        }
+    }
+    if let Some(snapshot) = QuotaSnapshotResponse::fetch_latest(&state.pool)
+        .await
+        .map_err(internal_error_tuple_local)?
+    {
+        match broadcast_quota_if_changed(&state.broadcaster, &state.broadcast_state_cache, snapshot)
+            .await
+        {
+            Ok(true) => broadcast_count += 1,
+            Ok(false) => {}
+            Err(err) => warn!(?err, "failed to rebroadcast quota snapshot"),
+        }
+    }
+
+    Ok(Json(AdminRefreshSummariesResponse {
+        summaries,
+        broadcast_count,
+    }))
+}
+
+/// Everything the dashboard needs on first paint, loaded concurrently so the
+/// page doesn't wait on one round-trip per widget.
+pub(crate) async fn fetch_bootstrap(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<BootstrapResponse>, ApiError> {
+    let recent_query = ListQuery {
+        limit: Some(BOOTSTRAP_RECENT_INVOCATION_LIMIT),
+        page: Some(1),
+        page_size: Some(BOOTSTRAP_RECENT_INVOCATION_LIMIT),
+        sort_by: Some("occurredAt".to_string()),
+        sort_order: Some("desc".to_string()),
+        ..ListQuery::default()
+    };
+    let (version, config, quota, summaries, recent) = tokio::join!(
+        get_versions(State(state.clone())),
+        get_settings(State(state.clone())),
+        latest_quota_snapshot(State(state.clone())),
+        collect_summary_snapshots(
+            &state.pool,
+            state.clock.as_ref(),
+            state.archive_db_attached,
+            state.config.invocation_max_days,
+            state.config.treat_empty_error_as_success,
+        ),
+        list_invocations(State(state.clone()), Query(recent_query)),
+    );
+    let Json(version) = version?;
+    let Json(config) = config?;
+    let Json(quota) = quota?;
+    let Json(recent) = recent?;
+    Ok(Json(BootstrapResponse {
+        version,
+        config,
+        quota,
+        summaries: summaries?,
+        recent_invocations: recent.records,
+    }))
+}
+
 pub(crate) async fn list_system_task_runs(
     State(state): State<Arc<AppState>>,
     Query(query): Query<SystemTaskRunsQuery>,
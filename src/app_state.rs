@@ -384,11 +384,82 @@ pub(crate) fn prune_bounded_runtime_tombstones_locked(
     before.saturating_sub(tombstones.len())
 }
 
+/// Wall-clock source for windowing math, injectable so tests can pin "now".
+pub(crate) trait Clock: Send + Sync + fmt::Debug {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct FixedClock(pub(crate) DateTime<Utc>);
+
+#[cfg(test)]
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// Rolling window of recent terminal capture costs used to flag sudden spikes.
+#[derive(Debug, Default)]
+pub(crate) struct CostSpikeTracker {
+    recent_costs: std::sync::Mutex<VecDeque<f64>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CostSpike {
+    pub(crate) cost: f64,
+    pub(crate) rolling_average: f64,
+}
+
+impl CostSpikeTracker {
+    pub(crate) fn observe(&self, cost: f64, factor: f64) -> Option<CostSpike> {
+        if !cost.is_finite() || cost < 0.0 {
+            return None;
+        }
+        let mut recent = self
+            .recent_costs
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        let spike = if recent.len() >= COST_SPIKE_MIN_SAMPLES {
+            let rolling_average = recent.iter().sum::<f64>() / recent.len() as f64;
+            (rolling_average > 0.0 && cost > rolling_average * factor).then_some(CostSpike {
+                cost,
+                rolling_average,
+            })
+        } else {
+            None
+        };
+        if recent.len() >= COST_SPIKE_WINDOW {
+            recent.pop_front();
+        }
+        recent.push_back(cost);
+        spike
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct AppState {
     pub(crate) config: AppConfig,
     pub(crate) pool: Pool<Sqlite>,
     pub(crate) process_started_at_utc: DateTime<Utc>,
+    pub(crate) clock: Arc<dyn Clock>,
+    /// `ARCHIVE_DB_PATH` is attached and has a `codex_invocations` table.
+    pub(crate) archive_db_attached: bool,
+    /// The linked SQLite passed the JSON1 probe at startup; `payloadKey`
+    /// invocation filters are rejected without it.
+    pub(crate) sqlite_json1_available: bool,
+    pub(crate) cost_spike_tracker: Arc<CostSpikeTracker>,
+    pub(crate) log_buffer: Arc<LogRingBuffer>,
     pub(crate) sqlite_batch_writer: Arc<SqliteBatchWriter>,
     pub(crate) pool_account_selection_runtime: Arc<PoolAccountSelectionRuntime>,
     pub(crate) proxy_runtime_invocations: Arc<ProxyRuntimeInvocationStore>,
@@ -421,6 +492,8 @@ pub(crate) struct AppState {
     pub(crate) dashboard_activity_snapshot_cache: Arc<Mutex<DashboardActivitySnapshotCacheState>>,
     pub(crate) maintenance_stats_cache: Arc<Mutex<StatsMaintenanceCacheState>>,
     pub(crate) system_status_cache: Arc<Mutex<SystemStatusCacheState>>,
+    pub(crate) timeseries_response_cache: Arc<TimeseriesResponseCache>,
+    pub(crate) sse_connections: Arc<SseConnectionRegistry>,
     pub(crate) pool_routing_reservations:
         Arc<std::sync::Mutex<HashMap<String, PoolRoutingReservation>>>,
     pub(crate) pool_routing_runtime_cache: Arc<Mutex<Option<PoolRoutingRuntimeCache>>>,
@@ -893,26 +966,30 @@ pub(crate) struct HttpClients {
     pub(crate) proxy: Client,
     pub(crate) timeout: Duration,
     pub(crate) user_agent: String,
+    pub(crate) danger_accept_invalid_certs: bool,
 }
 
 impl HttpClients {
     pub(crate) fn build(config: &AppConfig) -> Result<Self> {
         let timeout = config.request_timeout;
         let user_agent = config.user_agent.clone();
+        let danger_accept_invalid_certs = config.upstream_danger_accept_invalid_certs;
 
-        let shared = Self::builder(Some(timeout), &user_agent)
+        let shared = Self::builder(Some(timeout), &user_agent, danger_accept_invalid_certs)
             .pool_max_idle_per_host(config.shared_connection_parallelism)
+            .gzip(true)
+            .brotli(true)
             .build()
             .context("failed to construct shared HTTP client")?;
 
         // Pool live upstream traffic can legitimately stream well past REQUEST_TIMEOUT_SECS.
         // Handshake and upload budgets are enforced by route-specific timeout wrappers instead.
-        let pool_upstream = Self::builder(None, &user_agent)
+        let pool_upstream = Self::builder(None, &user_agent, danger_accept_invalid_certs)
             .pool_max_idle_per_host(config.shared_connection_parallelism)
             .build()
             .context("failed to construct pool upstream HTTP client")?;
 
-        let proxy = Self::builder(None, &user_agent)
+        let proxy = Self::builder(None, &user_agent, danger_accept_invalid_certs)
             .pool_max_idle_per_host(config.shared_connection_parallelism)
             .connect_timeout(timeout)
             .redirect(reqwest::redirect::Policy::none())
@@ -925,15 +1002,20 @@ impl HttpClients {
             proxy,
             timeout,
             user_agent,
+            danger_accept_invalid_certs,
         })
     }
 
     pub(crate) fn client_for_parallelism(&self, force_new_connection: bool) -> Result<Client> {
         if force_new_connection {
-            let client = Self::builder(Some(self.timeout), &self.user_agent)
-                .pool_max_idle_per_host(0)
-                .build()
-                .context("failed to construct dedicated HTTP client")?;
+            let client = Self::builder(
+                Some(self.timeout),
+                &self.user_agent,
+                self.danger_accept_invalid_certs,
+            )
+            .pool_max_idle_per_host(0)
+            .build()
+            .context("failed to construct dedicated HTTP client")?;
             Ok(client)
         } else {
             Ok(self.shared.clone())
@@ -949,7 +1031,7 @@ impl HttpClients {
             return Ok(self.proxy.clone());
         };
 
-        Self::builder(None, &self.user_agent)
+        Self::builder(None, &self.user_agent, self.danger_accept_invalid_certs)
             .pool_max_idle_per_host(2)
             .connect_timeout(self.timeout)
             .redirect(reqwest::redirect::Policy::none())
@@ -961,14 +1043,23 @@ impl HttpClients {
             .context("failed to construct forward proxy HTTP client")
     }
 
-    pub(crate) fn builder(timeout: Option<Duration>, user_agent: &str) -> ClientBuilder {
+    pub(crate) fn builder(
+        timeout: Option<Duration>,
+        user_agent: &str,
+        danger_accept_invalid_certs: bool,
+    ) -> ClientBuilder {
         let builder = Client::builder()
             .user_agent(user_agent)
+            .danger_accept_invalid_certs(danger_accept_invalid_certs)
             .pool_idle_timeout(Duration::from_secs(90))
             .tcp_keepalive(Duration::from_secs(90))
             .http2_keep_alive_interval(Duration::from_secs(30))
             .http2_keep_alive_timeout(Duration::from_secs(30))
-            .http2_keep_alive_while_idle(true);
+            .http2_keep_alive_while_idle(true)
+            // Relay clients must hand compressed bodies through untouched; only the
+            // shared client opts back into transparent decoding.
+            .gzip(false)
+            .brotli(false);
 
         if let Some(timeout) = timeout {
             builder.timeout(timeout)
@@ -61,6 +61,38 @@ impl FromStr for RawCompressionCodec {
     }
 }
 
+/// Durability level applied through `PRAGMA synchronous` on every connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SqliteSynchronousMode {
+    Off,
+    Normal,
+    Full,
+}
+
+impl SqliteSynchronousMode {
+    pub(crate) fn as_sqlx(self) -> SqliteSynchronous {
+        match self {
+            Self::Off => SqliteSynchronous::Off,
+            Self::Normal => SqliteSynchronous::Normal,
+            Self::Full => SqliteSynchronous::Full,
+        }
+    }
+}
+
+impl FromStr for SqliteSynchronousMode {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "normal" => Ok(Self::Normal),
+            "full" => Ok(Self::Full),
+            _ => bail!("invalid {ENV_SQLITE_SYNCHRONOUS} value: {raw}; expected off|normal|full"),
+        }
+    }
+}
+
 pub(crate) fn resolve_forward_proxy_algo_config(
     primary_raw: Option<&str>,
     legacy_raw: Option<&str>,
@@ -242,6 +274,23 @@ pub(crate) struct CliArgs {
     /// Force retention maintenance to simulate actions without mutating data.
     #[arg(long, default_value_t = false)]
     pub(crate) retention_dry_run: bool,
+    /// Print backend and frontend versions as JSON and exit.
+    #[arg(long, default_value_t = false)]
+    pub(crate) version: bool,
+}
+
+pub(crate) fn resolve_static_dir(cli: &CliArgs) -> Option<PathBuf> {
+    cli.static_dir
+        .clone()
+        .or_else(|| env::var(ENV_STATIC_DIR).ok().map(PathBuf::from))
+        .or_else(|| {
+            let default = PathBuf::from("web/dist");
+            if default.exists() {
+                Some(default)
+            } else {
+                None
+            }
+        })
 }
 
 pub(crate) fn should_recover_pending_pool_attempts_on_startup(cli: &CliArgs) -> bool {
@@ -311,6 +360,10 @@ pub(crate) struct AppConfig {
     pub(crate) max_parallel_polls: usize,
     pub(crate) shared_connection_parallelism: usize,
     pub(crate) http_bind: SocketAddr,
+    pub(crate) tls_cert_path: Option<PathBuf>,
+    pub(crate) tls_key_path: Option<PathBuf>,
+    /// Path every JSON API route is nested under; `/events`, `/health` and the SPA stay at root.
+    pub(crate) api_prefix: String,
     pub(crate) cors_allowed_origins: Vec<String>,
     pub(crate) list_limit_max: usize,
     pub(crate) user_agent: String,
@@ -322,16 +375,39 @@ pub(crate) struct AppConfig {
     pub(crate) retention_batch_rows: usize,
     pub(crate) retention_catchup_budget: Duration,
     pub(crate) archive_dir: PathBuf,
+    pub(crate) archive_db_path: Option<PathBuf>,
+    pub(crate) sqlite_synchronous: SqliteSynchronousMode,
+    pub(crate) sqlite_cache_size_kb: Option<u64>,
+    pub(crate) max_timeseries_points: u64,
+    /// Upper clamp for `top` on the error distribution endpoints.
+    pub(crate) error_top_max: u64,
+    /// Upper clamp for `limit` on `/api/stats/errors/others`.
+    pub(crate) other_errors_limit_max: u64,
+    /// Live `/events` connections allowed at once; `None` means unlimited.
+    pub(crate) max_sse_clients: Option<u64>,
+    pub(crate) record_log_path: Option<PathBuf>,
+    pub(crate) record_log_max_bytes: u64,
+    pub(crate) log_buffer_lines: usize,
+    pub(crate) shutdown_timeout: Duration,
     pub(crate) codex_invocation_archive_layout: ArchiveBatchLayout,
     pub(crate) codex_invocation_archive_segment_granularity: ArchiveSegmentGranularity,
     pub(crate) invocation_archive_codec: ArchiveFileCodec,
     pub(crate) invocation_success_full_days: u64,
     pub(crate) invocation_max_days: u64,
     pub(crate) invocation_archive_ttl_days: u64,
+    pub(crate) raw_response_retention_days: Option<u64>,
     pub(crate) forward_proxy_attempts_retention_days: u64,
     pub(crate) pool_upstream_request_attempts_retention_days: u64,
     pub(crate) pool_upstream_request_attempts_archive_ttl_days: u64,
     pub(crate) quota_snapshot_full_days: u64,
+    pub(crate) cost_spike_factor: f64,
+    pub(crate) cache_discount: f64,
+    pub(crate) monthly_budget: Option<f64>,
+    pub(crate) model_aliases: BTreeMap<String, String>,
+    pub(crate) model_allowlist: Option<BTreeSet<String>>,
+    pub(crate) cost_precision: u32,
+    pub(crate) upstream_danger_accept_invalid_certs: bool,
+    pub(crate) treat_empty_error_as_success: bool,
     pub(crate) upstream_accounts_oauth_client_id: String,
     pub(crate) upstream_accounts_oauth_issuer: Url,
     pub(crate) upstream_accounts_usage_base_url: Url,
@@ -527,6 +603,20 @@ impl AppConfig {
                 .context("invalid HTTP_BIND socket address")?
                 .unwrap_or_else(|| "127.0.0.1:8080".parse().expect("valid default address"))
         };
+        let tls_cert_path = env::var(ENV_TLS_CERT_PATH)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from);
+        let tls_key_path = env::var(ENV_TLS_KEY_PATH)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from);
+        if tls_cert_path.is_some() != tls_key_path.is_some() {
+            bail!("{ENV_TLS_CERT_PATH} and {ENV_TLS_KEY_PATH} must be set together");
+        }
+        let api_prefix = parse_api_prefix(env::var(ENV_API_PREFIX).ok().as_deref())?;
         let cors_allowed_origins = parse_cors_allowed_origins_env(ENV_CORS_ALLOWED_ORIGINS)?;
         let list_limit_max = overrides
             .list_limit_max
@@ -546,18 +636,7 @@ impl AppConfig {
             .ok()
             .map(|value| normalize_public_origin(&value))
             .transpose()?;
-        let static_dir = overrides
-            .static_dir
-            .clone()
-            .or_else(|| env::var(ENV_STATIC_DIR).ok().map(PathBuf::from))
-            .or_else(|| {
-                let default = PathBuf::from("web/dist");
-                if default.exists() {
-                    Some(default)
-                } else {
-                    None
-                }
-            });
+        let static_dir = resolve_static_dir(overrides);
         let retention_enabled =
             parse_bool_env_var(ENV_RETENTION_ENABLED, DEFAULT_RETENTION_ENABLED)?;
         let retention_dry_run = overrides.retention_dry_run
@@ -576,6 +655,45 @@ impl AppConfig {
             .ok()
             .map(PathBuf::from)
             .unwrap_or_else(|| PathBuf::from(DEFAULT_ARCHIVE_DIR));
+        let archive_db_path = env::var(ENV_ARCHIVE_DB_PATH)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from);
+        let sqlite_synchronous = match env::var(ENV_SQLITE_SYNCHRONOUS) {
+            Ok(raw) if !raw.trim().is_empty() => SqliteSynchronousMode::from_str(&raw)?,
+            _ => DEFAULT_SQLITE_SYNCHRONOUS,
+        };
+        let sqlite_cache_size_kb = match env::var(ENV_SQLITE_CACHE_SIZE_KB) {
+            Ok(raw) if !raw.trim().is_empty() => {
+                Some(parse_non_zero_u64_env_var(ENV_SQLITE_CACHE_SIZE_KB, 0)?)
+            }
+            _ => None,
+        };
+        let max_timeseries_points =
+            parse_non_zero_u64_env_var(ENV_MAX_TIMESERIES_POINTS, DEFAULT_MAX_TIMESERIES_POINTS)?;
+        let error_top_max = parse_non_zero_u64_env_var(ENV_ERROR_TOP_MAX, DEFAULT_ERROR_TOP_MAX)?;
+        let other_errors_limit_max =
+            parse_non_zero_u64_env_var(ENV_OTHER_ERRORS_LIMIT_MAX, DEFAULT_OTHER_ERRORS_LIMIT_MAX)?;
+        let max_sse_clients = match env::var(ENV_MAX_SSE_CLIENTS) {
+            Ok(raw) if !raw.trim().is_empty() => {
+                Some(parse_non_zero_u64_env_var(ENV_MAX_SSE_CLIENTS, 0)?)
+            }
+            _ => None,
+        };
+        let record_log_path = env::var(ENV_RECORD_LOG_PATH)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from);
+        let record_log_max_bytes =
+            parse_non_zero_u64_env_var(ENV_RECORD_LOG_MAX_BYTES, DEFAULT_RECORD_LOG_MAX_BYTES)?;
+        let log_buffer_lines =
+            parse_u64_env_var(ENV_LOG_BUFFER_LINES, DEFAULT_LOG_BUFFER_LINES as u64)? as usize;
+        let shutdown_timeout = Duration::from_secs(parse_non_zero_u64_env_var(
+            ENV_SHUTDOWN_TIMEOUT_SECS,
+            DEFAULT_SHUTDOWN_TIMEOUT_SECS,
+        )?);
         let invocation_success_full_days = parse_u64_env_var(
             ENV_INVOCATION_SUCCESS_FULL_DAYS,
             DEFAULT_INVOCATION_SUCCESS_FULL_DAYS,
@@ -586,6 +704,13 @@ impl AppConfig {
             ENV_INVOCATION_ARCHIVE_TTL_DAYS,
             DEFAULT_INVOCATION_ARCHIVE_TTL_DAYS,
         )?;
+        let raw_response_retention_days = match env::var(ENV_RAW_RESPONSE_RETENTION_DAYS) {
+            Ok(raw) if !raw.trim().is_empty() => Some(parse_non_zero_u64_env_var(
+                ENV_RAW_RESPONSE_RETENTION_DAYS,
+                0,
+            )?),
+            _ => None,
+        };
         let codex_invocation_archive_layout = resolve_archive_batch_layout_config(
             env::var(ENV_CODEX_INVOCATION_ARCHIVE_LAYOUT)
                 .ok()
@@ -616,6 +741,47 @@ impl AppConfig {
             ENV_QUOTA_SNAPSHOT_FULL_DAYS,
             DEFAULT_QUOTA_SNAPSHOT_FULL_DAYS,
         )?;
+        let cost_spike_factor =
+            parse_positive_f64_env_var(ENV_COST_SPIKE_FACTOR, DEFAULT_COST_SPIKE_FACTOR)?;
+        let cache_discount =
+            parse_positive_f64_env_var(ENV_CACHE_DISCOUNT, DEFAULT_CACHE_DISCOUNT)?;
+        if cache_discount > 1.0 {
+            bail!("{ENV_CACHE_DISCOUNT} must be a ratio between 0 and 1");
+        }
+        let monthly_budget = match env::var(ENV_MONTHLY_BUDGET) {
+            Ok(raw) if !raw.trim().is_empty() => {
+                Some(parse_positive_f64_env_var(ENV_MONTHLY_BUDGET, 0.0)?)
+            }
+            _ => None,
+        };
+        let model_aliases = match env::var(ENV_MODEL_ALIASES) {
+            Ok(raw) => parse_model_aliases(&raw)?,
+            Err(_) => BTreeMap::new(),
+        };
+        let model_allowlist = env::var(ENV_MODEL_ALLOWLIST)
+            .ok()
+            .map(|raw| {
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|model| !model.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect::<BTreeSet<_>>()
+            })
+            .filter(|models| !models.is_empty());
+        let cost_precision =
+            parse_u64_env_var(ENV_COST_PRECISION, u64::from(DEFAULT_COST_PRECISION))?;
+        if cost_precision > u64::from(MAX_COST_PRECISION) {
+            bail!("{ENV_COST_PRECISION} must be between 0 and {MAX_COST_PRECISION}");
+        }
+        let cost_precision = cost_precision as u32;
+        let upstream_danger_accept_invalid_certs = parse_bool_env_var(
+            ENV_UPSTREAM_DANGER_ACCEPT_INVALID_CERTS,
+            DEFAULT_UPSTREAM_DANGER_ACCEPT_INVALID_CERTS,
+        )?;
+        let treat_empty_error_as_success = parse_bool_env_var(
+            ENV_TREAT_EMPTY_ERROR_AS_SUCCESS,
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+        )?;
         let upstream_accounts_oauth_client_id = env::var(ENV_UPSTREAM_ACCOUNTS_OAUTH_CLIENT_ID)
             .ok()
             .filter(|value| !value.trim().is_empty())
@@ -717,6 +883,9 @@ impl AppConfig {
             max_parallel_polls,
             shared_connection_parallelism,
             http_bind,
+            tls_cert_path,
+            tls_key_path,
+            api_prefix,
             cors_allowed_origins,
             list_limit_max,
             user_agent,
@@ -728,16 +897,36 @@ impl AppConfig {
             retention_batch_rows,
             retention_catchup_budget,
             archive_dir,
+            archive_db_path,
+            sqlite_synchronous,
+            sqlite_cache_size_kb,
+            max_timeseries_points,
+            error_top_max,
+            other_errors_limit_max,
+            max_sse_clients,
+            record_log_path,
+            record_log_max_bytes,
+            log_buffer_lines,
+            shutdown_timeout,
             codex_invocation_archive_layout,
             codex_invocation_archive_segment_granularity,
             invocation_archive_codec,
             invocation_success_full_days,
             invocation_max_days,
             invocation_archive_ttl_days,
+            raw_response_retention_days,
             forward_proxy_attempts_retention_days,
             pool_upstream_request_attempts_retention_days,
             pool_upstream_request_attempts_archive_ttl_days,
             quota_snapshot_full_days,
+            cost_spike_factor,
+            cache_discount,
+            monthly_budget,
+            model_aliases,
+            model_allowlist,
+            cost_precision,
+            upstream_danger_accept_invalid_certs,
+            treat_empty_error_as_success,
             upstream_accounts_oauth_client_id,
             upstream_accounts_oauth_issuer,
             upstream_accounts_usage_base_url,
@@ -804,6 +993,55 @@ pub(crate) fn parse_usize_env_var(name: &str, default_value: usize) -> Result<us
     }
 }
 
+pub(crate) fn parse_positive_f64_env_var(name: &str, default_value: f64) -> Result<f64> {
+    let value = match env::var(name) {
+        Ok(raw) => raw
+            .trim()
+            .parse::<f64>()
+            .with_context(|| format!("invalid {name}: {raw}"))?,
+        Err(env::VarError::NotPresent) => default_value,
+        Err(err) => return Err(anyhow!("failed to read {name}: {err}")),
+    };
+    if !value.is_finite() || value <= 0.0 {
+        bail!("{name} must be a positive number");
+    }
+    Ok(value)
+}
+
+/// Normalises `API_PREFIX` to a leading-slash path without a trailing slash;
+/// unset or blank falls back to `/api`.
+pub(crate) fn parse_api_prefix(raw: Option<&str>) -> Result<String> {
+    let Some(raw) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(DEFAULT_API_PREFIX.to_string());
+    };
+    let prefix = raw.trim_end_matches('/');
+    if !prefix.starts_with('/') || prefix.is_empty() {
+        bail!("invalid {ENV_API_PREFIX} (expected a path like /api or /v1/api): {raw}");
+    }
+    Ok(prefix.to_string())
+}
+
+/// Parses `raw=canonical` pairs separated by commas into a lookup keyed by
+/// the raw model name.
+pub(crate) fn parse_model_aliases(raw: &str) -> Result<BTreeMap<String, String>> {
+    let mut aliases = BTreeMap::new();
+    for pair in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+    {
+        let Some((alias, canonical)) = pair.split_once('=') else {
+            bail!("invalid {ENV_MODEL_ALIASES} entry (expected raw=canonical): {pair}");
+        };
+        let (alias, canonical) = (alias.trim(), canonical.trim());
+        if alias.is_empty() || canonical.is_empty() {
+            bail!("invalid {ENV_MODEL_ALIASES} entry (expected raw=canonical): {pair}");
+        }
+        aliases.insert(alias.to_string(), canonical.to_string());
+    }
+    Ok(aliases)
+}
+
 pub(crate) fn parse_bool_string(raw: &str) -> Option<bool> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "1" | "true" | "yes" | "y" | "on" => Some(true),
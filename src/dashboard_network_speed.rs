@@ -471,12 +471,14 @@ impl DashboardNetworkSpeedCache {
         &self,
         record: &ApiInvocation,
         observed_at: DateTime<Utc>,
+        treat_empty_error_as_success: bool,
     ) {
         let observed_epoch_second = observed_at.timestamp();
         let success_like =
             prompt_cache_and_timeseries_shared::prompt_invocation_status_is_success_like(
                 record.status.as_deref(),
                 record.error_message.as_deref(),
+                treat_empty_error_as_success,
             );
         let classification = resolve_failure_classification(
             record.status.as_deref(),
@@ -0,0 +1,90 @@
+use super::*;
+
+include!(concat!(env!("OUT_DIR"), "/embedded_web_assets.rs"));
+
+/// Whether the binary was built with the `embed-web` feature and carries a SPA.
+pub(crate) fn embedded_web_available() -> bool {
+    embedded_web_asset("index.html").is_some()
+}
+
+pub(crate) fn embedded_web_asset(key: &str) -> Option<&'static [u8]> {
+    EMBEDDED_WEB_ASSETS
+        .iter()
+        .find(|(asset_key, _)| *asset_key == key)
+        .map(|(_, bytes)| *bytes)
+}
+
+pub(crate) fn embedded_web_index_html() -> Option<String> {
+    embedded_web_asset("index.html").map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+}
+
+pub(crate) fn embedded_web_frontend_version() -> Option<String> {
+    let payload: Value = serde_json::from_slice(embedded_web_asset("version.json")?).ok()?;
+    payload
+        .get("version")
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+}
+
+/// Maps a request path onto an embedded asset key; rejects traversal segments
+/// so lookups stay inside the bundle, mirroring `ServeDir`.
+pub(crate) fn embedded_web_asset_key(path: &str) -> Option<&str> {
+    let key = path.trim_start_matches('/');
+    if key.is_empty()
+        || key
+            .split('/')
+            .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+    {
+        return None;
+    }
+    Some(key)
+}
+
+/// Serves an embedded asset, falling back to the SPA index for unknown paths
+/// just like the `ServeDir` + `not_found_service` pairing.
+pub(crate) async fn serve_embedded_web_request(
+    state: Arc<AppState>,
+    request: Request<Body>,
+) -> Response {
+    if let Some(key) = embedded_web_asset_key(request.uri().path())
+        && key != "index.html"
+        && let Some(bytes) = embedded_web_asset(key)
+    {
+        return (
+            [(header::CONTENT_TYPE, embedded_web_content_type(key))],
+            bytes,
+        )
+            .into_response();
+    }
+    render_spa_index_response(state, request.headers()).await
+}
+
+#[cfg(feature = "embed-web")]
+fn embedded_web_content_type(key: &str) -> String {
+    mime_guess::from_path(key)
+        .first_or_octet_stream()
+        .as_ref()
+        .to_string()
+}
+
+/// Without `embed-web` the asset table is empty, so this is never reached.
+#[cfg(not(feature = "embed-web"))]
+fn embedded_web_content_type(_key: &str) -> String {
+    "application/octet-stream".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_web_asset_key_strips_leading_slash_and_rejects_traversal() {
+        assert_eq!(
+            embedded_web_asset_key("/assets/app.js"),
+            Some("assets/app.js")
+        );
+        assert_eq!(embedded_web_asset_key("/"), None);
+        assert_eq!(embedded_web_asset_key("/assets/../index.html"), None);
+        assert_eq!(embedded_web_asset_key("/assets//app.js"), None);
+    }
+}
@@ -630,22 +630,13 @@ pub(crate) fn detect_versions(static_dir: Option<&Path>) -> (String, String) {
 
     // Try to get frontend version from a version.json written during build
     let frontend = static_dir
-        .and_then(|p| {
-            let path = p.join("version.json");
-            fs::File::open(&path).ok().and_then(|mut f| {
-                let mut s = String::new();
-                if f.read_to_string(&mut s).is_ok() {
-                    serde_json::from_str::<serde_json::Value>(&s)
-                        .ok()
-                        .and_then(|v| {
-                            v.get("version")
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.to_string())
-                        })
-                } else {
-                    None
-                }
-            })
+        .and_then(|p| read_static_frontend_version(&p.join("version.json")))
+        .or_else(|| {
+            // Builds with the embed-web feature carry version.json in the binary
+            static_dir
+                .is_none()
+                .then(embedded_web_frontend_version)
+                .flatten()
         })
         .or_else(|| {
             // Fallback to reading the web/package.json in dev setups
@@ -676,6 +667,35 @@ pub(crate) fn detect_versions(static_dir: Option<&Path>) -> (String, String) {
     (backend, frontend)
 }
 
+/// Reads `version` from the build's `version.json`. A missing file is normal in
+/// dev setups, but a file that exists and cannot be used points at a broken
+/// deploy, so that case is logged before falling back.
+pub(crate) fn read_static_frontend_version(path: &Path) -> Option<String> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "failed to read frontend version.json");
+            return None;
+        }
+    };
+    let payload = match serde_json::from_str::<Value>(&raw) {
+        Ok(payload) => payload,
+        Err(err) => {
+            warn!(path = %path.display(), error = %err, "frontend version.json is not valid JSON");
+            return None;
+        }
+    };
+    let version = payload
+        .get("version")
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string());
+    if version.is_none() {
+        warn!(path = %path.display(), "frontend version.json has no string `version` field");
+    }
+    version
+}
+
 pub(crate) fn ensure_db_directory(path: &Path) -> Result<()> {
     if let Some(parent) = path.parent()
         && !parent.as_os_str().is_empty()
@@ -699,6 +719,56 @@ pub(crate) fn build_sqlite_connect_options(
     Ok(options)
 }
 
+/// Applies the operator's durability/cache tuning on top of the base options.
+/// A positive `cache_size_kb` is passed as a negative `PRAGMA cache_size`,
+/// which SQLite interprets as KiB rather than pages.
+pub(crate) fn apply_sqlite_tuning_options(
+    options: SqliteConnectOptions,
+    synchronous: SqliteSynchronousMode,
+    cache_size_kb: Option<u64>,
+) -> SqliteConnectOptions {
+    let options = options.synchronous(synchronous.as_sqlx());
+    match cache_size_kb {
+        Some(cache_size_kb) => options.pragma("cache_size", format!("-{cache_size_kb}")),
+        None => options,
+    }
+}
+
+/// Resolves the read-only `file:` URI for `ARCHIVE_DB_PATH`, or `None` when the
+/// historical database is not configured or missing on disk.
+pub(crate) fn resolve_archive_db_attach_uri(archive_db_path: Option<&Path>) -> Option<String> {
+    let archive_db_path = archive_db_path?;
+    let canonical = match fs::canonicalize(archive_db_path) {
+        Ok(path) if path.is_file() => path,
+        Ok(path) => {
+            warn!(path = %path.display(), "archive database path is not a file; skipping attach");
+            return None;
+        }
+        Err(err) => {
+            warn!(
+                path = %archive_db_path.display(),
+                error = %err,
+                "archive database is unavailable; skipping attach"
+            );
+            return None;
+        }
+    };
+    let mut uri = Url::from_file_path(&canonical).ok()?;
+    uri.set_query(Some("mode=ro"));
+    Some(uri.to_string())
+}
+
+pub(crate) async fn attach_archive_db(
+    conn: &mut SqliteConnection,
+    archive_db_uri: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(&format!("ATTACH DATABASE ?1 AS {ARCHIVE_DB_SCHEMA}"))
+        .bind(archive_db_uri)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct ForwardProxySettings {
@@ -21,9 +21,12 @@ use tokio::{
     net::{TcpListener, TcpStream},
     sync::{Notify, watch},
 };
+use tokio_rustls::TlsAcceptor;
 use tower::{Layer, Service, ServiceExt};
 use tracing::{error, trace};
 
+use crate::BoxedWsIo;
+
 #[derive(Clone, Debug)]
 pub(crate) struct DownstreamTransportObserver {
     inner: Arc<DownstreamTransportObserverInner>,
@@ -498,6 +501,7 @@ async fn tcp_accept(listener: &TcpListener) -> Option<(TcpStream, SocketAddr)> {
 pub(crate) async fn serve_router_with_graceful_shutdown<F>(
     tcp_listener: TcpListener,
     router: Router,
+    tls_acceptor: Option<TlsAcceptor>,
     signal: F,
 ) -> io::Result<()>
 where
@@ -532,7 +536,7 @@ where
         if let Ok(monitor_stream) = duplicate_tcp_stream_for_monitor(&tcp_stream) {
             observer.set_reset_monitor_stream(monitor_stream);
         }
-        let tcp_stream = TokioIo::new(ObservedTcpStream::new(tcp_stream, observer.clone()));
+        let tcp_stream = ObservedTcpStream::new(tcp_stream, observer.clone());
 
         poll_fn(|cx| Service::<SocketAddr>::poll_ready(&mut make_service, cx))
             .await
@@ -548,10 +552,26 @@ where
         let signal_tx = Arc::clone(&signal_tx);
         let close_rx = close_rx.clone();
         let observer_for_task = observer.clone();
+        let tls_acceptor = tls_acceptor.clone();
 
         tokio::spawn(async move {
+            // The TLS handshake runs inside the connection task so a slow
+            // client cannot stall the accept loop.
+            let stream: BoxedWsIo = match tls_acceptor {
+                Some(acceptor) => match acceptor.accept(tcp_stream).await {
+                    Ok(tls_stream) => Box::new(tls_stream),
+                    Err(err) => {
+                        trace!("tls handshake failed: {err:#}");
+                        observer_for_task.mark_connection_closed();
+                        drop(close_rx);
+                        return;
+                    }
+                },
+                None => Box::new(tcp_stream),
+            };
             let builder = Builder::new(TokioExecutor::new());
-            let connection = builder.serve_connection_with_upgrades(tcp_stream, hyper_service);
+            let connection =
+                builder.serve_connection_with_upgrades(TokioIo::new(stream), hyper_service);
             pin_mut!(connection);
 
             let signal_closed = signal_tx.closed().fuse();
@@ -0,0 +1,169 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write as _},
+    sync::Mutex,
+};
+
+use chrono::Utc;
+use serde::Serialize;
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{Layer, layer::Context};
+
+use super::*;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LogBufferLine {
+    pub(crate) timestamp: String,
+    pub(crate) level: String,
+    pub(crate) target: String,
+    pub(crate) message: String,
+}
+
+/// Keeps the most recent log events in memory for `/api/admin/logs`.
+///
+/// The buffer is created before configuration is loaded so startup logs are
+/// captured too; `set_capacity` applies the configured size afterwards.
+#[derive(Debug)]
+pub(crate) struct LogRingBuffer {
+    inner: Mutex<LogRingBufferInner>,
+}
+
+#[derive(Debug)]
+struct LogRingBufferInner {
+    capacity: usize,
+    lines: VecDeque<LogBufferLine>,
+}
+
+impl Default for LogRingBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_BUFFER_LINES)
+    }
+}
+
+impl LogRingBuffer {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(LogRingBufferInner {
+                capacity,
+                lines: VecDeque::with_capacity(capacity),
+            }),
+        }
+    }
+
+    pub(crate) fn set_capacity(&self, capacity: usize) {
+        let mut inner = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        inner.capacity = capacity;
+        while inner.lines.len() > capacity {
+            inner.lines.pop_front();
+        }
+    }
+
+    pub(crate) fn push(&self, line: LogBufferLine) {
+        let mut inner = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        if inner.capacity == 0 {
+            return;
+        }
+        if inner.lines.len() >= inner.capacity {
+            inner.lines.pop_front();
+        }
+        inner.lines.push_back(line);
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<LogBufferLine> {
+        let inner = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        inner.lines.iter().cloned().collect()
+    }
+}
+
+/// `tracing` layer that mirrors every enabled event into a [`LogRingBuffer`].
+pub(crate) struct LogBufferLayer {
+    buffer: Arc<LogRingBuffer>,
+}
+
+impl LogBufferLayer {
+    pub(crate) fn new(buffer: Arc<LogRingBuffer>) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = LogLineVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.buffer.push(LogBufferLine {
+            timestamp: format_utc_iso(Utc::now()),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+#[derive(Default)]
+struct LogLineVisitor {
+    message: String,
+    fields: String,
+}
+
+impl LogLineVisitor {
+    fn finish(self) -> String {
+        match (self.message.is_empty(), self.fields.is_empty()) {
+            (_, true) => self.message,
+            (true, false) => self.fields,
+            (false, false) => format!("{} {}", self.message, self.fields),
+        }
+    }
+}
+
+impl Visit for LogLineVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+            return;
+        }
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={value:?}", field.name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn log_buffer_layer_keeps_only_the_most_recent_lines() {
+        let buffer = Arc::new(LogRingBuffer::new(2));
+        let subscriber = tracing_subscriber::registry().with(LogBufferLayer::new(buffer.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("first");
+            tracing::warn!(attempt = 2, "second");
+            tracing::error!(reason = "boom", "third");
+        });
+
+        let lines = buffer.snapshot();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].level, "WARN");
+        assert_eq!(lines[0].message, "second attempt=2");
+        assert_eq!(lines[1].message, "third reason=\"boom\"");
+
+        buffer.set_capacity(1);
+        assert_eq!(buffer.snapshot().len(), 1);
+        assert_eq!(buffer.snapshot()[0].level, "ERROR");
+    }
+}
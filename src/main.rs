@@ -69,7 +69,7 @@ use serde_json::{Value, json};
 use sha2::{Digest, Sha256};
 use sqlx::{
     Connection, FromRow, Pool, QueryBuilder, Row, Sqlite, SqliteConnection,
-    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous},
 };
 use std::fs;
 use std::io::{self, BufRead, Read, Write};
@@ -81,7 +81,7 @@ use tokio::{
     task::JoinHandle,
     time::{MissedTickBehavior, interval, sleep, timeout},
 };
-use tokio_rustls::TlsConnector;
+use tokio_rustls::{TlsAcceptor, TlsConnector};
 use tokio_stream::wrappers::{BroadcastStream, ReceiverStream};
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, client_async_tls_with_config};
 use tokio_util::io::{ReaderStream, StreamReader};
@@ -101,9 +101,11 @@ mod app_state;
 mod config;
 mod dashboard_network_speed;
 mod db_pressure;
+mod embedded_web;
 mod external_api;
 mod forward_proxy;
 mod http_stream_tracking;
+mod log_buffer;
 mod maintenance;
 #[expect(
     clippy::too_many_arguments,
@@ -112,6 +114,8 @@ mod maintenance;
 mod oauth_bridge;
 mod pricing;
 mod proxy;
+mod record_log_sink;
+mod request_id;
 #[expect(
     clippy::too_many_arguments,
     reason = "Runtime shutdown coordination preserves established task handles."
@@ -137,12 +141,16 @@ mod upstream_accounts;
 use api::*;
 pub(crate) use app_state::*;
 pub(crate) use config::*;
+use embedded_web::*;
 use external_api::*;
 use forward_proxy::*;
 use http_stream_tracking::*;
+use log_buffer::*;
 pub(crate) use maintenance::*;
 pub(crate) use pricing::*;
 use proxy::*;
+use record_log_sink::*;
+use request_id::*;
 pub(crate) use runtime::*;
 pub(crate) use schema::*;
 pub(crate) use share_links::*;
@@ -164,6 +172,7 @@ const DEFAULT_OPENAI_PROXY_WEBSOCKET_ENABLED: bool = false;
 const DEFAULT_OPENAI_PROXY_UPSTREAM_WEBSOCKET_DEFAULT_ENABLED: bool = false;
 const DEFAULT_OPENAI_PROXY_ENCRYPTED_SESSION_OWNER_ROUTING_ENABLED: bool = false;
 const DEFAULT_SQLITE_BUSY_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_SQLITE_SYNCHRONOUS: SqliteSynchronousMode = SqliteSynchronousMode::Full;
 const CVM_INVOKE_ID_HEADER: &str = "x-cvm-invoke-id";
 const BACKFILL_BATCH_SIZE: i64 = 200;
 const BACKFILL_ACCOUNT_BIND_BATCH_SIZE: usize = 400;
@@ -215,8 +224,17 @@ const RAW_CODEC_GZIP: &str = "gzip";
 const POOL_REQUEST_REPLAY_MEMORY_THRESHOLD_BYTES: usize = 1024 * 1024;
 const ENV_DATABASE_PATH: &str = "DATABASE_PATH";
 const LEGACY_ENV_DATABASE_PATH: &str = "XY_DATABASE_PATH";
+const ENV_SQLITE_SYNCHRONOUS: &str = "SQLITE_SYNCHRONOUS";
+const ENV_SQLITE_CACHE_SIZE_KB: &str = "SQLITE_CACHE_SIZE_KB";
 const ENV_POLL_INTERVAL_SECS: &str = "POLL_INTERVAL_SECS";
 const LEGACY_ENV_POLL_INTERVAL_SECS: &str = "XY_POLL_INTERVAL_SECS";
+const ENV_MAX_TIMESERIES_POINTS: &str = "MAX_TIMESERIES_POINTS";
+const DEFAULT_MAX_TIMESERIES_POINTS: u64 = 10_000;
+const ENV_ERROR_TOP_MAX: &str = "ERROR_TOP_MAX";
+const DEFAULT_ERROR_TOP_MAX: u64 = 50;
+const ENV_OTHER_ERRORS_LIMIT_MAX: &str = "OTHER_ERRORS_LIMIT_MAX";
+const DEFAULT_OTHER_ERRORS_LIMIT_MAX: u64 = 200;
+const ENV_MAX_SSE_CLIENTS: &str = "MAX_SSE_CLIENTS";
 const ENV_REQUEST_TIMEOUT_SECS: &str = "REQUEST_TIMEOUT_SECS";
 const LEGACY_ENV_REQUEST_TIMEOUT_SECS: &str = "XY_REQUEST_TIMEOUT_SECS";
 const ENV_XRAY_BINARY: &str = "XRAY_BINARY";
@@ -236,6 +254,10 @@ const ENV_OPENAI_PROXY_ENCRYPTED_SESSION_OWNER_ROUTING_ENABLED: &str =
     "OPENAI_PROXY_ENCRYPTED_SESSION_OWNER_ROUTING_ENABLED";
 const ENV_HTTP_BIND: &str = "HTTP_BIND";
 const LEGACY_ENV_HTTP_BIND: &str = "XY_HTTP_BIND";
+const ENV_TLS_CERT_PATH: &str = "TLS_CERT_PATH";
+const ENV_TLS_KEY_PATH: &str = "TLS_KEY_PATH";
+const ENV_API_PREFIX: &str = "API_PREFIX";
+const DEFAULT_API_PREFIX: &str = "/api";
 const ENV_CORS_ALLOWED_ORIGINS: &str = "CORS_ALLOWED_ORIGINS";
 const LEGACY_ENV_CORS_ALLOWED_ORIGINS: &str = "XY_CORS_ALLOWED_ORIGINS";
 const ENV_LIST_LIMIT_MAX: &str = "LIST_LIMIT_MAX";
@@ -256,11 +278,21 @@ const LEGACY_ENV_RETENTION_BATCH_ROWS: &str = "XY_RETENTION_BATCH_ROWS";
 const ENV_RETENTION_CATCHUP_BUDGET_SECS: &str = "RETENTION_CATCHUP_BUDGET_SECS";
 const ENV_ARCHIVE_DIR: &str = "ARCHIVE_DIR";
 const LEGACY_ENV_ARCHIVE_DIR: &str = "XY_ARCHIVE_DIR";
+const ENV_ARCHIVE_DB_PATH: &str = "ARCHIVE_DB_PATH";
+const ARCHIVE_DB_SCHEMA: &str = "archive_db";
+const ENV_RECORD_LOG_PATH: &str = "RECORD_LOG_PATH";
+const ENV_RECORD_LOG_MAX_BYTES: &str = "RECORD_LOG_MAX_BYTES";
+const DEFAULT_RECORD_LOG_MAX_BYTES: u64 = 64 * 1024 * 1024;
+const ENV_LOG_BUFFER_LINES: &str = "LOG_BUFFER_LINES";
+const DEFAULT_LOG_BUFFER_LINES: usize = 500;
+const ENV_SHUTDOWN_TIMEOUT_SECS: &str = "SHUTDOWN_TIMEOUT_SECS";
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
 const ENV_INVOCATION_SUCCESS_FULL_DAYS: &str = "INVOCATION_SUCCESS_FULL_DAYS";
 const LEGACY_ENV_INVOCATION_SUCCESS_FULL_DAYS: &str = "XY_INVOCATION_SUCCESS_FULL_DAYS";
 const ENV_INVOCATION_MAX_DAYS: &str = "INVOCATION_MAX_DAYS";
 const LEGACY_ENV_INVOCATION_MAX_DAYS: &str = "XY_INVOCATION_MAX_DAYS";
 const ENV_INVOCATION_ARCHIVE_TTL_DAYS: &str = "INVOCATION_ARCHIVE_TTL_DAYS";
+const ENV_RAW_RESPONSE_RETENTION_DAYS: &str = "RAW_RESPONSE_RETENTION_DAYS";
 const ENV_CODEX_INVOCATION_ARCHIVE_LAYOUT: &str = "CODEX_INVOCATION_ARCHIVE_LAYOUT";
 const ENV_CODEX_INVOCATION_ARCHIVE_SEGMENT_GRANULARITY: &str =
     "CODEX_INVOCATION_ARCHIVE_SEGMENT_GRANULARITY";
@@ -288,11 +320,19 @@ const ENV_QUOTA_SNAPSHOT_FULL_DAYS: &str = "QUOTA_SNAPSHOT_FULL_DAYS";
 const ENV_PROXY_RAW_COMPRESSION: &str = "PROXY_RAW_COMPRESSION";
 const ENV_PROXY_RAW_IMMEDIATE_GZIP_BYTES: &str = "PROXY_RAW_IMMEDIATE_GZIP_BYTES";
 const ENV_PROXY_RAW_HOT_SECS: &str = "PROXY_RAW_HOT_SECS";
+const ENV_COST_SPIKE_FACTOR: &str = "COST_SPIKE_FACTOR";
+const ENV_CACHE_DISCOUNT: &str = "CACHE_DISCOUNT";
+const ENV_MONTHLY_BUDGET: &str = "MONTHLY_BUDGET";
+const ENV_MODEL_ALIASES: &str = "MODEL_ALIASES";
+const ENV_MODEL_ALLOWLIST: &str = "MODEL_ALLOWLIST";
+const ENV_UPSTREAM_DANGER_ACCEPT_INVALID_CERTS: &str = "UPSTREAM_DANGER_ACCEPT_INVALID_CERTS";
+const ENV_TREAT_EMPTY_ERROR_AS_SUCCESS: &str = "TREAT_EMPTY_ERROR_AS_SUCCESS";
 const LEGACY_ENV_QUOTA_SNAPSHOT_FULL_DAYS: &str = "XY_QUOTA_SNAPSHOT_FULL_DAYS";
 const DETAIL_LEVEL_FULL: &str = "full";
 const DETAIL_LEVEL_STRUCTURED_ONLY: &str = "structured_only";
 const DETAIL_PRUNE_REASON_SUCCESS_OVER_30D: &str = "success_over_30d";
 const DETAIL_PRUNE_REASON_MAX_AGE_ARCHIVED: &str = "max_age_archived";
+const DETAIL_PRUNE_REASON_RAW_RESPONSE_RETENTION: &str = "raw_response_retention";
 const DEFAULT_RETENTION_ENABLED: bool = false;
 const DEFAULT_RETENTION_DRY_RUN: bool = false;
 const DEFAULT_RETENTION_INTERVAL_SECS: u64 = 60 * 60;
@@ -313,6 +353,17 @@ const DEFAULT_POOL_UPSTREAM_REQUEST_ATTEMPTS_ARCHIVE_TTL_DAYS: u64 = 30;
 const DEFAULT_POOL_UPSTREAM_RESPONSES_ATTEMPT_TIMEOUT_SECS: u64 = 180;
 const DEFAULT_POOL_UPSTREAM_RESPONSES_TOTAL_TIMEOUT_SECS: u64 = 300;
 const DEFAULT_QUOTA_SNAPSHOT_FULL_DAYS: u64 = 30;
+const DEFAULT_COST_SPIKE_FACTOR: f64 = 3.0;
+const DEFAULT_CACHE_DISCOUNT: f64 = 0.9;
+const DEFAULT_UPSTREAM_DANGER_ACCEPT_INVALID_CERTS: bool = false;
+const DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS: bool = false;
+const COST_SPIKE_WINDOW: usize = 20;
+const COST_SPIKE_MIN_SAMPLES: usize = 5;
+const ALERT_KIND_COST_SPIKE: &str = "cost_spike";
+const TIMESERIES_RESPONSE_CACHE_CAPACITY: usize = 64;
+const ENV_COST_PRECISION: &str = "COST_PRECISION";
+const DEFAULT_COST_PRECISION: u32 = 6;
+const MAX_COST_PRECISION: u32 = 12;
 const ARCHIVE_STATUS_COMPLETED: &str = "completed";
 const ARCHIVE_LAYOUT_LEGACY_MONTH: &str = "legacy_month";
 const ARCHIVE_LAYOUT_SEGMENT_V1: &str = "segment_v1";
@@ -666,6 +666,7 @@ pub(crate) async fn materialize_usage_breakdown_historical_rollups_bounded_from_
             max_archive_batches,
             max_elapsed,
             bounded_skip,
+            config.treat_empty_error_as_success,
         )
         .await?;
     tx.commit().await?;
@@ -746,6 +747,7 @@ pub(crate) async fn materialize_historical_rollups_bounded_from_skip(
         max_archive_batches,
         max_elapsed,
         bounded_skip,
+        config.treat_empty_error_as_success,
     )
     .await?;
     let remaining_budget =
@@ -763,7 +765,11 @@ pub(crate) async fn materialize_historical_rollups_bounded_from_skip(
         if historical_rollup_materialization_budget_exhausted(started_at, max_elapsed) {
             break;
         }
-        let updated = replay_live_invocation_hourly_rollups_tx(tx.as_mut()).await?;
+        let updated = replay_live_invocation_hourly_rollups_tx(
+            tx.as_mut(),
+            config.treat_empty_error_as_success,
+        )
+        .await?;
         if updated == 0 {
             break;
         }
@@ -23,6 +23,7 @@ pub(crate) struct InvocationHourlyRollupDelta {
     pub(crate) cache_input_tokens: i64,
     pub(crate) total_cost: f64,
     pub(crate) non_success_cost: f64,
+    pub(crate) refund_count: i64,
     pub(crate) total_latency_sample_count: i64,
     pub(crate) total_latency_sum_ms: f64,
     pub(crate) first_byte_sample_count: i64,
@@ -217,6 +218,7 @@ pub(crate) fn record_proxy_perf_stage_sample(
 pub(crate) fn accumulate_invocation_hourly_overall_rollups(
     overall: &mut BTreeMap<(i64, String), InvocationHourlyRollupDelta>,
     rows: &[InvocationHourlySourceRecord],
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     for row in rows {
         let bucket_start_epoch = invocation_bucket_start_epoch(&row.occurred_at)?;
@@ -240,6 +242,7 @@ pub(crate) fn accumulate_invocation_hourly_overall_rollups(
         let is_success_like = archive_invocation_status_is_success_like(
             row.status.as_deref(),
             row.error_message.as_deref(),
+            treat_empty_error_as_success,
         ) && classification.failure_class == FailureClass::None;
         if is_success_like {
             overall_entry.success_count += 1;
@@ -250,6 +253,9 @@ pub(crate) fn accumulate_invocation_hourly_overall_rollups(
         overall_entry.cache_input_tokens += row.cache_input_tokens.unwrap_or_default();
         let cost = row.cost.unwrap_or_default();
         overall_entry.total_cost += cost;
+        if cost < 0.0 {
+            overall_entry.refund_count += 1;
+        }
         if invocation_counts_toward_non_success_usage(
             row.status.as_deref(),
             row.error_message.as_deref(),
@@ -302,6 +308,7 @@ pub(crate) fn accumulate_upstream_account_usage_breakdown_rollup(
         UpstreamAccountUsageBreakdownHourlyDelta,
     >,
     row: &InvocationHourlySourceRecord,
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     if runtime_text_equals(row.status.as_deref(), "running")
         || runtime_text_equals(row.status.as_deref(), "pending")
@@ -314,7 +321,11 @@ pub(crate) fn accumulate_upstream_account_usage_breakdown_rollup(
         row.payload.as_deref(),
         row.resolved_upstream_account_id(),
     );
-    let model = normalized_usage_breakdown_model(row.model.as_deref(), row.payload.as_deref());
+    let model = normalized_usage_breakdown_model(
+        row.model_canonical.as_deref(),
+        row.model.as_deref(),
+        row.payload.as_deref(),
+    );
     let reasoning_effort =
         normalized_usage_breakdown_reasoning_effort(row.payload.as_deref()).unwrap_or_default();
     let entry = breakdowns
@@ -341,6 +352,7 @@ pub(crate) fn accumulate_upstream_account_usage_breakdown_rollup(
     let is_success_like = archive_invocation_status_is_success_like(
         row.status.as_deref(),
         row.error_message.as_deref(),
+        treat_empty_error_as_success,
     ) && classification.failure_class == FailureClass::None;
     if is_success_like {
         entry.success_count += 1;
@@ -413,6 +425,7 @@ pub(crate) fn accumulate_upstream_account_usage_breakdown_rollup(
 
 pub(crate) fn invocation_archive_has_pruned_success_details(
     rows: &[InvocationHourlySourceRecord],
+    treat_empty_error_as_success: bool,
 ) -> bool {
     rows.iter().any(|row| {
         let classification = resolve_failure_classification(
@@ -426,6 +439,7 @@ pub(crate) fn invocation_archive_has_pruned_success_details(
             && archive_invocation_status_is_success_like(
                 row.status.as_deref(),
                 row.error_message.as_deref(),
+                treat_empty_error_as_success,
             )
             && classification.failure_class == FailureClass::None
     })
@@ -434,21 +448,34 @@ pub(crate) fn invocation_archive_has_pruned_success_details(
 pub(crate) fn accumulate_upstream_account_stats_delta(
     entry: &mut UpstreamAccountStatsDelta,
     row: &InvocationHourlySourceRecord,
+    treat_empty_error_as_success: bool,
 ) {
-    accumulate_upstream_account_stats_delta_with_mode(entry, row, false);
+    accumulate_upstream_account_stats_delta_with_mode(
+        entry,
+        row,
+        false,
+        treat_empty_error_as_success,
+    );
 }
 
 pub(crate) fn accumulate_upstream_account_activity_v2_delta(
     entry: &mut UpstreamAccountStatsDelta,
     row: &InvocationHourlySourceRecord,
+    treat_empty_error_as_success: bool,
 ) {
-    accumulate_upstream_account_stats_delta_with_mode(entry, row, true);
+    accumulate_upstream_account_stats_delta_with_mode(
+        entry,
+        row,
+        true,
+        treat_empty_error_as_success,
+    );
 }
 
 fn accumulate_upstream_account_stats_delta_with_mode(
     entry: &mut UpstreamAccountStatsDelta,
     row: &InvocationHourlySourceRecord,
     terminal_activity_only: bool,
+    treat_empty_error_as_success: bool,
 ) {
     let classification = resolve_failure_classification(
         row.status.as_deref(),
@@ -462,6 +489,7 @@ fn accumulate_upstream_account_stats_delta_with_mode(
     let is_success_like = archive_invocation_status_is_success_like(
         row.status.as_deref(),
         row.error_message.as_deref(),
+        treat_empty_error_as_success,
     ) && classification.failure_class == FailureClass::None;
 
     entry.total_count += 1;
@@ -84,11 +84,16 @@ pub(crate) async fn mark_retention_archived_hourly_rollup_targets_tx(
     dataset: &str,
     invocation_rows: &[InvocationHourlySourceRecord],
     forward_proxy_rows: &[ForwardProxyAttemptHourlySourceRecord],
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     match dataset {
         "codex_invocations" => {
-            mark_retention_archived_invocation_hourly_rollup_targets_tx(tx, invocation_rows)
-                .await?;
+            mark_retention_archived_invocation_hourly_rollup_targets_tx(
+                tx,
+                invocation_rows,
+                treat_empty_error_as_success,
+            )
+            .await?;
         }
         "forward_proxy_attempts" => {
             mark_forward_proxy_hourly_rollup_buckets_materialized_tx(tx, forward_proxy_rows)
@@ -102,10 +107,15 @@ pub(crate) async fn mark_retention_archived_hourly_rollup_targets_tx(
 async fn subtract_upstream_account_usage_breakdown_hourly_rows_tx(
     tx: &mut SqliteConnection,
     rows: &[InvocationHourlySourceRecord],
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     let mut breakdowns = BTreeMap::new();
     for row in rows {
-        accumulate_upstream_account_usage_breakdown_rollup(&mut breakdowns, row)?;
+        accumulate_upstream_account_usage_breakdown_rollup(
+            &mut breakdowns,
+            row,
+            treat_empty_error_as_success,
+        )?;
     }
 
     for (
@@ -231,6 +241,7 @@ async fn subtract_upstream_account_usage_breakdown_hourly_rows_tx(
 async fn mark_retention_archived_invocation_hourly_rollup_targets_tx(
     tx: &mut SqliteConnection,
     rows: &[InvocationHourlySourceRecord],
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     let mut overall_targets = HashSet::new();
     let mut upstream_account_usage_targets = HashSet::new();
@@ -274,7 +285,12 @@ async fn mark_retention_archived_invocation_hourly_rollup_targets_tx(
         }
     }
 
-    subtract_upstream_account_usage_breakdown_hourly_rows_tx(tx, rows).await?;
+    subtract_upstream_account_usage_breakdown_hourly_rows_tx(
+        tx,
+        rows,
+        treat_empty_error_as_success,
+    )
+    .await?;
 
     for bucket_start_epoch in upstream_account_usage_targets {
         if live_targets
@@ -644,6 +660,7 @@ pub(crate) fn build_legacy_compatible_invocation_archive_query(
     archive_columns: &HashSet<String>,
 ) -> String {
     let model = legacy_compatible_archive_select_expr(archive_columns, "model");
+    let model_canonical = legacy_compatible_archive_select_expr(archive_columns, "model_canonical");
     let input_tokens = legacy_compatible_archive_select_expr(archive_columns, "input_tokens");
     let output_tokens = legacy_compatible_archive_select_expr(archive_columns, "output_tokens");
     let cache_input_tokens =
@@ -665,6 +682,7 @@ pub(crate) fn build_legacy_compatible_invocation_archive_query(
             status,
             detail_level,
             {model},
+            {model_canonical},
             {input_tokens},
             {output_tokens},
             {cache_input_tokens},
@@ -1055,6 +1073,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
     tx: &mut SqliteConnection,
     rows: &[InvocationHourlySourceRecord],
     targets: &[&str],
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     if rows.is_empty() {
         return Ok(());
@@ -1104,7 +1123,11 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
     for row in rows {
         let bucket_start_epoch = invocation_bucket_start_epoch(&row.occurred_at)?;
         if upsert_overall {
-            accumulate_invocation_hourly_overall_rollups(&mut overall, std::slice::from_ref(row))?;
+            accumulate_invocation_hourly_overall_rollups(
+                &mut overall,
+                std::slice::from_ref(row),
+                treat_empty_error_as_success,
+            )?;
         }
 
         if upsert_failures {
@@ -1118,8 +1141,11 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
             if invocation_status_counts_toward_terminal_totals(row.status.as_deref())
                 && classification.failure_class != FailureClass::None
             {
-                let error_category =
-                    categorize_error(row.error_message.as_deref().unwrap_or_default());
+                let error_category = categorize_error_with_http_status(
+                    row.error_message.as_deref().unwrap_or_default(),
+                    row.http_status,
+                )
+                .code();
                 *failures
                     .entry((
                         bucket_start_epoch,
@@ -1196,6 +1222,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
             let is_success_like = invocation_status_is_success_like(
                 row.status.as_deref(),
                 row.error_message.as_deref(),
+                treat_empty_error_as_success,
             ) && classification.failure_class == FailureClass::None;
             if upsert_prompt_cache {
                 let entry = keyed_conversation_delta(
@@ -1281,6 +1308,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
             if invocation_status_is_success_like(
                 row.status.as_deref(),
                 row.error_message.as_deref(),
+                treat_empty_error_as_success,
             ) && classification.failure_class == FailureClass::None
             {
                 entry.success_count += 1;
@@ -1310,6 +1338,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
             accumulate_upstream_account_usage_breakdown_rollup(
                 &mut upstream_account_usage_breakdown,
                 row,
+                treat_empty_error_as_success,
             )?;
         }
 
@@ -1324,7 +1353,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
                         first_response_byte_total_histogram: empty_approx_histogram(),
                         ..UpstreamAccountStatsDelta::default()
                     });
-                accumulate_upstream_account_stats_delta(entry, row);
+                accumulate_upstream_account_stats_delta(entry, row, treat_empty_error_as_success);
             }
 
             if upsert_upstream_account_stats_minute {
@@ -1341,7 +1370,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
                         first_response_byte_total_histogram: empty_approx_histogram(),
                         ..UpstreamAccountStatsDelta::default()
                     });
-                accumulate_upstream_account_stats_delta(entry, row);
+                accumulate_upstream_account_stats_delta(entry, row, treat_empty_error_as_success);
             }
         }
 
@@ -1363,7 +1392,11 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
                         first_response_byte_total_histogram: empty_approx_histogram(),
                         ..UpstreamAccountStatsDelta::default()
                     });
-                accumulate_upstream_account_activity_v2_delta(entry, row);
+                accumulate_upstream_account_activity_v2_delta(
+                    entry,
+                    row,
+                    treat_empty_error_as_success,
+                );
                 if prompt_cache_key_from_payload(row.payload.as_deref()).is_none()
                     && entry
                         .latest_unkeyed_conversation_at
@@ -1405,6 +1438,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
             if invocation_status_is_success_like(
                 row.status.as_deref(),
                 row.error_message.as_deref(),
+                treat_empty_error_as_success,
             ) && classification.failure_class == FailureClass::None
             {
                 entry.success_count += 1;
@@ -1465,6 +1499,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
                     cache_input_tokens,
                     total_cost,
                     non_success_cost,
+                    refund_count,
                     total_latency_sample_count,
                     total_latency_sum_ms,
                     first_byte_sample_count,
@@ -1477,7 +1512,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
                     first_response_byte_total_histogram,
                     updated_at
                 )
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, datetime('now'))
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, datetime('now'))
                 ON CONFLICT(bucket_start_epoch, source) DO UPDATE SET
                     total_count = invocation_rollup_hourly.total_count + excluded.total_count,
                     success_count = invocation_rollup_hourly.success_count + excluded.success_count,
@@ -1486,6 +1521,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
                     cache_input_tokens = invocation_rollup_hourly.cache_input_tokens + excluded.cache_input_tokens,
                     total_cost = invocation_rollup_hourly.total_cost + excluded.total_cost,
                     non_success_cost = invocation_rollup_hourly.non_success_cost + excluded.non_success_cost,
+                    refund_count = invocation_rollup_hourly.refund_count + excluded.refund_count,
                     total_latency_sample_count = invocation_rollup_hourly.total_latency_sample_count + excluded.total_latency_sample_count,
                     total_latency_sum_ms = invocation_rollup_hourly.total_latency_sum_ms + excluded.total_latency_sum_ms,
                     first_byte_sample_count = invocation_rollup_hourly.first_byte_sample_count + excluded.first_byte_sample_count,
@@ -1508,6 +1544,7 @@ pub(crate) async fn upsert_invocation_hourly_rollups_tx(
             .bind(delta.cache_input_tokens)
             .bind(delta.total_cost)
             .bind(delta.non_success_cost)
+            .bind(delta.refund_count)
             .bind(delta.total_latency_sample_count)
             .bind(delta.total_latency_sum_ms)
             .bind(delta.first_byte_sample_count)
@@ -2458,6 +2495,7 @@ pub(crate) async fn load_live_invocation_hourly_rows_for_bucket_epochs_tx(
             status,
             detail_level,
             model,
+            model_canonical,
             input_tokens,
             output_tokens,
             cache_input_tokens,
@@ -2470,6 +2508,7 @@ pub(crate) async fn load_live_invocation_hourly_rows_for_bucket_epochs_tx(
             cost_output,
             cost_reasoning,
             error_message,
+            http_status,
             failure_kind,
             failure_class,
             is_actionable,
@@ -2505,6 +2544,7 @@ pub(crate) async fn load_live_invocation_hourly_rows_for_bucket_epochs_tx(
 pub(crate) async fn recompute_invocation_hourly_rollups_for_ids_tx(
     tx: &mut SqliteConnection,
     ids: &[i64],
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     if ids.is_empty() {
         return Ok(());
@@ -2534,6 +2574,19 @@ pub(crate) async fn recompute_invocation_hourly_rollups_for_ids_tx(
         .collect::<Result<Vec<_>>>()?;
     bucket_epochs.sort_unstable();
     bucket_epochs.dedup();
+    recompute_invocation_hourly_rollups_for_bucket_epochs_tx(
+        tx,
+        &bucket_epochs,
+        treat_empty_error_as_success,
+    )
+    .await
+}
+
+pub(crate) async fn recompute_invocation_hourly_rollups_for_bucket_epochs_tx(
+    tx: &mut SqliteConnection,
+    bucket_epochs: &[i64],
+    treat_empty_error_as_success: bool,
+) -> Result<()> {
     if bucket_epochs.is_empty() {
         return Ok(());
     }
@@ -2549,22 +2602,31 @@ pub(crate) async fn recompute_invocation_hourly_rollups_for_ids_tx(
         HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_STATS_HOURLY,
         HOURLY_ROLLUP_TARGET_STICKY_KEYS,
     ] {
-        delete_hourly_rollup_rows_for_bucket_epochs_tx(tx, table, &bucket_epochs).await?;
+        delete_hourly_rollup_rows_for_bucket_epochs_tx(tx, table, bucket_epochs).await?;
     }
     delete_rollup_rows_for_bucket_epochs_with_size_tx(
         tx,
         HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_STATS_MINUTE,
-        &bucket_epochs,
+        bucket_epochs,
         60,
     )
     .await?;
 
-    let rows = load_live_invocation_hourly_rows_for_bucket_epochs_tx(tx, &bucket_epochs).await?;
-    upsert_invocation_hourly_rollups_tx(tx, &rows, &INVOCATION_HOURLY_ROLLUP_TARGETS).await?;
+    let rows = load_live_invocation_hourly_rows_for_bucket_epochs_tx(tx, bucket_epochs).await?;
+    upsert_invocation_hourly_rollups_tx(
+        tx,
+        &rows,
+        &INVOCATION_HOURLY_ROLLUP_TARGETS,
+        treat_empty_error_as_success,
+    )
+    .await?;
     Ok(())
 }
 
-pub(crate) async fn replay_live_invocation_hourly_rollups(pool: &Pool<Sqlite>) -> Result<u64> {
+pub(crate) async fn replay_live_invocation_hourly_rollups(
+    pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
+) -> Result<u64> {
     let cursor_id =
         load_hourly_rollup_live_progress(pool, HOURLY_ROLLUP_DATASET_INVOCATIONS).await?;
     let account_activity_v2_cursor = load_hourly_rollup_live_progress(
@@ -2587,6 +2649,7 @@ pub(crate) async fn replay_live_invocation_hourly_rollups(pool: &Pool<Sqlite>) -
             status,
             detail_level,
             model,
+            model_canonical,
             input_tokens,
             output_tokens,
             cache_input_tokens,
@@ -2599,6 +2662,7 @@ pub(crate) async fn replay_live_invocation_hourly_rollups(pool: &Pool<Sqlite>) -
             cost_output,
             cost_reasoning,
             error_message,
+            http_status,
             failure_kind,
             failure_class,
             is_actionable,
@@ -2630,7 +2694,8 @@ pub(crate) async fn replay_live_invocation_hourly_rollups(pool: &Pool<Sqlite>) -
     let last_id = rows.last().map(|row| row.id).unwrap_or(cursor_id);
     let mut tx = pool.begin().await?;
     let targets = live_invocation_rollup_targets(account_activity_v2_cursor, cursor_id);
-    upsert_invocation_hourly_rollups_tx(tx.as_mut(), &rows, &targets).await?;
+    upsert_invocation_hourly_rollups_tx(tx.as_mut(), &rows, &targets, treat_empty_error_as_success)
+        .await?;
     save_hourly_rollup_live_progress_tx(tx.as_mut(), HOURLY_ROLLUP_DATASET_INVOCATIONS, last_id)
         .await?;
     if account_activity_v2_cursor == cursor_id {
@@ -2647,6 +2712,7 @@ pub(crate) async fn replay_live_invocation_hourly_rollups(pool: &Pool<Sqlite>) -
 
 pub(crate) async fn replay_live_invocation_hourly_rollups_tx(
     tx: &mut SqliteConnection,
+    treat_empty_error_as_success: bool,
 ) -> Result<u64> {
     let cursor_id =
         load_hourly_rollup_live_progress_tx(tx, HOURLY_ROLLUP_DATASET_INVOCATIONS).await?;
@@ -2668,6 +2734,7 @@ pub(crate) async fn replay_live_invocation_hourly_rollups_tx(
             status,
             detail_level,
             model,
+            model_canonical,
             input_tokens,
             output_tokens,
             cache_input_tokens,
@@ -2680,6 +2747,7 @@ pub(crate) async fn replay_live_invocation_hourly_rollups_tx(
             cost_output,
             cost_reasoning,
             error_message,
+            http_status,
             failure_kind,
             failure_class,
             is_actionable,
@@ -2709,7 +2777,7 @@ pub(crate) async fn replay_live_invocation_hourly_rollups_tx(
 
     let last_id = rows.last().map(|row| row.id).unwrap_or(cursor_id);
     let targets = live_invocation_rollup_targets(account_activity_v2_cursor, cursor_id);
-    upsert_invocation_hourly_rollups_tx(tx, &rows, &targets).await?;
+    upsert_invocation_hourly_rollups_tx(tx, &rows, &targets, treat_empty_error_as_success).await?;
     save_hourly_rollup_live_progress_tx(tx, HOURLY_ROLLUP_DATASET_INVOCATIONS, last_id).await?;
     if account_activity_v2_cursor == cursor_id {
         save_hourly_rollup_live_progress_tx(
@@ -2738,6 +2806,7 @@ fn live_invocation_rollup_targets(
 
 pub(crate) async fn repair_live_invocation_account_activity_v2_once(
     pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
 ) -> Result<u64> {
     let mut tx = pool.begin().await?;
     let shared_live_cursor =
@@ -2761,11 +2830,11 @@ pub(crate) async fn repair_live_invocation_account_activity_v2_once(
     let rows = sqlx::query_as::<_, InvocationHourlySourceRecord>(&format!(
         r#"
         SELECT
-            id, occurred_at, source, status, detail_level, model,
+            id, occurred_at, source, status, detail_level, model, model_canonical,
             input_tokens, output_tokens, cache_input_tokens, total_tokens, cost,
             {} AS upstream_account_id,
             cost_input, cost_cache_write, cost_cache_read, cost_output, cost_reasoning,
-            error_message, failure_kind, failure_class, is_actionable, payload,
+            error_message, http_status, failure_kind, failure_class, is_actionable, payload,
             t_total_ms, t_req_read_ms, t_req_parse_ms, t_upstream_connect_ms,
             t_upstream_ttfb_ms, t_upstream_stream_ms, t_resp_parse_ms, t_persist_ms
         FROM codex_invocations
@@ -2798,6 +2867,7 @@ pub(crate) async fn repair_live_invocation_account_activity_v2_once(
         tx.as_mut(),
         &rows,
         &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_ACTIVITY_V2],
+        treat_empty_error_as_success,
     )
     .await?;
     save_hourly_rollup_live_progress_tx(
@@ -2846,6 +2916,7 @@ async fn mark_live_account_activity_v2_coverage_tx(tx: &mut SqliteConnection) ->
 
 pub(crate) async fn repair_live_invocation_usage_breakdown_rollups(
     pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     if load_hourly_rollup_live_progress(
         pool,
@@ -2858,14 +2929,19 @@ pub(crate) async fn repair_live_invocation_usage_breakdown_rollups(
     }
 
     loop {
-        let updated = repair_live_invocation_usage_breakdown_rollups_once(pool).await?;
+        let updated =
+            repair_live_invocation_usage_breakdown_rollups_once(pool, treat_empty_error_as_success)
+                .await?;
         if updated == 0 {
             return Ok(());
         }
     }
 }
 
-async fn repair_live_invocation_usage_breakdown_rollups_once(pool: &Pool<Sqlite>) -> Result<u64> {
+async fn repair_live_invocation_usage_breakdown_rollups_once(
+    pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
+) -> Result<u64> {
     let mut tx = pool.begin().await?;
     if load_hourly_rollup_live_progress_tx(
         tx.as_mut(),
@@ -2909,6 +2985,7 @@ async fn repair_live_invocation_usage_breakdown_rollups_once(pool: &Pool<Sqlite>
             status,
             detail_level,
             model,
+            model_canonical,
             input_tokens,
             output_tokens,
             cache_input_tokens,
@@ -2921,6 +2998,7 @@ async fn repair_live_invocation_usage_breakdown_rollups_once(pool: &Pool<Sqlite>
             cost_output,
             cost_reasoning,
             error_message,
+            http_status,
             failure_kind,
             failure_class,
             is_actionable,
@@ -2969,6 +3047,7 @@ async fn repair_live_invocation_usage_breakdown_rollups_once(pool: &Pool<Sqlite>
         tx.as_mut(),
         &rows,
         &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_USAGE_BREAKDOWN],
+        treat_empty_error_as_success,
     )
     .await?;
     save_hourly_rollup_live_progress_tx(
@@ -3424,6 +3503,7 @@ pub(crate) async fn replay_live_upstream_host_network_minute_rollups_from_pool_a
 
 pub(crate) async fn backfill_invocation_rollup_hourly_from_sources(
     pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
 ) -> Result<usize> {
     let archive_files = sqlx::query_as::<_, ArchiveBatchFileRow>(
         r#"
@@ -3484,7 +3564,11 @@ pub(crate) async fn backfill_invocation_rollup_hourly_from_sources(
             if rows.is_empty() {
                 continue;
             }
-            accumulate_invocation_hourly_overall_rollups(&mut overall, &rows)?;
+            accumulate_invocation_hourly_overall_rollups(
+                &mut overall,
+                &rows,
+                treat_empty_error_as_success,
+            )?;
         }
         archive_pool.close().await;
         drop(temp_cleanup);
@@ -3512,6 +3596,7 @@ pub(crate) async fn backfill_invocation_rollup_hourly_from_sources(
                 cost_output,
                 cost_reasoning,
                 error_message,
+                http_status,
                 failure_kind,
                 failure_class,
                 is_actionable,
@@ -3542,7 +3627,11 @@ pub(crate) async fn backfill_invocation_rollup_hourly_from_sources(
         if rows.is_empty() {
             continue;
         }
-        accumulate_invocation_hourly_overall_rollups(&mut overall, &rows)?;
+        accumulate_invocation_hourly_overall_rollups(
+            &mut overall,
+            &rows,
+            treat_empty_error_as_success,
+        )?;
     }
 
     if overall.is_empty() {
@@ -3563,6 +3652,7 @@ pub(crate) async fn backfill_invocation_rollup_hourly_from_sources(
                 cache_input_tokens,
                 total_cost,
                 non_success_cost,
+                refund_count,
                 total_latency_sample_count,
                 total_latency_sum_ms,
                 first_byte_sample_count,
@@ -3575,7 +3665,7 @@ pub(crate) async fn backfill_invocation_rollup_hourly_from_sources(
                 first_response_byte_total_histogram,
                 updated_at
             )
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, datetime('now'))
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, datetime('now'))
             ON CONFLICT(bucket_start_epoch, source) DO UPDATE SET
                 total_count = excluded.total_count,
                 success_count = excluded.success_count,
@@ -3584,6 +3674,7 @@ pub(crate) async fn backfill_invocation_rollup_hourly_from_sources(
                 cache_input_tokens = excluded.cache_input_tokens,
                 total_cost = excluded.total_cost,
                 non_success_cost = excluded.non_success_cost,
+                refund_count = excluded.refund_count,
                 total_latency_sample_count = excluded.total_latency_sample_count,
                 total_latency_sum_ms = excluded.total_latency_sum_ms,
                 first_byte_sample_count = excluded.first_byte_sample_count,
@@ -3606,6 +3697,7 @@ pub(crate) async fn backfill_invocation_rollup_hourly_from_sources(
         .bind(delta.cache_input_tokens)
         .bind(delta.total_cost)
         .bind(delta.non_success_cost)
+        .bind(delta.refund_count)
         .bind(delta.total_latency_sample_count)
         .bind(delta.total_latency_sum_ms)
         .bind(delta.first_byte_sample_count)
@@ -3628,6 +3720,7 @@ pub(crate) async fn backfill_invocation_rollup_hourly_from_sources(
 
 pub(crate) async fn rebuild_upstream_account_stats_rollups_from_sources(
     pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
 ) -> Result<(usize, usize)> {
     let archive_files = sqlx::query_as::<_, ArchiveBatchFileRow>(
         r#"
@@ -3770,6 +3863,7 @@ pub(crate) async fn rebuild_upstream_account_stats_rollups_from_sources(
                 HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_STATS_HOURLY,
                 HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_STATS_MINUTE,
             ],
+            treat_empty_error_as_success,
         )
         .await?;
     }
@@ -3814,6 +3908,7 @@ mod upstream_host_network_minute_tests {
                 status TEXT,
                 detail_level TEXT,
                 model TEXT,
+                model_canonical TEXT,
                 input_tokens INTEGER,
                 output_tokens INTEGER,
                 cache_input_tokens INTEGER,
@@ -3825,6 +3920,7 @@ mod upstream_host_network_minute_tests {
                 cost_output REAL,
                 cost_reasoning REAL,
                 error_message TEXT,
+                http_status INTEGER,
                 failure_kind TEXT,
                 failure_class TEXT,
                 is_actionable INTEGER,
@@ -4320,7 +4416,7 @@ mod upstream_host_network_minute_tests {
         .expect("insert fallback attempt for retained invocation");
         save_progress(&pool, HOURLY_ROLLUP_DATASET_INVOCATIONS, 41).await;
 
-        repair_live_invocation_usage_breakdown_rollups(&pool)
+        repair_live_invocation_usage_breakdown_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
             .await
             .expect("repair missing usage breakdown live rows");
 
@@ -4391,6 +4487,7 @@ mod retention_breakdown_materialization_tests {
                 status TEXT,
                 detail_level TEXT NOT NULL DEFAULT 'full',
                 model TEXT,
+                model_canonical TEXT,
                 input_tokens INTEGER,
                 output_tokens INTEGER,
                 cache_input_tokens INTEGER,
@@ -4402,6 +4499,7 @@ mod retention_breakdown_materialization_tests {
                 cost_output REAL,
                 cost_reasoning REAL,
                 error_message TEXT,
+                http_status INTEGER,
                 failure_kind TEXT,
                 failure_class TEXT,
                 is_actionable INTEGER,
@@ -4545,6 +4643,7 @@ mod retention_breakdown_materialization_tests {
             status: Some("success".to_string()),
             detail_level: DETAIL_LEVEL_FULL.to_string(),
             model: Some("gpt-5".to_string()),
+            model_canonical: None,
             input_tokens: Some(10),
             output_tokens: Some(20),
             cache_input_tokens: Some(0),
@@ -4557,6 +4656,7 @@ mod retention_breakdown_materialization_tests {
             cost_output: Some(0.08),
             cost_reasoning: Some(0.0),
             error_message: None,
+            http_status: None,
             failure_kind: None,
             failure_class: None,
             is_actionable: None,
@@ -4609,6 +4709,7 @@ mod retention_breakdown_materialization_tests {
             "codex_invocations",
             &[row],
             &[],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await
         .expect("mark retention archived hourly rollup targets");
@@ -4669,6 +4770,7 @@ mod retention_breakdown_materialization_tests {
             status: Some("success".to_string()),
             detail_level: DETAIL_LEVEL_FULL.to_string(),
             model: Some("gpt-5".to_string()),
+            model_canonical: None,
             input_tokens: Some(10),
             output_tokens: Some(20),
             cache_input_tokens: Some(0),
@@ -4681,6 +4783,7 @@ mod retention_breakdown_materialization_tests {
             cost_output: Some(0.08),
             cost_reasoning: Some(0.0),
             error_message: None,
+            http_status: None,
             failure_kind: None,
             failure_class: None,
             is_actionable: None,
@@ -4839,6 +4942,7 @@ mod retention_breakdown_materialization_tests {
             "codex_invocations",
             &[archived_row],
             &[],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await
         .expect("mark retention archived hourly rollup targets");
@@ -4905,6 +5009,7 @@ mod retention_breakdown_materialization_tests {
             status: Some("success".to_string()),
             detail_level: DETAIL_LEVEL_FULL.to_string(),
             model: Some("gpt-5".to_string()),
+            model_canonical: None,
             input_tokens: Some(10),
             output_tokens: Some(20),
             cache_input_tokens: Some(0),
@@ -4917,6 +5022,7 @@ mod retention_breakdown_materialization_tests {
             cost_output: Some(0.08),
             cost_reasoning: Some(0.0),
             error_message: None,
+            http_status: None,
             failure_kind: None,
             failure_class: None,
             is_actionable: None,
@@ -4992,6 +5098,7 @@ mod retention_breakdown_materialization_tests {
             "codex_invocations",
             &[archived_row],
             &[],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await
         .expect("mark retention archived hourly rollup targets");
@@ -5055,6 +5162,7 @@ mod retention_breakdown_materialization_tests {
             status: Some("success".to_string()),
             detail_level: DETAIL_LEVEL_FULL.to_string(),
             model: Some("gpt-5".to_string()),
+            model_canonical: None,
             input_tokens: Some(25),
             output_tokens: Some(35),
             cache_input_tokens: Some(5),
@@ -5067,6 +5175,7 @@ mod retention_breakdown_materialization_tests {
             cost_output: Some(0.15),
             cost_reasoning: Some(0.0),
             error_message: None,
+            http_status: None,
             failure_kind: None,
             failure_class: None,
             is_actionable: None,
@@ -5091,6 +5200,7 @@ mod retention_breakdown_materialization_tests {
             tx.as_mut(),
             &[row],
             &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_USAGE_BREAKDOWN],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await
         .expect("upsert breakdown rollup");
@@ -5134,6 +5244,7 @@ mod retention_breakdown_materialization_tests {
                 status: Some("success".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: Some("gpt-5".to_string()),
+                model_canonical: None,
                 input_tokens: Some(10),
                 output_tokens: Some(20),
                 cache_input_tokens: Some(0),
@@ -5146,6 +5257,7 @@ mod retention_breakdown_materialization_tests {
                 cost_output: None,
                 cost_reasoning: None,
                 error_message: None,
+                http_status: None,
                 failure_kind: None,
                 failure_class: Some("none".to_string()),
                 is_actionable: Some(0),
@@ -5166,6 +5278,7 @@ mod retention_breakdown_materialization_tests {
                 status: Some("failed".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: Some("gpt-5".to_string()),
+                model_canonical: None,
                 input_tokens: Some(11),
                 output_tokens: Some(21),
                 cache_input_tokens: Some(0),
@@ -5178,6 +5291,7 @@ mod retention_breakdown_materialization_tests {
                 cost_output: None,
                 cost_reasoning: None,
                 error_message: Some("upstream stream error".to_string()),
+                http_status: None,
                 failure_kind: Some("upstream_response_failed".to_string()),
                 failure_class: Some("service_failure".to_string()),
                 is_actionable: Some(1),
@@ -5198,6 +5312,7 @@ mod retention_breakdown_materialization_tests {
                 status: Some("running".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: Some("gpt-5".to_string()),
+                model_canonical: None,
                 input_tokens: Some(12),
                 output_tokens: Some(22),
                 cache_input_tokens: Some(0),
@@ -5210,6 +5325,7 @@ mod retention_breakdown_materialization_tests {
                 cost_output: None,
                 cost_reasoning: None,
                 error_message: None,
+                http_status: None,
                 failure_kind: None,
                 failure_class: Some("none".to_string()),
                 is_actionable: Some(0),
@@ -5230,6 +5346,7 @@ mod retention_breakdown_materialization_tests {
                 status: Some("pending".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: Some("gpt-5".to_string()),
+                model_canonical: None,
                 input_tokens: Some(13),
                 output_tokens: Some(23),
                 cache_input_tokens: Some(0),
@@ -5242,6 +5359,7 @@ mod retention_breakdown_materialization_tests {
                 cost_output: None,
                 cost_reasoning: None,
                 error_message: None,
+                http_status: None,
                 failure_kind: None,
                 failure_class: Some("none".to_string()),
                 is_actionable: Some(0),
@@ -5262,6 +5380,7 @@ mod retention_breakdown_materialization_tests {
             tx.as_mut(),
             &rows,
             &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_USAGE_BREAKDOWN],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await
         .expect("upsert breakdown rollup");
@@ -5390,9 +5509,12 @@ mod retention_breakdown_materialization_tests {
             .expect("seed breakdown rollup row");
         }
 
-        let touched = repair_materialized_invocation_archive_usage_breakdown_backfill_state(&pool)
-            .await
-            .expect("repair materialized usage breakdown state");
+        let touched = repair_materialized_invocation_archive_usage_breakdown_backfill_state(
+            &pool,
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+        )
+        .await
+        .expect("repair materialized usage breakdown state");
         assert_eq!(touched, 2);
 
         let remaining_rows: i64 = sqlx::query_scalar(
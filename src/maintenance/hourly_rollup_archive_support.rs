@@ -134,9 +134,12 @@ pub(crate) fn shanghai_utc_cutoff_string(days: u64) -> String {
     format_naive(shanghai_retention_cutoff(days).naive_utc())
 }
 
+/// Shared success rule for stats and timeseries: a success status, `http_200`
+/// without an error, or (when enabled) no status and no error at all.
 pub(crate) fn invocation_status_is_success_like(
     status: Option<&str>,
     error_message: Option<&str>,
+    treat_empty_error_as_success: bool,
 ) -> bool {
     let normalized_status = status.map(str::trim).unwrap_or_default();
     let error_message_empty = error_message.map(str::trim).is_none_or(str::is_empty);
@@ -145,14 +148,23 @@ pub(crate) fn invocation_status_is_success_like(
         || normalized_status.eq_ignore_ascii_case("completed")
         || normalized_status.eq_ignore_ascii_case(INVOCATION_STATUS_WARNING_SUCCESS)
         || (normalized_status.eq_ignore_ascii_case("http_200") && error_message_empty)
+        || (treat_empty_error_as_success && normalized_status.is_empty() && error_message_empty)
 }
 
 pub(crate) fn invocation_status_is_success_like_sql(
     status_column: &str,
     error_message_column: &str,
+    treat_empty_error_as_success: bool,
 ) -> String {
+    let empty_error_clause = if treat_empty_error_as_success {
+        format!(
+            " OR (TRIM(COALESCE({status_column}, '')) = '' AND TRIM(COALESCE({error_message_column}, '')) = '')"
+        )
+    } else {
+        String::new()
+    };
     format!(
-        "(LOWER(TRIM(COALESCE({status_column}, ''))) IN ('success', 'completed', '{warning_success}') OR (LOWER(TRIM(COALESCE({status_column}, ''))) = 'http_200' AND TRIM(COALESCE({error_message_column}, '')) = ''))",
+        "(LOWER(TRIM(COALESCE({status_column}, ''))) IN ('success', 'completed', '{warning_success}') OR (LOWER(TRIM(COALESCE({status_column}, ''))) = 'http_200' AND TRIM(COALESCE({error_message_column}, '')) = ''){empty_error_clause})",
         warning_success = INVOCATION_STATUS_WARNING_SUCCESS,
     )
 }
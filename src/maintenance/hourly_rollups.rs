@@ -11,10 +11,13 @@ const LEGACY_MATERIALIZED_UPSTREAM_ACCOUNT_ARCHIVE_REPLAY_TARGETS: [&str; 3] = [
     HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_STATS_MINUTE,
 ];
 
-pub(crate) async fn sync_hourly_rollups_from_live_tables(pool: &Pool<Sqlite>) -> Result<()> {
+pub(crate) async fn sync_hourly_rollups_from_live_tables(
+    pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
+) -> Result<()> {
     let mut attempt = 1_u32;
     loop {
-        match sync_hourly_rollups_from_live_tables_once(pool).await {
+        match sync_hourly_rollups_from_live_tables_once(pool, treat_empty_error_as_success).await {
             Ok(()) => return Ok(()),
             Err(err)
                 if attempt < LIVE_ROLLUP_LOCK_RETRY_MAX_ATTEMPTS
@@ -42,9 +45,13 @@ pub(crate) async fn sync_hourly_rollups_from_live_tables(pool: &Pool<Sqlite>) ->
     }
 }
 
-async fn sync_hourly_rollups_from_live_tables_once(pool: &Pool<Sqlite>) -> Result<()> {
+async fn sync_hourly_rollups_from_live_tables_once(
+    pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
+) -> Result<()> {
     loop {
-        let updated = replay_live_invocation_hourly_rollups(pool).await?;
+        let updated =
+            replay_live_invocation_hourly_rollups(pool, treat_empty_error_as_success).await?;
         if updated == 0 {
             break;
         }
@@ -69,7 +76,7 @@ async fn sync_hourly_rollups_from_live_tables_once(pool: &Pool<Sqlite>) -> Resul
             break;
         }
     }
-    repair_live_invocation_account_activity_v2_once(pool).await?;
+    repair_live_invocation_account_activity_v2_once(pool, treat_empty_error_as_success).await?;
     Ok(())
 }
 
@@ -295,6 +302,7 @@ async fn reopen_materialized_invocation_archive_usage_breakdown_backfill_tx(
     file_path: &str,
     coverage_start_at: Option<&str>,
     coverage_end_at: Option<&str>,
+    treat_empty_error_as_success: bool,
 ) -> Result<Vec<String>> {
     let mut reopened_file_paths = vec![file_path.to_string()];
     if let (Some(coverage_start_at), Some(coverage_end_at)) = (coverage_start_at, coverage_end_at) {
@@ -321,6 +329,7 @@ async fn reopen_materialized_invocation_archive_usage_breakdown_backfill_tx(
             tx,
             &retained_live_rows,
             &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_USAGE_BREAKDOWN],
+            treat_empty_error_as_success,
         )
         .await?;
     }
@@ -332,6 +341,7 @@ async fn reopen_materialized_invocation_archive_usage_breakdown_backfill_tx(
 
 pub(crate) async fn repair_materialized_invocation_archive_usage_breakdown_backfill_state(
     pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
 ) -> Result<usize> {
     let mut tx = pool.begin().await?;
     let archive_rows =
@@ -358,6 +368,7 @@ pub(crate) async fn repair_materialized_invocation_archive_usage_breakdown_backf
             &file_path,
             coverage_start_at.as_deref(),
             coverage_end_at.as_deref(),
+            treat_empty_error_as_success,
         )
         .await?;
         for reopened_file_path in reopened {
@@ -709,6 +720,7 @@ pub(crate) fn build_invocation_archive_rows_chunk_query(
     let cache_input_tokens =
         legacy_compatible_archive_select_expr(archive_columns, "cache_input_tokens");
     let model = legacy_compatible_archive_select_expr(archive_columns, "model");
+    let model_canonical = legacy_compatible_archive_select_expr(archive_columns, "model_canonical");
     let cost_input = legacy_compatible_archive_select_expr(archive_columns, "cost_input");
     let cost_cache_write =
         legacy_compatible_archive_select_expr(archive_columns, "cost_cache_write");
@@ -724,6 +736,7 @@ pub(crate) fn build_invocation_archive_rows_chunk_query(
             status,
             detail_level,
             {model},
+            {model_canonical},
             {input_tokens},
             {output_tokens},
             {cache_input_tokens},
@@ -821,8 +834,13 @@ pub(crate) async fn load_archive_coverage_bounds(
 
 pub(crate) async fn invocation_archive_has_pruned_success_details_in_db(
     archive_pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
 ) -> Result<bool> {
-    let success_like_sql = invocation_status_is_success_like_sql("status", "error_message");
+    let success_like_sql = invocation_status_is_success_like_sql(
+        "status",
+        "error_message",
+        treat_empty_error_as_success,
+    );
     let query = format!(
         r#"
         SELECT EXISTS(
@@ -849,6 +867,7 @@ pub(crate) async fn replay_invocation_archive_rows_into_hourly_rollups_tx_with_b
     pending_targets: &[&str],
     started_at: Instant,
     max_elapsed: Option<Duration>,
+    treat_empty_error_as_success: bool,
 ) -> Result<HistoricalRollupArchiveReplayResult> {
     let mut start_after_id = initial_cursor_id.max(0);
     let archive_columns = load_sqlite_table_columns(archive_pool, "codex_invocations").await?;
@@ -870,7 +889,13 @@ pub(crate) async fn replay_invocation_archive_rows_into_hourly_rollups_tx_with_b
             });
         }
 
-        upsert_invocation_hourly_rollups_tx(tx, &rows, pending_targets).await?;
+        upsert_invocation_hourly_rollups_tx(
+            tx,
+            &rows,
+            pending_targets,
+            treat_empty_error_as_success,
+        )
+        .await?;
         mark_invocation_hourly_rollup_buckets_materialized_tx(tx, &rows).await?;
         start_after_id = rows
             .last()
@@ -893,6 +918,7 @@ pub(crate) async fn replay_invocation_archive_rows_into_hourly_rollups_tx_with_b
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn replay_invocation_archive_rows_into_hourly_rollups_until_cursor_tx_with_budget(
     tx: &mut SqliteConnection,
     archive_pool: &Pool<Sqlite>,
@@ -901,6 +927,7 @@ async fn replay_invocation_archive_rows_into_hourly_rollups_until_cursor_tx_with
     pending_targets: &[&str],
     started_at: Instant,
     max_elapsed: Option<Duration>,
+    treat_empty_error_as_success: bool,
 ) -> Result<HistoricalRollupArchiveReplayResult> {
     let mut start_after_id = initial_cursor_id.max(0);
     let target_cursor_id = target_cursor_id.max(0);
@@ -934,7 +961,13 @@ async fn replay_invocation_archive_rows_into_hourly_rollups_until_cursor_tx_with
             });
         }
 
-        upsert_invocation_hourly_rollups_tx(tx, &bounded_rows, pending_targets).await?;
+        upsert_invocation_hourly_rollups_tx(
+            tx,
+            &bounded_rows,
+            pending_targets,
+            treat_empty_error_as_success,
+        )
+        .await?;
         mark_invocation_hourly_rollup_buckets_materialized_tx(tx, &bounded_rows).await?;
         start_after_id = bounded_rows
             .last()
@@ -1074,6 +1107,7 @@ async fn replay_invocation_archive_files_into_hourly_rollups_tx_with_limits(
     max_elapsed: Option<Duration>,
     skip_archive_batches: usize,
     archive_files: Vec<ArchiveBatchFileRow>,
+    treat_empty_error_as_success: bool,
 ) -> Result<HistoricalRollupArchiveReplaySummary> {
     let mut summary = HistoricalRollupArchiveReplaySummary::default();
     let mut skip_remaining = skip_archive_batches;
@@ -1199,8 +1233,11 @@ async fn replay_invocation_archive_files_into_hourly_rollups_tx_with_limits(
         ));
         let temp_cleanup = TempSqliteCleanup(temp_path.clone());
         let archive_pool = open_historical_rollup_archive_pool(&archive_path, &temp_path).await?;
-        let has_pruned_success_details =
-            invocation_archive_has_pruned_success_details_in_db(&archive_pool).await?;
+        let has_pruned_success_details = invocation_archive_has_pruned_success_details_in_db(
+            &archive_pool,
+            treat_empty_error_as_success,
+        )
+        .await?;
         if has_pruned_success_details {
             let mut replayable_targets = Vec::with_capacity(pending_targets.len());
             let mut structured_rollup_targets = Vec::new();
@@ -1244,6 +1281,7 @@ async fn replay_invocation_archive_files_into_hourly_rollups_tx_with_limits(
                 &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_ACTIVITY_V2],
                 started_at,
                 max_elapsed,
+                treat_empty_error_as_success,
             )
             .await?;
             if replay.outcome == HistoricalRollupArchiveReplayOutcome::HitBudget {
@@ -1340,6 +1378,7 @@ async fn replay_invocation_archive_files_into_hourly_rollups_tx_with_limits(
                     &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_USAGE_BREAKDOWN],
                     started_at,
                     max_elapsed,
+                    treat_empty_error_as_success,
                 )
                 .await?;
             if catch_up_outcome.cursor_id > usage_breakdown_cursor {
@@ -1369,6 +1408,7 @@ async fn replay_invocation_archive_files_into_hourly_rollups_tx_with_limits(
             &pending_targets,
             started_at,
             max_elapsed,
+            treat_empty_error_as_success,
         )
         .await?;
         archive_pool.close().await;
@@ -1454,6 +1494,7 @@ pub(crate) async fn replay_invocation_archives_into_hourly_rollups_tx_with_limit
     max_archive_batches: Option<u64>,
     max_elapsed: Option<Duration>,
     skip_archive_batches: usize,
+    treat_empty_error_as_success: bool,
 ) -> Result<HistoricalRollupArchiveReplaySummary> {
     let archive_files = sqlx::query_as::<_, ArchiveBatchFileRow>(
         r#"
@@ -1475,6 +1516,7 @@ pub(crate) async fn replay_invocation_archives_into_hourly_rollups_tx_with_limit
         max_elapsed,
         skip_archive_batches,
         archive_files,
+        treat_empty_error_as_success,
     )
     .await
 }
@@ -1485,6 +1527,7 @@ pub(crate) async fn replay_invocation_usage_breakdown_archives_into_hourly_rollu
     max_archive_batches: Option<u64>,
     max_elapsed: Option<Duration>,
     skip_archive_batches: usize,
+    treat_empty_error_as_success: bool,
 ) -> Result<HistoricalRollupArchiveReplaySummary> {
     let archive_files = load_invocation_archive_files_missing_rollup_target(
         &mut *tx,
@@ -1498,15 +1541,24 @@ pub(crate) async fn replay_invocation_usage_breakdown_archives_into_hourly_rollu
         max_elapsed,
         skip_archive_batches,
         archive_files,
+        treat_empty_error_as_success,
     )
     .await
 }
 
 pub(crate) async fn replay_invocation_archives_into_hourly_rollups_tx(
     tx: &mut SqliteConnection,
+    treat_empty_error_as_success: bool,
 ) -> Result<HistoricalRollupArchiveReplaySummary> {
-    replay_invocation_archives_into_hourly_rollups_tx_with_limits(tx, Instant::now(), None, None, 0)
-        .await
+    replay_invocation_archives_into_hourly_rollups_tx_with_limits(
+        tx,
+        Instant::now(),
+        None,
+        None,
+        0,
+        treat_empty_error_as_success,
+    )
+    .await
 }
 
 pub(crate) async fn replay_forward_proxy_archives_into_hourly_rollups_tx_with_limits(
@@ -1967,10 +2019,17 @@ pub(crate) async fn replay_forward_proxy_archives_into_hourly_rollups_tx(
     .await
 }
 
-pub(crate) async fn bootstrap_hourly_rollups(pool: &Pool<Sqlite>) -> Result<()> {
-    repair_live_invocation_usage_breakdown_rollups(pool).await?;
-    sync_hourly_rollups_from_live_tables(pool).await?;
-    repair_materialized_invocation_archive_usage_breakdown_backfill_state(pool).await?;
+pub(crate) async fn bootstrap_hourly_rollups(
+    pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
+) -> Result<()> {
+    repair_live_invocation_usage_breakdown_rollups(pool, treat_empty_error_as_success).await?;
+    sync_hourly_rollups_from_live_tables(pool, treat_empty_error_as_success).await?;
+    repair_materialized_invocation_archive_usage_breakdown_backfill_state(
+        pool,
+        treat_empty_error_as_success,
+    )
+    .await?;
     repair_materialized_upstream_account_archive_markers(pool).await?;
     let account_stats_hourly_count: i64 =
         sqlx::query_scalar("SELECT COUNT(*) FROM upstream_account_stats_hourly")
@@ -1981,27 +2040,33 @@ pub(crate) async fn bootstrap_hourly_rollups(pool: &Pool<Sqlite>) -> Result<()>
             .fetch_one(pool)
             .await?;
     if account_stats_hourly_count == 0 || account_stats_minute_count == 0 {
-        rebuild_upstream_account_stats_rollups_from_sources(pool).await?;
+        rebuild_upstream_account_stats_rollups_from_sources(pool, treat_empty_error_as_success)
+            .await?;
         repair_materialized_upstream_account_archive_markers(pool).await?;
     }
     Ok(())
 }
 
-pub(crate) async fn refresh_hourly_rollups_for_read_surfaces(pool: &Pool<Sqlite>) -> Result<()> {
-    sync_hourly_rollups_from_live_tables(pool).await?;
-    ensure_invocation_summary_rollups_ready_best_effort(pool).await?;
+pub(crate) async fn refresh_hourly_rollups_for_read_surfaces(
+    pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
+) -> Result<()> {
+    sync_hourly_rollups_from_live_tables(pool, treat_empty_error_as_success).await?;
+    ensure_invocation_summary_rollups_ready_best_effort(pool, treat_empty_error_as_success).await?;
     Ok(())
 }
 
 pub(crate) async fn ensure_hourly_rollups_caught_up(state: &AppState) -> Result<()> {
     let _guard = state.hourly_rollup_sync_lock.lock().await;
-    sync_hourly_rollups_from_live_tables(&state.pool).await
+    sync_hourly_rollups_from_live_tables(&state.pool, state.config.treat_empty_error_as_success)
+        .await
 }
 
 pub(crate) async fn refresh_hourly_rollups_for_read_surfaces_best_effort(
     pool: &Pool<Sqlite>,
     hourly_rollup_sync_lock: &Mutex<()>,
     reason: &'static str,
+    treat_empty_error_as_success: bool,
 ) {
     let gate = crate::db_pressure::global_db_pressure_gate();
     let _permit = match gate.try_begin_background("hourly_rollup_refresh") {
@@ -2017,7 +2082,9 @@ pub(crate) async fn refresh_hourly_rollups_for_read_surfaces_best_effort(
     };
     let _guard = hourly_rollup_sync_lock.lock().await;
 
-    if let Err(err) = refresh_hourly_rollups_for_read_surfaces(pool).await {
+    if let Err(err) =
+        refresh_hourly_rollups_for_read_surfaces(pool, treat_empty_error_as_success).await
+    {
         gate.record_error("hourly_rollup_refresh", &err);
         warn!(
             error = %err,
@@ -2128,278 +2195,290 @@ mod hourly_rollup_archive_support;
 pub(crate) use hourly_rollup_archive_support::*;
 
 pub(crate) fn build_health_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
-    router
-        .route("/health", get(health_check))
-        .route("/api/version", get(get_versions))
+    router.route("/health", get(health_check))
 }
 
 pub(crate) fn build_settings_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
     router
-        .route("/api/settings", get(get_settings))
+        .route("/version", get(get_versions))
+        .route("/bootstrap", get(fetch_bootstrap))
+        .route("/settings", get(get_settings))
         .route(
-            "/api/settings/external-api-keys",
+            "/settings/external-api-keys",
             get(list_external_api_keys).post(create_external_api_key),
         )
         .route(
-            "/api/settings/external-api-keys/:id/rotate",
+            "/settings/external-api-keys/:id/rotate",
             post(rotate_external_api_key),
         )
         .route(
-            "/api/settings/external-api-keys/:id/disable",
+            "/settings/external-api-keys/:id/disable",
             post(disable_external_api_key),
         )
         .route(
-            "/api/settings/proxy-models",
+            "/settings/proxy-models",
             any(removed_proxy_model_settings_endpoint),
         )
-        .route("/api/settings/proxy", put(put_proxy_settings))
-        .route(
-            "/api/settings/forward-proxy",
-            put(put_forward_proxy_settings),
-        )
+        .route("/settings/proxy", put(put_proxy_settings))
+        .route("/settings/forward-proxy", put(put_forward_proxy_settings))
         .route(
-            "/api/settings/forward-proxy/validate",
+            "/settings/forward-proxy/validate",
             post(post_forward_proxy_candidate_validation),
         )
         .route(
-            "/api/settings/forward-proxy/refresh-subscriptions",
+            "/settings/forward-proxy/refresh-subscriptions",
             post(post_forward_proxy_refresh_subscriptions),
         )
         .route(
-            "/api/settings/forward-proxy/nodes/:proxy_key/test-stream",
+            "/settings/forward-proxy/nodes/:proxy_key/test-stream",
             get(stream_forward_proxy_node_latency_test),
         )
         .route(
-            "/api/settings/forward-proxy/nodes/test-stream",
+            "/settings/forward-proxy/nodes/test-stream",
             get(stream_forward_proxy_nodes_latency_test),
         )
-        .route("/api/settings/pricing", put(put_pricing_settings))
+        .route("/settings/pricing", put(put_pricing_settings))
 }
 
 pub(crate) fn build_invocation_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
     router
-        .route("/api/invocations", get(list_invocations))
-        .route("/api/invocations/locate", get(locate_invocation))
+        .route("/invocations", get(list_invocations_with_fields))
+        .route("/invocations/locate", get(locate_invocation))
         .route(
-            "/api/invocations/:invoke_id/pool-attempts",
+            "/invocations/:id",
+            get(fetch_invocation_by_id).delete(delete_invocation_by_id),
+        )
+        .route("/invocations/:id/label", put(update_invocation_label))
+        .route(
+            "/invocations/:invoke_id/pool-attempts",
             get(fetch_invocation_pool_attempts),
         )
         .route(
-            "/api/invocations/:id/detail",
+            "/invocations/:id/detail",
             get(fetch_invocation_record_detail),
         )
         .route(
-            "/api/invocations/:id/workflow-detail",
+            "/invocations/:id/workflow-detail",
             get(fetch_invocation_workflow_detail),
         )
         .route(
-            "/api/invocations/:id/response-body",
+            "/invocations/:id/response-body",
             get(fetch_invocation_response_body),
         )
         .route(
-            "/api/invocations/:id/request-body",
+            "/invocations/:id/request-body",
             get(fetch_invocation_request_body),
         )
-        .route("/api/invocations/summary", get(fetch_invocation_summary))
+        .route("/invocations/summary", get(fetch_invocation_summary))
         .route(
-            "/api/invocations/suggestions",
+            "/invocations/suggestions",
             get(fetch_invocation_suggestions),
         )
         .route(
-            "/api/invocations/new-count",
+            "/invocations/new-count",
             get(fetch_invocation_new_records_count),
         )
 }
 
 pub(crate) fn build_stats_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
     router
-        .route("/api/stats", get(fetch_stats))
-        .route("/api/stats/summary", get(fetch_summary))
+        .route("/stats", get(fetch_stats_cached))
+        .route("/stats/summary", get(fetch_summary_in_units))
+        .route("/stats/dashboard-activity", get(fetch_dashboard_activity))
         .route(
-            "/api/stats/dashboard-activity",
-            get(fetch_dashboard_activity),
-        )
-        .route(
-            "/api/stats/dashboard-activity/recent",
+            "/stats/dashboard-activity/recent",
             get(fetch_dashboard_activity_recent),
         )
         .route(
-            "/api/stats/dashboard-network-timeseries",
+            "/stats/dashboard-network-timeseries",
             get(fetch_dashboard_network_timeseries),
         )
         .route(
-            "/api/stats/dashboard-network-recent",
+            "/stats/dashboard-network-recent",
             get(fetch_dashboard_network_recent),
         )
         .route(
-            "/api/stats/upstream-account-activity",
+            "/stats/upstream-account-activity",
             get(fetch_upstream_account_activity),
         )
+        .route("/stats/forward-proxy", get(fetch_forward_proxy_live_stats))
         .route(
-            "/api/stats/forward-proxy",
-            get(fetch_forward_proxy_live_stats),
-        )
-        .route(
-            "/api/stats/forward-proxy/timeseries",
+            "/stats/forward-proxy/timeseries",
             get(fetch_forward_proxy_timeseries),
         )
-        .route("/api/stats/timeseries", get(fetch_timeseries))
+        .route("/stats/timeseries", get(fetch_timeseries_cached))
+        .route("/stats/timeseries.svg", get(fetch_timeseries_svg))
+        .route("/stats/peak", get(fetch_timeseries_peak))
+        .route("/stats/comparison", get(fetch_stats_comparison))
         .route(
-            "/api/stats/parallel-work",
+            "/stats/parallel-work",
             get(fetch_parallel_work_stats_cached),
         )
-        .route("/api/stats/perf", get(fetch_perf_stats))
-        .route("/api/stats/errors", get(fetch_error_distribution))
-        .route("/api/stats/failures/summary", get(fetch_failure_summary))
-        .route("/api/stats/errors/others", get(fetch_other_errors))
+        .route("/stats/perf", get(fetch_perf_stats))
+        .route("/stats/efficiency", get(fetch_model_efficiency))
+        .route("/stats/cache-savings", get(fetch_cache_savings))
+        .route("/stats/hourly-profile", get(fetch_hourly_profile))
+        .route("/stats/streak", get(fetch_streak))
+        .route("/stats/scatter", get(fetch_scatter))
+        .route("/stats/by-period", get(fetch_billing_period_totals))
+        .route("/budget", get(fetch_budget))
+        .route("/stats/errors", get(fetch_error_distribution))
+        .route("/stats/failures/summary", get(fetch_failure_summary))
+        .route("/stats/errors/others", get(fetch_other_errors))
+        .route("/stats/errors/sample", get(fetch_error_samples))
+        .route("/stats/errors/summary", get(fetch_error_summary))
         .route(
-            "/api/stats/prompt-cache-conversations",
+            "/stats/prompt-cache-conversations",
             get(fetch_prompt_cache_conversations),
         )
         .route(
-            "/api/stats/prompt-cache-conversation-bindings/bulk-actions",
+            "/stats/prompt-cache-conversation-bindings/bulk-actions",
             post(post_bulk_prompt_cache_conversation_bindings),
         )
         .route(
-            "/api/stats/prompt-cache-conversation-binding-events/*encodedPromptCacheKey",
+            "/stats/prompt-cache-conversation-binding-events/*encodedPromptCacheKey",
             get(list_prompt_cache_conversation_operation_events),
         )
         .route(
-            "/api/stats/prompt-cache-conversation-bindings/*encodedPromptCacheKey",
+            "/stats/prompt-cache-conversation-bindings/*encodedPromptCacheKey",
             get(get_prompt_cache_conversation_binding)
                 .patch(patch_prompt_cache_conversation_binding),
         )
-        .route("/api/quota/latest", get(latest_quota_snapshot))
+        .route("/quota/latest", get(latest_quota_snapshot_cached))
 }
 
 pub(crate) fn build_system_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
     router
-        .route("/api/system/status", get(fetch_system_status))
-        .route("/api/system/tasks", get(list_system_task_runs))
+        .route("/system/status", get(fetch_system_status))
+        .route("/system/tasks", get(list_system_task_runs))
+        .route("/admin/stats", get(fetch_admin_stats))
+        .route("/admin/logs", get(fetch_admin_logs))
+        .route("/admin/connections", get(fetch_admin_connections))
+        .route("/admin/refresh-summaries", post(refresh_admin_summaries))
 }
 
 pub(crate) fn build_pool_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
     router
         .route(
-            "/api/pool/routing-settings",
+            "/pool/routing-settings",
             get(get_pool_routing_settings).put(update_pool_routing_settings),
         )
-        .route("/api/pool/tags", get(list_tags))
+        .route("/pool/tags", get(list_tags))
         .route(
-            "/api/pool/forward-proxy-binding-nodes",
+            "/pool/forward-proxy-binding-nodes",
             get(list_forward_proxy_binding_nodes),
         )
         .route(
-            "/api/pool/upstream-accounts",
+            "/pool/upstream-accounts",
             get(list_upstream_accounts_from_uri).post(bulk_update_upstream_accounts),
         )
         .route(
-            "/api/pool/upstream-account-events",
+            "/pool/upstream-account-events",
             get(list_upstream_account_action_events),
         )
         .route(
-            "/api/pool/upstream-accounts/:account_id/call-attempts/locate",
+            "/pool/upstream-accounts/:account_id/call-attempts/locate",
             get(locate_upstream_account_attempt),
         )
         .route(
-            "/api/pool/upstream-accounts/:account_id/call-attempts",
+            "/pool/upstream-accounts/:account_id/call-attempts",
             get(list_upstream_account_attempts),
         )
         .route(
-            "/api/pool/upstream-accounts/window-usage",
+            "/pool/upstream-accounts/window-usage",
             post(get_upstream_account_window_usage),
         )
         .route(
-            "/api/pool/upstream-accounts/bulk-sync-jobs",
+            "/pool/upstream-accounts/bulk-sync-jobs",
             post(create_bulk_upstream_account_sync_job),
         )
         .route(
-            "/api/pool/upstream-accounts/bulk-sync-jobs/:jobId/events",
+            "/pool/upstream-accounts/bulk-sync-jobs/:jobId/events",
             get(stream_bulk_upstream_account_sync_job_events),
         )
         .route(
-            "/api/pool/upstream-accounts/bulk-sync-jobs/:jobId",
+            "/pool/upstream-accounts/bulk-sync-jobs/:jobId",
             get(get_bulk_upstream_account_sync_job).delete(cancel_bulk_upstream_account_sync_job),
         )
         .route(
-            "/api/pool/upstream-account-groups/*groupName",
+            "/pool/upstream-account-groups/*groupName",
             put(update_upstream_account_group).delete(delete_upstream_account_group),
         )
         .route(
-            "/api/pool/upstream-accounts/:id/sticky-keys",
+            "/pool/upstream-accounts/:id/sticky-keys",
             get(get_upstream_account_sticky_keys),
         )
         .route(
-            "/api/pool/upstream-accounts/:id",
+            "/pool/upstream-accounts/:id",
             get(get_upstream_account)
                 .patch(update_upstream_account)
                 .delete(delete_upstream_account),
         )
         .route(
-            "/api/pool/upstream-accounts/:id/sync",
+            "/pool/upstream-accounts/:id/sync",
             post(sync_upstream_account),
         )
         .route(
-            "/api/pool/upstream-accounts/:id/oauth/relogin",
+            "/pool/upstream-accounts/:id/oauth/relogin",
             post(relogin_upstream_account),
         )
         .route(
-            "/api/pool/upstream-accounts/api-keys",
+            "/pool/upstream-accounts/api-keys",
             post(create_api_key_account),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/login-sessions",
+            "/pool/upstream-accounts/oauth/login-sessions",
             post(create_oauth_login_session),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/imports/validate",
+            "/pool/upstream-accounts/oauth/imports/validate",
             post(validate_imported_oauth_accounts)
                 .layer(DefaultBodyLimit::max(IMPORTED_OAUTH_ROUTE_MAX_BODY_BYTES)),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/imports/validation-jobs",
+            "/pool/upstream-accounts/oauth/imports/validation-jobs",
             post(create_imported_oauth_validation_job)
                 .layer(DefaultBodyLimit::max(IMPORTED_OAUTH_ROUTE_MAX_BODY_BYTES)),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/imports/validation-jobs/:jobId/events",
+            "/pool/upstream-accounts/oauth/imports/validation-jobs/:jobId/events",
             get(stream_imported_oauth_validation_job_events),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/imports/validation-jobs/:jobId",
+            "/pool/upstream-accounts/oauth/imports/validation-jobs/:jobId",
             delete(cancel_imported_oauth_validation_job),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/imports",
+            "/pool/upstream-accounts/oauth/imports",
             post(import_validated_oauth_accounts)
                 .layer(DefaultBodyLimit::max(IMPORTED_OAUTH_ROUTE_MAX_BODY_BYTES)),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/mailbox-sessions",
+            "/pool/upstream-accounts/oauth/mailbox-sessions",
             post(create_oauth_mailbox_session),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/mailbox-sessions/status",
+            "/pool/upstream-accounts/oauth/mailbox-sessions/status",
             post(get_oauth_mailbox_session_status),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/mailbox-sessions/:sessionId",
+            "/pool/upstream-accounts/oauth/mailbox-sessions/:sessionId",
             delete(delete_oauth_mailbox_session),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/login-sessions/:loginId",
+            "/pool/upstream-accounts/oauth/login-sessions/:loginId",
             get(get_oauth_login_session).patch(update_oauth_login_session),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/login-sessions/:loginId/complete",
+            "/pool/upstream-accounts/oauth/login-sessions/:loginId/complete",
             post(complete_oauth_login_session),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/login-sessions/:loginId/confirm-identity-overwrite",
+            "/pool/upstream-accounts/oauth/login-sessions/:loginId/confirm-identity-overwrite",
             post(confirm_oauth_login_session_identity_overwrite),
         )
         .route(
-            "/api/pool/upstream-accounts/oauth/callback",
+            "/pool/upstream-accounts/oauth/callback",
             get(oauth_callback),
         )
 }
@@ -2411,26 +2490,41 @@ pub(crate) fn build_event_routes(router: Router<Arc<AppState>>) -> Router<Arc<Ap
 pub(crate) fn build_external_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
     router
         .route(
-            "/api/external/v1/upstream-accounts/oauth/:sourceAccountId",
+            "/external/v1/upstream-accounts/oauth/:sourceAccountId",
             put(external_upsert_oauth_upstream_account_route)
                 .patch(external_patch_oauth_upstream_account_route),
         )
         .route(
-            "/api/external/v1/upstream-accounts/oauth/:sourceAccountId/relogin",
+            "/external/v1/upstream-accounts/oauth/:sourceAccountId/relogin",
             post(external_relogin_oauth_upstream_account_route),
         )
 }
 
+/// Grafana SimpleJSON datasource endpoints; Grafana probes the bare base URL.
+pub(crate) fn build_grafana_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
+    router
+        .route("/grafana", get(grafana_health))
+        .route("/grafana/", get(grafana_health))
+        .route("/grafana/search", post(grafana_search))
+        .route("/grafana/query", post(grafana_query))
+}
+
 pub(crate) fn build_proxy_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
     router.route("/v1/*path", any(proxy_openai_v1_with_connect_info))
 }
 
+/// JSON API routes, relative to `AppConfig::api_prefix`.
+pub(crate) fn build_api_routes(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
+    build_external_routes(build_pool_routes(build_system_routes(build_stats_routes(
+        build_invocation_routes(build_settings_routes(router)),
+    ))))
+}
+
 pub(crate) fn build_app_router(state: Arc<AppState>) -> Router {
-    build_proxy_routes(build_event_routes(build_external_routes(
-        build_pool_routes(build_system_routes(build_stats_routes(
-            build_invocation_routes(build_settings_routes(build_health_routes(Router::new()))),
-        ))),
+    build_proxy_routes(build_grafana_routes(build_event_routes(
+        build_health_routes(Router::new()),
     )))
+    .nest(&state.config.api_prefix, build_api_routes(Router::new()))
     .with_state(state)
 }
 
@@ -2449,22 +2543,48 @@ pub(crate) fn inject_absolute_social_preview_urls(
     index_html.replace(SOCIAL_PREVIEW_RELATIVE_ATTR, &absolute_attr)
 }
 
+pub(crate) const SPA_API_PREFIX_GLOBAL: &str = "__CVM_API_PREFIX__";
+
+/// Exposes a non-default `API_PREFIX` to the SPA as `window.__CVM_API_PREFIX__`
+/// so the bundled client rewrites its `/api/*` requests onto the mounted path.
+pub(crate) fn inject_spa_api_prefix(index_html: String, api_prefix: &str) -> String {
+    if api_prefix == DEFAULT_API_PREFIX {
+        return index_html;
+    }
+    let Some(head_end) = index_html.find("</head>") else {
+        return index_html;
+    };
+    let literal = serde_json::to_string(api_prefix)
+        .unwrap_or_else(|_| format!("\"{DEFAULT_API_PREFIX}\""))
+        .replace("</", "<\\/");
+    let script = format!("<script>window.{SPA_API_PREFIX_GLOBAL} = {literal};</script>");
+    let mut rendered = index_html;
+    rendered.insert_str(head_end, &script);
+    rendered
+}
+
 pub(crate) async fn render_spa_index_response(
     state: Arc<AppState>,
     headers: &HeaderMap,
 ) -> Response {
-    let Some(static_dir) = state.config.static_dir.as_ref() else {
-        return StatusCode::NOT_FOUND.into_response();
-    };
-    let index_file = static_dir.join("index.html");
-    let index_html = match tokio::fs::read_to_string(&index_file).await {
-        Ok(contents) => contents,
-        Err(err) => {
-            error!(path = %index_file.display(), ?err, "failed to read static index.html");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    let index_html = match state.config.static_dir.as_ref() {
+        Some(static_dir) => {
+            let index_file = static_dir.join("index.html");
+            match tokio::fs::read_to_string(&index_file).await {
+                Ok(contents) => contents,
+                Err(err) => {
+                    error!(path = %index_file.display(), ?err, "failed to read static index.html");
+                    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                }
+            }
         }
+        None => match embedded_web_index_html() {
+            Some(contents) => contents,
+            None => return StatusCode::NOT_FOUND.into_response(),
+        },
     };
 
+    let index_html = inject_spa_api_prefix(index_html, &state.config.api_prefix);
     Html(inject_absolute_social_preview_urls(
         index_html,
         headers,
@@ -2478,8 +2598,9 @@ pub(crate) async fn spawn_http_server(
 ) -> Result<(SocketAddr, JoinHandle<()>)> {
     let cors_layer = build_cors_layer(&state.config);
     let mut router = build_app_router(state.clone())
-        .layer(TraceLayer::new_for_http())
-        .layer(cors_layer);
+        .layer(TraceLayer::new_for_http().make_span_with(make_request_span))
+        .layer(cors_layer)
+        .layer(axum::middleware::from_fn(request_id_middleware));
 
     // Optionally attach headers in the future; standard EventSource cannot read headers
 
@@ -2515,18 +2636,45 @@ pub(crate) async fn spawn_http_server(
                 "static index.html not found; SPA fallback disabled"
             );
         }
+    } else if embedded_web_available() {
+        info!("serving SPA from the embedded web bundle");
+        let index_state = state.clone();
+        let spa_index_service = service_fn(move |request: Request<Body>| {
+            let state = index_state.clone();
+            let headers = request.headers().clone();
+            async move { Ok::<_, Infallible>(render_spa_index_response(state, &headers).await) }
+        });
+        let asset_state = state.clone();
+        let embedded_service = service_fn(move |request: Request<Body>| {
+            let state = asset_state.clone();
+            async move { Ok::<_, Infallible>(serve_embedded_web_request(state, request).await) }
+        });
+        router = router
+            .route_service("/", spa_index_service.clone())
+            .route_service("/index.html", spa_index_service)
+            .fallback_service(embedded_service);
     }
 
+    let tls_acceptor = match (&state.config.tls_cert_path, &state.config.tls_key_path) {
+        (Some(cert_path), Some(key_path)) => Some(load_tls_acceptor(cert_path, key_path)?),
+        _ => None,
+    };
     let listener = TcpListener::bind(&state.config.http_bind).await?;
     let addr = listener.local_addr()?;
-    info!(%addr, "http server listening");
+    info!(
+        %addr,
+        tls = tls_acceptor.is_some(),
+        api_prefix = %state.config.api_prefix,
+        "http server listening"
+    );
 
     let shutdown = state.shutdown.clone();
     let handle = tokio::spawn(async move {
-        if let Err(err) = serve_router_with_graceful_shutdown(listener, router, async move {
-            shutdown.cancelled().await
-        })
-        .await
+        if let Err(err) =
+            serve_router_with_graceful_shutdown(listener, router, tls_acceptor, async move {
+                shutdown.cancelled().await
+            })
+            .await
         {
             error!(?err, "http server exited with error");
         }
@@ -2535,6 +2683,36 @@ pub(crate) async fn spawn_http_server(
     Ok((addr, handle))
 }
 
+/// Builds the rustls acceptor for `TLS_CERT_PATH` / `TLS_KEY_PATH`, failing
+/// startup when either PEM file is missing or unusable.
+pub(crate) fn load_tls_acceptor(cert_path: &Path, key_path: &Path) -> Result<TlsAcceptor> {
+    use rustls_pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
+
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| {
+            anyhow!(
+                "failed to read TLS certificate {}: {err}",
+                cert_path.display()
+            )
+        })?;
+    if certs.is_empty() {
+        bail!("no TLS certificate found in {}", cert_path.display());
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path).map_err(|err| {
+        anyhow!(
+            "failed to read TLS private key {}: {err}",
+            key_path.display()
+        )
+    })?;
+    let mut config = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("invalid TLS certificate/key pair")?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
 #[cfg(test)]
 mod social_preview_tests {
     use super::*;
@@ -2590,6 +2768,21 @@ mod social_preview_tests {
 
         assert!(rewritten.contains(r#"content="https://preview.example.com/social-preview.png""#));
     }
+
+    #[test]
+    fn inject_spa_api_prefix_only_rewrites_non_default_prefixes() {
+        let html = "<html><head><title>x</title></head><body></body></html>".to_string();
+
+        assert_eq!(
+            inject_spa_api_prefix(html.clone(), DEFAULT_API_PREFIX),
+            html
+        );
+
+        let rendered = inject_spa_api_prefix(html, "/v1/api");
+        assert!(
+            rendered.contains(r#"<script>window.__CVM_API_PREFIX__ = "/v1/api";</script></head>"#)
+        );
+    }
 }
 
 pub(crate) fn spawn_shutdown_signal_listener(cancel: CancellationToken) -> JoinHandle<()> {
@@ -2618,6 +2811,8 @@ pub(crate) async fn shutdown_listener() {
     }
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub(crate) struct SummaryPublish {
     pub(crate) window: String,
     pub(crate) summary: StatsResponse,
@@ -2655,11 +2850,14 @@ pub(crate) fn summary_broadcast_specs() -> Vec<SummaryBroadcastSpec> {
 
 pub(crate) async fn collect_summary_snapshots(
     pool: &Pool<Sqlite>,
+    clock: &dyn Clock,
+    archive_db_attached: bool,
     invocation_max_days: u64,
+    treat_empty_error_as_success: bool,
 ) -> Result<Vec<SummaryPublish>> {
     let mut summaries = Vec::new();
     let mut cached_all: Option<StatsResponse> = None;
-    let now = Utc::now();
+    let now = clock.now();
     let source_scope = resolve_default_source_scope(pool).await?;
     let in_progress_started_at = Instant::now();
     let in_progress_conversation_count =
@@ -2697,20 +2895,28 @@ pub(crate) async fn collect_summary_snapshots(
                 if let Some(existing) = &cached_all {
                     existing.clone()
                 } else {
-                    let stats = query_combined_totals(pool, StatsFilter::All, source_scope)
-                        .await?
-                        .into_response();
+                    let stats = query_combined_totals(
+                        pool,
+                        StatsFilter::All,
+                        source_scope,
+                        archive_db_attached,
+                        treat_empty_error_as_success,
+                    )
+                    .await?
+                    .into_response();
                     cached_all = Some(stats.clone());
                     stats
                 }
             }
             Some(duration) => {
                 let start = now - duration;
-                query_hourly_backed_summary_since_with_config(
+                query_hourly_backed_summary_range_with_config(
                     pool,
                     invocation_max_days,
                     start,
+                    now,
                     source_scope,
+                    treat_empty_error_as_success,
                 )
                 .await
                 .map_err(|err| anyhow!("{err:?}"))?
@@ -2780,6 +2986,9 @@ pub(crate) fn codex_invocations_create_sql(table_name: &str) -> String {
             t_resp_parse_ms REAL,
             t_persist_ms REAL,
             created_at TEXT NOT NULL DEFAULT (STRFTIME('%Y-%m-%dT%H:%M:%fZ', 'now')),
+            http_status INTEGER,
+            model_canonical TEXT,
+            label TEXT,
             UNIQUE(invoke_id, occurred_at)
         )
         "#,
@@ -9,6 +9,8 @@ pub(crate) struct RetentionRunSummary {
     pub(crate) raw_bytes_after: u64,
     pub(crate) raw_bytes_after_estimated: u64,
     pub(crate) invocation_details_pruned: usize,
+    pub(crate) raw_response_rows_stripped: usize,
+    pub(crate) raw_response_bytes_reclaimed: u64,
     pub(crate) invocation_rows_archived: usize,
     pub(crate) forward_proxy_attempt_rows_archived: usize,
     pub(crate) pool_upstream_request_attempt_rows_archived: usize,
@@ -24,6 +26,7 @@ impl RetentionRunSummary {
         self.raw_files_compression_candidates > 0
             || self.raw_files_compressed > 0
             || self.invocation_details_pruned > 0
+            || self.raw_response_rows_stripped > 0
             || self.invocation_rows_archived > 0
             || self.forward_proxy_attempt_rows_archived > 0
             || self.pool_upstream_request_attempt_rows_archived > 0
@@ -291,6 +294,8 @@ pub(crate) struct InvocationHourlySourceRecord {
     pub(crate) detail_level: String,
     #[sqlx(default)]
     pub(crate) model: Option<String>,
+    #[sqlx(default)]
+    pub(crate) model_canonical: Option<String>,
     pub(crate) input_tokens: Option<i64>,
     pub(crate) output_tokens: Option<i64>,
     pub(crate) cache_input_tokens: Option<i64>,
@@ -309,6 +314,8 @@ pub(crate) struct InvocationHourlySourceRecord {
     #[sqlx(default)]
     pub(crate) cost_reasoning: Option<f64>,
     pub(crate) error_message: Option<String>,
+    #[sqlx(default)]
+    pub(crate) http_status: Option<i64>,
     pub(crate) failure_kind: Option<String>,
     pub(crate) failure_class: Option<String>,
     pub(crate) is_actionable: Option<i64>,
@@ -737,6 +744,7 @@ pub(crate) async fn run_data_retention_maintenance_best_effort(
 
         match run_data_retention_maintenance(&state.pool, &state.config, None, Some(cancel)).await {
             Ok(summary) => {
+                state.sqlite_batch_writer.bump_write_generation();
                 if let Some(handle) = task_run.as_ref() {
                     let (brief, detail) = summarize_retention_run_for_system_task(&summary);
                     finish_system_task_run_batched(
@@ -774,6 +782,7 @@ pub(crate) async fn run_data_retention_maintenance_best_effort(
             &state.pool,
             &state.hourly_rollup_sync_lock,
             rollup_refresh_reason,
+            state.config.treat_empty_error_as_success,
         )
         .await;
     }
@@ -804,7 +813,7 @@ pub(crate) async fn run_data_retention_maintenance(
     let raw_path_fallback_root = config.database_path.parent();
 
     if !dry_run {
-        sync_hourly_rollups_from_live_tables(pool)
+        sync_hourly_rollups_from_live_tables(pool, config.treat_empty_error_as_success)
             .await
             .context("failed to sync hourly rollups from live tables before retention")?;
         let janitor = cleanup_stale_archive_temp_files(config, false)?;
@@ -848,6 +857,16 @@ pub(crate) async fn run_data_retention_maintenance(
         return Ok(summary);
     }
 
+    let stripped = strip_old_invocation_raw_responses(pool, config, dry_run)
+        .await
+        .context("failed to strip old invocation raw responses during retention")?;
+    summary.raw_response_rows_stripped += stripped.0;
+    summary.raw_response_bytes_reclaimed += stripped.1;
+
+    if should_stop_data_retention_maintenance(shutdown) {
+        return Ok(summary);
+    }
+
     let invocation_archive = archive_old_invocations(pool, config, raw_path_fallback_root, dry_run)
         .await
         .context("failed to archive old invocations during retention")?;
@@ -1055,7 +1074,11 @@ pub(crate) async fn compress_cold_proxy_raw_payload_lane(
     let cutoff = shanghai_local_cutoff_for_age_secs_string(config.proxy_raw_hot_secs);
     let prune_cutoff = shanghai_local_cutoff_string(config.invocation_success_full_days);
     let archive_cutoff = shanghai_local_cutoff_string(config.invocation_max_days);
-    let success_like_condition = invocation_status_is_success_like_sql("status", "error_message");
+    let success_like_condition = invocation_status_is_success_like_sql(
+        "status",
+        "error_message",
+        config.treat_empty_error_as_success,
+    );
     let sql = format!(
         r#"
         SELECT id, occurred_at, {path_column} AS raw_path
@@ -1427,7 +1450,11 @@ pub(crate) async fn prune_old_invocation_details(
     let prune_cutoff = shanghai_local_cutoff_string(config.invocation_success_full_days);
     let archive_cutoff = shanghai_local_cutoff_string(config.invocation_max_days);
     let spec = archive_table_spec("codex_invocations");
-    let success_like_condition = invocation_status_is_success_like_sql("status", "error_message");
+    let success_like_condition = invocation_status_is_success_like_sql(
+        "status",
+        "error_message",
+        config.treat_empty_error_as_success,
+    );
     if dry_run {
         let sql = format!(
             r#"
@@ -1579,6 +1606,122 @@ pub(crate) async fn prune_old_invocation_details(
     Ok((rows_pruned, archive_batches, raw_files_removed))
 }
 
+/// Clears `raw_response` and reduces `payload` to the retained account id for
+/// rows older than `RAW_RESPONSE_RETENTION_DAYS`, whatever their status. The
+/// structured columns stay, so aggregates are unaffected; unlike
+/// `prune_old_invocation_details` nothing is archived first.
+pub(crate) async fn strip_old_invocation_raw_responses(
+    pool: &Pool<Sqlite>,
+    config: &AppConfig,
+    dry_run: bool,
+) -> Result<(usize, u64)> {
+    let Some(retention_days) = config.raw_response_retention_days else {
+        return Ok((0, 0));
+    };
+    let cutoff = shanghai_local_cutoff_string(retention_days);
+    let detail_bytes_sql = "COALESCE(LENGTH(CAST(raw_response AS BLOB)), 0) \
+         + COALESCE(LENGTH(CAST(payload AS BLOB)), 0)";
+
+    if dry_run {
+        let (rows, bytes): (i64, i64) = sqlx::query_as(&format!(
+            "SELECT COUNT(*), COALESCE(SUM({detail_bytes_sql}), 0) FROM codex_invocations \
+             WHERE detail_level = ?1 AND occurred_at < ?2"
+        ))
+        .bind(DETAIL_LEVEL_FULL)
+        .bind(&cutoff)
+        .fetch_one(pool)
+        .await?;
+        info!(
+            rows,
+            bytes_before = bytes,
+            cutoff = %cutoff,
+            "retention dry-run planned raw response strip"
+        );
+        return Ok((rows.max(0) as usize, 0));
+    }
+
+    let mut rows_stripped = 0usize;
+    let mut bytes_reclaimed = 0u64;
+    loop {
+        let ids = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT id
+            FROM codex_invocations
+            WHERE detail_level = ?1
+              AND occurred_at < ?2
+            ORDER BY occurred_at ASC, id ASC
+            LIMIT ?3
+            "#,
+        )
+        .bind(DETAIL_LEVEL_FULL)
+        .bind(&cutoff)
+        .bind(config.retention_batch_rows as i64)
+        .fetch_all(pool)
+        .await?;
+        if ids.is_empty() {
+            break;
+        }
+
+        let mut tx = pool.begin().await?;
+        let bytes_before =
+            sum_invocation_detail_bytes_tx(tx.as_mut(), detail_bytes_sql, &ids).await?;
+        let pruned_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "UPDATE codex_invocations SET payload = CASE WHEN json_valid(payload) AND json_extract(payload, '$.upstreamAccountId') IS NOT NULL THEN json_object('upstreamAccountId', json_extract(payload, '$.upstreamAccountId')) ELSE NULL END, raw_response = '', detail_level = ",
+        );
+        query
+            .push_bind(DETAIL_LEVEL_STRUCTURED_ONLY)
+            .push(", detail_pruned_at = ")
+            .push_bind(pruned_at)
+            .push(", detail_prune_reason = ")
+            .push_bind(DETAIL_PRUNE_REASON_RAW_RESPONSE_RETENTION)
+            .push(" WHERE id IN (");
+        {
+            let mut separated = query.separated(", ");
+            for id in &ids {
+                separated.push_bind(id);
+            }
+        }
+        query.push(")");
+        query.build().execute(tx.as_mut()).await?;
+        let bytes_after =
+            sum_invocation_detail_bytes_tx(tx.as_mut(), detail_bytes_sql, &ids).await?;
+        tx.commit().await?;
+
+        rows_stripped += ids.len();
+        bytes_reclaimed += bytes_before.saturating_sub(bytes_after);
+    }
+
+    if rows_stripped > 0 {
+        info!(
+            rows = rows_stripped,
+            bytes_reclaimed,
+            cutoff = %cutoff,
+            "retention stripped raw responses from old invocations"
+        );
+    }
+    Ok((rows_stripped, bytes_reclaimed))
+}
+
+async fn sum_invocation_detail_bytes_tx(
+    conn: &mut SqliteConnection,
+    detail_bytes_sql: &str,
+    ids: &[i64],
+) -> Result<u64> {
+    let mut query = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT COALESCE(SUM({detail_bytes_sql}), 0) FROM codex_invocations WHERE id IN ("
+    ));
+    {
+        let mut separated = query.separated(", ");
+        for id in ids {
+            separated.push_bind(id);
+        }
+    }
+    query.push(")");
+    let bytes: i64 = query.build_query_scalar().fetch_one(conn).await?;
+    Ok(bytes.max(0) as u64)
+}
+
 pub(crate) async fn archive_old_invocations(
     pool: &Pool<Sqlite>,
     config: &AppConfig,
@@ -1710,6 +1853,7 @@ pub(crate) async fn archive_old_invocations(
                     status: candidate.status.clone(),
                     detail_level: DETAIL_LEVEL_FULL.to_string(),
                     model: None,
+                    model_canonical: None,
                     input_tokens: candidate.input_tokens,
                     output_tokens: candidate.output_tokens,
                     cache_input_tokens: candidate.cache_input_tokens,
@@ -1722,6 +1866,7 @@ pub(crate) async fn archive_old_invocations(
                     cost_output: None,
                     cost_reasoning: None,
                     error_message: None,
+                    http_status: None,
                     failure_kind: None,
                     failure_class: None,
                     is_actionable: None,
@@ -1769,6 +1914,7 @@ pub(crate) async fn archive_old_invocations(
                 spec.dataset,
                 &materialized_rows,
                 &[],
+                config.treat_empty_error_as_success,
             )
             .await?;
             tx.commit().await?;
@@ -2031,6 +2177,7 @@ pub(crate) async fn archive_timestamped_dataset(
                 spec.dataset,
                 &[],
                 &materialized_forward_proxy_rows,
+                config.treat_empty_error_as_success,
             )
             .await?;
             tx.commit().await?;
@@ -439,6 +439,7 @@ pub(crate) async fn run_startup_backfill_maintenance_pass(
         &state.pool,
         state.hourly_rollup_sync_lock.as_ref(),
         "startup backfill maintenance pass",
+        state.config.treat_empty_error_as_success,
     )
     .await;
 
@@ -531,6 +532,9 @@ pub(crate) async fn run_startup_backfill_task_if_due_with_gate(
         .await
     {
         Ok((run, detail)) => {
+            if run.updated > 0 {
+                state.sqlite_batch_writer.bump_write_generation();
+            }
             let zero_update_streak = if run.updated == 0 {
                 progress.zero_update_streak.saturating_add(1)
             } else {
@@ -616,6 +620,7 @@ pub(crate) async fn run_startup_backfill_task(
                 raw_path_fallback_root,
                 Some(STARTUP_BACKFILL_SCAN_LIMIT),
                 max_elapsed,
+                state.config.treat_empty_error_as_success,
             )
             .await?;
             let detail = format!(
@@ -660,6 +665,7 @@ pub(crate) async fn run_startup_backfill_task(
                 &response_tier_price_version,
                 Some(STARTUP_BACKFILL_SCAN_LIMIT),
                 max_elapsed,
+                state.config.treat_empty_error_as_success,
             )
             .await?;
             let detail = format!(
@@ -685,6 +691,7 @@ pub(crate) async fn run_startup_backfill_task(
                 raw_path_fallback_root,
                 Some(STARTUP_BACKFILL_SCAN_LIMIT),
                 max_elapsed,
+                state.config.treat_empty_error_as_success,
             )
             .await?;
             let detail = format!(
@@ -791,6 +798,7 @@ pub(crate) async fn run_startup_backfill_task(
                 raw_path_fallback_root,
                 Some(STARTUP_BACKFILL_SCAN_LIMIT),
                 max_elapsed,
+                state.config.treat_empty_error_as_success,
             )
             .await?;
             Ok((
@@ -386,6 +386,7 @@ pub(crate) async fn persist_pre_attempt_proxy_capture_error(
         invoke_id: invoke_id.to_string(),
         occurred_at: occurred_at.to_string(),
         model: request_info.model.clone(),
+        model_canonical: None,
         usage,
         cost,
         cost_breakdown: None,
@@ -637,6 +638,7 @@ pub(crate) async fn proxy_openai_v1_capture_target(
                 invoke_id,
                 occurred_at,
                 model: None,
+                model_canonical: None,
                 usage,
                 cost,
                 cost_breakdown: None,
@@ -818,6 +820,7 @@ pub(crate) async fn proxy_openai_v1_capture_target(
                 invoke_id,
                 occurred_at,
                 model: None,
+                model_canonical: None,
                 usage,
                 cost,
                 cost_breakdown: None,
@@ -1244,6 +1247,7 @@ pub(crate) async fn proxy_openai_v1_capture_target(
                     invoke_id,
                     occurred_at,
                     model: request_info.model.clone(),
+                    model_canonical: None,
                     usage,
                     cost,
                     cost_breakdown: None,
@@ -1507,6 +1511,7 @@ pub(crate) async fn proxy_openai_v1_capture_target(
                     invoke_id,
                     occurred_at,
                     model: request_info.model.clone(),
+                    model_canonical: None,
                     usage,
                     cost,
                     cost_breakdown: None,
@@ -1720,6 +1725,7 @@ pub(crate) async fn proxy_openai_v1_capture_target(
                 invoke_id,
                 occurred_at,
                 model: request_info.model.clone(),
+                model_canonical: None,
                 usage,
                 cost,
                 cost_breakdown: None,
@@ -3052,6 +3058,7 @@ pub(crate) async fn proxy_openai_v1_capture_target(
             invoke_id: invoke_id_for_task,
             occurred_at: occurred_at_for_task,
             model: response_info.model,
+            model_canonical: None,
             usage: response_info.usage,
             cost,
             cost_breakdown,
@@ -4,6 +4,7 @@ use super::*;
 pub(crate) async fn backfill_proxy_missing_costs(
     pool: &Pool<Sqlite>,
     catalog: &PricingCatalog,
+    treat_empty_error_as_success: bool,
 ) -> Result<ProxyCostBackfillSummary> {
     let attempt_version = pricing_backfill_attempt_version(catalog);
     let requested_tier_price_version =
@@ -27,6 +28,7 @@ pub(crate) async fn backfill_proxy_missing_costs(
         &response_tier_price_version,
         None,
         None,
+        treat_empty_error_as_success,
     )
     .await?
     .summary)
@@ -39,6 +41,7 @@ pub(crate) async fn backfill_proxy_missing_costs_up_to_id(
     snapshot_max_id: i64,
     catalog: &PricingCatalog,
     attempt_version: &str,
+    treat_empty_error_as_success: bool,
 ) -> Result<ProxyCostBackfillSummary> {
     let requested_tier_price_version =
         proxy_price_version(&catalog.version, ProxyPricingMode::RequestedTier);
@@ -54,6 +57,7 @@ pub(crate) async fn backfill_proxy_missing_costs_up_to_id(
         &response_tier_price_version,
         None,
         None,
+        treat_empty_error_as_success,
     )
     .await?
     .summary)
@@ -66,10 +70,11 @@ pub(crate) const TEST_PROXY_COST_BACKFILL_LOCK_RETRY_DELAY: Duration = Duration:
 pub(crate) async fn run_cost_backfill_with_retry(
     pool: &Pool<Sqlite>,
     catalog: &PricingCatalog,
+    treat_empty_error_as_success: bool,
 ) -> Result<ProxyCostBackfillSummary> {
     let mut attempt = 1_u32;
     loop {
-        match backfill_proxy_missing_costs(pool, catalog).await {
+        match backfill_proxy_missing_costs(pool, catalog, treat_empty_error_as_success).await {
             Ok(summary) => return Ok(summary),
             Err(err)
                 if attempt < BACKFILL_LOCK_RETRY_MAX_ATTEMPTS && is_sqlite_lock_error(&err) =>
@@ -102,6 +107,7 @@ pub(crate) async fn backfill_proxy_prompt_cache_keys_from_cursor(
     raw_path_fallback_root: Option<&Path>,
     scan_limit: Option<u64>,
     max_elapsed: Option<Duration>,
+    treat_empty_error_as_success: bool,
 ) -> Result<BackfillBatchOutcome<ProxyPromptCacheKeyBackfillSummary>> {
     let started_at = Instant::now();
     let mut summary = ProxyPromptCacheKeyBackfillSummary::default();
@@ -225,7 +231,12 @@ pub(crate) async fn backfill_proxy_prompt_cache_keys_from_cursor(
                 }
             }
             if !updated_ids.is_empty() {
-                recompute_invocation_hourly_rollups_for_ids_tx(tx.as_mut(), &updated_ids).await?;
+                recompute_invocation_hourly_rollups_for_ids_tx(
+                    tx.as_mut(),
+                    &updated_ids,
+                    treat_empty_error_as_success,
+                )
+                .await?;
             }
             tx.commit().await?;
         }
@@ -243,12 +254,18 @@ pub(crate) async fn backfill_proxy_prompt_cache_keys_from_cursor(
 pub(crate) async fn backfill_proxy_prompt_cache_keys(
     pool: &Pool<Sqlite>,
     raw_path_fallback_root: Option<&Path>,
+    treat_empty_error_as_success: bool,
 ) -> Result<ProxyPromptCacheKeyBackfillSummary> {
-    Ok(
-        backfill_proxy_prompt_cache_keys_from_cursor(pool, 0, raw_path_fallback_root, None, None)
-            .await?
-            .summary,
+    Ok(backfill_proxy_prompt_cache_keys_from_cursor(
+        pool,
+        0,
+        raw_path_fallback_root,
+        None,
+        None,
+        treat_empty_error_as_success,
     )
+    .await?
+    .summary)
 }
 
 pub(crate) async fn backfill_proxy_requested_service_tiers_from_cursor(
@@ -944,9 +961,11 @@ pub(crate) fn should_upgrade_to_upstream_response_failed(
         return false;
     }
 
+    // Failure-kind repair keys off the recorded status, so it keeps the strict rule.
     crate::maintenance::invocation_status_is_success_like(
         row.status.as_deref(),
         row.error_message.as_deref(),
+        false,
     ) || existing_kind.is_none()
         || existing_kind == Some(PROXY_FAILURE_UPSTREAM_RESPONSE_FAILED)
 }
@@ -999,6 +1018,7 @@ pub(crate) async fn backfill_failure_classification_from_cursor(
     raw_path_fallback_root: Option<&Path>,
     scan_limit: Option<u64>,
     max_elapsed: Option<Duration>,
+    treat_empty_error_as_success: bool,
 ) -> Result<BackfillBatchOutcome<FailureClassificationBackfillSummary>> {
     let started_at = Instant::now();
     let mut summary = FailureClassificationBackfillSummary::default();
@@ -1193,7 +1213,12 @@ pub(crate) async fn backfill_failure_classification_from_cursor(
             }
         }
         if !updated_ids.is_empty() {
-            recompute_invocation_hourly_rollups_for_ids_tx(tx.as_mut(), &updated_ids).await?;
+            recompute_invocation_hourly_rollups_for_ids_tx(
+                tx.as_mut(),
+                &updated_ids,
+                treat_empty_error_as_success,
+            )
+            .await?;
         }
         tx.commit().await?;
     }
@@ -1211,12 +1236,18 @@ pub(crate) async fn backfill_failure_classification_from_cursor(
 pub(crate) async fn backfill_failure_classification(
     pool: &Pool<Sqlite>,
     raw_path_fallback_root: Option<&Path>,
+    treat_empty_error_as_success: bool,
 ) -> Result<FailureClassificationBackfillSummary> {
-    Ok(
-        backfill_failure_classification_from_cursor(pool, 0, raw_path_fallback_root, None, None)
-            .await?
-            .summary,
+    Ok(backfill_failure_classification_from_cursor(
+        pool,
+        0,
+        raw_path_fallback_root,
+        None,
+        None,
+        treat_empty_error_as_success,
     )
+    .await?
+    .summary)
 }
 
 pub(crate) fn is_sqlite_lock_error(err: &anyhow::Error) -> bool {
@@ -1855,6 +1886,7 @@ pub(crate) fn build_cors_layer(config: &AppConfig) -> CorsLayer {
         .allow_origin(allow_origin)
         .allow_methods(Any)
         .allow_headers(Any)
+        .expose_headers([HeaderName::from_static(REQUEST_ID_HEADER)])
 }
 
 pub(crate) fn origin_allowed(origin_raw: &str, configured: &HashSet<String>) -> bool {
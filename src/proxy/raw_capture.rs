@@ -460,6 +460,65 @@ pub(crate) fn estimate_proxy_cost_breakdown(
     (Some(breakdown), true, price_version)
 }
 
+/// Rounds a cost to `precision` decimal places through decimal formatting, so
+/// float tails like `0.0012300000001` persist as `0.00123`.
+pub(crate) fn round_cost_to_precision(value: f64, precision: u32) -> f64 {
+    if !value.is_finite() {
+        return value;
+    }
+    format!("{value:.*}", precision as usize)
+        .parse::<f64>()
+        .unwrap_or(value)
+}
+
+pub(crate) fn round_proxy_capture_record_costs(record: &mut ProxyCaptureRecord, precision: u32) {
+    record.cost = record
+        .cost
+        .map(|cost| round_cost_to_precision(cost, precision));
+    record.cost_breakdown = record
+        .cost_breakdown
+        .map(|breakdown| breakdown.rounded(precision));
+}
+
+pub(crate) fn apply_proxy_capture_model_alias(
+    record: &mut ProxyCaptureRecord,
+    aliases: &BTreeMap<String, String>,
+) {
+    record.model_canonical = record.model.as_deref().map(|model| {
+        aliases
+            .get(model.trim())
+            .cloned()
+            .unwrap_or_else(|| model.to_string())
+    });
+}
+
+/// Whether `MODEL_ALLOWLIST` lets this record be stored; an unset allowlist
+/// keeps every record. Either the raw or the aliased model may match.
+pub(crate) fn proxy_capture_model_allowed(
+    record: &ProxyCaptureRecord,
+    allowlist: Option<&BTreeSet<String>>,
+) -> bool {
+    let Some(allowlist) = allowlist else {
+        return true;
+    };
+    [record.model.as_deref(), record.model_canonical.as_deref()]
+        .into_iter()
+        .flatten()
+        .any(|model| allowlist.contains(model.trim()))
+}
+
+static MODEL_ALLOWLIST_SKIPPED_RECORDS: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn log_model_allowlist_skip(invoke_id: &str, model: Option<&str>) {
+    let skipped_total = MODEL_ALLOWLIST_SKIPPED_RECORDS.fetch_add(1, Ordering::Relaxed) + 1;
+    debug!(
+        invoke_id,
+        model = model.unwrap_or_default(),
+        skipped_total,
+        "proxy capture record skipped because its model is not in MODEL_ALLOWLIST"
+    );
+}
+
 pub(crate) async fn store_raw_payload_file(
     config: &AppConfig,
     invoke_id: &str,
@@ -565,10 +624,13 @@ pub(crate) enum ProxyCaptureFollowUpBroadcastMode {
 
 pub(crate) async fn broadcast_proxy_capture_follow_up(
     pool: &Pool<Sqlite>,
+    clock: &dyn Clock,
+    archive_db_attached: bool,
     _hourly_rollup_sync_lock: &Mutex<()>,
     broadcaster: &broadcast::Sender<BroadcastPayload>,
     broadcast_state_cache: &Mutex<BroadcastStateCache>,
     invocation_max_days: u64,
+    treat_empty_error_as_success: bool,
     mode: ProxyCaptureFollowUpBroadcastMode,
     invoke_id: &str,
 ) {
@@ -578,7 +640,15 @@ pub(crate) async fn broadcast_proxy_capture_follow_up(
         return;
     }
 
-    match collect_summary_snapshots(pool, invocation_max_days).await {
+    match collect_summary_snapshots(
+        pool,
+        clock,
+        archive_db_attached,
+        invocation_max_days,
+        treat_empty_error_as_success,
+    )
+    .await
+    {
         Ok(summaries) => {
             for summary in summaries {
                 if let Err(err) = broadcast_summary_if_changed(
@@ -648,10 +718,13 @@ pub(crate) struct SummaryQuotaBroadcastIdleContext<'a> {
     pub(crate) broadcast_running: &'a AtomicBool,
     pub(crate) shutdown: &'a CancellationToken,
     pub(crate) pool: &'a Pool<Sqlite>,
+    pub(crate) clock: &'a dyn Clock,
+    pub(crate) archive_db_attached: bool,
     pub(crate) hourly_rollup_sync_lock: &'a Mutex<()>,
     pub(crate) broadcaster: &'a broadcast::Sender<BroadcastPayload>,
     pub(crate) broadcast_state_cache: &'a Mutex<BroadcastStateCache>,
     pub(crate) invocation_max_days: u64,
+    pub(crate) treat_empty_error_as_success: bool,
     pub(crate) invoke_id: &'a str,
 }
 
@@ -675,10 +748,13 @@ pub(crate) async fn finish_summary_quota_broadcast_idle(
         );
         broadcast_proxy_capture_follow_up(
             ctx.pool,
+            ctx.clock,
+            ctx.archive_db_attached,
             ctx.hourly_rollup_sync_lock,
             ctx.broadcaster,
             ctx.broadcast_state_cache,
             ctx.invocation_max_days,
+            ctx.treat_empty_error_as_success,
             ProxyCaptureFollowUpBroadcastMode::ShutdownFlush,
             ctx.invoke_id,
         )
@@ -702,10 +778,13 @@ pub(crate) async fn schedule_proxy_capture_follow_up_worker(
         );
         broadcast_proxy_capture_follow_up(
             &state.pool,
+            state.clock.as_ref(),
+            state.archive_db_attached,
             state.hourly_rollup_sync_lock.as_ref(),
             &state.broadcaster,
             state.broadcast_state_cache.as_ref(),
             state.config.invocation_max_days,
+            state.config.treat_empty_error_as_success,
             ProxyCaptureFollowUpBroadcastMode::ShutdownFlush,
             invoke_id,
         )
@@ -727,10 +806,13 @@ pub(crate) async fn schedule_proxy_capture_follow_up_worker(
         );
         broadcast_proxy_capture_follow_up(
             &state.pool,
+            state.clock.as_ref(),
+            state.archive_db_attached,
             state.hourly_rollup_sync_lock.as_ref(),
             &state.broadcaster,
             state.broadcast_state_cache.as_ref(),
             state.config.invocation_max_days,
+            state.config.treat_empty_error_as_success,
             ProxyCaptureFollowUpBroadcastMode::ShutdownFlush,
             invoke_id,
         )
@@ -748,10 +830,13 @@ pub(crate) async fn schedule_proxy_capture_follow_up_worker(
     let latest_broadcast_seq = state.proxy_summary_quota_broadcast_seq.clone();
     let broadcast_running = state.proxy_summary_quota_broadcast_running.clone();
     let pool = state.pool.clone();
+    let clock = state.clock.clone();
+    let archive_db_attached = state.archive_db_attached;
     let hourly_rollup_sync_lock = state.hourly_rollup_sync_lock.clone();
     let broadcaster = state.broadcaster.clone();
     let broadcast_state_cache = state.broadcast_state_cache.clone();
     let invocation_max_days = state.config.invocation_max_days;
+    let treat_empty_error_as_success = state.config.treat_empty_error_as_success;
     let shutdown = state.shutdown.clone();
     let broadcast_handle_slot = state.proxy_summary_quota_broadcast_handle.clone();
     let invoke_id = invoke_id.to_string();
@@ -767,10 +852,13 @@ pub(crate) async fn schedule_proxy_capture_follow_up_worker(
                     );
                     broadcast_proxy_capture_follow_up(
                         &pool,
+                        clock.as_ref(),
+                        archive_db_attached,
                         hourly_rollup_sync_lock.as_ref(),
                         &broadcaster,
                         broadcast_state_cache.as_ref(),
                         invocation_max_days,
+                        treat_empty_error_as_success,
                         ProxyCaptureFollowUpBroadcastMode::ShutdownFlush,
                         &invoke_id,
                     )
@@ -791,10 +879,13 @@ pub(crate) async fn schedule_proxy_capture_follow_up_worker(
                         broadcast_running: broadcast_running.as_ref(),
                         shutdown: &shutdown,
                         pool: &pool,
+                        clock: clock.as_ref(),
+                        archive_db_attached,
                         hourly_rollup_sync_lock: hourly_rollup_sync_lock.as_ref(),
                         broadcaster: &broadcaster,
                         broadcast_state_cache: broadcast_state_cache.as_ref(),
                         invocation_max_days,
+                        treat_empty_error_as_success,
                         invoke_id: &invoke_id,
                     },
                     synced_seq,
@@ -811,10 +902,13 @@ pub(crate) async fn schedule_proxy_capture_follow_up_worker(
                 _ = shutdown.cancelled() => {
                     broadcast_proxy_capture_follow_up(
                         &pool,
+                        clock.as_ref(),
+                        archive_db_attached,
                         hourly_rollup_sync_lock.as_ref(),
                         &broadcaster,
                         broadcast_state_cache.as_ref(),
                         invocation_max_days,
+                        treat_empty_error_as_success,
                         ProxyCaptureFollowUpBroadcastMode::ShutdownFlush,
                         &invoke_id,
                     )
@@ -828,10 +922,13 @@ pub(crate) async fn schedule_proxy_capture_follow_up_worker(
                 }
                 _ = broadcast_proxy_capture_follow_up(
                     &pool,
+                    clock.as_ref(),
+                    archive_db_attached,
                     hourly_rollup_sync_lock.as_ref(),
                     &broadcaster,
                     broadcast_state_cache.as_ref(),
                     invocation_max_days,
+                    treat_empty_error_as_success,
                     ProxyCaptureFollowUpBroadcastMode::ActiveSubscribers,
                     &invoke_id,
                 ) => {}
@@ -882,10 +979,13 @@ pub(crate) fn schedule_proxy_capture_follow_up_after_terminal_enqueue(
     }
 
     let pool = state.pool.clone();
+    let clock = state.clock.clone();
+    let archive_db_attached = state.archive_db_attached;
     let hourly_rollup_sync_lock = state.hourly_rollup_sync_lock.clone();
     let broadcaster = state.broadcaster.clone();
     let broadcast_state_cache = state.broadcast_state_cache.clone();
     let invocation_max_days = state.config.invocation_max_days;
+    let treat_empty_error_as_success = state.config.treat_empty_error_as_success;
     let shutdown = state.shutdown.clone();
     let invoke_id = invoke_id.to_string();
     tokio::spawn(async move {
@@ -903,10 +1003,13 @@ pub(crate) fn schedule_proxy_capture_follow_up_after_terminal_enqueue(
         );
         broadcast_proxy_capture_follow_up(
             &pool,
+            clock.as_ref(),
+            archive_db_attached,
             hourly_rollup_sync_lock.as_ref(),
             &broadcaster,
             broadcast_state_cache.as_ref(),
             invocation_max_days,
+            treat_empty_error_as_success,
             mode,
             &invoke_id,
         )
@@ -923,8 +1026,27 @@ pub(crate) async fn persist_and_broadcast_proxy_capture(
     if !record.timings.t_total_ms.is_finite() || record.timings.t_total_ms <= 0.0 {
         record.timings.t_total_ms = elapsed_ms(capture_started);
     }
+    round_proxy_capture_record_costs(&mut record, state.config.cost_precision);
+    apply_proxy_capture_model_alias(&mut record, &state.config.model_aliases);
     let inserted_record = api_invocation_from_runtime_record(&record);
     let invoke_id = inserted_record.invoke_id.clone();
+    let cost = inserted_record.cost;
+    if !proxy_capture_model_allowed(&record, state.config.model_allowlist.as_ref()) {
+        log_model_allowlist_skip(&invoke_id, record.model.as_deref());
+        remove_proxy_runtime_snapshot_by_key(
+            state,
+            &invoke_id,
+            &inserted_record.occurred_at,
+            "model_allowlist_raw_terminal",
+        );
+        schedule_dashboard_activity_live_snapshot(state);
+        schedule_proxy_capture_follow_up_after_terminal_enqueue(
+            state,
+            &invoke_id,
+            "model_allowlist_raw_terminal",
+        );
+        return Ok(());
+    }
     let duplicate_terminal = remove_proxy_runtime_snapshot_for_terminal(state, &inserted_record);
     if duplicate_terminal {
         debug!(
@@ -980,18 +1102,21 @@ pub(crate) async fn persist_and_broadcast_proxy_capture(
             .await;
     }
     if state.broadcaster.receiver_count() > 0
-        && let Err(err) = state.broadcaster.send(BroadcastPayload::Records {
-            records: vec![inserted_record],
-        })
+        && state
+            .broadcaster
+            .send(BroadcastPayload::Records {
+                records: vec![inserted_record],
+            })
+            .is_err()
     {
-        warn!(
-            ?err,
+        debug!(
             invoke_id = %invoke_id,
-            "failed to broadcast new proxy capture record"
+            "new proxy capture record not broadcast; all receivers disconnected"
         );
     }
     schedule_dashboard_activity_live_snapshot(state);
     if terminal_enqueued {
+        broadcast_cost_spike_if_detected(state, &invoke_id, cost);
         schedule_proxy_capture_follow_up_after_terminal_enqueue(state, &invoke_id, "raw_terminal");
     }
     Ok(())
@@ -1001,8 +1126,16 @@ pub(crate) async fn persist_proxy_capture_record(
     pool: &Pool<Sqlite>,
     capture_started: Instant,
     record: ProxyCaptureRecord,
+    treat_empty_error_as_success: bool,
 ) -> Result<Option<ApiInvocation>> {
-    persist_proxy_capture_record_core(pool, capture_started, record, true).await
+    persist_proxy_capture_record_core(
+        pool,
+        capture_started,
+        record,
+        true,
+        treat_empty_error_as_success,
+    )
+    .await
 }
 
 pub(crate) async fn persist_proxy_capture_record_core(
@@ -1010,7 +1143,9 @@ pub(crate) async fn persist_proxy_capture_record_core(
     capture_started: Instant,
     mut record: ProxyCaptureRecord,
     write_derived_inline: bool,
+    treat_empty_error_as_success: bool,
 ) -> Result<Option<ApiInvocation>> {
+    record.occurred_at = canonical_occurred_at(&record.occurred_at);
     let raw_response = if record.response_body_preview_enabled {
         record.raw_response.clone()
     } else {
@@ -1127,12 +1262,14 @@ pub(crate) async fn persist_proxy_capture_record_core(
                 t_upstream_stream_ms,
                 t_resp_parse_ms,
                 t_persist_ms,
-                created_at
+                created_at,
+                http_status,
+                model_canonical
             )
             VALUES (
                 ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19,
                 ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36,
-                ?37, ?38, ?39, ?40, ?41, ?42, ?43
+                ?37, ?38, ?39, ?40, ?41, ?42, ?43, ?44, ?45
             )
             "#,
         )
@@ -1179,6 +1316,11 @@ pub(crate) async fn persist_proxy_capture_record_core(
         .bind(record.timings.t_resp_parse_ms)
         .bind(t_persist_ms)
         .bind(created_at)
+        .bind(resolve_invocation_http_status(
+            record.payload.as_deref(),
+            record.error_message.as_deref(),
+        ))
+        .bind(record.model_canonical.as_deref())
         .execute(tx.as_mut())
         .await?;
         if insert_result.rows_affected() > 0 {
@@ -1240,7 +1382,12 @@ pub(crate) async fn persist_proxy_capture_record_core(
     }
 
     if write_derived_inline {
-        recompute_invocation_hourly_rollups_for_ids_tx(tx.as_mut(), &[invocation_id]).await?;
+        recompute_invocation_hourly_rollups_for_ids_tx(
+            tx.as_mut(),
+            &[invocation_id],
+            treat_empty_error_as_success,
+        )
+        .await?;
         save_hourly_rollup_live_progress_tx(
             tx.as_mut(),
             HOURLY_ROLLUP_DATASET_INVOCATIONS,
@@ -1358,6 +1505,7 @@ pub(crate) async fn backfill_proxy_usage_tokens_from_cursor(
     raw_path_fallback_root: Option<&Path>,
     scan_limit: Option<u64>,
     max_elapsed: Option<Duration>,
+    treat_empty_error_as_success: bool,
 ) -> Result<BackfillBatchOutcome<ProxyUsageBackfillSummary>> {
     let started_at = Instant::now();
     let mut summary = ProxyUsageBackfillSummary::default();
@@ -1477,7 +1625,12 @@ pub(crate) async fn backfill_proxy_usage_tokens_from_cursor(
                 }
             }
             if !updated_ids.is_empty() {
-                recompute_invocation_hourly_rollups_for_ids_tx(tx.as_mut(), &updated_ids).await?;
+                recompute_invocation_hourly_rollups_for_ids_tx(
+                    tx.as_mut(),
+                    &updated_ids,
+                    treat_empty_error_as_success,
+                )
+                .await?;
             }
             tx.commit().await?;
             summary.updated += updated_this_batch;
@@ -1496,6 +1649,7 @@ pub(crate) async fn backfill_proxy_usage_tokens_from_cursor(
 pub(crate) async fn backfill_proxy_usage_tokens(
     pool: &Pool<Sqlite>,
     raw_path_fallback_root: Option<&Path>,
+    treat_empty_error_as_success: bool,
 ) -> Result<ProxyUsageBackfillSummary> {
     let snapshot_max_id = current_proxy_usage_backfill_snapshot_max_id(pool).await?;
     Ok(backfill_proxy_usage_tokens_from_cursor(
@@ -1505,6 +1659,7 @@ pub(crate) async fn backfill_proxy_usage_tokens(
         raw_path_fallback_root,
         None,
         None,
+        treat_empty_error_as_success,
     )
     .await?
     .summary)
@@ -1515,6 +1670,7 @@ pub(crate) async fn backfill_proxy_usage_tokens_up_to_id(
     pool: &Pool<Sqlite>,
     snapshot_max_id: i64,
     raw_path_fallback_root: Option<&Path>,
+    treat_empty_error_as_success: bool,
 ) -> Result<ProxyUsageBackfillSummary> {
     Ok(backfill_proxy_usage_tokens_from_cursor(
         pool,
@@ -1523,6 +1679,7 @@ pub(crate) async fn backfill_proxy_usage_tokens_up_to_id(
         raw_path_fallback_root,
         None,
         None,
+        treat_empty_error_as_success,
     )
     .await?
     .summary)
@@ -1535,10 +1692,17 @@ pub(crate) const TEST_PROXY_USAGE_BACKFILL_LOCK_RETRY_DELAY: Duration = Duration
 pub(crate) async fn run_backfill_with_retry(
     pool: &Pool<Sqlite>,
     raw_path_fallback_root: Option<&Path>,
+    treat_empty_error_as_success: bool,
 ) -> Result<ProxyUsageBackfillSummary> {
     let mut attempt = 1_u32;
     loop {
-        match backfill_proxy_usage_tokens(pool, raw_path_fallback_root).await {
+        match backfill_proxy_usage_tokens(
+            pool,
+            raw_path_fallback_root,
+            treat_empty_error_as_success,
+        )
+        .await
+        {
             Ok(summary) => return Ok(summary),
             Err(err)
                 if attempt < BACKFILL_LOCK_RETRY_MAX_ATTEMPTS && is_sqlite_lock_error(&err) =>
@@ -1687,6 +1851,7 @@ pub(crate) async fn backfill_proxy_missing_costs_from_cursor(
     response_tier_price_version: &str,
     scan_limit: Option<u64>,
     max_elapsed: Option<Duration>,
+    treat_empty_error_as_success: bool,
 ) -> Result<BackfillBatchOutcome<ProxyCostBackfillSummary>> {
     let started_at = Instant::now();
     let mut summary = ProxyCostBackfillSummary::default();
@@ -2009,7 +2174,12 @@ pub(crate) async fn backfill_proxy_missing_costs_from_cursor(
                 }
             }
             if !updated_ids.is_empty() {
-                recompute_invocation_hourly_rollups_for_ids_tx(tx.as_mut(), &updated_ids).await?;
+                recompute_invocation_hourly_rollups_for_ids_tx(
+                    tx.as_mut(),
+                    &updated_ids,
+                    treat_empty_error_as_success,
+                )
+                .await?;
             }
             tx.commit().await?;
             summary.updated += updated_this_batch;
@@ -1,10 +1,29 @@
 use super::*;
 
+pub(crate) const HEALTH_BROADCAST_QUEUED_HEADER: &str = "x-broadcast-queued";
+pub(crate) const HEALTH_BROADCAST_QUEUED_MAX_HEADER: &str = "x-broadcast-queued-max";
+
 pub(crate) async fn health_check(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    // The body stays a plain readiness marker for probes; the broadcast backlog
+    // rides along as headers so it is visible without the admin token.
+    let broadcast_queued = state.broadcaster.len();
+    state
+        .subscription_hub
+        .observe_broadcast_queued(broadcast_queued);
+    let headers = [
+        (
+            HeaderName::from_static(HEALTH_BROADCAST_QUEUED_HEADER),
+            HeaderValue::from(broadcast_queued),
+        ),
+        (
+            HeaderName::from_static(HEALTH_BROADCAST_QUEUED_MAX_HEADER),
+            HeaderValue::from(state.subscription_hub.broadcast_queued_max()),
+        ),
+    ];
     if state.startup_ready.load(Ordering::Acquire) {
-        (StatusCode::OK, "ok")
+        (StatusCode::OK, headers, "ok")
     } else {
-        (StatusCode::SERVICE_UNAVAILABLE, "starting")
+        (StatusCode::SERVICE_UNAVAILABLE, headers, "starting")
     }
 }
 
@@ -1085,9 +1085,12 @@ pub(crate) enum ProxyInvocationRecoveryScope<'a> {
 pub(crate) async fn recover_proxy_invocations_with_scope(
     pool: &Pool<Sqlite>,
     scope: ProxyInvocationRecoveryScope<'_>,
+    treat_empty_error_as_success: bool,
 ) -> Result<Vec<RecoveredInvocationRow>> {
     let mut tx = pool.begin().await?;
-    let rows = recover_proxy_invocations_with_scope_tx(tx.as_mut(), scope).await?;
+    let rows =
+        recover_proxy_invocations_with_scope_tx(tx.as_mut(), scope, treat_empty_error_as_success)
+            .await?;
     tx.commit().await?;
     Ok(rows)
 }
@@ -1095,6 +1098,7 @@ pub(crate) async fn recover_proxy_invocations_with_scope(
 pub(crate) async fn recover_proxy_invocations_with_scope_tx(
     tx: &mut SqliteConnection,
     scope: ProxyInvocationRecoveryScope<'_>,
+    treat_empty_error_as_success: bool,
 ) -> Result<Vec<RecoveredInvocationRow>> {
     let rows = match scope {
         ProxyInvocationRecoveryScope::AllInFlight => {
@@ -1197,7 +1201,12 @@ pub(crate) async fn recover_proxy_invocations_with_scope_tx(
 
     if !rows.is_empty() {
         let updated_ids: Vec<i64> = rows.iter().map(|row| row.id).collect();
-        recompute_invocation_hourly_rollups_for_ids_tx(&mut *tx, &updated_ids).await?;
+        recompute_invocation_hourly_rollups_for_ids_tx(
+            &mut *tx,
+            &updated_ids,
+            treat_empty_error_as_success,
+        )
+        .await?;
         if let Some(max_id) = updated_ids.iter().copied().max() {
             save_hourly_rollup_live_progress_tx(
                 &mut *tx,
@@ -1211,12 +1220,17 @@ pub(crate) async fn recover_proxy_invocations_with_scope_tx(
     Ok(rows)
 }
 
-pub(crate) async fn recover_orphaned_proxy_invocations(pool: &Pool<Sqlite>) -> Result<u64> {
-    Ok(
-        recover_proxy_invocations_with_scope(pool, ProxyInvocationRecoveryScope::AllInFlight)
-            .await?
-            .len() as u64,
+pub(crate) async fn recover_orphaned_proxy_invocations(
+    pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
+) -> Result<u64> {
+    Ok(recover_proxy_invocations_with_scope(
+        pool,
+        ProxyInvocationRecoveryScope::AllInFlight,
+        treat_empty_error_as_success,
     )
+    .await?
+    .len() as u64)
 }
 
 pub(crate) fn stale_started_before_string(timeout: Duration, grace: Duration) -> String {
@@ -1244,6 +1258,7 @@ pub(crate) async fn broadcast_recovered_proxy_invocations(
     if recovered.is_empty() {
         return Ok(());
     }
+    state.sqlite_batch_writer.bump_write_generation();
 
     let selectors: Vec<_> = recovered
         .iter()
@@ -1282,13 +1297,13 @@ pub(crate) async fn broadcast_recovered_proxy_invocations(
     }
 
     let summary_invoke_id = records[0].invoke_id.clone();
-    if state.broadcaster.receiver_count() > 0 {
-        state
+    if state.broadcaster.receiver_count() > 0
+        && state
             .broadcaster
             .send(BroadcastPayload::Records { records })
-            .map_err(|err| {
-                anyhow!("failed to broadcast recovered proxy invocation records: {err}")
-            })?;
+            .is_err()
+    {
+        debug!("recovered proxy invocation records not broadcast; all receivers disconnected");
     }
     schedule_dashboard_activity_live_snapshot(state);
     schedule_proxy_capture_follow_up_worker(state, &summary_invoke_id).await?;
@@ -1495,6 +1510,7 @@ pub(crate) async fn recover_guard_dropped_pool_early_phase_orphan(
             recover_proxy_invocations_with_scope_tx(
                 tx.as_mut(),
                 ProxyInvocationRecoveryScope::Selectors(std::slice::from_ref(&selector)),
+                state.config.treat_empty_error_as_success,
             )
             .await?
         } else {
@@ -1583,6 +1599,7 @@ pub(crate) async fn recover_guard_dropped_pool_invocation_orphan(
     let recovered_invocations = recover_proxy_invocations_with_scope(
         &state.pool,
         ProxyInvocationRecoveryScope::Selectors(std::slice::from_ref(&selector)),
+        state.config.treat_empty_error_as_success,
     )
     .await?;
 
@@ -1674,6 +1691,7 @@ pub(crate) async fn recover_stale_pool_early_phase_orphans_runtime(
     let recovered_invocations = recover_proxy_invocations_with_scope_tx(
         tx.as_mut(),
         ProxyInvocationRecoveryScope::Selectors(&selectors),
+        state.config.treat_empty_error_as_success,
     )
     .await?;
     tx.commit().await?;
@@ -1738,13 +1756,20 @@ pub(crate) async fn broadcast_pool_upstream_attempts_snapshot(
             return Err(err);
         }
     };
-    state
+    // The receivers may all have disconnected while the attempts were loading.
+    if state
         .broadcaster
         .send(BroadcastPayload::PoolAttempts {
             invoke_id: invoke_id.to_string(),
             attempts,
         })
-        .map_err(|err| anyhow!("failed to broadcast pool attempts snapshot: {err}"))?;
+        .is_err()
+    {
+        debug!(
+            invoke_id,
+            "pool attempts snapshot not broadcast; all receivers disconnected"
+        );
+    }
     Ok(())
 }
 
@@ -2507,7 +2532,9 @@ pub(crate) async fn update_existing_proxy_invocation_record_tx(
             t_upstream_ttfb_ms = ?38,
             t_upstream_stream_ms = ?39,
             t_resp_parse_ms = ?40,
-            t_persist_ms = ?41
+            t_persist_ms = ?41,
+            http_status = ?42,
+            model_canonical = ?43
         WHERE id = ?1
           AND (
                 LOWER(TRIM(COALESCE(status, ''))) IN ('running', 'pending')
@@ -2559,6 +2586,11 @@ pub(crate) async fn update_existing_proxy_invocation_record_tx(
     .bind(t_upstream_stream_ms)
     .bind(t_resp_parse_ms)
     .bind(t_persist_ms)
+    .bind(resolve_invocation_http_status(
+        record.payload.as_deref(),
+        record.error_message.as_deref(),
+    ))
+    .bind(record.model_canonical.as_deref())
     .execute(&mut *tx)
     .await?;
 
@@ -2602,6 +2634,8 @@ pub(crate) fn api_invocation_from_runtime_record(record: &ProxyCaptureRecord) ->
         live_phase: None,
         error_message: record.error_message.clone(),
         downstream_status_code: payload_i64(payload, "downstreamStatusCode"),
+        http_status: resolve_invocation_http_status(payload, record.error_message.as_deref()),
+        label: None,
         failure_kind: failure
             .failure_kind
             .clone()
@@ -2673,13 +2707,7 @@ pub(crate) fn persisted_invocation_allows_proxy_record_update(
             ))
 }
 
-pub(crate) async fn load_persisted_api_invocation_tx(
-    tx: &mut SqliteConnection,
-    invoke_id: &str,
-    occurred_at: &str,
-) -> Result<ApiInvocation> {
-    let mut record = sqlx::query_as::<_, ApiInvocation>(
-        r#"
+const PERSISTED_API_INVOCATION_SELECT_SQL: &str = r#"
         SELECT
             id,
             invoke_id,
@@ -2705,6 +2733,8 @@ pub(crate) async fn load_persisted_api_invocation_tx(
         status,
             error_message,
             CASE WHEN json_valid(payload) THEN json_extract(payload, '$.downstreamStatusCode') END AS downstream_status_code,
+            http_status,
+            label,
             CASE WHEN json_valid(payload) THEN json_extract(payload, '$.endpoint') END AS endpoint,
             CASE WHEN json_valid(payload) THEN json_extract(payload, '$.compactionRequestKind') END AS compaction_request_kind,
             CASE WHEN json_valid(payload) THEN json_extract(payload, '$.compactionResponseKind') END AS compaction_response_kind,
@@ -2780,19 +2810,40 @@ pub(crate) async fn load_persisted_api_invocation_tx(
             t_persist_ms,
             created_at
         FROM codex_invocations
-        WHERE invoke_id = ?1 AND occurred_at = ?2
-        ORDER BY id DESC
-        LIMIT 1
-        "#,
-    )
-    .bind(invoke_id)
-    .bind(occurred_at)
-    .fetch_one(&mut *tx)
-    .await?;
+"#;
+
+pub(crate) async fn load_persisted_api_invocation_tx(
+    tx: &mut SqliteConnection,
+    invoke_id: &str,
+    occurred_at: &str,
+) -> Result<ApiInvocation> {
+    let sql = format!(
+        "{PERSISTED_API_INVOCATION_SELECT_SQL} WHERE invoke_id = ?1 AND occurred_at = ?2 ORDER BY id DESC LIMIT 1"
+    );
+    let mut record = sqlx::query_as::<_, ApiInvocation>(&sql)
+        .bind(invoke_id)
+        .bind(occurred_at)
+        .fetch_one(&mut *tx)
+        .await?;
     hydrate_api_invocation_blocked_binding(&mut record);
     Ok(record)
 }
 
+pub(crate) async fn load_persisted_api_invocation_by_id(
+    pool: &Pool<Sqlite>,
+    id: i64,
+) -> Result<Option<ApiInvocation>> {
+    let sql = format!("{PERSISTED_API_INVOCATION_SELECT_SQL} WHERE id = ?1");
+    let mut record = sqlx::query_as::<_, ApiInvocation>(&sql)
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    if let Some(record) = record.as_mut() {
+        hydrate_api_invocation_blocked_binding(record);
+    }
+    Ok(record)
+}
+
 pub(crate) async fn touch_invocation_upstream_account_last_activity_tx(
     tx: &mut SqliteConnection,
     occurred_at: &str,
@@ -2847,14 +2898,16 @@ pub(crate) async fn persist_and_broadcast_proxy_capture_runtime_snapshot(
         .dashboard_network_speed_cache
         .observe_dashboard_activity_runtime_snapshot(&persisted_record, Utc::now());
     if state.broadcaster.receiver_count() > 0
-        && let Err(err) = state.broadcaster.send(BroadcastPayload::Records {
-            records: vec![persisted_record],
-        })
+        && state
+            .broadcaster
+            .send(BroadcastPayload::Records {
+                records: vec![persisted_record],
+            })
+            .is_err()
     {
-        warn!(
-            ?err,
+        debug!(
             invoke_id = %invoke_id,
-            "failed to broadcast runtime proxy capture snapshot"
+            "runtime proxy capture snapshot not broadcast; all receivers disconnected"
         );
     }
     schedule_dashboard_activity_live_snapshot(state);
@@ -2880,7 +2933,11 @@ pub(crate) fn remove_proxy_runtime_snapshot_for_terminal(
 ) -> bool {
     state
         .dashboard_network_speed_cache
-        .finalize_dashboard_activity_invocation(record, Utc::now());
+        .finalize_dashboard_activity_invocation(
+            record,
+            Utc::now(),
+            state.config.treat_empty_error_as_success,
+        );
     state
         .dashboard_network_speed_cache
         .finish_invocation(&record.invoke_id, &record.occurred_at);
@@ -2956,7 +3013,11 @@ pub(crate) fn terminalize_proxy_runtime_snapshot_by_key(
     record.pool_attempt_terminal_reason = Some(PROXY_FAILURE_INVOCATION_INTERRUPTED.to_string());
     state
         .dashboard_network_speed_cache
-        .finalize_dashboard_activity_invocation(&record, Utc::now());
+        .finalize_dashboard_activity_invocation(
+            &record,
+            Utc::now(),
+            state.config.treat_empty_error_as_success,
+        );
     state
         .dashboard_network_speed_cache
         .finish_invocation(invoke_id, occurred_at);
@@ -2973,16 +3034,18 @@ pub(crate) fn terminalize_proxy_runtime_snapshot_by_key(
         "non-terminal proxy runtime snapshot terminalized by key"
     );
     if state.broadcaster.receiver_count() > 0
-        && let Err(err) = state.broadcaster.send(BroadcastPayload::Records {
-            records: vec![record],
-        })
+        && state
+            .broadcaster
+            .send(BroadcastPayload::Records {
+                records: vec![record],
+            })
+            .is_err()
     {
-        warn!(
-            ?err,
+        debug!(
             invoke_id,
             occurred_at,
             reason,
-            "failed to broadcast terminalized proxy runtime snapshot"
+            "terminalized proxy runtime snapshot not broadcast; all receivers disconnected"
         );
     }
     true
@@ -3031,7 +3094,11 @@ pub(crate) fn terminalize_proxy_runtime_snapshot_with_error(
     record.pool_attempt_terminal_reason = Some(failure_kind.to_string());
     state
         .dashboard_network_speed_cache
-        .finalize_dashboard_activity_invocation(&record, Utc::now());
+        .finalize_dashboard_activity_invocation(
+            &record,
+            Utc::now(),
+            state.config.treat_empty_error_as_success,
+        );
     state
         .dashboard_network_speed_cache
         .finish_invocation(invoke_id, occurred_at);
@@ -3051,16 +3118,18 @@ pub(crate) fn terminalize_proxy_runtime_snapshot_with_error(
         "non-terminal proxy runtime snapshot terminalized with error overlay"
     );
     if state.broadcaster.receiver_count() > 0
-        && let Err(err) = state.broadcaster.send(BroadcastPayload::Records {
-            records: vec![record],
-        })
+        && state
+            .broadcaster
+            .send(BroadcastPayload::Records {
+                records: vec![record],
+            })
+            .is_err()
     {
-        warn!(
-            ?err,
+        debug!(
             invoke_id,
             occurred_at,
             reason,
-            "failed to broadcast terminal error proxy runtime snapshot"
+            "terminal error proxy runtime snapshot not broadcast; all receivers disconnected"
         );
     }
     true
@@ -3068,11 +3137,30 @@ pub(crate) fn terminalize_proxy_runtime_snapshot_with_error(
 
 pub(crate) async fn persist_and_broadcast_proxy_capture_terminal_record(
     state: &AppState,
-    record: ProxyCaptureRecord,
+    mut record: ProxyCaptureRecord,
 ) -> Result<()> {
     let enqueue_started = Instant::now();
+    round_proxy_capture_record_costs(&mut record, state.config.cost_precision);
+    apply_proxy_capture_model_alias(&mut record, &state.config.model_aliases);
     let persisted_record = api_invocation_from_runtime_record(&record);
     let invoke_id = persisted_record.invoke_id.clone();
+    let cost = persisted_record.cost;
+    if !proxy_capture_model_allowed(&record, state.config.model_allowlist.as_ref()) {
+        log_model_allowlist_skip(&invoke_id, record.model.as_deref());
+        remove_proxy_runtime_snapshot_by_key(
+            state,
+            &invoke_id,
+            &persisted_record.occurred_at,
+            "model_allowlist_runtime_terminal",
+        );
+        schedule_dashboard_activity_live_snapshot(state);
+        schedule_proxy_capture_follow_up_after_terminal_enqueue(
+            state,
+            &invoke_id,
+            "model_allowlist_runtime_terminal",
+        );
+        return Ok(());
+    }
     let duplicate_terminal = remove_proxy_runtime_snapshot_for_terminal(state, &persisted_record);
     if duplicate_terminal {
         debug!(
@@ -3128,18 +3216,21 @@ pub(crate) async fn persist_and_broadcast_proxy_capture_terminal_record(
             .await;
     }
     if state.broadcaster.receiver_count() > 0
-        && let Err(err) = state.broadcaster.send(BroadcastPayload::Records {
-            records: vec![persisted_record],
-        })
+        && state
+            .broadcaster
+            .send(BroadcastPayload::Records {
+                records: vec![persisted_record],
+            })
+            .is_err()
     {
-        warn!(
-            ?err,
+        debug!(
             invoke_id = %invoke_id,
-            "failed to broadcast terminal proxy capture record"
+            "terminal proxy capture record not broadcast; all receivers disconnected"
         );
     }
     schedule_dashboard_activity_live_snapshot(state);
     if terminal_enqueued {
+        broadcast_cost_spike_if_detected(state, &invoke_id, cost);
         schedule_proxy_capture_follow_up_after_terminal_enqueue(
             state,
             &invoke_id,
@@ -3150,18 +3241,62 @@ pub(crate) async fn persist_and_broadcast_proxy_capture_terminal_record(
     Ok(())
 }
 
+pub(crate) fn broadcast_cost_spike_if_detected(
+    state: &AppState,
+    invoke_id: &str,
+    cost: Option<f64>,
+) {
+    let Some(cost) = cost else {
+        return;
+    };
+    let factor = state.config.cost_spike_factor;
+    let Some(spike) = state.cost_spike_tracker.observe(cost, factor) else {
+        return;
+    };
+    warn!(
+        invoke_id = %invoke_id,
+        cost = spike.cost,
+        rolling_average = spike.rolling_average,
+        factor,
+        "proxy capture cost spike detected"
+    );
+    if state.broadcaster.receiver_count() > 0
+        && state
+            .broadcaster
+            .send(BroadcastPayload::Alert {
+                kind: ALERT_KIND_COST_SPIKE.to_string(),
+                detail: format!(
+                    "invocation {invoke_id} cost ${:.4}, {:.1}x the recent average of ${:.4}",
+                    spike.cost,
+                    spike.cost / spike.rolling_average,
+                    spike.rolling_average
+                ),
+            })
+            .is_err()
+    {
+        debug!(
+            invoke_id = %invoke_id,
+            "cost spike alert not broadcast; all receivers disconnected"
+        );
+    }
+}
+
 pub(crate) async fn persist_proxy_capture_runtime_record(
     pool: &Pool<Sqlite>,
     record: ProxyCaptureRecord,
+    treat_empty_error_as_success: bool,
 ) -> Result<Option<ApiInvocation>> {
-    persist_proxy_capture_runtime_record_core(pool, record, true).await
+    persist_proxy_capture_runtime_record_core(pool, record, true, treat_empty_error_as_success)
+        .await
 }
 
 pub(crate) async fn persist_proxy_capture_runtime_record_core(
     pool: &Pool<Sqlite>,
-    record: ProxyCaptureRecord,
+    mut record: ProxyCaptureRecord,
     write_derived_inline: bool,
+    treat_empty_error_as_success: bool,
 ) -> Result<Option<ApiInvocation>> {
+    record.occurred_at = canonical_occurred_at(&record.occurred_at);
     let raw_response = if record.response_body_preview_enabled {
         record.raw_response.clone()
     } else {
@@ -3278,12 +3413,14 @@ pub(crate) async fn persist_proxy_capture_runtime_record_core(
                 t_upstream_stream_ms,
                 t_resp_parse_ms,
                 t_persist_ms,
-                created_at
+                created_at,
+                http_status,
+                model_canonical
             )
             VALUES (
                 ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19,
                 ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36,
-                ?37, ?38, ?39, ?40, ?41, ?42, ?43
+                ?37, ?38, ?39, ?40, ?41, ?42, ?43, ?44, ?45
             )
             "#,
         )
@@ -3330,6 +3467,11 @@ pub(crate) async fn persist_proxy_capture_runtime_record_core(
         .bind(None::<f64>)
         .bind(None::<f64>)
         .bind(created_at)
+        .bind(resolve_invocation_http_status(
+            record.payload.as_deref(),
+            record.error_message.as_deref(),
+        ))
+        .bind(record.model_canonical.as_deref())
         .execute(tx.as_mut())
         .await?;
         if insert_result.rows_affected() == 0 {
@@ -3394,6 +3536,7 @@ pub(crate) async fn persist_proxy_capture_runtime_record_core(
                 status: Some(record.status.clone()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: record.model.clone(),
+                model_canonical: record.model_canonical.clone(),
                 input_tokens: record.usage.input_tokens,
                 output_tokens: record.usage.output_tokens,
                 cache_input_tokens: record.usage.cache_input_tokens,
@@ -3408,6 +3551,10 @@ pub(crate) async fn persist_proxy_capture_runtime_record_core(
                 cost_output: record.cost_breakdown.map(|value| value.output),
                 cost_reasoning: record.cost_breakdown.map(|value| value.reasoning),
                 error_message: record.error_message.clone(),
+                http_status: resolve_invocation_http_status(
+                    record.payload.as_deref(),
+                    record.error_message.as_deref(),
+                ),
                 failure_kind: failure_kind.clone(),
                 failure_class: Some(failure.failure_class.as_str().to_string()),
                 is_actionable: Some(failure.is_actionable as i64),
@@ -3422,6 +3569,7 @@ pub(crate) async fn persist_proxy_capture_runtime_record_core(
                 t_persist_ms: None,
             }],
             &INVOCATION_HOURLY_ROLLUP_TARGETS,
+            treat_empty_error_as_success,
         )
         .await?;
         save_hourly_rollup_live_progress_tx(
@@ -3500,6 +3648,7 @@ pub(crate) fn build_running_proxy_capture_record(
         invoke_id: invoke_id.to_string(),
         occurred_at: occurred_at.to_string(),
         model: request_info.model.clone(),
+        model_canonical: None,
         usage: ParsedUsage::default(),
         cost: None,
         cost_breakdown: None,
@@ -2166,6 +2166,7 @@ pub(crate) async fn persist_ws_usage_event(
             invoke_id,
             occurred_at,
             model: event.model,
+            model_canonical: None,
             usage: event.usage,
             cost,
             cost_breakdown,
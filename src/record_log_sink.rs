@@ -0,0 +1,267 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use tokio::{
+    fs::{self, File, OpenOptions},
+    io::{AsyncWriteExt, BufWriter},
+    sync::{Mutex, mpsc},
+    task::JoinHandle,
+    time::{MissedTickBehavior, interval},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::*;
+
+pub(crate) const RECORD_LOG_FSYNC_INTERVAL: Duration = Duration::from_secs(5);
+pub(crate) const RECORD_LOG_CHANNEL_CAPACITY: usize = 4_096;
+
+/// Appends persisted invocations as JSON lines for external log shippers.
+///
+/// Appends never block the caller: lines go through a bounded channel and are
+/// dropped with a warning when the writer falls behind. Write errors are
+/// logged and the file is reopened on the next line.
+#[derive(Debug)]
+pub(crate) struct RecordLogSink {
+    sender: mpsc::Sender<String>,
+    dropped_lines: AtomicU64,
+    shutdown: CancellationToken,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl RecordLogSink {
+    pub(crate) fn spawn(path: PathBuf, max_bytes: u64) -> Arc<Self> {
+        let (sender, receiver) = mpsc::channel(RECORD_LOG_CHANNEL_CAPACITY);
+        let shutdown = CancellationToken::new();
+        let handle = tokio::spawn(run_record_log_sink(
+            RecordLogWriter::new(path, max_bytes),
+            receiver,
+            shutdown.clone(),
+        ));
+        Arc::new(Self {
+            sender,
+            dropped_lines: AtomicU64::new(0),
+            shutdown,
+            handle: Mutex::new(Some(handle)),
+        })
+    }
+
+    pub(crate) fn append(&self, record: &ApiInvocation) {
+        match serde_json::to_string(record) {
+            Ok(line) => self.append_json_line(line),
+            Err(err) => warn!(
+                invoke_id = %record.invoke_id,
+                error = %err,
+                "failed to serialize invocation for record log"
+            ),
+        }
+    }
+
+    pub(crate) fn append_json_line(&self, line: String) {
+        if self.sender.try_send(line).is_err() {
+            let dropped = self.dropped_lines.fetch_add(1, Ordering::Relaxed) + 1;
+            warn!(
+                dropped_lines = dropped,
+                "record log sink is saturated; dropping invocation line"
+            );
+        }
+    }
+
+    pub(crate) async fn shutdown_and_drain(&self) {
+        self.shutdown.cancel();
+        let handle = self.handle.lock().await.take();
+        if let Some(handle) = handle
+            && let Err(err) = handle.await
+        {
+            warn!(?err, "record log sink terminated unexpectedly");
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RecordLogWriter {
+    path: PathBuf,
+    max_bytes: u64,
+    file: Option<BufWriter<File>>,
+    written_bytes: u64,
+    needs_sync: bool,
+}
+
+impl RecordLogWriter {
+    fn new(path: PathBuf, max_bytes: u64) -> Self {
+        Self {
+            path,
+            max_bytes,
+            file: None,
+            written_bytes: 0,
+            needs_sync: false,
+        }
+    }
+
+    async fn write_line(&mut self, line: &str) -> Result<()> {
+        let line_bytes = line.len() as u64 + 1;
+        if self.file.is_none() {
+            self.open().await?;
+        }
+        if self.written_bytes > 0 && self.written_bytes + line_bytes > self.max_bytes {
+            self.rotate().await?;
+        }
+        let file = self.file.as_mut().context("record log file is not open")?;
+        file.write_all(line.as_bytes()).await?;
+        file.write_all(b"\n").await?;
+        self.written_bytes += line_bytes;
+        self.needs_sync = true;
+        Ok(())
+    }
+
+    async fn sync(&mut self) -> Result<()> {
+        if !self.needs_sync {
+            return Ok(());
+        }
+        if let Some(file) = self.file.as_mut() {
+            file.flush().await?;
+            file.get_ref().sync_data().await?;
+        }
+        self.needs_sync = false;
+        Ok(())
+    }
+
+    async fn open(&mut self) -> Result<()> {
+        if let Some(parent) = self.path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("create record log dir {}", parent.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .with_context(|| format!("open record log {}", self.path.display()))?;
+        self.written_bytes = file.metadata().await?.len();
+        self.file = Some(BufWriter::new(file));
+        Ok(())
+    }
+
+    /// Keeps a single rotated generation next to the live file (`<path>.1`).
+    async fn rotate(&mut self) -> Result<()> {
+        self.sync().await?;
+        self.file = None;
+        let rotated = rotated_record_log_path(&self.path);
+        fs::rename(&self.path, &rotated).await.with_context(|| {
+            format!(
+                "rotate record log {} -> {}",
+                self.path.display(),
+                rotated.display()
+            )
+        })?;
+        info!(
+            path = %self.path.display(),
+            rotated = %rotated.display(),
+            "record log rotated"
+        );
+        self.open().await
+    }
+
+    async fn handle_line(&mut self, line: &str) {
+        if let Err(err) = self.write_line(line).await {
+            warn!(
+                path = %self.path.display(),
+                error = %err,
+                "failed to append invocation to record log"
+            );
+            // Reopen on the next line so a transient error (e.g. the file being
+            // moved away by a shipper) does not wedge the sink.
+            self.file = None;
+            self.needs_sync = false;
+        }
+    }
+
+    async fn handle_sync(&mut self) {
+        if let Err(err) = self.sync().await {
+            warn!(
+                path = %self.path.display(),
+                error = %err,
+                "failed to fsync record log"
+            );
+            self.file = None;
+            self.needs_sync = false;
+        }
+    }
+}
+
+pub(crate) fn rotated_record_log_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+async fn run_record_log_sink(
+    mut writer: RecordLogWriter,
+    mut receiver: mpsc::Receiver<String>,
+    shutdown: CancellationToken,
+) {
+    let mut ticker = interval(RECORD_LOG_FSYNC_INTERVAL);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            biased;
+            _ = shutdown.cancelled() => {
+                while let Ok(line) = receiver.try_recv() {
+                    writer.handle_line(&line).await;
+                }
+                writer.handle_sync().await;
+                return;
+            }
+            maybe_line = receiver.recv() => {
+                let Some(line) = maybe_line else {
+                    writer.handle_sync().await;
+                    return;
+                };
+                writer.handle_line(&line).await;
+            }
+            _ = ticker.tick() => {
+                writer.handle_sync().await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn record_log_sink_appends_json_lines_and_rotates_at_cap() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-record-log-sink-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("records.jsonl");
+
+        let sink = RecordLogSink::spawn(path.clone(), 48);
+        sink.append_json_line(r#"{"invokeId":"first"}"#.to_string());
+        sink.append_json_line(r#"{"invokeId":"second"}"#.to_string());
+        sink.append_json_line(r#"{"invokeId":"third"}"#.to_string());
+        sink.shutdown_and_drain().await;
+
+        let rotated = std::fs::read_to_string(rotated_record_log_path(&path))
+            .expect("read rotated record log");
+        assert_eq!(
+            rotated,
+            "{\"invokeId\":\"first\"}\n{\"invokeId\":\"second\"}\n"
+        );
+        let live = std::fs::read_to_string(&path).expect("read live record log");
+        assert_eq!(live, "{\"invokeId\":\"third\"}\n");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,154 @@
+use axum::middleware::Next;
+use rand::RngCore;
+
+use super::*;
+
+pub(crate) const REQUEST_ID_HEADER: &str = "x-request-id";
+const REQUEST_ID_MAX_LEN: usize = 128;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// Correlation id for one HTTP request, stored in the request extensions so the
+/// trace span and handlers can read it without touching forwarded headers.
+#[derive(Debug, Clone)]
+pub(crate) struct RequestId(pub(crate) String);
+
+pub(crate) fn generate_request_id() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    // RFC 4122 version 4 / variant 1 bits.
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Accepts a client-supplied id only when it is short and made of token
+/// characters, so it cannot smuggle whitespace or control bytes into logs.
+pub(crate) fn normalize_client_request_id(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty()
+        || trimmed.len() > REQUEST_ID_MAX_LEN
+        || !trimmed
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b':'))
+    {
+        return None;
+    }
+    Some(trimmed.to_string())
+}
+
+/// The id of the request currently being handled, if any.
+pub(crate) fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+pub(crate) async fn request_id_middleware(mut request: Request<Body>, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(normalize_client_request_id)
+        .unwrap_or_else(generate_request_id);
+    request
+        .extensions_mut()
+        .insert(RequestId(request_id.clone()));
+    let mut response = CURRENT_REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .await;
+    // Proxied upstream responses carry their own x-request-id; keep it intact.
+    if !response.headers().contains_key(REQUEST_ID_HEADER)
+        && let Ok(value) = HeaderValue::from_str(&request_id)
+    {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+pub(crate) fn make_request_span(request: &Request<Body>) -> tracing::Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.as_str())
+        .unwrap_or("-");
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id = %request_id,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::ServiceExt;
+
+    fn test_router() -> Router {
+        Router::new()
+            .route(
+                "/boom",
+                get(|| async { Err::<(), _>(ApiError::Internal(anyhow!("boom"))) }),
+            )
+            .layer(axum::middleware::from_fn(request_id_middleware))
+    }
+
+    #[tokio::test]
+    async fn request_id_middleware_echoes_client_id_into_header_and_error_body() {
+        let response = test_router()
+            .oneshot(
+                Request::builder()
+                    .uri("/boom")
+                    .header(REQUEST_ID_HEADER, "client-req-42")
+                    .body(Body::empty())
+                    .expect("build request"),
+            )
+            .await
+            .expect("serve request");
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            response.headers().get(REQUEST_ID_HEADER),
+            Some(&HeaderValue::from_static("client-req-42"))
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        assert_eq!(
+            std::str::from_utf8(&body).expect("utf8 body"),
+            "boom (request id: client-req-42)"
+        );
+    }
+
+    #[tokio::test]
+    async fn request_id_middleware_generates_id_for_missing_or_unsafe_header() {
+        let response = test_router()
+            .oneshot(
+                Request::builder()
+                    .uri("/boom")
+                    .header(REQUEST_ID_HEADER, "bad id\twith spaces")
+                    .body(Body::empty())
+                    .expect("build request"),
+            )
+            .await
+            .expect("serve request");
+        let generated = response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .expect("generated request id header");
+        assert_eq!(generated.len(), 36);
+        assert_eq!(generated.as_bytes()[14], b'4');
+    }
+}
@@ -3,23 +3,50 @@ use super::*;
 pub(crate) async fn run() -> Result<()> {
     dotenv().ok();
     dotenvy::from_filename(".env.local").ok();
-    init_tracing();
+    let cli = CliArgs::parse();
+    // Checked before tracing starts so stdout carries only the JSON line.
+    if cli.version {
+        println!(
+            "{}",
+            render_versions_json(resolve_static_dir(&cli).as_deref())?
+        );
+        return Ok(());
+    }
+    let log_buffer = init_tracing();
     let startup_started_at = Instant::now();
 
-    let cli = CliArgs::parse();
     let config = AppConfig::from_sources(&cli)?;
+    log_buffer.set_capacity(config.log_buffer_lines);
     let (backend_ver, frontend_ver) = detect_versions(config.static_dir.as_deref());
     info!(?config, backend_version = %backend_ver, frontend_version = %frontend_ver, "starting codex vibe monitor");
 
     let database_url = config.database_url();
     ensure_db_directory(&config.database_path)?;
-    let connect_opts = build_sqlite_connect_options(
-        &database_url,
-        Duration::from_secs(DEFAULT_SQLITE_BUSY_TIMEOUT_SECS),
-    )?;
+    let connect_opts = apply_sqlite_tuning_options(
+        build_sqlite_connect_options(
+            &database_url,
+            Duration::from_secs(DEFAULT_SQLITE_BUSY_TIMEOUT_SECS),
+        )?,
+        config.sqlite_synchronous,
+        config.sqlite_cache_size_kb,
+    );
+    if config.sqlite_synchronous == SqliteSynchronousMode::Off {
+        warn!(
+            "{ENV_SQLITE_SYNCHRONOUS}=off: SQLite will not fsync commits; a crash or power loss can lose recent writes or corrupt the database"
+        );
+    }
     let db_connect_started_at = Instant::now();
-    let pool = SqlitePoolOptions::new()
-        .max_connections(5)
+    let mut pool_options = SqlitePoolOptions::new().max_connections(5);
+    let archive_db_uri = resolve_archive_db_attach_uri(config.archive_db_path.as_deref());
+    let archive_db_configured = archive_db_uri.is_some();
+    if let Some(archive_db_uri) = archive_db_uri {
+        info!(archive_db = %archive_db_uri, "attaching read-only archive database");
+        pool_options = pool_options.after_connect(move |conn, _meta| {
+            let archive_db_uri = archive_db_uri.clone();
+            Box::pin(async move { attach_archive_db(conn, &archive_db_uri).await })
+        });
+    }
+    let pool = pool_options
         .connect_with(connect_opts)
         .await
         .context("failed to open sqlite database")?;
@@ -28,8 +55,18 @@ pub(crate) async fn run() -> Result<()> {
     let schema_started_at = Instant::now();
     ensure_schema(&pool).await?;
     log_startup_phase("schema", schema_started_at);
+    let archive_db_attached =
+        archive_db_configured && crate::stats::archive_db_invocations_available(&pool).await?;
+    if archive_db_configured && !archive_db_attached {
+        warn!("archive database has no codex_invocations table; it will not be merged into stats");
+    }
+    let sqlite_json1_available = probe_sqlite_json1(&pool).await;
+    if !sqlite_json1_available {
+        warn!("linked SQLite has no JSON1 support; payloadKey invocation filters are disabled");
+    }
     if should_recover_pending_pool_attempts_on_startup(&cli) {
-        let recovered_running_invocations = recover_orphaned_proxy_invocations(&pool).await?;
+        let recovered_running_invocations =
+            recover_orphaned_proxy_invocations(&pool, config.treat_empty_error_as_success).await?;
         if recovered_running_invocations > 0 {
             warn!(
                 recovered_running_invocations,
@@ -47,8 +84,12 @@ pub(crate) async fn run() -> Result<()> {
     }
     if should_run_blocking_startup_hourly_rollup_bootstrap(&cli) {
         let rollup_bootstrap_started_at = Instant::now();
-        bootstrap_hourly_rollups(&pool).await?;
-        ensure_invocation_summary_rollups_ready_best_effort(&pool).await?;
+        bootstrap_hourly_rollups(&pool, config.treat_empty_error_as_success).await?;
+        ensure_invocation_summary_rollups_ready_best_effort(
+            &pool,
+            config.treat_empty_error_as_success,
+        )
+        .await?;
         log_startup_phase("hourly_rollup_bootstrap", rollup_bootstrap_started_at);
     }
     if should_run_blocking_startup_persistent_prep(&cli) {
@@ -108,6 +149,12 @@ pub(crate) async fn run() -> Result<()> {
     })?;
     let pricing_catalog = Arc::new(RwLock::new(pricing_catalog));
 
+    if config.upstream_danger_accept_invalid_certs {
+        warn!(
+            env = ENV_UPSTREAM_DANGER_ACCEPT_INVALID_CERTS,
+            "upstream TLS certificate verification is DISABLED; only use this with trusted self-hosted gateways"
+        );
+    }
     let http_clients = HttpClients::build(&config)?;
     let upstream_accounts = Arc::new(UpstreamAccountsRuntime::from_env()?);
     let (tx, _rx) = broadcast::channel(128);
@@ -125,8 +172,20 @@ pub(crate) async fn run() -> Result<()> {
         pool.clone(),
         shutdown.clone(),
         prompt_cache_conversation_cache.clone(),
+        config.treat_empty_error_as_success,
     );
     sqlite_batch_writer.set_terminal_runtime_store(proxy_runtime_invocations.clone());
+    if let Some(record_log_path) = config.record_log_path.clone() {
+        info!(
+            path = %record_log_path.display(),
+            max_bytes = config.record_log_max_bytes,
+            "appending persisted invocations to record log"
+        );
+        sqlite_batch_writer.set_record_log_sink(RecordLogSink::spawn(
+            record_log_path,
+            config.record_log_max_bytes,
+        ));
+    }
     let pool_account_selection_runtime = Arc::new(PoolAccountSelectionRuntime::default());
     let subscription_hub = Arc::new(SubscriptionHub::new());
 
@@ -134,6 +193,11 @@ pub(crate) async fn run() -> Result<()> {
         config: config.clone(),
         pool,
         process_started_at_utc,
+        clock: Arc::new(SystemClock),
+        archive_db_attached,
+        sqlite_json1_available,
+        cost_spike_tracker: Arc::new(CostSpikeTracker::default()),
+        log_buffer,
         sqlite_batch_writer,
         pool_account_selection_runtime,
         proxy_runtime_invocations,
@@ -171,6 +235,8 @@ pub(crate) async fn run() -> Result<()> {
         )),
         maintenance_stats_cache: Arc::new(Mutex::new(StatsMaintenanceCacheState::default())),
         system_status_cache: Arc::new(Mutex::new(SystemStatusCacheState::default())),
+        timeseries_response_cache: Arc::new(TimeseriesResponseCache::default()),
+        sse_connections: Arc::new(SseConnectionRegistry::default()),
         pool_routing_reservations: Arc::new(std::sync::Mutex::new(HashMap::new())),
         pool_routing_runtime_cache: Arc::new(Mutex::new(None)),
         pool_live_attempt_ids: Arc::new(std::sync::Mutex::new(HashSet::new())),
@@ -740,48 +806,59 @@ pub(crate) async fn drain_runtime_after_shutdown(
         info!("http server graceful drain finished");
     }
 
-    if let Some(poller_handle) = poller_handle {
-        if let Err(err) = poller_handle.await {
-            error!(?err, "poller task terminated unexpectedly");
+    // Bound the background drain so a hung upstream call cannot keep the
+    // process alive; anything still running past the deadline is detached.
+    let shutdown_timeout = state.config.shutdown_timeout;
+    let background_drain = async {
+        if let Some(poller_handle) = poller_handle {
+            if let Err(err) = poller_handle.await {
+                error!(?err, "poller task terminated unexpectedly");
+            }
+            info!("scheduler drained");
         }
-        info!("scheduler drained");
-    }
-    if let Some(upstream_accounts_handle) = upstream_accounts_handle
-        && let Err(err) = upstream_accounts_handle.await
-    {
-        error!(
-            ?err,
-            "upstream account maintenance task terminated unexpectedly"
-        );
-    }
-    state.upstream_accounts.drain_background_tasks().await;
-    if let Some(forward_proxy_handle) = forward_proxy_handle
-        && let Err(err) = forward_proxy_handle.await
-    {
-        error!(
-            ?err,
-            "forward proxy maintenance task terminated unexpectedly"
-        );
-    }
-    if let Some(pool_orphan_recovery_handle) = pool_orphan_recovery_handle
-        && let Err(err) = pool_orphan_recovery_handle.await
-    {
-        error!(
-            ?err,
-            "pool orphan recovery maintenance task terminated unexpectedly"
-        );
-    }
-    if let Some(retention_handle) = retention_handle
-        && let Err(err) = retention_handle.await
-    {
-        error!(?err, "retention maintenance task terminated unexpectedly");
-    }
-    if let Some(startup_backfill_handle) = startup_backfill_handle
-        && let Err(err) = startup_backfill_handle.await
-    {
-        error!(
-            ?err,
-            "startup backfill maintenance task terminated unexpectedly"
+        if let Some(upstream_accounts_handle) = upstream_accounts_handle
+            && let Err(err) = upstream_accounts_handle.await
+        {
+            error!(
+                ?err,
+                "upstream account maintenance task terminated unexpectedly"
+            );
+        }
+        state.upstream_accounts.drain_background_tasks().await;
+        if let Some(forward_proxy_handle) = forward_proxy_handle
+            && let Err(err) = forward_proxy_handle.await
+        {
+            error!(
+                ?err,
+                "forward proxy maintenance task terminated unexpectedly"
+            );
+        }
+        if let Some(pool_orphan_recovery_handle) = pool_orphan_recovery_handle
+            && let Err(err) = pool_orphan_recovery_handle.await
+        {
+            error!(
+                ?err,
+                "pool orphan recovery maintenance task terminated unexpectedly"
+            );
+        }
+        if let Some(retention_handle) = retention_handle
+            && let Err(err) = retention_handle.await
+        {
+            error!(?err, "retention maintenance task terminated unexpectedly");
+        }
+        if let Some(startup_backfill_handle) = startup_backfill_handle
+            && let Err(err) = startup_backfill_handle.await
+        {
+            error!(
+                ?err,
+                "startup backfill maintenance task terminated unexpectedly"
+            );
+        }
+    };
+    if timeout(shutdown_timeout, background_drain).await.is_err() {
+        warn!(
+            timeout_secs = shutdown_timeout.as_secs(),
+            "background tasks did not drain before the shutdown timeout; abandoning them"
         );
     }
 
@@ -817,14 +894,20 @@ pub(crate) async fn drain_runtime_after_shutdown(
     Ok(())
 }
 
-pub(crate) fn init_tracing() {
+pub(crate) fn init_tracing() -> Arc<LogRingBuffer> {
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+    let log_buffer = Arc::new(LogRingBuffer::default());
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "info,tower_http=info".into()),
         )
         .with_target(false)
+        .finish()
+        .with(LogBufferLayer::new(log_buffer.clone()))
         .init();
+    log_buffer
 }
 
 pub(crate) fn log_startup_phase(phase: &'static str, started_at: Instant) {
@@ -8,6 +8,9 @@ pub(crate) const INVOCATION_PROMPT_CACHE_KEY_EXPR_SQL: &str = "CASE WHEN json_va
 pub(crate) const INVOCATION_UPSTREAM_ACCOUNT_ID_EXPR_SQL: &str = "CASE WHEN json_valid(payload) THEN CAST(json_extract(payload, '$.upstreamAccountId') AS INTEGER) END";
 pub(crate) const PROMPT_CACHE_WORKING_SET_WINDOW_SECONDS: i64 = 300;
 pub(crate) const SHANGHAI_NOW_SQL: &str = "datetime('now', '+8 hours')";
+/// Highest `schema_migrations.version` this binary knows how to produce. Bump it
+/// alongside any migration that must run exactly once.
+pub(crate) const CURRENT_SCHEMA_VERSION: i64 = 4;
 
 pub(crate) fn ensure_schema_lock_key(pool: &Pool<Sqlite>) -> String {
     let connect_options = pool.connect_options();
@@ -911,7 +914,8 @@ pub(crate) async fn migrate_pool_upstream_node_health_hourly_archive_identity(
 pub(crate) async fn backfill_upstream_account_usage_hourly_status_counts(
     pool: &Pool<Sqlite>,
 ) -> Result<()> {
-    let success_like_sql = invocation_status_is_success_like_sql("status", "error_message");
+    // One-shot column migration; it runs before config is applied and keeps the strict rule.
+    let success_like_sql = invocation_status_is_success_like_sql("status", "error_message", false);
     let resolved_failure_sql = crate::api::INVOCATION_RESOLVED_FAILURE_CLASS_SQL;
     let upstream_account_id_sql = "CASE WHEN json_valid(payload) THEN CAST(json_extract(payload, '$.upstreamAccountId') AS INTEGER) END";
     let bucket_epoch_sql = "((CASE
@@ -1111,6 +1115,259 @@ pub(crate) async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<()> {
     let schema_lock = ensure_schema_lock(pool);
     let _schema_guard = schema_lock.lock_owned().await;
 
+    ensure_schema_migrations_table(pool).await?;
+    let applied_version = load_applied_schema_version(pool).await?;
+    ensure_schema_version_supported(applied_version)?;
+
+    ensure_schema_baseline(pool).await?;
+    for version in (applied_version + 1)..=CURRENT_SCHEMA_VERSION {
+        apply_schema_migration(pool, version).await?;
+        record_schema_version(pool, version).await?;
+    }
+
+    Ok(())
+}
+
+async fn apply_schema_migration(pool: &Pool<Sqlite>, version: i64) -> Result<()> {
+    match version {
+        // Version 1 is the baseline reconciliation, which runs on every start.
+        1 => Ok(()),
+        2 => normalize_stored_occurred_at(pool).await,
+        3 => backfill_invocation_http_status(pool).await,
+        4 => rewrite_failure_rollup_error_categories(pool).await,
+        _ => bail!("no migration registered for schema version {version}"),
+    }
+}
+
+const CANONICAL_OCCURRED_AT_GLOB: &str =
+    "[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9] [0-9][0-9]:[0-9][0-9]:[0-9][0-9]";
+
+/// Rewrites `occurred_at` values stored in any other format (e.g. RFC3339) to
+/// the canonical form produced by `canonical_occurred_at`. Rows whose canonical
+/// value would collide with an existing `(invoke_id, occurred_at)` are left as-is.
+pub(crate) async fn normalize_stored_occurred_at(pool: &Pool<Sqlite>) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let mut normalized = 0_u64;
+    let mut collisions = 0_u64;
+    for table in ["codex_invocations", "pool_upstream_request_attempts"] {
+        let select = format!("SELECT id, occurred_at FROM {table} WHERE occurred_at NOT GLOB ?1");
+        let rows: Vec<(i64, String)> = sqlx::query_as(&select)
+            .bind(CANONICAL_OCCURRED_AT_GLOB)
+            .fetch_all(tx.as_mut())
+            .await
+            .with_context(|| format!("failed to load non-canonical {table}.occurred_at rows"))?;
+        let update = format!("UPDATE OR IGNORE {table} SET occurred_at = ?1 WHERE id = ?2");
+        for (id, occurred_at) in rows {
+            let canonical = canonical_occurred_at(&occurred_at);
+            if canonical == occurred_at {
+                continue;
+            }
+            let affected = sqlx::query(&update)
+                .bind(&canonical)
+                .bind(id)
+                .execute(tx.as_mut())
+                .await
+                .with_context(|| format!("failed to normalize {table}.occurred_at for id {id}"))?
+                .rows_affected();
+            if affected == 0 {
+                collisions += 1;
+            } else {
+                normalized += affected;
+            }
+        }
+    }
+    tx.commit().await?;
+    if collisions > 0 {
+        warn!(
+            normalized,
+            collisions, "normalized stored occurred_at values; colliding rows were left unchanged"
+        );
+    } else if normalized > 0 {
+        info!(normalized, "normalized stored occurred_at values");
+    }
+    Ok(())
+}
+
+/// Populates `http_status` for rows written before the column existed, using the
+/// same payload-then-error-message resolution as the write path.
+pub(crate) async fn backfill_invocation_http_status(pool: &Pool<Sqlite>) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let from_payload = sqlx::query(
+        r#"
+        UPDATE codex_invocations
+        SET http_status = CAST(json_extract(payload, '$.statusCode') AS INTEGER)
+        WHERE http_status IS NULL
+          AND json_valid(payload)
+          AND json_type(payload, '$.statusCode') = 'integer'
+        "#,
+    )
+    .execute(tx.as_mut())
+    .await
+    .context("failed to backfill codex_invocations.http_status from payload")?
+    .rows_affected();
+
+    let rows: Vec<(i64, String)> = sqlx::query_as(
+        r#"
+        SELECT id, error_message
+        FROM codex_invocations
+        WHERE http_status IS NULL
+          AND error_message IS NOT NULL
+          AND TRIM(error_message) <> ''
+        "#,
+    )
+    .fetch_all(tx.as_mut())
+    .await
+    .context("failed to load codex_invocations error messages for http_status backfill")?;
+    let mut from_error_message = 0_u64;
+    for (id, error_message) in rows {
+        let Some(http_status) = extract_http_code(&error_message) else {
+            continue;
+        };
+        from_error_message +=
+            sqlx::query("UPDATE codex_invocations SET http_status = ?1 WHERE id = ?2")
+                .bind(i64::from(http_status))
+                .bind(id)
+                .execute(tx.as_mut())
+                .await
+                .with_context(|| {
+                    format!("failed to backfill codex_invocations.http_status for id {id}")
+                })?
+                .rows_affected();
+    }
+    tx.commit().await?;
+    if from_payload > 0 || from_error_message > 0 {
+        info!(
+            from_payload,
+            from_error_message, "backfilled codex_invocations.http_status"
+        );
+    }
+    Ok(())
+}
+
+/// Rewrites `invocation_failure_rollup_hourly.error_category` values written as
+/// display labels to the stable `ErrorCategory::code`, merging counts when the
+/// code already has a row for the same bucket.
+///
+/// Every row is a legacy label when this runs, so values are always parsed as
+/// labels: a JSON error type such as `rate_limited` must not be read as the
+/// fixed code of the same spelling. Rows are staged under a reserved prefix
+/// first so a label that equals another category's code is never merged into
+/// it mid-rewrite.
+pub(crate) async fn rewrite_failure_rollup_error_categories(pool: &Pool<Sqlite>) -> Result<()> {
+    const STAGED_PREFIX: &str = "\u{1}staged:";
+    let mut tx = pool.begin().await?;
+    let categories: Vec<String> =
+        sqlx::query_scalar("SELECT DISTINCT error_category FROM invocation_failure_rollup_hourly")
+            .fetch_all(tx.as_mut())
+            .await
+            .context("failed to load invocation_failure_rollup_hourly error categories")?;
+    let mut staged = Vec::with_capacity(categories.len());
+    for legacy in categories {
+        let code = ErrorCategory::from_label(&legacy).code();
+        let staged_code = format!("{STAGED_PREFIX}{code}");
+        move_failure_rollup_error_category(&mut tx, &legacy, &staged_code).await?;
+        staged.push((staged_code, code));
+    }
+    staged.sort();
+    staged.dedup();
+    let mut rewritten = 0_u64;
+    for (staged_code, code) in staged {
+        rewritten += move_failure_rollup_error_category(&mut tx, &staged_code, &code).await?;
+    }
+    tx.commit().await?;
+    if rewritten > 0 {
+        info!(
+            rewritten,
+            "rewrote failure rollup error categories to codes"
+        );
+    }
+    Ok(())
+}
+
+async fn move_failure_rollup_error_category(
+    tx: &mut sqlx::Transaction<'_, Sqlite>,
+    from: &str,
+    to: &str,
+) -> Result<u64> {
+    let moved = sqlx::query(
+        r#"
+        INSERT INTO invocation_failure_rollup_hourly (
+            bucket_start_epoch,
+            source,
+            failure_class,
+            is_actionable,
+            error_category,
+            failure_count,
+            updated_at
+        )
+        SELECT bucket_start_epoch, source, failure_class, is_actionable, ?2, failure_count, updated_at
+        FROM invocation_failure_rollup_hourly
+        WHERE error_category = ?1
+        ON CONFLICT(bucket_start_epoch, source, failure_class, is_actionable, error_category) DO UPDATE SET
+            failure_count = invocation_failure_rollup_hourly.failure_count + excluded.failure_count,
+            updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(from)
+    .bind(to)
+    .execute(tx.as_mut())
+    .await
+    .with_context(|| format!("failed to rewrite failure rollup category {from:?}"))?
+    .rows_affected();
+    sqlx::query("DELETE FROM invocation_failure_rollup_hourly WHERE error_category = ?1")
+        .bind(from)
+        .execute(tx.as_mut())
+        .await
+        .with_context(|| format!("failed to drop failure rollup category {from:?}"))?;
+    Ok(moved)
+}
+
+async fn ensure_schema_migrations_table(pool: &Pool<Sqlite>) -> Result<()> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+    )
+    .execute(pool)
+    .await
+    .context("failed to ensure schema_migrations table existence")?;
+    Ok(())
+}
+
+pub(crate) async fn load_applied_schema_version(pool: &Pool<Sqlite>) -> Result<i64> {
+    let version: Option<i64> = sqlx::query_scalar("SELECT MAX(version) FROM schema_migrations")
+        .fetch_one(pool)
+        .await
+        .context("failed to load applied schema version")?;
+    Ok(version.unwrap_or(0))
+}
+
+/// Refuses to touch a database written by a newer binary: the baseline
+/// reconciliation below could otherwise rewrite columns that binary relies on.
+pub(crate) fn ensure_schema_version_supported(applied_version: i64) -> Result<()> {
+    if applied_version > CURRENT_SCHEMA_VERSION {
+        bail!(
+            "database schema version {applied_version} is newer than this binary supports ({CURRENT_SCHEMA_VERSION}); refusing to start after a downgrade"
+        );
+    }
+    Ok(())
+}
+
+async fn record_schema_version(pool: &Pool<Sqlite>, version: i64) -> Result<()> {
+    sqlx::query("INSERT OR IGNORE INTO schema_migrations (version) VALUES (?1)")
+        .bind(version)
+        .execute(pool)
+        .await
+        .with_context(|| format!("failed to record schema version {version}"))?;
+    Ok(())
+}
+
+/// Version 1: the idempotent table/column reconciliation that predates version
+/// tracking. It still runs on every start so legacy databases keep converging.
+async fn ensure_schema_baseline(pool: &Pool<Sqlite>) -> Result<()> {
     let create_sql = codex_invocations_create_sql("codex_invocations");
     sqlx::query(&create_sql)
         .execute(pool)
@@ -1167,6 +1424,9 @@ pub(crate) async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<()> {
         ("t_upstream_stream_ms", "REAL"),
         ("t_resp_parse_ms", "REAL"),
         ("t_persist_ms", "REAL"),
+        ("http_status", "INTEGER"),
+        ("model_canonical", "TEXT"),
+        ("label", "TEXT"),
     ] {
         if !existing.contains(column) {
             let statement = format!("ALTER TABLE codex_invocations ADD COLUMN {column} {ty}");
@@ -1889,6 +2149,7 @@ pub(crate) async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<()> {
             cache_input_tokens INTEGER NOT NULL DEFAULT 0,
             total_cost REAL NOT NULL,
             non_success_cost REAL NOT NULL DEFAULT 0,
+            refund_count INTEGER NOT NULL DEFAULT 0,
             total_latency_sample_count INTEGER NOT NULL DEFAULT 0,
             total_latency_sum_ms REAL NOT NULL DEFAULT 0,
             first_byte_sample_count INTEGER NOT NULL DEFAULT 0,
@@ -1914,6 +2175,7 @@ pub(crate) async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<()> {
     for (column, ty) in [
         ("cache_input_tokens", "INTEGER NOT NULL DEFAULT 0"),
         ("non_success_cost", "REAL NOT NULL DEFAULT 0"),
+        ("refund_count", "INTEGER NOT NULL DEFAULT 0"),
         ("total_latency_sample_count", "INTEGER NOT NULL DEFAULT 0"),
         ("total_latency_sum_ms", "REAL NOT NULL DEFAULT 0"),
         (
@@ -1956,7 +2218,8 @@ pub(crate) async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<()> {
     .await
     .context("failed to ensure index idx_invocation_rollup_hourly_source_bucket")?;
     if added_invocation_rollup_rebuild_columns {
-        let rebuilt_rows = backfill_invocation_rollup_hourly_from_sources(pool).await?;
+        // Column migrations rebuild with the strict success rule, like the status-count backfill.
+        let rebuilt_rows = backfill_invocation_rollup_hourly_from_sources(pool, false).await?;
         info!(
             rebuilt_rows,
             "backfilled invocation hourly rollups after adding aggregate columns"
@@ -2721,7 +2984,7 @@ pub(crate) async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<()> {
         || added_upstream_account_stats_columns
     {
         reopen_upstream_account_stats_rollup_archives(pool).await?;
-        rebuild_upstream_account_stats_rollups_from_sources(pool)
+        rebuild_upstream_account_stats_rollups_from_sources(pool, false)
             .await
             .context("failed to rebuild upstream account stats rollups from sources")?;
     }
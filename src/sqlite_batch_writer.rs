@@ -265,6 +265,10 @@ pub(crate) struct SqliteBatchWriter {
     pending_depth: Arc<AtomicUsize>,
     dropped_writes: Arc<AtomicU64>,
     terminal_runtime_store: Arc<std::sync::Mutex<Option<Arc<ProxyRuntimeInvocationStore>>>>,
+    record_log_sink: Arc<std::sync::Mutex<Option<Arc<RecordLogSink>>>>,
+    write_generation: Arc<AtomicU64>,
+    #[cfg(test)]
+    treat_empty_error_as_success: bool,
     #[cfg(test)]
     prompt_cache_conversation_cache: Option<Arc<Mutex<PromptCacheConversationsCacheState>>>,
     handle: Mutex<Option<JoinHandle<()>>>,
@@ -279,12 +283,15 @@ impl SqliteBatchWriter {
         pool: Pool<Sqlite>,
         _shutdown: CancellationToken,
         prompt_cache_conversation_cache: Arc<Mutex<PromptCacheConversationsCacheState>>,
+        treat_empty_error_as_success: bool,
     ) -> Arc<Self> {
         let (write_sender, write_receiver) = mpsc::channel(SQLITE_BATCH_CHANNEL_CAPACITY);
         let (control_sender, control_receiver) = mpsc::channel(128);
         let pending_depth = Arc::new(AtomicUsize::new(0));
         let dropped_writes = Arc::new(AtomicU64::new(0));
         let terminal_runtime_store = Arc::new(std::sync::Mutex::new(None));
+        let record_log_sink = Arc::new(std::sync::Mutex::new(None));
+        let write_generation = Arc::new(AtomicU64::new(0));
         let cache_for_task = prompt_cache_conversation_cache.clone();
         let handle = tokio::spawn(run_sqlite_batch_writer(
             pool,
@@ -293,6 +300,9 @@ impl SqliteBatchWriter {
             pending_depth.clone(),
             Some(cache_for_task),
             terminal_runtime_store.clone(),
+            record_log_sink.clone(),
+            write_generation.clone(),
+            treat_empty_error_as_success,
         ));
         Arc::new(Self {
             write_sender,
@@ -300,6 +310,10 @@ impl SqliteBatchWriter {
             pending_depth,
             dropped_writes,
             terminal_runtime_store,
+            record_log_sink,
+            write_generation,
+            #[cfg(test)]
+            treat_empty_error_as_success,
             #[cfg(test)]
             prompt_cache_conversation_cache: Some(prompt_cache_conversation_cache),
             handle: Mutex::new(Some(handle)),
@@ -329,6 +343,9 @@ impl SqliteBatchWriter {
             pending_depth: Arc::new(AtomicUsize::new(0)),
             dropped_writes: Arc::new(AtomicU64::new(0)),
             terminal_runtime_store: Arc::new(std::sync::Mutex::new(None)),
+            record_log_sink: Arc::new(std::sync::Mutex::new(None)),
+            write_generation: Arc::new(AtomicU64::new(0)),
+            treat_empty_error_as_success: DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
             prompt_cache_conversation_cache: Some(prompt_cache_conversation_cache),
             handle: Mutex::new(None),
             buffered_writes: Some(Arc::new(std::sync::Mutex::new(Vec::new()))),
@@ -356,6 +373,25 @@ impl SqliteBatchWriter {
         }
     }
 
+    pub(crate) fn set_record_log_sink(&self, sink: Arc<RecordLogSink>) {
+        if let Ok(mut guard) = self.record_log_sink.lock() {
+            *guard = Some(sink);
+        }
+    }
+
+    /// Bumped after every flush that committed rows; read-side caches compare
+    /// it to decide whether their snapshot predates newer invocation data.
+    pub(crate) fn write_generation(&self) -> u64 {
+        self.write_generation.load(Ordering::Acquire)
+    }
+
+    /// Marks invocation rows committed outside the batch writer (deletes,
+    /// labels, retention, backfills, orphan recovery) so generation-keyed
+    /// caches stop serving snapshots taken before them.
+    pub(crate) fn bump_write_generation(&self) {
+        self.write_generation.fetch_add(1, Ordering::Release);
+    }
+
     pub(crate) fn enqueue(&self, write: SqliteBatchWrite) -> bool {
         #[cfg(test)]
         if let Some(buffered_writes) = &self.buffered_writes {
@@ -466,6 +502,14 @@ impl SqliteBatchWriter {
         if let Err(err) = handle.await {
             warn!(error = %err, "sqlite batch writer task failed during shutdown");
         }
+        let record_log_sink = self
+            .record_log_sink
+            .lock()
+            .ok()
+            .and_then(|mut guard| guard.take());
+        if let Some(sink) = record_log_sink {
+            sink.shutdown_and_drain().await;
+        }
     }
 
     #[cfg(test)]
@@ -477,19 +521,41 @@ impl SqliteBatchWriter {
     }
 
     #[cfg(test)]
-    pub(crate) async fn flush_for_test(pool: &Pool<Sqlite>, writes: Vec<SqliteBatchWrite>) {
+    pub(crate) async fn flush_for_test(
+        pool: &Pool<Sqlite>,
+        writes: Vec<SqliteBatchWrite>,
+        treat_empty_error_as_success: bool,
+    ) {
         let mut batch = PendingBatch::default();
         for write in writes {
             batch.push(write);
         }
         let terminal_runtime_store = Arc::new(std::sync::Mutex::new(None));
-        let deferred = flush_pending_batch_inner(pool, &batch, None, &terminal_runtime_store)
-            .await
-            .expect("flush pending sqlite batch writes");
+        let record_log_sink = Arc::new(std::sync::Mutex::new(None));
+        let write_generation = Arc::new(AtomicU64::new(0));
+        let deferred = flush_pending_batch_inner(
+            pool,
+            &batch,
+            None,
+            &terminal_runtime_store,
+            &record_log_sink,
+            &write_generation,
+            treat_empty_error_as_success,
+        )
+        .await
+        .expect("flush pending sqlite batch writes");
         if !deferred.is_empty() {
-            flush_pending_batch_inner(pool, &deferred, None, &terminal_runtime_store)
-                .await
-                .expect("flush deferred pending sqlite batch writes");
+            flush_pending_batch_inner(
+                pool,
+                &deferred,
+                None,
+                &terminal_runtime_store,
+                &record_log_sink,
+                &write_generation,
+                treat_empty_error_as_success,
+            )
+            .await
+            .expect("flush deferred pending sqlite batch writes");
         }
     }
 
@@ -518,6 +584,9 @@ impl SqliteBatchWriter {
                 &batch,
                 self.prompt_cache_conversation_cache.as_ref(),
                 &self.terminal_runtime_store,
+                &self.record_log_sink,
+                &self.write_generation,
+                self.treat_empty_error_as_success,
             )
             .await
             .expect("flush buffered sqlite batch writes for test");
@@ -536,6 +605,7 @@ impl SqliteBatchWriter {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn run_sqlite_batch_writer(
     pool: Pool<Sqlite>,
     mut write_receiver: mpsc::Receiver<SqliteBatchWrite>,
@@ -543,6 +613,9 @@ pub(crate) async fn run_sqlite_batch_writer(
     pending_depth: Arc<AtomicUsize>,
     prompt_cache_conversation_cache: Option<Arc<Mutex<PromptCacheConversationsCacheState>>>,
     terminal_runtime_store: Arc<std::sync::Mutex<Option<Arc<ProxyRuntimeInvocationStore>>>>,
+    record_log_sink: Arc<std::sync::Mutex<Option<Arc<RecordLogSink>>>>,
+    write_generation: Arc<AtomicU64>,
+    treat_empty_error_as_success: bool,
 ) {
     let mut ticker = interval(SQLITE_BATCH_FLUSH_INTERVAL);
     ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
@@ -571,6 +644,9 @@ pub(crate) async fn run_sqlite_batch_writer(
                             FlushReason::Barrier,
                             prompt_cache_conversation_cache.as_ref(),
                             &terminal_runtime_store,
+                            &record_log_sink,
+                            &write_generation,
+                            treat_empty_error_as_success,
                         )
                         .await
                         {
@@ -609,6 +685,9 @@ pub(crate) async fn run_sqlite_batch_writer(
                                 FlushReason::Shutdown,
                                 prompt_cache_conversation_cache.as_ref(),
                                 &terminal_runtime_store,
+                                &record_log_sink,
+                                &write_generation,
+                                treat_empty_error_as_success,
                             )
                             .await
                             {
@@ -646,6 +725,9 @@ pub(crate) async fn run_sqlite_batch_writer(
                             FlushReason::Shutdown,
                             prompt_cache_conversation_cache.as_ref(),
                             &terminal_runtime_store,
+                            &record_log_sink,
+                            &write_generation,
+                            treat_empty_error_as_success,
                         )
                         .await;
                     }
@@ -661,6 +743,9 @@ pub(crate) async fn run_sqlite_batch_writer(
                             FlushReason::RowLimit,
                             prompt_cache_conversation_cache.as_ref(),
                             &terminal_runtime_store,
+                            &record_log_sink,
+                            &write_generation,
+                            treat_empty_error_as_success,
                         )
                         .await
                     {
@@ -700,6 +785,9 @@ pub(crate) async fn run_sqlite_batch_writer(
                             flush_reason,
                             prompt_cache_conversation_cache.as_ref(),
                             &terminal_runtime_store,
+                            &record_log_sink,
+                            &write_generation,
+                            treat_empty_error_as_success,
                         )
                         .await
                     {
@@ -730,12 +818,16 @@ pub(crate) fn drain_queued_batch_writes(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn flush_pending_batch(
     pool: &Pool<Sqlite>,
     batch: PendingBatch,
     reason: FlushReason,
     prompt_cache_conversation_cache: Option<&Arc<Mutex<PromptCacheConversationsCacheState>>>,
     terminal_runtime_store: &Arc<std::sync::Mutex<Option<Arc<ProxyRuntimeInvocationStore>>>>,
+    record_log_sink: &Arc<std::sync::Mutex<Option<Arc<RecordLogSink>>>>,
+    write_generation: &Arc<AtomicU64>,
+    treat_empty_error_as_success: bool,
 ) -> Option<RetainedBatch> {
     if batch.is_empty() {
         return None;
@@ -787,6 +879,9 @@ pub(crate) async fn flush_pending_batch(
         &batch,
         prompt_cache_conversation_cache,
         terminal_runtime_store,
+        record_log_sink,
+        write_generation,
+        treat_empty_error_as_success,
     )
     .await
     {
@@ -887,21 +982,42 @@ pub(crate) async fn flush_pending_batch_inner(
     batch: &PendingBatch,
     prompt_cache_conversation_cache: Option<&Arc<Mutex<PromptCacheConversationsCacheState>>>,
     terminal_runtime_store: &Arc<std::sync::Mutex<Option<Arc<ProxyRuntimeInvocationStore>>>>,
+    record_log_sink: &Arc<std::sync::Mutex<Option<Arc<RecordLogSink>>>>,
+    write_generation: &Arc<AtomicU64>,
+    treat_empty_error_as_success: bool,
 ) -> Result<PendingBatch> {
     let mut deferred_batch = PendingBatch::default();
     let mut should_invalidate_prompt_cache_conversations = false;
+    let record_log_sink = record_log_sink
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().cloned());
     for terminal in batch.terminal_invocations.values() {
         let persisted = if terminal.raw_capture {
             let capture_started = terminal.capture_started.unwrap_or_else(Instant::now);
-            persist_proxy_capture_record_core(pool, capture_started, terminal.record.clone(), false)
-                .await
-                .with_context(|| "flush terminal raw proxy invocation")?
+            persist_proxy_capture_record_core(
+                pool,
+                capture_started,
+                terminal.record.clone(),
+                false,
+                treat_empty_error_as_success,
+            )
+            .await
+            .with_context(|| "flush terminal raw proxy invocation")?
         } else {
-            persist_proxy_capture_runtime_record_core(pool, terminal.record.clone(), false)
-                .await
-                .with_context(|| "flush terminal runtime proxy invocation")?
+            persist_proxy_capture_runtime_record_core(
+                pool,
+                terminal.record.clone(),
+                false,
+                treat_empty_error_as_success,
+            )
+            .await
+            .with_context(|| "flush terminal runtime proxy invocation")?
         };
         let derived_identity = if let Some(persisted) = persisted {
+            if let Some(sink) = record_log_sink.as_ref() {
+                sink.append(&persisted);
+            }
             if persisted
                 .prompt_cache_key
                 .as_deref()
@@ -955,6 +1071,9 @@ pub(crate) async fn flush_pending_batch_inner(
         {
             invalidate_prompt_cache_conversations_cache(cache).await;
         }
+        if !batch.terminal_invocations.is_empty() {
+            write_generation.fetch_add(1, Ordering::Release);
+        }
         return Ok(deferred_batch);
     }
 
@@ -1015,15 +1134,24 @@ pub(crate) async fn flush_pending_batch_inner(
         let live_rollup_cursor_before =
             load_hourly_rollup_live_progress_tx(tx.as_mut(), HOURLY_ROLLUP_DATASET_INVOCATIONS)
                 .await?;
-        replay_live_invocation_hourly_rollups_until_tx(tx.as_mut(), target_invocation_id).await?;
+        replay_live_invocation_hourly_rollups_until_tx(
+            tx.as_mut(),
+            target_invocation_id,
+            treat_empty_error_as_success,
+        )
+        .await?;
         let skipped_terminal_ids = invocation_derived
             .keys()
             .filter(|invocation_id| **invocation_id <= live_rollup_cursor_before)
             .copied()
             .collect::<Vec<_>>();
         if !skipped_terminal_ids.is_empty() {
-            recompute_invocation_hourly_rollups_for_ids_tx(tx.as_mut(), &skipped_terminal_ids)
-                .await?;
+            recompute_invocation_hourly_rollups_for_ids_tx(
+                tx.as_mut(),
+                &skipped_terminal_ids,
+                treat_empty_error_as_success,
+            )
+            .await?;
         }
         for derived in invocation_derived.values() {
             touch_invocation_upstream_account_last_activity_tx(
@@ -1102,12 +1230,14 @@ pub(crate) async fn flush_pending_batch_inner(
     {
         invalidate_prompt_cache_conversations_cache(cache).await;
     }
+    write_generation.fetch_add(1, Ordering::Release);
     Ok(deferred_batch)
 }
 
 pub(crate) async fn replay_live_invocation_hourly_rollups_until_tx(
     tx: &mut SqliteConnection,
     target_invocation_id: i64,
+    treat_empty_error_as_success: bool,
 ) -> Result<u64> {
     let mut total_updated = 0;
     loop {
@@ -1116,7 +1246,8 @@ pub(crate) async fn replay_live_invocation_hourly_rollups_until_tx(
         if cursor >= target_invocation_id {
             return Ok(total_updated);
         }
-        let updated = replay_live_invocation_hourly_rollups_tx(tx).await?;
+        let updated =
+            replay_live_invocation_hourly_rollups_tx(tx, treat_empty_error_as_success).await?;
         total_updated += updated;
         if updated == 0 {
             return Ok(total_updated);
@@ -1197,6 +1328,7 @@ mod tests {
                     compact_support_reason: Some("cached_probe".to_string()),
                 }),
             ],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await;
 
@@ -1268,6 +1400,7 @@ mod tests {
                 compact_support_status: Some("stale".to_string()),
                 compact_support_reason: Some("should_not_apply".to_string()),
             })],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await;
 
@@ -1326,6 +1459,7 @@ mod tests {
             pool.clone(),
             shutdown.clone(),
             Arc::new(Mutex::new(PromptCacheConversationsCacheState::default())),
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         );
 
         assert!(
@@ -1374,6 +1508,7 @@ mod tests {
             pool.clone(),
             shutdown.clone(),
             Arc::new(Mutex::new(PromptCacheConversationsCacheState::default())),
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         );
 
         assert!(
@@ -1440,6 +1575,7 @@ mod tests {
                     duration_ms: 125,
                 },
             )],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await;
 
@@ -1514,6 +1650,7 @@ mod tests {
                     terminal_overlay_key: None,
                 },
             )],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await;
 
@@ -1577,6 +1714,7 @@ mod tests {
                     record,
                 },
             )],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await;
 
@@ -1762,6 +1900,7 @@ mod tests {
             pool.clone(),
             CancellationToken::new(),
             Arc::new(Mutex::new(PromptCacheConversationsCacheState::default())),
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         );
         assert!(writer.enqueue(SqliteBatchWrite::TerminalInvocation(
             BatchedTerminalInvocationWrite {
@@ -1813,6 +1952,7 @@ mod tests {
                     selected_at: "2026-07-01T10:00:02Z".to_string(),
                 }),
             ],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         )
         .await;
 
@@ -1,6 +1,5 @@
 use super::*;
 
-pub(crate) const STATS_SUCCESS_LIKE_SQL: &str = "(LOWER(TRIM(COALESCE(status, ''))) IN ('success', 'completed', 'warning_success') OR (LOWER(TRIM(COALESCE(status, ''))) = 'http_200' AND TRIM(COALESCE(error_message, '')) = ''))";
 pub(crate) const STATS_TERMINAL_STATUS_SQL: &str =
     "(LOWER(TRIM(COALESCE(status, ''))) NOT IN ('running', 'pending'))";
 pub(crate) const INVOCATION_SUMMARY_ROLLUP_REPAIR_MARKER_LIVE_CURSOR_DATASET: &str =
@@ -17,7 +16,7 @@ pub(crate) enum SummaryWindow {
     PreviousFullDays(i64),
 }
 
-pub(crate) fn stats_success_failure_select_sql() -> String {
+pub(crate) fn stats_success_failure_select_sql(treat_empty_error_as_success: bool) -> String {
     format!(
         "COUNT(*) AS total_count, \
          COALESCE(SUM(CASE WHEN {success_like} AND {resolved_failure} = 'none' THEN 1 ELSE 0 END), 0) AS success_count, \
@@ -25,7 +24,11 @@ pub(crate) fn stats_success_failure_select_sql() -> String {
          COALESCE(SUM(cost), 0.0) AS total_cost, \
          COALESCE(SUM(total_tokens), 0) AS total_tokens, \
          COALESCE(SUM(CASE WHEN {terminal_status} AND {resolved_failure} IN ('service_failure', 'client_failure', 'client_abort') THEN COALESCE(cost, 0.0) ELSE 0.0 END), 0.0) AS non_success_cost",
-        success_like = STATS_SUCCESS_LIKE_SQL,
+        success_like = invocation_status_is_success_like_sql(
+            "status",
+            "error_message",
+            treat_empty_error_as_success
+        ),
         terminal_status = STATS_TERMINAL_STATUS_SQL,
         resolved_failure = crate::api::INVOCATION_RESOLVED_FAILURE_CLASS_SQL,
     )
@@ -310,8 +313,16 @@ impl BucketAggregate {
         Some(self.total_latency_sum_ms / self.total_latency_sample_count as f64)
     }
 
-    fn validated_success_ttfb_value(status: Option<&str>, ttfb_ms: Option<f64>) -> Option<f64> {
-        if !crate::maintenance::invocation_status_is_success_like(status, None) {
+    fn validated_success_ttfb_value(
+        status: Option<&str>,
+        ttfb_ms: Option<f64>,
+        treat_empty_error_as_success: bool,
+    ) -> Option<f64> {
+        if !crate::maintenance::invocation_status_is_success_like(
+            status,
+            None,
+            treat_empty_error_as_success,
+        ) {
             return None;
         }
         let value = ttfb_ms?;
@@ -341,15 +352,29 @@ impl BucketAggregate {
         add_approx_histogram_sample(&mut self.first_response_byte_total_histogram, value);
     }
 
-    pub(crate) fn record_ttfb_sample(&mut self, status: Option<&str>, ttfb_ms: Option<f64>) {
-        let Some(value) = Self::validated_success_ttfb_value(status, ttfb_ms) else {
+    pub(crate) fn record_ttfb_sample(
+        &mut self,
+        status: Option<&str>,
+        ttfb_ms: Option<f64>,
+        treat_empty_error_as_success: bool,
+    ) {
+        let Some(value) =
+            Self::validated_success_ttfb_value(status, ttfb_ms, treat_empty_error_as_success)
+        else {
             return;
         };
         self.record_first_byte_ttfb_value(value);
     }
 
-    pub(crate) fn record_exact_ttfb_sample(&mut self, status: Option<&str>, ttfb_ms: Option<f64>) {
-        let Some(value) = Self::validated_success_ttfb_value(status, ttfb_ms) else {
+    pub(crate) fn record_exact_ttfb_sample(
+        &mut self,
+        status: Option<&str>,
+        ttfb_ms: Option<f64>,
+        treat_empty_error_as_success: bool,
+    ) {
+        let Some(value) =
+            Self::validated_success_ttfb_value(status, ttfb_ms, treat_empty_error_as_success)
+        else {
             return;
         };
         self.record_first_byte_ttfb_value(value);
@@ -531,8 +556,11 @@ pub(crate) struct RangeWindow {
     pub(crate) duration: ChronoDuration,
 }
 
-pub(crate) fn resolve_range_window(spec: &str, tz: Tz) -> Result<RangeWindow> {
-    let now = Utc::now();
+pub(crate) fn resolve_range_window_at(
+    spec: &str,
+    tz: Tz,
+    now: DateTime<Utc>,
+) -> Result<RangeWindow> {
     if let Some((start, raw_end)) = named_range_bounds(spec, now, tz) {
         // Clamp to "now" so charts do not render future empty buckets.
         let mut end = now.min(raw_end);
@@ -673,11 +701,29 @@ pub(crate) fn start_of_next_month(start: DateTime<Utc>, tz: Tz) -> DateTime<Utc>
     local_naive_to_utc(naive, tz)
 }
 
+static NON_SINGLE_LOCAL_TIME_WARNED: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+fn warn_non_single_local_time_once(naive: NaiveDateTime, tz: Tz, kind: &'static str) {
+    if !NON_SINGLE_LOCAL_TIME_WARNED.swap(true, std::sync::atomic::Ordering::Relaxed) {
+        warn!(
+            %naive,
+            time_zone = %tz,
+            kind,
+            "local time does not map to a single instant; using the earliest valid instant"
+        );
+    }
+}
+
 pub(crate) fn local_naive_to_utc(naive: NaiveDateTime, tz: Tz) -> DateTime<Utc> {
     match tz.from_local_datetime(&naive) {
         LocalResult::Single(dt) => dt.with_timezone(&Utc),
-        LocalResult::Ambiguous(dt, _) => dt.with_timezone(&Utc),
+        LocalResult::Ambiguous(earliest, _) => {
+            warn_non_single_local_time_once(naive, tz, "ambiguous");
+            earliest.with_timezone(&Utc)
+        }
         LocalResult::None => {
+            warn_non_single_local_time_once(naive, tz, "nonexistent");
             // Handle nonexistent local times (e.g. DST spring-forward gaps) by
             // selecting the first valid local instant *after* the requested time.
             // This avoids silently interpreting a local timestamp as UTC.
@@ -777,12 +823,13 @@ pub(crate) async fn query_stats_row(
     pool: &Pool<Sqlite>,
     filter: StatsFilter,
     source_scope: InvocationSourceScope,
+    treat_empty_error_as_success: bool,
 ) -> Result<StatsRow> {
     match (filter, source_scope) {
         (StatsFilter::All, InvocationSourceScope::ProxyOnly) => {
             let query = format!(
                 "SELECT {} FROM codex_invocations WHERE source = ?1",
-                stats_success_failure_select_sql()
+                stats_success_failure_select_sql(treat_empty_error_as_success)
             );
             sqlx::query_as::<_, StatsRow>(&query)
                 .bind(SOURCE_PROXY)
@@ -793,7 +840,7 @@ pub(crate) async fn query_stats_row(
         (StatsFilter::All, InvocationSourceScope::All) => {
             let query = format!(
                 "SELECT {} FROM codex_invocations",
-                stats_success_failure_select_sql()
+                stats_success_failure_select_sql(treat_empty_error_as_success)
             );
             sqlx::query_as::<_, StatsRow>(&query)
                 .fetch_one(pool)
@@ -803,7 +850,7 @@ pub(crate) async fn query_stats_row(
         (StatsFilter::Since(start), InvocationSourceScope::ProxyOnly) => {
             let query = format!(
                 "SELECT {} FROM codex_invocations WHERE source = ?1 AND occurred_at >= ?2",
-                stats_success_failure_select_sql()
+                stats_success_failure_select_sql(treat_empty_error_as_success)
             );
             sqlx::query_as::<_, StatsRow>(&query)
                 .bind(SOURCE_PROXY)
@@ -815,7 +862,7 @@ pub(crate) async fn query_stats_row(
         (StatsFilter::Since(start), InvocationSourceScope::All) => {
             let query = format!(
                 "SELECT {} FROM codex_invocations WHERE occurred_at >= ?1",
-                stats_success_failure_select_sql()
+                stats_success_failure_select_sql(treat_empty_error_as_success)
             );
             sqlx::query_as::<_, StatsRow>(&query)
                 .bind(db_occurred_at_lower_bound(start))
@@ -826,7 +873,7 @@ pub(crate) async fn query_stats_row(
         (StatsFilter::Range(start, end), InvocationSourceScope::ProxyOnly) => {
             let query = format!(
                 "SELECT {} FROM codex_invocations WHERE source = ?1 AND occurred_at >= ?2 AND occurred_at < ?3",
-                stats_success_failure_select_sql()
+                stats_success_failure_select_sql(treat_empty_error_as_success)
             );
             sqlx::query_as::<_, StatsRow>(&query)
                 .bind(SOURCE_PROXY)
@@ -839,7 +886,7 @@ pub(crate) async fn query_stats_row(
         (StatsFilter::Range(start, end), InvocationSourceScope::All) => {
             let query = format!(
                 "SELECT {} FROM codex_invocations WHERE occurred_at >= ?1 AND occurred_at < ?2",
-                stats_success_failure_select_sql()
+                stats_success_failure_select_sql(treat_empty_error_as_success)
             );
             sqlx::query_as::<_, StatsRow>(&query)
                 .bind(db_occurred_at_lower_bound(start))
@@ -858,7 +905,7 @@ pub(crate) async fn query_stats_row(
                     LIMIT ?2 \
                 ) \
                 SELECT {} FROM recent",
-                stats_success_failure_select_sql()
+                stats_success_failure_select_sql(treat_empty_error_as_success)
             );
             sqlx::query_as::<_, StatsRow>(&query)
                 .bind(SOURCE_PROXY)
@@ -876,7 +923,7 @@ pub(crate) async fn query_stats_row(
                     LIMIT ?1 \
                 ) \
                 SELECT {} FROM recent",
-                stats_success_failure_select_sql()
+                stats_success_failure_select_sql(treat_empty_error_as_success)
             );
             sqlx::query_as::<_, StatsRow>(&query)
                 .bind(limit)
@@ -892,10 +939,13 @@ pub(crate) async fn query_upstream_account_stats_row(
     filter: StatsFilter,
     source_scope: InvocationSourceScope,
     upstream_account_id: i64,
+    treat_empty_error_as_success: bool,
 ) -> Result<StatsRow> {
     let account_filter = "CASE WHEN json_valid(payload) THEN CAST(json_extract(payload, '$.upstreamAccountId') AS INTEGER) END";
     let mut query = QueryBuilder::<Sqlite>::new("SELECT ");
-    query.push(stats_success_failure_select_sql());
+    query.push(stats_success_failure_select_sql(
+        treat_empty_error_as_success,
+    ));
     match filter {
         StatsFilter::All => {
             query.push(" FROM codex_invocations WHERE ");
@@ -993,6 +1043,8 @@ pub(crate) struct ArchivedInvocationFailureRow {
     pub(crate) source: String,
     pub(crate) status: Option<String>,
     pub(crate) error_message: Option<String>,
+    #[sqlx(default)]
+    pub(crate) http_status: Option<i64>,
     pub(crate) failure_kind: Option<String>,
     pub(crate) failure_class: Option<String>,
     pub(crate) is_actionable: Option<i64>,
@@ -1834,6 +1886,7 @@ pub(crate) fn merge_invocation_hourly_rollup_delta(
     target.total_tokens += delta.total_tokens;
     target.total_cost += delta.total_cost;
     target.non_success_cost += delta.non_success_cost;
+    target.refund_count += delta.refund_count;
     target.total_latency_sample_count += delta.total_latency_sample_count;
     target.total_latency_sum_ms += delta.total_latency_sum_ms;
     target.first_byte_sample_count += delta.first_byte_sample_count;
@@ -1930,6 +1983,7 @@ pub(crate) async fn load_materialized_invocation_archive_hourly_rollup_deltas_fo
     source_scope: InvocationSourceScope,
     range: Option<(DateTime<Utc>, DateTime<Utc>)>,
     bucket_sources: &HashSet<(i64, String)>,
+    treat_empty_error_as_success: bool,
 ) -> Result<(
     BTreeMap<(i64, String), InvocationHourlyRollupDelta>,
     HashSet<i64>,
@@ -1991,7 +2045,11 @@ pub(crate) async fn load_materialized_invocation_archive_hourly_rollup_deltas_fo
             if filtered_rows.is_empty() {
                 continue;
             }
-            accumulate_invocation_hourly_overall_rollups(&mut materialized, &filtered_rows)?;
+            accumulate_invocation_hourly_overall_rollups(
+                &mut materialized,
+                &filtered_rows,
+                treat_empty_error_as_success,
+            )?;
         }
 
         archive_pool.close().await;
@@ -2023,6 +2081,12 @@ pub(crate) fn subtract_nonnegative_f64(archive_value: f64, materialized_value: f
     (archive_value - materialized_value).max(0.0)
 }
 
+/// Costs are not monotonic: provider refunds land as negative `cost` rows, so a
+/// bucket's net cost (and its delta) may legitimately drop below zero.
+pub(crate) fn subtract_signed_cost(archive_value: f64, materialized_value: f64) -> f64 {
+    archive_value - materialized_value
+}
+
 pub(crate) async fn load_materialized_invocation_rollup_record(
     pool: &Pool<Sqlite>,
     bucket_start_epoch: i64,
@@ -2128,11 +2192,11 @@ pub(crate) fn build_invocation_hourly_rollup_delta_record(
             .map(|row| row.cache_input_tokens.max(0))
             .unwrap_or(0),
     );
-    let total_cost = subtract_nonnegative_f64(
+    let total_cost = subtract_signed_cost(
         archive_delta.total_cost,
         materialized_row.map(|row| row.total_cost).unwrap_or(0.0),
     );
-    let non_success_cost = subtract_nonnegative_f64(
+    let non_success_cost = subtract_signed_cost(
         archive_delta.non_success_cost,
         materialized_row
             .map(|row| row.non_success_cost)
@@ -2265,13 +2329,13 @@ pub(crate) fn build_materialized_pending_invocation_rollup_overlap_record(
             .map(|delta| delta.cache_input_tokens.max(0))
             .unwrap_or(0),
     );
-    let total_cost = subtract_nonnegative_f64(
+    let total_cost = subtract_signed_cost(
         materialized_row.total_cost,
         completed_archive_delta
             .map(|delta| delta.total_cost)
             .unwrap_or(0.0),
     );
-    let non_success_cost = subtract_nonnegative_f64(
+    let non_success_cost = subtract_signed_cost(
         materialized_row.non_success_cost,
         completed_archive_delta
             .map(|delta| delta.non_success_cost)
@@ -2378,12 +2442,14 @@ pub(crate) async fn query_unmaterialized_invocation_archive_hourly_rollup_deltas
     source_scope: InvocationSourceScope,
     range: Option<(DateTime<Utc>, DateTime<Utc>)>,
     exclude_invocation_ids: Option<&HashSet<i64>>,
+    treat_empty_error_as_success: bool,
 ) -> Result<Vec<InvocationHourlyRollupRecord>> {
     let pending_state = load_pending_invocation_archive_hourly_rollup_deltas(
         pool,
         source_scope,
         range,
         exclude_invocation_ids,
+        treat_empty_error_as_success,
     )
     .await?;
     let mut pending_bucket_sources = pending_state
@@ -2404,6 +2470,7 @@ pub(crate) async fn query_unmaterialized_invocation_archive_hourly_rollup_deltas
             source_scope,
             range,
             &materialized_bucket_sources,
+            treat_empty_error_as_success,
         )
         .await?;
     let mut known_materialized_overall = materialized_overall;
@@ -2449,6 +2516,7 @@ pub(crate) async fn load_pending_invocation_archive_hourly_rollup_deltas(
     source_scope: InvocationSourceScope,
     range: Option<(DateTime<Utc>, DateTime<Utc>)>,
     exclude_invocation_ids: Option<&HashSet<i64>>,
+    treat_empty_error_as_success: bool,
 ) -> Result<PendingInvocationArchiveOverallState> {
     let archive_rows = load_invocation_archives_missing_rollup_target(
         pool,
@@ -2500,6 +2568,7 @@ pub(crate) async fn load_pending_invocation_archive_hourly_rollup_deltas(
                     &mut pending_state.unmaterialized
                 },
                 &filtered_rows,
+                treat_empty_error_as_success,
             )?;
         }
 
@@ -2541,7 +2610,11 @@ pub(crate) fn archived_failure_rollup_key(
         row.source.clone(),
         classification.failure_class.as_str().to_string(),
         classification.is_actionable as i64,
-        categorize_error(row.error_message.as_deref().unwrap_or_default()),
+        categorize_error_with_http_status(
+            row.error_message.as_deref().unwrap_or_default(),
+            row.http_status,
+        )
+        .code(),
     )))
 }
 
@@ -2968,6 +3041,7 @@ pub(crate) async fn query_unmaterialized_invocation_archive_totals(
     source_scope: InvocationSourceScope,
     range: Option<(DateTime<Utc>, DateTime<Utc>)>,
     exclude_invocation_ids: Option<&HashSet<i64>>,
+    treat_empty_error_as_success: bool,
 ) -> Result<StatsTotals> {
     let mut totals = StatsTotals::default();
     for row in query_unmaterialized_invocation_archive_hourly_rollup_deltas(
@@ -2975,6 +3049,7 @@ pub(crate) async fn query_unmaterialized_invocation_archive_totals(
         source_scope,
         range,
         exclude_invocation_ids,
+        treat_empty_error_as_success,
     )
     .await?
     {
@@ -3153,8 +3228,9 @@ pub(crate) async fn query_completed_invocation_archive_non_success_usage(
 pub(crate) fn add_account_invocation_row_to_stats_delta(
     entry: &mut UpstreamAccountStatsDelta,
     row: &InvocationHourlySourceRecord,
+    treat_empty_error_as_success: bool,
 ) {
-    accumulate_upstream_account_stats_delta(entry, row);
+    accumulate_upstream_account_stats_delta(entry, row, treat_empty_error_as_success);
 }
 
 pub(crate) async fn query_unmaterialized_upstream_account_archive_hourly_rollup_deltas(
@@ -3164,6 +3240,7 @@ pub(crate) async fn query_unmaterialized_upstream_account_archive_hourly_rollup_
     range: Option<(DateTime<Utc>, DateTime<Utc>)>,
     exclude_invocation_ids: Option<&HashSet<i64>>,
     upstream_account_id: i64,
+    treat_empty_error_as_success: bool,
 ) -> Result<Vec<UpstreamAccountStatsRollupRecord>> {
     let archive_rows =
         load_invocation_archives_missing_effective_rollup_target(pool, rollup_target, range)
@@ -3202,7 +3279,11 @@ pub(crate) async fn query_unmaterialized_upstream_account_archive_hourly_rollup_
                 }
                 let bucket_start_epoch = summary_rollup_bucket_start_epoch(&row.occurred_at)?;
                 let entry = archive_deltas.entry(bucket_start_epoch).or_default();
-                add_account_invocation_row_to_stats_delta(entry, &row);
+                add_account_invocation_row_to_stats_delta(
+                    entry,
+                    &row,
+                    treat_empty_error_as_success,
+                );
             }
         }
 
@@ -3251,6 +3332,7 @@ pub(crate) async fn query_unmaterialized_upstream_account_archive_totals(
     range: Option<(DateTime<Utc>, DateTime<Utc>)>,
     exclude_invocation_ids: Option<&HashSet<i64>>,
     upstream_account_id: i64,
+    treat_empty_error_as_success: bool,
 ) -> Result<StatsTotals> {
     let mut totals = StatsTotals::default();
     for row in query_unmaterialized_upstream_account_archive_hourly_rollup_deltas(
@@ -3260,6 +3342,7 @@ pub(crate) async fn query_unmaterialized_upstream_account_archive_totals(
         range,
         exclude_invocation_ids,
         upstream_account_id,
+        treat_empty_error_as_success,
     )
     .await?
     {
@@ -3407,9 +3490,15 @@ pub(crate) async fn load_failure_rows_from_archive_pool(
     end: DateTime<Utc>,
     source_scope: InvocationSourceScope,
 ) -> Result<Vec<ArchivedInvocationFailureRow>> {
-    let mut query = QueryBuilder::<Sqlite>::new(
-        "SELECT id, occurred_at, source, status, error_message, failure_kind, failure_class, is_actionable FROM codex_invocations WHERE occurred_at >= ",
-    );
+    let http_status_sql =
+        if sqlite_table_has_column(archive_pool, "codex_invocations", "http_status").await? {
+            "http_status"
+        } else {
+            "NULL AS http_status"
+        };
+    let mut query = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT id, occurred_at, source, status, error_message, {http_status_sql}, failure_kind, failure_class, is_actionable FROM codex_invocations WHERE occurred_at >= "
+    ));
     query.push_bind(db_occurred_at_lower_bound(start));
     query
         .push(" AND occurred_at < ")
@@ -3605,6 +3694,7 @@ pub(crate) async fn load_materialized_failure_rollup_row_counts_for_keys(
     Ok((counts, unreadable_bucket_start_epochs))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn rebuild_invocation_summary_rollups_from_archive_batch(
     tx: &mut SqliteConnection,
     archive_row: &ArchiveBatchPathRow,
@@ -3613,6 +3703,7 @@ pub(crate) async fn rebuild_invocation_summary_rollups_from_archive_batch(
     cleared_rollup_buckets: &mut ClearedSummaryRollupBuckets,
     targets: &[&str],
     replace_existing_rollups: bool,
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     if targets.is_empty() {
         return Ok(());
@@ -3673,7 +3764,8 @@ pub(crate) async fn rebuild_invocation_summary_rollups_from_archive_batch(
                     .await?;
             }
         }
-        upsert_invocation_hourly_rollups_tx(tx, &rows, targets).await?;
+        upsert_invocation_hourly_rollups_tx(tx, &rows, targets, treat_empty_error_as_success)
+            .await?;
     }
 
     archive_pool.close().await;
@@ -3768,6 +3860,7 @@ pub(crate) async fn load_live_invocation_summary_rows_for_cleared_buckets_up_to_
             total_tokens,
             cost,
             error_message,
+            http_status,
             failure_kind,
             failure_class,
             is_actionable,
@@ -3817,6 +3910,7 @@ pub(crate) async fn rebuild_invocation_summary_rollups_from_live_rows(
     seen_ids: &mut HashSet<i64>,
     targets: &[&str],
     start_after_id: i64,
+    treat_empty_error_as_success: bool,
 ) -> Result<i64> {
     let mut cursor_id = start_after_id;
     loop {
@@ -3835,7 +3929,8 @@ pub(crate) async fn rebuild_invocation_summary_rollups_from_live_rows(
         if rows.is_empty() {
             continue;
         }
-        upsert_invocation_hourly_rollups_tx(tx, &rows, targets).await?;
+        upsert_invocation_hourly_rollups_tx(tx, &rows, targets, treat_empty_error_as_success)
+            .await?;
     }
     Ok(cursor_id)
 }
@@ -3931,7 +4026,10 @@ pub(crate) async fn invocation_summary_repair_live_cursor_state_tx(
     ))
 }
 
-pub(crate) async fn repair_invocation_summary_rollups(pool: &Pool<Sqlite>) -> Result<()> {
+pub(crate) async fn repair_invocation_summary_rollups(
+    pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
+) -> Result<()> {
     let (repair_marker_done, repair_live_cursor_exists, shared_live_cursor, repair_live_cursor) =
         invocation_summary_repair_live_cursor_state(pool).await?;
     if repair_marker_done && repair_live_cursor_exists && repair_live_cursor >= shared_live_cursor {
@@ -3992,6 +4090,7 @@ pub(crate) async fn repair_invocation_summary_rollups(pool: &Pool<Sqlite>) -> Re
             &mut cleared_rollup_buckets,
             &INVOCATION_SUMMARY_ROLLUP_TARGETS,
             preserve_materialized_archives,
+            treat_empty_error_as_success,
         )
         .await?;
     }
@@ -4008,6 +4107,7 @@ pub(crate) async fn repair_invocation_summary_rollups(pool: &Pool<Sqlite>) -> Re
             tx.as_mut(),
             &restored_live_rows,
             &INVOCATION_SUMMARY_ROLLUP_TARGETS,
+            treat_empty_error_as_success,
         )
         .await?;
     }
@@ -4021,6 +4121,7 @@ pub(crate) async fn repair_invocation_summary_rollups(pool: &Pool<Sqlite>) -> Re
         } else {
             0
         },
+        treat_empty_error_as_success,
     )
     .await?;
     save_hourly_rollup_live_progress_tx(
@@ -4041,6 +4142,7 @@ pub(crate) async fn repair_invocation_summary_rollups(pool: &Pool<Sqlite>) -> Re
 
 pub(crate) async fn backfill_missing_invocation_summary_archive_rollups(
     pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
     let archive_rows = load_invocation_archives_missing_summary_rollup_markers(pool).await?;
     if archive_rows.is_empty() {
@@ -4092,6 +4194,7 @@ pub(crate) async fn backfill_missing_invocation_summary_archive_rollups(
             &mut cleared_rollup_buckets,
             &targets,
             true,
+            treat_empty_error_as_success,
         )
         .await?;
     }
@@ -4109,6 +4212,7 @@ pub(crate) async fn backfill_missing_invocation_summary_archive_rollups(
             tx.as_mut(),
             &restored_overall_live_rows,
             &[HOURLY_ROLLUP_TARGET_INVOCATIONS],
+            treat_empty_error_as_success,
         )
         .await?;
     }
@@ -4126,6 +4230,7 @@ pub(crate) async fn backfill_missing_invocation_summary_archive_rollups(
             tx.as_mut(),
             &restored_failure_live_rows,
             &[HOURLY_ROLLUP_TARGET_INVOCATION_FAILURES],
+            treat_empty_error_as_success,
         )
         .await?;
     }
@@ -4133,7 +4238,10 @@ pub(crate) async fn backfill_missing_invocation_summary_archive_rollups(
     Ok(())
 }
 
-pub(crate) async fn ensure_invocation_summary_rollups_ready(pool: &Pool<Sqlite>) -> Result<()> {
+pub(crate) async fn ensure_invocation_summary_rollups_ready(
+    pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
+) -> Result<()> {
     if load_completed_invocation_archive_paths(pool)
         .await?
         .is_empty()
@@ -4141,15 +4249,16 @@ pub(crate) async fn ensure_invocation_summary_rollups_ready(pool: &Pool<Sqlite>)
         return Ok(());
     }
 
-    repair_invocation_summary_rollups(pool).await?;
-    backfill_missing_invocation_summary_archive_rollups(pool).await?;
+    repair_invocation_summary_rollups(pool, treat_empty_error_as_success).await?;
+    backfill_missing_invocation_summary_archive_rollups(pool, treat_empty_error_as_success).await?;
     Ok(())
 }
 
 pub(crate) async fn ensure_invocation_summary_rollups_ready_best_effort(
     pool: &Pool<Sqlite>,
+    treat_empty_error_as_success: bool,
 ) -> Result<()> {
-    match ensure_invocation_summary_rollups_ready(pool).await {
+    match ensure_invocation_summary_rollups_ready(pool, treat_empty_error_as_success).await {
         Ok(()) => Ok(()),
         Err(err) if is_missing_invocation_summary_archive_error(&err) => {
             warn!(
@@ -4177,6 +4286,7 @@ pub(crate) struct AllTimeRollupTotals {
 pub(crate) async fn query_invocation_all_time_rollup_totals(
     pool: &Pool<Sqlite>,
     source_scope: InvocationSourceScope,
+    treat_empty_error_as_success: bool,
 ) -> Result<AllTimeRollupTotals> {
     let non_success_cost_expr =
         if sqlite_table_has_column(pool, "invocation_rollup_hourly", "non_success_cost").await? {
@@ -4219,11 +4329,11 @@ pub(crate) async fn query_invocation_all_time_rollup_totals(
     let tail_query = match source_scope {
         InvocationSourceScope::ProxyOnly => format!(
             "SELECT {} FROM codex_invocations WHERE id > ?1 AND source = ?2",
-            stats_success_failure_select_sql()
+            stats_success_failure_select_sql(treat_empty_error_as_success)
         ),
         InvocationSourceScope::All => format!(
             "SELECT {} FROM codex_invocations WHERE id > ?1",
-            stats_success_failure_select_sql()
+            stats_success_failure_select_sql(treat_empty_error_as_success)
         ),
     };
     let tail = match source_scope {
@@ -4249,10 +4359,159 @@ pub(crate) async fn query_invocation_all_time_rollup_totals(
     })
 }
 
+/// Projects the attached `ARCHIVE_DB_PATH` database onto the live invocation columns.
+///
+/// The archive must contain a `codex_invocations` table with at least `occurred_at`
+/// (Asia/Shanghai naive `YYYY-MM-DD HH:MM:SS` or RFC3339), `source`, `status`,
+/// `error_message`, `total_tokens` and `cost`; columns introduced later are read as NULL.
+/// RFC3339 values are converted to the Asia/Shanghai naive form so they compare
+/// correctly against the live window bounds.
+pub(crate) fn archive_db_invocations_sql() -> String {
+    format!(
+        "SELECT CASE WHEN instr(occurred_at, 'T') > 0 \
+            THEN datetime(occurred_at, '+8 hours') ELSE occurred_at END AS occurred_at, \
+         source, status, error_message, total_tokens, cost, \
+         NULL AS failure_class, NULL AS failure_kind, NULL AS payload \
+         FROM {ARCHIVE_DB_SCHEMA}.codex_invocations"
+    )
+}
+
+/// Probed once after the pool attaches `ARCHIVE_DB_PATH`; the answer is kept in
+/// `AppState::archive_db_attached` so stats queries never re-check it.
+pub(crate) async fn archive_db_invocations_available(pool: &Pool<Sqlite>) -> Result<bool> {
+    let attached =
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM pragma_database_list WHERE name = ?1")
+            .bind(ARCHIVE_DB_SCHEMA)
+            .fetch_one(pool)
+            .await?;
+    if attached == 0 {
+        return Ok(false);
+    }
+    let has_table = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM {ARCHIVE_DB_SCHEMA}.sqlite_master WHERE type = 'table' AND name = 'codex_invocations'"
+    ))
+    .fetch_one(pool)
+    .await?;
+    Ok(has_table > 0)
+}
+
+pub(crate) fn push_archive_db_occurred_at_filters(
+    query: &mut QueryBuilder<'_, Sqlite>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    source_scope: InvocationSourceScope,
+) {
+    query.push(" WHERE 1 = 1");
+    if let Some(start) = start {
+        query
+            .push(" AND occurred_at >= ")
+            .push_bind(db_occurred_at_lower_bound(start));
+    }
+    if let Some(end) = end {
+        query
+            .push(" AND occurred_at < ")
+            .push_bind(db_occurred_at_upper_bound(end));
+    }
+    if source_scope == InvocationSourceScope::ProxyOnly {
+        query.push(" AND source = ").push_bind(SOURCE_PROXY);
+    }
+}
+
+pub(crate) async fn query_archive_db_totals(
+    pool: &Pool<Sqlite>,
+    filter: &StatsFilter,
+    source_scope: InvocationSourceScope,
+    treat_empty_error_as_success: bool,
+) -> Result<Option<StatsTotals>> {
+    let (start, end) = match filter {
+        StatsFilter::All => (None, None),
+        StatsFilter::Since(start) => (Some(*start), None),
+        StatsFilter::Range(start, end) => (Some(*start), Some(*end)),
+        // "Most recent N" windows only ever describe live traffic.
+        StatsFilter::RecentLimit(_) => return Ok(None),
+    };
+    let mut query = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT {} FROM ({})",
+        stats_success_failure_select_sql(treat_empty_error_as_success),
+        archive_db_invocations_sql()
+    ));
+    push_archive_db_occurred_at_filters(&mut query, start, end, source_scope);
+    let row = query.build_query_as::<StatsRow>().fetch_one(pool).await?;
+    Ok(Some(StatsTotals::from(row)))
+}
+
+pub(crate) async fn query_archive_db_hourly_rollup_records(
+    pool: &Pool<Sqlite>,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+    source_scope: InvocationSourceScope,
+    treat_empty_error_as_success: bool,
+) -> Result<Vec<InvocationHourlyRollupRecord>> {
+    let mut query = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT \
+            (CAST(strftime('%s', occurred_at || '+08:00') AS INTEGER) / 3600) * 3600 AS bucket_start_epoch, \
+            {}, \
+            0 AS cache_input_tokens, \
+            0 AS total_latency_sample_count, \
+            0.0 AS total_latency_sum_ms, \
+            0 AS first_byte_sample_count, \
+            0.0 AS first_byte_sum_ms, \
+            0.0 AS first_byte_max_ms, \
+            '' AS first_byte_histogram, \
+            0 AS first_response_byte_total_sample_count, \
+            0.0 AS first_response_byte_total_sum_ms, \
+            0.0 AS first_response_byte_total_max_ms, \
+            '' AS first_response_byte_total_histogram \
+         FROM ({})",
+        stats_success_failure_select_sql(treat_empty_error_as_success),
+        archive_db_invocations_sql()
+    ));
+    push_archive_db_occurred_at_filters(
+        &mut query,
+        Some(range_start),
+        Some(range_end),
+        source_scope,
+    );
+    query.push(" GROUP BY bucket_start_epoch ORDER BY bucket_start_epoch ASC");
+    query
+        .build_query_as::<InvocationHourlyRollupRecord>()
+        .fetch_all(pool)
+        .await
+        .map_err(Into::into)
+}
+
 pub(crate) async fn query_invocation_totals(
     pool: &Pool<Sqlite>,
     filter: StatsFilter,
     source_scope: InvocationSourceScope,
+    archive_db_attached: bool,
+    treat_empty_error_as_success: bool,
+) -> Result<StatsTotals> {
+    let live = query_live_invocation_totals(
+        pool,
+        filter.clone(),
+        source_scope,
+        treat_empty_error_as_success,
+    )
+    .await?;
+    if !archive_db_attached {
+        return Ok(live);
+    }
+    Ok(
+        match query_archive_db_totals(pool, &filter, source_scope, treat_empty_error_as_success)
+            .await?
+        {
+            Some(archived) => live.add(archived),
+            None => live,
+        },
+    )
+}
+
+pub(crate) async fn query_live_invocation_totals(
+    pool: &Pool<Sqlite>,
+    filter: StatsFilter,
+    source_scope: InvocationSourceScope,
+    treat_empty_error_as_success: bool,
 ) -> Result<StatsTotals> {
     if matches!(filter, StatsFilter::All) {
         if load_completed_invocation_archive_paths(pool)
@@ -4260,7 +4519,13 @@ pub(crate) async fn query_invocation_totals(
             .is_empty()
         {
             return Ok(StatsTotals::from(
-                query_stats_row(pool, StatsFilter::All, source_scope).await?,
+                query_stats_row(
+                    pool,
+                    StatsFilter::All,
+                    source_scope,
+                    treat_empty_error_as_success,
+                )
+                .await?,
             ));
         }
 
@@ -4268,20 +4533,26 @@ pub(crate) async fn query_invocation_totals(
         // Background startup / follow-up maintenance is responsible for rebuilding stale archived
         // hourly rollups and summary replay markers; requests reuse the current materialized
         // rollups plus any still-unmaterialized archive batches instead of writing through here.
-        let all_time = query_invocation_all_time_rollup_totals(pool, source_scope).await?;
+        let all_time = query_invocation_all_time_rollup_totals(
+            pool,
+            source_scope,
+            treat_empty_error_as_success,
+        )
+        .await?;
         return Ok(all_time.totals.add(
             query_unmaterialized_invocation_archive_totals(
                 pool,
                 source_scope,
                 None,
                 Some(&all_time.live_tail_ids),
+                treat_empty_error_as_success,
             )
             .await?,
         ));
     }
 
     Ok(StatsTotals::from(
-        query_stats_row(pool, filter, source_scope).await?,
+        query_stats_row(pool, filter, source_scope, treat_empty_error_as_success).await?,
     ))
 }
 
@@ -4440,8 +4711,92 @@ pub(crate) async fn query_combined_totals(
     pool: &Pool<Sqlite>,
     filter: StatsFilter,
     source_scope: InvocationSourceScope,
+    archive_db_attached: bool,
+    treat_empty_error_as_success: bool,
 ) -> Result<StatsTotals> {
-    query_invocation_totals(pool, filter, source_scope).await
+    query_invocation_totals(
+        pool,
+        filter,
+        source_scope,
+        archive_db_attached,
+        treat_empty_error_as_success,
+    )
+    .await
+}
+
+/// Provider credits arrive as negative-cost rows; `SUM(cost)` already nets them out,
+/// so this only counts them, over the same rows the all-time totals cover. Archived
+/// rows come from the materialized `refund_count` rollup column so requests never
+/// inflate archive batches; batches still awaiting materialization are picked up once
+/// background maintenance replays them.
+pub(crate) async fn query_all_time_refund_invocation_count(
+    pool: &Pool<Sqlite>,
+    source_scope: InvocationSourceScope,
+    archive_db_attached: bool,
+) -> Result<i64> {
+    let mut count = if load_completed_invocation_archive_paths(pool)
+        .await?
+        .is_empty()
+    {
+        count_live_refund_invocations_after_id(pool, source_scope, 0).await?
+    } else {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "SELECT COALESCE(SUM(refund_count), 0) FROM invocation_rollup_hourly WHERE 1 = 1",
+        );
+        if source_scope == InvocationSourceScope::ProxyOnly {
+            query.push(" AND source = ").push_bind(SOURCE_PROXY);
+        }
+        let materialized = query
+            .build_query_scalar::<i64>()
+            .fetch_one(pool)
+            .await
+            .context("failed to sum materialized refund invocations")?;
+        let live_progress_cursor =
+            load_hourly_rollup_live_progress(pool, HOURLY_ROLLUP_DATASET_INVOCATIONS).await?;
+        let repair_live_cursor = load_hourly_rollup_live_progress(
+            pool,
+            INVOCATION_SUMMARY_ROLLUP_REPAIR_MARKER_LIVE_CURSOR_DATASET,
+        )
+        .await?;
+        let tail_cursor = live_progress_cursor.max(repair_live_cursor).max(0);
+        materialized
+            + count_live_refund_invocations_after_id(pool, source_scope, tail_cursor).await?
+    };
+
+    if archive_db_attached {
+        let mut query = QueryBuilder::<Sqlite>::new(format!(
+            "SELECT COUNT(*) FROM ({})",
+            archive_db_invocations_sql()
+        ));
+        push_archive_db_occurred_at_filters(&mut query, None, None, source_scope);
+        query.push(" AND cost < 0");
+        count += query
+            .build_query_scalar::<i64>()
+            .fetch_one(pool)
+            .await
+            .context("failed to count archived refund invocations")?;
+    }
+
+    Ok(count)
+}
+
+async fn count_live_refund_invocations_after_id(
+    pool: &Pool<Sqlite>,
+    source_scope: InvocationSourceScope,
+    after_id: i64,
+) -> Result<i64> {
+    let mut query = QueryBuilder::<Sqlite>::new(
+        "SELECT COUNT(*) FROM codex_invocations WHERE cost < 0 AND id > ",
+    );
+    query.push_bind(after_id);
+    if source_scope == InvocationSourceScope::ProxyOnly {
+        query.push(" AND source = ").push_bind(SOURCE_PROXY);
+    }
+    query
+        .build_query_scalar::<i64>()
+        .fetch_one(pool)
+        .await
+        .context("failed to count refund invocations")
 }
 
 pub(crate) async fn resolve_default_source_scope(
@@ -4471,7 +4826,13 @@ impl IntoResponse for ApiError {
             ApiError::BadRequest(err) => (StatusCode::BAD_REQUEST, err),
             ApiError::Internal(err) => (StatusCode::INTERNAL_SERVER_ERROR, err),
         };
-        let message = format!("{err}");
+        let message = match current_request_id() {
+            // Server faults get the correlation id in the body so users can quote it.
+            Some(request_id) if status.is_server_error() => {
+                format!("{err} (request id: {request_id})")
+            }
+            _ => format!("{err}"),
+        };
         (status, message).into_response()
     }
 }
@@ -4503,14 +4864,20 @@ pub(crate) fn parse_to_utc_datetime(s: &str) -> Option<DateTime<Utc>> {
         return Some(dt.with_timezone(&Utc));
     }
     if let Ok(naive) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
-        if let Some(loc) = Shanghai.from_local_datetime(&naive).single() {
-            return Some(loc.with_timezone(&Utc));
-        }
-        return Some(Utc.from_utc_datetime(&naive));
+        return Some(local_naive_to_utc(naive, Shanghai));
     }
     None
 }
 
+/// Canonical `occurred_at` storage form: Asia/Shanghai wall clock as
+/// `%Y-%m-%d %H:%M:%S`, which every range query compares against. Values that
+/// cannot be parsed are kept verbatim.
+pub(crate) fn canonical_occurred_at(raw: &str) -> String {
+    parse_to_utc_datetime(raw.trim())
+        .map(|dt| format_naive(dt.with_timezone(&Shanghai).naive_local()))
+        .unwrap_or_else(|| raw.to_string())
+}
+
 #[allow(clippy::ptr_arg)]
 pub(crate) fn serialize_local_naive_to_utc_iso<S>(
     value: &String,
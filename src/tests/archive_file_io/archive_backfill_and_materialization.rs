@@ -1550,6 +1550,7 @@ async fn replay_invocation_archives_into_hourly_rollups_respects_caller_elapsed_
         Some(1),
         Some(Duration::from_millis(1)),
         0,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("replay bounded invocation archives");
@@ -2022,7 +2023,7 @@ async fn materialize_historical_rollups_marks_replayed_batches_as_materialized_a
     .await;
     seed_forward_proxy_attempt_at(&pool, "proxy-replayed", old_attempt, true).await;
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live hourly rollups before retention");
     let retention = run_data_retention_maintenance(&pool, &config, Some(false), None)
@@ -2131,7 +2132,7 @@ async fn materialize_historical_rollups_replays_usage_breakdown_when_account_tar
     )
     .await;
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live hourly rollups before retention");
     let retention = run_data_retention_maintenance(&pool, &config, Some(false), None)
@@ -2308,7 +2309,7 @@ async fn bootstrap_hourly_rollups_reopens_materialized_batches_missing_usage_bre
     )
     .await;
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live hourly rollups before retention");
     let retention = run_data_retention_maintenance(&pool, &config, Some(false), None)
@@ -2350,7 +2351,7 @@ async fn bootstrap_hourly_rollups_reopens_materialized_batches_missing_usage_bre
         HistoricalRollupBackfillAlertLevel::Critical
     );
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("bootstrap should reopen missing usage breakdown backfill");
 
@@ -2477,7 +2478,7 @@ async fn bootstrap_hourly_rollups_repairs_pre_upgrade_breakdown_rows_before_sync
     .await
     .expect("seed pre-upgrade shared invocation cursor");
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("bootstrap should repair old breakdown rows before syncing new live rows");
 
@@ -2538,7 +2539,7 @@ async fn bootstrap_hourly_rollups_repairs_legacy_account_replay_markers_when_bre
     )
     .await;
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live hourly rollups before retention");
     let retention = run_data_retention_maintenance(&pool, &config, Some(false), None)
@@ -2638,7 +2639,7 @@ async fn bootstrap_hourly_rollups_repairs_legacy_account_replay_markers_when_bre
     .await
     .expect("drop legacy account replay markers");
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("bootstrap should repair legacy account replay markers");
 
@@ -2688,7 +2689,7 @@ async fn bootstrap_hourly_rollups_reopens_partially_populated_usage_breakdown_hi
         .await;
     }
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live hourly rollups before retention");
     let retention = run_data_retention_maintenance(&pool, &config, Some(false), None)
@@ -2766,7 +2767,7 @@ async fn bootstrap_hourly_rollups_reopens_partially_populated_usage_breakdown_hi
     .await
     .expect("seed second invocation materialized bucket");
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("bootstrap should reopen partial usage breakdown history");
 
@@ -2845,7 +2846,7 @@ async fn bootstrap_hourly_rollups_reopens_same_bucket_partial_usage_breakdown_gr
     )
     .await;
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live hourly rollups before same-bucket repair");
     let retention = run_data_retention_maintenance(&pool, &config, Some(false), None)
@@ -2900,7 +2901,7 @@ async fn bootstrap_hourly_rollups_reopens_same_bucket_partial_usage_breakdown_gr
     .await
     .expect("seed same-bucket invocation materialized marker");
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("bootstrap should reopen same-bucket partial breakdown groups");
 
@@ -2959,7 +2960,7 @@ async fn usage_breakdown_repair_preserves_retained_live_rows_in_reopened_archive
         Some(0.42),
     )
     .await;
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live breakdown rollup before repair");
 
@@ -3018,9 +3019,12 @@ async fn usage_breakdown_repair_preserves_retained_live_rows_in_reopened_archive
     .await
     .expect("seed materialized archive batch overlapping retained live bucket");
 
-    let touched = repair_materialized_invocation_archive_usage_breakdown_backfill_state(&pool)
-        .await
-        .expect("repair missing usage breakdown replay state");
+    let touched = repair_materialized_invocation_archive_usage_breakdown_backfill_state(
+        &pool,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("repair missing usage breakdown replay state");
     assert_eq!(touched, 1);
 
     let retained_live_rows: i64 = sqlx::query_scalar(
@@ -3395,9 +3399,13 @@ async fn recompute_invocation_hourly_rollups_ignores_archive_manifests_for_live_
         .execute(tx.as_mut())
         .await
         .expect("update live invocation before recompute");
-    recompute_invocation_hourly_rollups_for_ids_tx(tx.as_mut(), &[live_id])
-        .await
-        .expect("recompute invocation hourly rollups without archive dependency");
+    recompute_invocation_hourly_rollups_for_ids_tx(
+        tx.as_mut(),
+        &[live_id],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("recompute invocation hourly rollups without archive dependency");
     tx.commit().await.expect("commit recompute tx");
 
     let after = sqlx::query_as::<_, StatsRow>(
@@ -4984,7 +4992,7 @@ async fn bootstrap_hourly_rollups_keeps_retention_materialized_totals_unchanged_
         .expect("seed materialized archive batch");
     }
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("replay hourly rollups after retention");
     let invocation_total_after: i64 = sqlx::query_scalar(
@@ -5059,7 +5067,7 @@ async fn bootstrap_hourly_rollups_ignores_missing_replay_markers() {
     )
     .await;
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live hourly rollups before retention");
     let summary = run_data_retention_maintenance(&pool, &config, Some(false), None)
@@ -5067,7 +5075,7 @@ async fn bootstrap_hourly_rollups_ignores_missing_replay_markers() {
         .expect("run retention before bootstrap replay");
     assert_eq!(summary.invocation_rows_archived, 1);
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("bootstrap hourly rollups after retention");
 
@@ -5094,7 +5102,7 @@ async fn bootstrap_hourly_rollups_ignores_missing_replay_markers() {
     .await
     .expect("delete one invocation replay marker");
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("bootstrap should replay only the missing target");
 
@@ -5159,7 +5167,7 @@ async fn ensure_schema_backfills_first_response_byte_totals_for_legacy_invocatio
     .await
     .expect("seed staged latency fields");
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live hourly rollups before retention");
     let summary = run_data_retention_maintenance(&pool, &config, Some(false), None)
@@ -5621,7 +5629,7 @@ async fn ensure_schema_backfill_deduplicates_detail_prune_archives() {
     .await
     .expect("seed staged latency fields");
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed live hourly rollups before detail prune");
     run_data_retention_maintenance(&pool, &config, Some(false), None)
@@ -24,7 +24,7 @@ async fn bootstrap_hourly_rollups_ignores_missing_invocation_archive_batch() {
     .await
     .expect("insert missing codex_invocations archive manifest");
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("missing codex_invocations archive batch should not affect bootstrap");
 
@@ -54,7 +54,7 @@ async fn bootstrap_hourly_rollups_ignores_missing_forward_proxy_archive_batch()
     .await
     .expect("insert missing forward_proxy_attempts archive manifest");
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("missing forward_proxy_attempts archive batch should not affect bootstrap");
 
@@ -1406,6 +1406,32 @@ async fn drain_runtime_after_shutdown_waits_for_summary_quota_broadcast_workers(
     );
 }
 
+#[tokio::test]
+async fn drain_runtime_after_shutdown_abandons_background_tasks_after_timeout() {
+    let mut config = test_config();
+    config.shutdown_timeout = Duration::from_millis(50);
+    let state = test_state_from_config(config, false).await;
+    let finished_task = tokio::spawn(async {});
+    let hung_task = tokio::spawn(std::future::pending::<()>());
+
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        drain_runtime_after_shutdown(
+            state,
+            None,
+            Some(finished_task),
+            None,
+            None,
+            None,
+            Some(hung_task),
+            None,
+        ),
+    )
+    .await
+    .expect("runtime drain should stop waiting once the shutdown timeout elapses")
+    .expect("runtime drain should still finish cleanly");
+}
+
 #[tokio::test]
 async fn run_runtime_until_shutdown_exits_when_shutdown_token_is_cancelled_directly() {
     let state = test_state_from_config(test_config(), false).await;
@@ -1726,10 +1752,13 @@ async fn finish_summary_quota_broadcast_idle_flushes_pending_tail_when_shutdown_
             broadcast_running: state.proxy_summary_quota_broadcast_running.as_ref(),
             shutdown: &state.shutdown,
             pool: &state.pool,
+            clock: state.clock.as_ref(),
+            archive_db_attached: state.archive_db_attached,
             hourly_rollup_sync_lock: state.hourly_rollup_sync_lock.as_ref(),
             broadcaster: &state.broadcaster,
             broadcast_state_cache: state.broadcast_state_cache.as_ref(),
             invocation_max_days: state.config.invocation_max_days,
+            treat_empty_error_as_success: state.config.treat_empty_error_as_success,
             invoke_id: "idle-shutdown-tail",
         },
         1,
@@ -1766,7 +1795,9 @@ async fn finish_summary_quota_broadcast_idle_flushes_pending_tail_when_shutdown_
             BroadcastPayload::Records { .. }
             | BroadcastPayload::Version { .. }
             | BroadcastPayload::PoolAttempts { .. }
-            | BroadcastPayload::DashboardActivityLive { .. } => {}
+            | BroadcastPayload::DashboardActivityLive { .. }
+            | BroadcastPayload::Alert { .. }
+            | BroadcastPayload::Deleted { .. } => {}
         }
 
         if saw_quota && summary_windows.len() == expected_summary_windows {
@@ -1830,7 +1861,9 @@ async fn persist_and_broadcast_proxy_capture_flushes_follow_up_when_shutdown_beg
             }
             BroadcastPayload::Version { .. }
             | BroadcastPayload::PoolAttempts { .. }
-            | BroadcastPayload::DashboardActivityLive { .. } => {}
+            | BroadcastPayload::DashboardActivityLive { .. }
+            | BroadcastPayload::Alert { .. }
+            | BroadcastPayload::Deleted { .. } => {}
         }
 
         if saw_record && saw_quota && summary_windows.len() == expected_summary_windows {
@@ -1902,7 +1935,9 @@ async fn persist_and_broadcast_runtime_terminal_schedules_follow_up_after_flush(
             }
             BroadcastPayload::Version { .. }
             | BroadcastPayload::PoolAttempts { .. }
-            | BroadcastPayload::DashboardActivityLive { .. } => {}
+            | BroadcastPayload::DashboardActivityLive { .. }
+            | BroadcastPayload::Alert { .. }
+            | BroadcastPayload::Deleted { .. } => {}
         }
 
         if saw_record && saw_quota && summary_windows.len() == expected_summary_windows {
@@ -1997,6 +2032,7 @@ async fn broadcast_recovered_proxy_invocations_skips_follow_up_without_subscribe
     let recovered = recover_proxy_invocations_with_scope(
         &state.pool,
         ProxyInvocationRecoveryScope::Selectors(&selectors),
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("recover invocation for runtime broadcast");
@@ -2533,6 +2569,7 @@ async fn advance_pool_upstream_request_attempt_phase_buffers_progress_without_im
             compact_support_status: None,
             compact_support_reason: None,
         })],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await;
 
@@ -2846,9 +2883,10 @@ async fn recover_orphaned_pool_upstream_request_attempts_keeps_startup_sequence_
     .await
     .expect("advance attempt into sending-request");
 
-    let recovered_invocations = recover_orphaned_proxy_invocations(&state.pool)
-        .await
-        .expect("recover orphaned invocations first");
+    let recovered_invocations =
+        recover_orphaned_proxy_invocations(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("recover orphaned invocations first");
     assert_eq!(recovered_invocations, 0);
 
     let recovered_attempts = recover_orphaned_pool_upstream_request_attempts(&state.pool)
@@ -3097,9 +3135,10 @@ async fn recover_orphaned_proxy_invocations_marks_running_rows_interrupted() {
     .await
     .expect("insert non-proxy running invocation");
 
-    let affected = recover_orphaned_proxy_invocations(&state.pool)
-        .await
-        .expect("recover orphaned invocations");
+    let affected =
+        recover_orphaned_proxy_invocations(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("recover orphaned invocations");
     assert_eq!(affected, 1);
 
     let recovered = sqlx::query_as::<
@@ -5299,6 +5338,7 @@ async fn recover_proxy_invocations_with_selector_batches_handles_large_selector_
     let recovered = recover_proxy_invocations_with_scope(
         &state.pool,
         ProxyInvocationRecoveryScope::Selectors(&selectors),
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("recover large selector batch");
@@ -6435,6 +6475,7 @@ async fn recover_stale_pool_upstream_request_attempt_candidates_rechecks_phase_b
             compact_support_status: None,
             compact_support_reason: None,
         })],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await;
     let finished_at = shanghai_now_string();
@@ -6555,6 +6596,7 @@ async fn recover_stale_pool_upstream_request_attempt_candidates_rechecks_attempt
             compact_support_status: None,
             compact_support_reason: None,
         })],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await;
     sqlx::query(
@@ -6692,6 +6734,7 @@ async fn recover_stale_pool_upstream_request_attempt_candidates_rechecks_invocat
             compact_support_status: None,
             compact_support_reason: None,
         })],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await;
     sqlx::query(
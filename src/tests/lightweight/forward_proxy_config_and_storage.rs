@@ -752,6 +752,7 @@ async fn persist_proxy_capture_runtime_record_preserves_warning_success_diagnost
             invoke_id: "proxy-test-downstream-closed-runtime".to_string(),
             occurred_at: "2026-04-10 00:00:00".to_string(),
             model: Some("gpt-5.4".to_string()),
+            model_canonical: None,
             usage: ParsedUsage::default(),
             cost: None,
             cost_breakdown: None,
@@ -776,6 +777,7 @@ async fn persist_proxy_capture_runtime_record_preserves_warning_success_diagnost
                 t_persist_ms: 0.0,
             },
         },
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("persist runtime record")
@@ -838,6 +840,7 @@ async fn persist_proxy_capture_record_preserves_warning_success_diagnostics() {
             invoke_id: "proxy-test-downstream-closed-terminal".to_string(),
             occurred_at: "2026-04-10 00:00:00".to_string(),
             model: Some("gpt-5.4".to_string()),
+            model_canonical: None,
             usage: ParsedUsage::default(),
             cost: None,
             cost_breakdown: None,
@@ -862,6 +865,7 @@ async fn persist_proxy_capture_record_preserves_warning_success_diagnostics() {
                 t_persist_ms: 0.0,
             },
         },
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("persist terminal record")
@@ -922,6 +926,7 @@ async fn persist_proxy_capture_runtime_record_keeps_response_size_when_preview_d
             invoke_id: "proxy-test-runtime-response-body-disabled".to_string(),
             occurred_at: "2026-04-10 00:00:00".to_string(),
             model: Some("gpt-5.4".to_string()),
+            model_canonical: None,
             usage: ParsedUsage::default(),
             cost: None,
             cost_breakdown: None,
@@ -951,6 +956,7 @@ async fn persist_proxy_capture_runtime_record_keeps_response_size_when_preview_d
                 t_persist_ms: 0.0,
             },
         },
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("persist runtime record")
@@ -996,6 +1002,7 @@ async fn persist_proxy_capture_record_omits_response_preview_and_raw_path_when_d
             invoke_id: "proxy-test-response-body-disabled".to_string(),
             occurred_at: "2026-04-10 00:00:00".to_string(),
             model: Some("gpt-5.4".to_string()),
+            model_canonical: None,
             usage: ParsedUsage::default(),
             cost: None,
             cost_breakdown: None,
@@ -1025,6 +1032,7 @@ async fn persist_proxy_capture_record_omits_response_preview_and_raw_path_when_d
                 t_persist_ms: 0.0,
             },
         },
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("persist terminal record")
@@ -1435,6 +1443,30 @@ fn startup_pending_attempt_recovery_skips_all_retention_run_once_modes() {
     assert!(!should_recover_pending_pool_attempts_on_startup(&cli));
 }
 
+#[test]
+fn version_flag_prints_backend_and_frontend_versions_for_static_dir() {
+    let cli = CliArgs::try_parse_from([
+        "codex-vibe-monitor",
+        "--version",
+        "--static-dir",
+        "/srv/web",
+    ])
+    .expect("--version should parse as a regular flag");
+    assert!(cli.version);
+    assert_eq!(resolve_static_dir(&cli), Some(PathBuf::from("/srv/web")));
+
+    let temp_dir = make_temp_test_dir("version-flag-static-dir");
+    fs::write(temp_dir.join("version.json"), r#"{"version":"9.8.7"}"#)
+        .expect("write frontend version.json");
+    let rendered = render_versions_json(Some(&temp_dir)).expect("render version json");
+    let value: Value = serde_json::from_str(&rendered).expect("version output should be json");
+    let (backend, frontend) = detect_versions(Some(&temp_dir));
+    assert!(frontend.starts_with("9.8.7"), "{frontend}");
+    assert_eq!(value["frontend"], frontend);
+    assert_eq!(value["backend"], backend);
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
 #[test]
 fn app_config_from_sources_rejects_legacy_database_path_env() {
     let _guard = APP_CONFIG_ENV_LOCK.blocking_lock();
@@ -1510,6 +1542,8 @@ fn app_config_from_sources_reads_renamed_public_envs() {
         (ENV_PROXY_RAW_COMPRESSION, Some("none")),
         (ENV_PROXY_RAW_IMMEDIATE_GZIP_BYTES, Some("2097152")),
         (ENV_PROXY_RAW_HOT_SECS, Some("1234")),
+        (ENV_COST_SPIKE_FACTOR, Some("4.5")),
+        (ENV_UPSTREAM_DANGER_ACCEPT_INVALID_CERTS, Some("true")),
         (ENV_FORWARD_PROXY_ALGO, Some("v2")),
     ]);
     let _env = EnvVarGuard::set(&cases);
@@ -1565,6 +1599,141 @@ fn app_config_from_sources_reads_renamed_public_envs() {
     assert_eq!(config.proxy_raw_compression, RawCompressionCodec::None);
     assert_eq!(config.proxy_raw_immediate_gzip_bytes, Some(2 * 1024 * 1024));
     assert_eq!(config.proxy_raw_hot_secs, 1234);
+    assert_eq!(config.cost_spike_factor, 4.5);
+    assert!(config.upstream_danger_accept_invalid_certs);
+}
+
+#[test]
+fn app_config_from_sources_rejects_non_positive_cost_spike_factor() {
+    let _guard = APP_CONFIG_ENV_LOCK.blocking_lock();
+    let _env = EnvVarGuard::set(&[(ENV_COST_SPIKE_FACTOR, Some("0"))]);
+
+    let err = AppConfig::from_sources(&CliArgs::default())
+        .expect_err("zero cost spike factor should be rejected");
+    assert_eq!(
+        err.to_string(),
+        format!("{ENV_COST_SPIKE_FACTOR} must be a positive number")
+    );
+}
+
+#[test]
+fn app_config_from_sources_parses_sqlite_tuning_and_rejects_unknown_synchronous() {
+    let _guard = APP_CONFIG_ENV_LOCK.blocking_lock();
+    {
+        let _env = EnvVarGuard::set(&[
+            (ENV_SQLITE_SYNCHRONOUS, Some("Normal")),
+            (ENV_SQLITE_CACHE_SIZE_KB, Some("65536")),
+        ]);
+        let config =
+            AppConfig::from_sources(&CliArgs::default()).expect("sqlite tuning should parse");
+        assert_eq!(config.sqlite_synchronous, SqliteSynchronousMode::Normal);
+        assert_eq!(config.sqlite_cache_size_kb, Some(65_536));
+    }
+
+    let _env = EnvVarGuard::set(&[(ENV_SQLITE_SYNCHRONOUS, Some("extra"))]);
+    let err = AppConfig::from_sources(&CliArgs::default())
+        .expect_err("unknown synchronous level should be rejected");
+    assert_eq!(
+        err.to_string(),
+        format!("invalid {ENV_SQLITE_SYNCHRONOUS} value: extra; expected off|normal|full")
+    );
+}
+
+#[test]
+fn read_static_frontend_version_returns_none_for_missing_or_malformed_files() {
+    let temp_dir = make_temp_test_dir("frontend-version-json");
+    let path = temp_dir.join("version.json");
+    assert_eq!(read_static_frontend_version(&path), None);
+
+    fs::write(&path, r#"{"version":"1.4.0"}"#).expect("write version.json");
+    assert_eq!(
+        read_static_frontend_version(&path).as_deref(),
+        Some("1.4.0")
+    );
+
+    fs::write(&path, r#"{"build":"abc"}"#).expect("write version.json without version");
+    assert_eq!(read_static_frontend_version(&path), None);
+
+    fs::write(&path, "not json").expect("write malformed version.json");
+    assert_eq!(read_static_frontend_version(&path), None);
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn app_config_from_sources_requires_tls_cert_and_key_together() {
+    let _guard = APP_CONFIG_ENV_LOCK.blocking_lock();
+    let _env = EnvVarGuard::set(&[
+        (ENV_TLS_CERT_PATH, Some("/etc/monitor/cert.pem")),
+        (ENV_TLS_KEY_PATH, None),
+    ]);
+
+    let err = AppConfig::from_sources(&CliArgs::default())
+        .expect_err("a TLS certificate without a key should be rejected");
+    assert_eq!(
+        err.to_string(),
+        format!("{ENV_TLS_CERT_PATH} and {ENV_TLS_KEY_PATH} must be set together")
+    );
+}
+
+#[test]
+fn parse_api_prefix_normalizes_and_rejects_relative_paths() {
+    assert_eq!(parse_api_prefix(None).expect("default prefix"), "/api");
+    assert_eq!(parse_api_prefix(Some("  ")).expect("blank prefix"), "/api");
+    assert_eq!(
+        parse_api_prefix(Some("/v1/api/")).expect("trailing slash"),
+        "/v1/api"
+    );
+    for invalid in ["v1/api", "/"] {
+        let err = parse_api_prefix(Some(invalid)).expect_err("invalid prefix should be rejected");
+        assert!(
+            err.to_string().starts_with("invalid API_PREFIX"),
+            "unexpected error: {err}"
+        );
+    }
+}
+
+#[test]
+fn load_tls_acceptor_rejects_missing_or_malformed_pem_files() {
+    let temp_dir = make_temp_test_dir("tls-acceptor");
+    let cert_path = temp_dir.join("cert.pem");
+    let key_path = temp_dir.join("key.pem");
+
+    let err = load_tls_acceptor(&cert_path, &key_path)
+        .err()
+        .expect("a missing certificate file should fail");
+    assert!(
+        err.to_string().starts_with(&format!(
+            "failed to read TLS certificate {}",
+            cert_path.display()
+        )),
+        "unexpected error: {err}"
+    );
+
+    fs::write(&cert_path, "not a certificate").expect("write malformed cert");
+    fs::write(&key_path, "not a key").expect("write malformed key");
+    let err = load_tls_acceptor(&cert_path, &key_path)
+        .err()
+        .expect("a PEM file without certificates should fail");
+    assert_eq!(
+        err.to_string(),
+        format!("no TLS certificate found in {}", cert_path.display())
+    );
+
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[test]
+fn app_config_from_sources_rejects_out_of_range_cost_precision() {
+    let _guard = APP_CONFIG_ENV_LOCK.blocking_lock();
+    let _env = EnvVarGuard::set(&[(ENV_COST_PRECISION, Some("13"))]);
+
+    let err = AppConfig::from_sources(&CliArgs::default())
+        .expect_err("cost precision above the cap should be rejected");
+    assert_eq!(
+        err.to_string(),
+        format!("{ENV_COST_PRECISION} must be between 0 and {MAX_COST_PRECISION}")
+    );
 }
 
 #[test]
@@ -1885,6 +2054,9 @@ pub(crate) fn test_config() -> AppConfig {
         max_parallel_polls: 2,
         shared_connection_parallelism: 1,
         http_bind: "127.0.0.1:0".parse().expect("valid socket address"),
+        tls_cert_path: None,
+        tls_key_path: None,
+        api_prefix: DEFAULT_API_PREFIX.to_string(),
         cors_allowed_origins: Vec::new(),
         list_limit_max: 100,
         user_agent: "codex-test".to_string(),
@@ -1896,6 +2068,17 @@ pub(crate) fn test_config() -> AppConfig {
         retention_batch_rows: DEFAULT_RETENTION_BATCH_ROWS,
         retention_catchup_budget: Duration::from_secs(DEFAULT_RETENTION_CATCHUP_BUDGET_SECS),
         archive_dir: PathBuf::from("target/archive-tests"),
+        archive_db_path: None,
+        sqlite_synchronous: DEFAULT_SQLITE_SYNCHRONOUS,
+        sqlite_cache_size_kb: None,
+        max_timeseries_points: DEFAULT_MAX_TIMESERIES_POINTS,
+        error_top_max: DEFAULT_ERROR_TOP_MAX,
+        other_errors_limit_max: DEFAULT_OTHER_ERRORS_LIMIT_MAX,
+        max_sse_clients: None,
+        record_log_path: None,
+        record_log_max_bytes: DEFAULT_RECORD_LOG_MAX_BYTES,
+        log_buffer_lines: DEFAULT_LOG_BUFFER_LINES,
+        shutdown_timeout: Duration::from_secs(DEFAULT_SHUTDOWN_TIMEOUT_SECS),
         codex_invocation_archive_layout: DEFAULT_CODEX_INVOCATION_ARCHIVE_LAYOUT,
         codex_invocation_archive_segment_granularity:
             DEFAULT_CODEX_INVOCATION_ARCHIVE_SEGMENT_GRANULARITY,
@@ -1903,12 +2086,21 @@ pub(crate) fn test_config() -> AppConfig {
         invocation_success_full_days: DEFAULT_INVOCATION_SUCCESS_FULL_DAYS,
         invocation_max_days: DEFAULT_INVOCATION_MAX_DAYS,
         invocation_archive_ttl_days: DEFAULT_INVOCATION_ARCHIVE_TTL_DAYS,
+        raw_response_retention_days: None,
         forward_proxy_attempts_retention_days: DEFAULT_FORWARD_PROXY_ATTEMPTS_RETENTION_DAYS,
         pool_upstream_request_attempts_retention_days:
             DEFAULT_POOL_UPSTREAM_REQUEST_ATTEMPTS_RETENTION_DAYS,
         pool_upstream_request_attempts_archive_ttl_days:
             DEFAULT_POOL_UPSTREAM_REQUEST_ATTEMPTS_ARCHIVE_TTL_DAYS,
         quota_snapshot_full_days: DEFAULT_QUOTA_SNAPSHOT_FULL_DAYS,
+        cost_spike_factor: DEFAULT_COST_SPIKE_FACTOR,
+        cache_discount: DEFAULT_CACHE_DISCOUNT,
+        monthly_budget: None,
+        model_aliases: BTreeMap::new(),
+        model_allowlist: None,
+        cost_precision: DEFAULT_COST_PRECISION,
+        upstream_danger_accept_invalid_certs: DEFAULT_UPSTREAM_DANGER_ACCEPT_INVALID_CERTS,
+        treat_empty_error_as_success: DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         upstream_accounts_oauth_client_id: DEFAULT_UPSTREAM_ACCOUNTS_OAUTH_CLIENT_ID.to_string(),
         upstream_accounts_oauth_issuer: Url::parse(DEFAULT_UPSTREAM_ACCOUNTS_OAUTH_ISSUER)
             .expect("valid oauth issuer"),
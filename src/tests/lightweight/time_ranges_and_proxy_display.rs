@@ -531,6 +531,64 @@ fn named_range_this_week_end_respects_dst() {
     );
 }
 
+#[test]
+fn canonical_occurred_at_collapses_mixed_formats_to_shanghai_wall_clock() {
+    for raw in [
+        "2026-07-01 10:00:00",
+        " 2026-07-01 10:00:00 ",
+        "2026-07-01T02:00:00Z",
+        "2026-07-01T10:00:00+08:00",
+        "2026-06-30T21:00:00-05:00",
+    ] {
+        assert_eq!(canonical_occurred_at(raw), "2026-07-01 10:00:00", "{raw}");
+    }
+    assert_eq!(canonical_occurred_at("not-a-time"), "not-a-time");
+}
+
+#[test]
+fn align_reporting_bucket_epoch_uses_local_wall_clock_for_half_hour_offsets() {
+    // Asia/Kolkata is UTC+05:30, so epoch-multiple alignment would land on :30.
+    let tz: Tz = "Asia/Kolkata".parse().expect("valid kolkata tz");
+    let timestamp = Utc
+        .with_ymd_and_hms(2026, 6, 22, 10, 47, 12)
+        .single()
+        .expect("valid dt");
+    assert_ne!(
+        align_bucket_epoch(timestamp.timestamp(), 3_600, 0) % 3_600,
+        align_reporting_bucket_epoch(timestamp.timestamp(), 3_600, tz).expect("align 1h") % 3_600,
+    );
+
+    for (bucket_seconds, expected_local_hour) in [(3_600, 16), (6 * 3_600, 12)] {
+        let bucket_start_epoch =
+            align_reporting_bucket_epoch(timestamp.timestamp(), bucket_seconds, tz)
+                .expect("align bucket");
+        let bucket_start_local = Utc
+            .timestamp_opt(bucket_start_epoch, 0)
+            .single()
+            .expect("valid bucket start")
+            .with_timezone(&tz);
+        // 10:47:12Z is 16:17:12 local.
+        assert_eq!(bucket_start_local.hour(), expected_local_hour);
+        assert_eq!(bucket_start_local.minute(), 0);
+        assert_eq!(bucket_start_local.second(), 0);
+
+        let next_local = Utc
+            .timestamp_opt(
+                next_reporting_bucket_epoch(bucket_start_epoch, bucket_seconds, tz)
+                    .expect("next bucket"),
+                0,
+            )
+            .single()
+            .expect("valid next bucket")
+            .with_timezone(&tz);
+        assert_eq!(
+            (next_local - bucket_start_local).num_seconds(),
+            bucket_seconds
+        );
+        assert_eq!(next_local.minute(), 0);
+    }
+}
+
 #[test]
 fn next_reporting_bucket_epoch_respects_dst_for_multi_hour_buckets() {
     let tz = chrono_tz::America::New_York;
@@ -561,6 +619,34 @@ fn next_reporting_bucket_epoch_respects_dst_for_multi_hour_buckets() {
     assert_eq!(bucket_end_local.minute(), 0);
 }
 
+#[test]
+fn local_naive_to_utc_resolves_dst_gap_and_overlap_without_utc_fallback() {
+    let tz = chrono_tz::America::New_York;
+    let local = |raw: &str| {
+        NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S").expect("valid local time")
+    };
+    let utc = |raw: &str| {
+        DateTime::parse_from_rfc3339(raw)
+            .expect("valid utc time")
+            .with_timezone(&Utc)
+    };
+
+    // 02:30 does not exist on spring-forward day; the first valid instant is 03:00 EDT.
+    assert_eq!(
+        local_naive_to_utc(local("2026-03-08 02:30:00"), tz),
+        utc("2026-03-08T07:00:00Z")
+    );
+    // 01:30 occurs twice on fall-back day; the earlier (EDT) instant wins.
+    assert_eq!(
+        local_naive_to_utc(local("2026-11-01 01:30:00"), tz),
+        utc("2026-11-01T05:30:00Z")
+    );
+    assert_eq!(
+        local_naive_to_utc(local("2026-03-08 01:30:00"), tz),
+        utc("2026-03-08T06:30:00Z")
+    );
+}
+
 #[test]
 fn parse_summary_window_accepts_yesterday_calendar_window() {
     let window = parse_summary_window(
@@ -2346,3 +2432,72 @@ async fn forward_proxy_settings_does_not_reprobe_when_subscription_is_unchanged(
     subscription_handle.abort();
     proxy_handle.abort();
 }
+
+#[test]
+fn categorize_error_exposes_stable_code_and_legacy_label() {
+    let rate_limited = categorize_error("HTTP 429 Too Many Requests");
+    assert_eq!(rate_limited, ErrorCategory::RateLimited);
+    assert_eq!(rate_limited.code(), "rate_limited");
+    assert_eq!(rate_limited.label(), "too_many_requests");
+
+    let server = categorize_error("upstream responded with HTTP 503");
+    assert_eq!(server, ErrorCategory::HttpServer(503));
+    assert_eq!(
+        serde_json::to_value(&server).expect("serialize category"),
+        json!({ "code": "http_503", "label": "HTTP 503" })
+    );
+
+    assert_eq!(categorize_error("HTTP 429").label(), "http_429");
+    assert_eq!(categorize_error("   ").code(), "other");
+
+    for label in [
+        "HTTP 503",
+        "http_429",
+        "too_many_requests",
+        "usage_limit_reached",
+        "invalid_request_error",
+        "request failed connection reset",
+        "Other",
+    ] {
+        assert_eq!(ErrorCategory::from_label(label).label(), label);
+    }
+
+    // Codes keep the JSON error type and normalized message, so they round-trip
+    // without guessing; a one-word message is not mistaken for a JSON error.
+    for category in [
+        ErrorCategory::HttpServer(503),
+        ErrorCategory::HttpClient(429),
+        ErrorCategory::UsageNotIncluded,
+        ErrorCategory::JsonError("invalid_request_error".to_string()),
+        ErrorCategory::Message("timeout".to_string()),
+        ErrorCategory::Other,
+    ] {
+        assert_eq!(
+            ErrorCategory::from_code(&category.code()),
+            Some(category.clone())
+        );
+        assert_eq!(ErrorCategory::from_persisted(&category.code()), category);
+    }
+    assert_eq!(
+        ErrorCategory::JsonError("invalid_request_error".to_string()).code(),
+        "upstream_type:invalid_request_error"
+    );
+    // An upstream type spelled like a fixed code stays in its own namespace.
+    let upstream_rate_limited = ErrorCategory::JsonError("rate_limited".to_string());
+    assert_ne!(
+        upstream_rate_limited.code(),
+        ErrorCategory::RateLimited.code()
+    );
+    assert_eq!(
+        ErrorCategory::from_persisted(&upstream_rate_limited.code()).label(),
+        "rate_limited"
+    );
+    assert_ne!(
+        ErrorCategory::JsonError("a".to_string()).code(),
+        ErrorCategory::JsonError("b".to_string()).code()
+    );
+    assert_eq!(
+        ErrorCategory::from_persisted("HTTP 503"),
+        ErrorCategory::HttpServer(503)
+    );
+}
@@ -664,6 +664,217 @@ async fn ensure_schema_creates_prompt_cache_conversation_operation_events_table(
     assert!(columns.iter().any(|column| column == "sticky_after_json"));
 }
 
+#[tokio::test]
+async fn ensure_schema_records_version_and_refuses_newer_database() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("connect isolated in-memory sqlite");
+
+    ensure_schema(&pool)
+        .await
+        .expect("schema should initialize");
+    ensure_schema(&pool)
+        .await
+        .expect("schema should reopen at the same version");
+    assert_eq!(
+        load_applied_schema_version(&pool)
+            .await
+            .expect("load applied schema version"),
+        CURRENT_SCHEMA_VERSION
+    );
+    let recorded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations")
+        .fetch_one(&pool)
+        .await
+        .expect("count schema migrations");
+    assert_eq!(recorded, CURRENT_SCHEMA_VERSION);
+
+    sqlx::query("INSERT INTO schema_migrations (version) VALUES (?1)")
+        .bind(CURRENT_SCHEMA_VERSION + 1)
+        .execute(&pool)
+        .await
+        .expect("simulate newer binary migration");
+    let err = ensure_schema(&pool)
+        .await
+        .expect_err("newer schema version should be rejected");
+    assert!(
+        err.to_string()
+            .contains("refusing to start after a downgrade"),
+        "unexpected error: {err:#}"
+    );
+}
+
+#[tokio::test]
+async fn ensure_schema_migration_normalizes_stored_occurred_at_formats() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("connect isolated in-memory sqlite");
+    ensure_schema(&pool)
+        .await
+        .expect("schema should initialize");
+
+    for (invoke_id, occurred_at) in [
+        ("occurred-at-rfc3339-utc", "2026-07-01T02:00:00Z"),
+        ("occurred-at-rfc3339-offset", "2026-07-01T11:30:00+08:00"),
+        ("occurred-at-canonical", "2026-07-01 12:00:00"),
+        ("occurred-at-collision", "2026-07-01 13:00:00"),
+        ("occurred-at-collision", "2026-07-01T05:00:00Z"),
+    ] {
+        sqlx::query(
+            "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, raw_response) VALUES (?1, ?2, 'proxy', 'success', '')",
+        )
+        .bind(invoke_id)
+        .bind(occurred_at)
+        .execute(&pool)
+        .await
+        .expect("seed invocation");
+    }
+    sqlx::query("DELETE FROM schema_migrations WHERE version >= 2")
+        .execute(&pool)
+        .await
+        .expect("rewind schema version");
+
+    ensure_schema(&pool)
+        .await
+        .expect("schema should rerun the occurred_at migration");
+
+    let rows: Vec<(String, String)> =
+        sqlx::query_as("SELECT invoke_id, occurred_at FROM codex_invocations ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .expect("load normalized invocations");
+    let occurred_at = rows
+        .iter()
+        .map(|(_, occurred_at)| occurred_at.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        occurred_at,
+        vec![
+            "2026-07-01 10:00:00",
+            "2026-07-01 11:30:00",
+            "2026-07-01 12:00:00",
+            "2026-07-01 13:00:00",
+            // Collides with the canonical row above, so it is left untouched.
+            "2026-07-01T05:00:00Z",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn ensure_schema_migration_backfills_invocation_http_status() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("connect isolated in-memory sqlite");
+    ensure_schema(&pool)
+        .await
+        .expect("schema should initialize");
+
+    for (invoke_id, payload, error_message) in [
+        (
+            "http-status-payload",
+            Some(r#"{"statusCode":429}"#),
+            Some("HTTP 500 boom"),
+        ),
+        (
+            "http-status-message",
+            Some("{}"),
+            Some("upstream HTTP 502: bad gateway"),
+        ),
+        ("http-status-none", None, Some("stream closed early")),
+    ] {
+        sqlx::query(
+            "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, payload, error_message, raw_response) VALUES (?1, '2026-07-01 12:00:00', 'proxy', 'failed', ?2, ?3, '')",
+        )
+        .bind(invoke_id)
+        .bind(payload)
+        .bind(error_message)
+        .execute(&pool)
+        .await
+        .expect("seed invocation");
+    }
+    sqlx::query("DELETE FROM schema_migrations WHERE version >= 3")
+        .execute(&pool)
+        .await
+        .expect("rewind schema version");
+
+    ensure_schema(&pool)
+        .await
+        .expect("schema should rerun the http_status backfill");
+
+    let rows: Vec<(String, Option<i64>)> =
+        sqlx::query_as("SELECT invoke_id, http_status FROM codex_invocations ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .expect("load backfilled invocations");
+    assert_eq!(
+        rows,
+        vec![
+            ("http-status-payload".to_string(), Some(429)),
+            ("http-status-message".to_string(), Some(502)),
+            ("http-status-none".to_string(), None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn ensure_schema_migration_rewrites_failure_rollup_labels_to_codes() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("connect isolated in-memory sqlite");
+    ensure_schema(&pool)
+        .await
+        .expect("schema should initialize");
+
+    for (error_category, failure_count) in [
+        ("too_many_requests", 2_i64),
+        ("rate_limited", 3),
+        ("upstream_response_failed", 1),
+        ("stream closed early", 1),
+        ("HTTP 502", 4),
+    ] {
+        sqlx::query(
+            "INSERT INTO invocation_failure_rollup_hourly (bucket_start_epoch, source, failure_class, is_actionable, error_category, failure_count) VALUES (0, 'proxy', 'service_failure', 1, ?1, ?2)",
+        )
+        .bind(error_category)
+        .bind(failure_count)
+        .execute(&pool)
+        .await
+        .expect("seed failure rollup");
+    }
+    sqlx::query("DELETE FROM schema_migrations WHERE version >= 4")
+        .execute(&pool)
+        .await
+        .expect("rewind schema version");
+
+    ensure_schema(&pool)
+        .await
+        .expect("schema should rerun the failure rollup migration");
+
+    let rows: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT error_category, failure_count FROM invocation_failure_rollup_hourly ORDER BY error_category",
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("load rewritten failure rollups");
+    assert_eq!(
+        rows,
+        vec![
+            ("http_502".to_string(), 4),
+            ("message:stream closed early".to_string(), 1),
+            ("rate_limited".to_string(), 2),
+            ("upstream_type:rate_limited".to_string(), 3),
+            ("upstream_type:upstream_response_failed".to_string(), 1),
+        ]
+    );
+}
+
 #[tokio::test]
 async fn ensure_schema_creates_sticky_affinity_generation_and_routing_source_storage() {
     let pool = SqlitePool::connect("sqlite::memory:?cache=shared")
@@ -861,6 +1072,178 @@ async fn list_invocations_filters_by_sticky_key_and_upstream_account_id() {
     assert_eq!(response.records[0].upstream_account_id, Some(7));
 }
 
+#[tokio::test]
+async fn invocation_label_requires_api_key_and_filters_list() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    for invoke_id in ["label-incident", "label-untagged"] {
+        sqlx::query(
+            "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, raw_response) \
+             VALUES (?1, '2026-03-11 10:00:00', ?2, 'success', '{}')",
+        )
+        .bind(invoke_id)
+        .bind(SOURCE_PROXY)
+        .execute(&state.pool)
+        .await
+        .expect("insert label invocation");
+    }
+    let id: i64 =
+        sqlx::query_scalar("SELECT id FROM codex_invocations WHERE invoke_id = 'label-incident'")
+            .fetch_one(&state.pool)
+            .await
+            .expect("load label invocation id");
+    let request = || InvocationLabelRequest {
+        label: Some("  prod incident ".to_string()),
+    };
+
+    let err = update_invocation_label(
+        State(state.clone()),
+        HeaderMap::new(),
+        axum::extract::Path(id),
+        Json(request()),
+    )
+    .await
+    .expect_err("label update without api key should be rejected");
+    assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+
+    let created = create_external_api_key_inner(
+        state.clone(),
+        CreateExternalApiKeyRequest {
+            name: "Labels".to_string(),
+        },
+    )
+    .await
+    .expect("create external api key");
+    let created = serde_json::to_value(&created).expect("serialize external api key");
+    let secret = created["secret"].as_str().expect("secret present");
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::AUTHORIZATION,
+        header::HeaderValue::from_str(&format!("Bearer {secret}")).expect("valid auth header"),
+    );
+    let response = update_invocation_label(
+        State(state.clone()),
+        headers.clone(),
+        axum::extract::Path(id),
+        Json(request()),
+    )
+    .await
+    .expect("label update should succeed");
+    assert_eq!(response.status(), StatusCode::OK);
+    let missing = update_invocation_label(
+        State(state.clone()),
+        headers,
+        axum::extract::Path(id + 100),
+        Json(request()),
+    )
+    .await
+    .expect("missing label target should respond");
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+    let Json(response) = list_invocations(
+        State(state.clone()),
+        Query(ListQuery {
+            label: Some("Prod Incident".to_string()),
+            page: Some(1),
+            page_size: Some(20),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("label filter should succeed");
+    assert_eq!(response.total, 1);
+    assert_eq!(response.records[0].invoke_id, "label-incident");
+    assert_eq!(response.records[0].label.as_deref(), Some("prod incident"));
+    let body = serde_json::to_value(&response).expect("serialize label list response");
+    assert_eq!(body["records"][0]["label"], json!("prod incident"));
+
+    let detail = load_persisted_api_invocation_by_id(&state.pool, id)
+        .await
+        .expect("load labelled invocation")
+        .expect("labelled invocation exists");
+    assert_eq!(detail.label.as_deref(), Some("prod incident"));
+}
+
+#[tokio::test]
+async fn list_invocations_filters_by_payload_json_key_and_value() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    for (invoke_id, payload) in [
+        (
+            "payload-mini",
+            json!({ "model": "gpt-5-mini", "usage": { "tier": "flex" } }),
+        ),
+        ("payload-full", json!({ "model": "gpt-5" })),
+        ("payload-empty", json!({})),
+    ] {
+        sqlx::query(
+            "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, payload, raw_response) \
+             VALUES (?1, '2026-03-11 10:00:00', ?2, 'success', ?3, '{}')",
+        )
+        .bind(invoke_id)
+        .bind(SOURCE_PROXY)
+        .bind(payload.to_string())
+        .execute(&state.pool)
+        .await
+        .expect("insert payload filter invocation");
+    }
+    let list = |payload_key: Option<&str>, payload_val: Option<&str>| {
+        list_invocations(
+            State(state.clone()),
+            Query(ListQuery {
+                payload_key: payload_key.map(ToOwned::to_owned),
+                payload_val: payload_val.map(ToOwned::to_owned),
+                page: Some(1),
+                page_size: Some(20),
+                ..Default::default()
+            }),
+        )
+    };
+
+    let Json(response) = list(Some("model"), Some("gpt-5-mini"))
+        .await
+        .expect("payload key/value filter should succeed");
+    assert_eq!(response.total, 1);
+    assert_eq!(response.records[0].invoke_id, "payload-mini");
+
+    let Json(response) = list(Some("usage.tier"), Some("FLEX"))
+        .await
+        .expect("nested payload filter should succeed");
+    assert_eq!(response.total, 1);
+
+    let Json(response) = list(Some("model"), None)
+        .await
+        .expect("payload key presence filter should succeed");
+    assert_eq!(response.total, 2);
+
+    assert!(list(Some("model') OR 1=1 --"), None).await.is_err());
+    assert!(list(None, Some("gpt-5")).await.is_err());
+
+    assert!(probe_sqlite_json1(&state.pool).await);
+    let err = build_resolved_invocation_list_request(
+        &state.pool,
+        &ListQuery {
+            payload_key: Some("model".to_string()),
+            ..Default::default()
+        },
+        state.config.list_limit_max as i64,
+        false,
+    )
+    .await
+    .expect_err("payloadKey filters need JSON1");
+    let ApiError::BadRequest(message) = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(
+        message.to_string(),
+        "payloadKey filters need SQLite with JSON1 support"
+    );
+}
+
 #[tokio::test]
 async fn invocation_queries_filter_upstream_scope_and_treat_legacy_rows_as_external() {
     let state = test_state_with_openai_base(
@@ -1130,6 +1513,115 @@ async fn list_invocations_response_omits_raw_expires_at_field() {
     );
 }
 
+#[tokio::test]
+async fn list_invocations_fields_param_projects_records_and_rejects_unknown_fields() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    sqlx::query(
+        "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, total_tokens, raw_response) VALUES (?1, ?2, ?3, 'success', 42, '{}')",
+    )
+    .bind("proxy-fields-projection")
+    .bind("2026-02-25 10:02:00")
+    .bind(SOURCE_PROXY)
+    .execute(&state.pool)
+    .await
+    .expect("insert proxy invocation");
+
+    let read_json = |response: Response| async move {
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read invocations body");
+        serde_json::from_slice::<Value>(&body).expect("decode invocations body")
+    };
+
+    let full = read_json(
+        list_invocations_with_fields(
+            State(state.clone()),
+            Query(ListQuery {
+                limit: Some(10),
+                ..Default::default()
+            }),
+        )
+        .await
+        .expect("list all invocation fields"),
+    )
+    .await;
+    let full_record = full["records"][0].as_object().expect("full record object");
+    let mut serialized_keys = full_record
+        .keys()
+        .map(String::as_str)
+        .collect::<BTreeSet<_>>();
+    // ApiInvocation skips these keys while they are None.
+    serialized_keys.extend(["livePhase", "blockedBinding", "costAudit"]);
+    assert_eq!(
+        serialized_keys,
+        INVOCATION_LIST_FIELDS
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>()
+    );
+    for (keys, sql) in invocation_select_columns() {
+        for key in keys {
+            assert!(
+                INVOCATION_LIST_FIELDS.contains(key) || *key == "stickyKey",
+                "select column {sql} maps to unknown field {key}"
+            );
+        }
+    }
+    let slim_fields = HashSet::from(["invokeId".to_string(), "totalTokens".to_string()]);
+    let slim_sql = build_invocation_projected_select_query(Some(&slim_fields))
+        .sql()
+        .to_string();
+    assert!(slim_sql.contains("total_tokens"));
+    assert!(!slim_sql.contains("AS proxy_display_name"));
+    assert!(!slim_sql.contains("AS upstream_account_name"));
+    assert!(
+        build_invocation_select_query()
+            .sql()
+            .contains("AS proxy_display_name")
+    );
+
+    let slim = read_json(
+        list_invocations_with_fields(
+            State(state.clone()),
+            Query(ListQuery {
+                limit: Some(10),
+                fields: Some("invokeId, totalTokens".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .expect("list projected invocation fields"),
+    )
+    .await;
+    assert_eq!(
+        slim["records"][0],
+        json!({ "invokeId": "proxy-fields-projection", "totalTokens": 42 })
+    );
+    assert_eq!(slim["total"], full["total"]);
+
+    let err = list_invocations_with_fields(
+        State(state),
+        Query(ListQuery {
+            limit: Some(10),
+            fields: Some("invokeId,rawResponse".to_string()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect_err("unknown fields should be rejected");
+    let ApiError::BadRequest(message) = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    assert_eq!(
+        message.to_string(),
+        "unknown invocation fields: rawResponse"
+    );
+}
+
 #[tokio::test]
 async fn list_invocations_tolerates_malformed_payload_json() {
     let state = test_state_with_openai_base(
@@ -3201,6 +3693,11 @@ async fn stats_endpoints_preserve_historical_xy_records() {
             settlement_hour: None,
             time_zone: None,
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -3325,6 +3822,11 @@ async fn yesterday_summary_and_timeseries_only_include_previous_local_day() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -3599,7 +4101,7 @@ async fn prompt_cache_conversations_groups_recent_keys_and_uses_history_totals()
     )
     .await;
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before count-mode prompt-cache read");
 
@@ -3716,7 +4218,7 @@ async fn prompt_cache_last24h_requests_keep_null_status_rows_neutral() {
     .await
     .expect("insert null-status prompt cache row");
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before neutral status read");
 
@@ -3787,7 +4289,7 @@ async fn prompt_cache_last24h_requests_treat_running_rows_with_failure_class_as_
     .await
     .expect("insert running prompt cache failure row");
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before running failure read");
 
@@ -3857,7 +4359,7 @@ async fn prompt_cache_last24h_requests_treat_running_rows_with_error_text_as_fai
     .await
     .expect("insert running prompt cache row with error text");
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before running error-text read");
 
@@ -3934,7 +4436,7 @@ async fn prompt_cache_last24h_requests_treat_pending_rows_with_failure_kind_as_f
     .await
     .expect("insert pending prompt cache row with failure kind");
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before pending failure-kind read");
 
@@ -4004,7 +4506,7 @@ async fn prompt_cache_last24h_requests_keep_status_only_http_failures_marked_as_
     .await
     .expect("insert http-status-only prompt cache failure row");
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before http-status-only read");
 
@@ -1659,6 +1659,11 @@ async fn capture_target_pool_route_keeps_late_logical_failure_when_downstream_di
             .await
             .expect("reload persisted api invocation");
     assert_eq!(reloaded.downstream_status_code, Some(200));
+    assert!(reloaded.http_status.is_some());
+    assert_eq!(
+        reloaded.http_status,
+        invocation_payload["statusCode"].as_i64()
+    );
     assert!(
         reloaded.downstream_error_message.as_deref().is_some_and(
             |value| value.contains("downstream closed while streaming upstream response")
@@ -2150,7 +2155,7 @@ async fn pool_openai_v1_responses_network_marks_after_first_byte_downstream_clos
         .expect("bind proxy test server");
     let addr = listener.local_addr().expect("proxy test server addr");
     let server_handle = tokio::spawn(async move {
-        crate::serve_router_with_graceful_shutdown(listener, app, std::future::pending())
+        crate::serve_router_with_graceful_shutdown(listener, app, None, std::future::pending())
             .await
             .expect("proxy test server should run");
     });
@@ -2301,6 +2306,11 @@ async fn proxy_openai_v1_e2e_stream_survives_short_request_timeout() {
         dashboard_activity_live_broadcast_seq: Arc::new(AtomicU64::new(0)),
         dashboard_activity_live_broadcast_running: Arc::new(AtomicBool::new(false)),
         process_started_at_utc: chrono::Utc::now(),
+        clock: Arc::new(SystemClock),
+        archive_db_attached: false,
+        sqlite_json1_available: true,
+        cost_spike_tracker: Arc::new(CostSpikeTracker::default()),
+        log_buffer: Arc::new(LogRingBuffer::default()),
         dashboard_network_speed_cache: Arc::new(
             crate::dashboard_network_speed::DashboardNetworkSpeedCache::new(chrono::Utc::now()),
         ),
@@ -2331,6 +2341,8 @@ async fn proxy_openai_v1_e2e_stream_survives_short_request_timeout() {
         )),
         maintenance_stats_cache: Arc::new(Mutex::new(StatsMaintenanceCacheState::default())),
         system_status_cache: Arc::new(Mutex::new(SystemStatusCacheState::default())),
+        timeseries_response_cache: Arc::new(TimeseriesResponseCache::default()),
+        sse_connections: Arc::new(SseConnectionRegistry::default()),
         pool_routing_reservations: Arc::new(std::sync::Mutex::new(HashMap::new())),
         pool_routing_runtime_cache: Arc::new(Mutex::new(None)),
         pool_live_attempt_ids: Arc::new(std::sync::Mutex::new(HashSet::new())),
@@ -2899,7 +2911,7 @@ async fn build_account_sticky_keys_response_keeps_attached_keys_without_recent_a
     .execute(&state.pool)
     .await
     .expect("insert stale sticky invocation");
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("sync hourly rollups before sticky aggregate response");
 
@@ -2964,10 +2976,15 @@ async fn upstream_account_sticky_keys_reads_inline_rollups_without_read_time_cat
         })
         .to_string(),
     );
-    persist_proxy_capture_record(&state.pool, std::time::Instant::now(), record)
-        .await
-        .expect("persist sticky proxy capture")
-        .expect("sticky invocation should persist");
+    persist_proxy_capture_record(
+        &state.pool,
+        std::time::Instant::now(),
+        record,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("persist sticky proxy capture")
+    .expect("sticky invocation should persist");
 
     let response = build_account_sticky_keys_response(
         &state.pool,
@@ -3086,7 +3103,7 @@ async fn build_account_sticky_keys_response_activity_window_filters_recent_keys_
         .expect("insert sticky invocation");
     }
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("sync hourly rollups before sticky activity-window response");
 
@@ -3182,7 +3199,7 @@ async fn build_account_sticky_keys_response_activity_window_previews_respect_tim
         .expect("insert sticky invocation");
     }
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("sync hourly rollups before sticky preview response");
 
@@ -3261,7 +3278,7 @@ async fn build_account_sticky_keys_response_activity_window_caps_results_to_fift
         .expect("insert sticky invocation");
     }
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("sync hourly rollups before sticky activity-window response");
 
@@ -3349,7 +3366,7 @@ async fn build_account_sticky_keys_response_includes_recent_invocations_sorted_a
         .expect("insert preview invocation");
     }
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("sync hourly rollups before sticky preview response");
 
@@ -3424,7 +3441,7 @@ async fn prompt_cache_views_ignore_sticky_only_internal_keys() {
         .expect("insert prompt cache test invocation");
     }
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before sticky-only prompt-cache read");
 
@@ -226,9 +226,12 @@ pub(crate) async fn seed_invocation_archive_batch(
 }
 
 async fn run_background_invocation_summary_rollup_repair(pool: &SqlitePool) {
-    crate::stats::ensure_invocation_summary_rollups_ready(pool)
-        .await
-        .expect("run background invocation summary rollup repair");
+    crate::stats::ensure_invocation_summary_rollups_ready(
+        pool,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("run background invocation summary rollup repair");
 }
 
 async fn insert_materialized_rollup_bucket_marker(
@@ -1110,6 +1113,11 @@ async fn timeseries_includes_first_byte_avg_and_p95_for_success_samples() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -1131,6 +1139,247 @@ async fn timeseries_includes_first_byte_avg_and_p95_for_success_samples() {
     );
 }
 
+#[tokio::test]
+async fn timeseries_usage_floor_excludes_zero_usage_pings() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(
+        (Utc::now() - ChronoDuration::minutes(5))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    insert_timeseries_invocation(
+        &state.pool,
+        "usage-floor-real",
+        &occurred_at,
+        "success",
+        None,
+    )
+    .await;
+    insert_timeseries_invocation(
+        &state.pool,
+        "usage-floor-ping",
+        &occurred_at,
+        "success",
+        None,
+    )
+    .await;
+    sqlx::query(
+        "UPDATE codex_invocations SET total_tokens = 0, cost = 0 WHERE invoke_id = 'usage-floor-ping'",
+    )
+    .execute(&state.pool)
+    .await
+    .expect("zero out ping usage");
+
+    let query = |bucket: &str, min_cost: Option<f64>, min_tokens: Option<i64>| TimeseriesQuery {
+        range: "1d".to_string(),
+        bucket: Some(bucket.to_string()),
+        settlement_hour: None,
+        time_zone: Some("Asia/Shanghai".to_string()),
+        upstream_account_id: None,
+        min_cost,
+        min_tokens,
+        force: None,
+        ts: None,
+        resolution: None,
+    };
+    let total_count = |response: &TimeseriesResponse| {
+        response
+            .points
+            .iter()
+            .map(|point| point.total_count)
+            .sum::<i64>()
+    };
+
+    let Json(unfiltered) = fetch_timeseries(State(state.clone()), Query(query("15m", None, None)))
+        .await
+        .expect("fetch unfiltered timeseries");
+    assert_eq!(total_count(&unfiltered), 2);
+
+    for (bucket, min_cost, min_tokens) in [
+        ("15m", None, Some(1)),
+        ("15m", Some(0.001), None),
+        ("1h", Some(0.001), Some(1)),
+    ] {
+        let Json(filtered) = fetch_timeseries(
+            State(state.clone()),
+            Query(query(bucket, min_cost, min_tokens)),
+        )
+        .await
+        .expect("fetch filtered timeseries");
+        assert_eq!(total_count(&filtered), 1, "bucket={bucket}");
+        let total_tokens = filtered
+            .points
+            .iter()
+            .map(|point| point.total_tokens)
+            .sum::<i64>();
+        assert_eq!(total_tokens, 10);
+    }
+
+    let err = fetch_timeseries(State(state), Query(query("15m", Some(-1.0), None)))
+        .await
+        .expect_err("negative minCost should be rejected");
+    assert!(
+        matches!(err, ApiError::BadRequest(_)),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn timeseries_rejects_point_count_above_cap_unless_forced() {
+    let mut config = test_config();
+    config.openai_upstream_base_url =
+        Url::parse("https://api.openai.com/").expect("valid upstream base url");
+    config.max_timeseries_points = 10;
+    let state = test_state_from_config(config, true).await;
+    let query = |force: Option<bool>| TimeseriesQuery {
+        range: "1h".to_string(),
+        bucket: Some("1m".to_string()),
+        settlement_hour: None,
+        time_zone: Some("Asia/Shanghai".to_string()),
+        upstream_account_id: None,
+        min_cost: None,
+        min_tokens: None,
+        force,
+        ts: None,
+        resolution: None,
+    };
+
+    let err = fetch_timeseries(State(state.clone()), Query(query(None)))
+        .await
+        .expect_err("fine bucket above the point cap should be rejected");
+    let ApiError::BadRequest(message) = err else {
+        panic!("unexpected error: {err:?}");
+    };
+    let message = message.to_string();
+    assert!(message.contains("limit of 10"), "{message}");
+    assert!(message.contains("bucket=15m"), "{message}");
+
+    let Json(forced) = fetch_timeseries(State(state), Query(query(Some(true))))
+        .await
+        .expect("force=true should coarsen the bucket");
+    assert_eq!(forced.bucket_seconds, 360);
+}
+
+#[tokio::test]
+async fn timeseries_resolution_shifts_only_the_default_bucket() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let query = |bucket: Option<&str>, resolution: Option<&str>| TimeseriesQuery {
+        range: "1d".to_string(),
+        bucket: bucket.map(str::to_string),
+        settlement_hour: None,
+        time_zone: Some("Asia/Shanghai".to_string()),
+        upstream_account_id: None,
+        min_cost: None,
+        min_tokens: None,
+        force: None,
+        ts: None,
+        resolution: resolution.map(str::to_string),
+    };
+
+    for (resolution, expected_bucket_seconds) in [
+        (None, 1_800),
+        (Some("normal"), 1_800),
+        (Some("fine"), 900),
+        (Some("coarse"), 3_600),
+    ] {
+        let Json(response) = fetch_timeseries(State(state.clone()), Query(query(None, resolution)))
+            .await
+            .expect("fetch timeseries with resolution");
+        assert_eq!(
+            response.bucket_seconds, expected_bucket_seconds,
+            "resolution={resolution:?}"
+        );
+    }
+
+    let Json(explicit) =
+        fetch_timeseries(State(state.clone()), Query(query(Some("1h"), Some("fine"))))
+            .await
+            .expect("explicit bucket should win over resolution");
+    assert_eq!(explicit.bucket_seconds, 3_600);
+
+    assert_eq!(TimeseriesResolution::Fine.scale_default_bucket(60), 60);
+    assert_eq!(
+        TimeseriesResolution::Coarse.scale_default_bucket(86_400),
+        86_400
+    );
+
+    let err = fetch_timeseries(State(state), Query(query(None, Some("ultra"))))
+        .await
+        .expect_err("unknown resolution should be rejected");
+    assert!(
+        matches!(err, ApiError::BadRequest(_)),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn timeseries_peak_reports_busiest_bucket_and_average() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let peak_query = || TimeseriesPeakQuery {
+        range: "1h".to_string(),
+        bucket: Some("1m".to_string()),
+        time_zone: Some("Asia/Shanghai".to_string()),
+    };
+
+    let Json(empty) = fetch_timeseries_peak(State(state.clone()), Query(peak_query()))
+        .await
+        .expect("fetch peak for empty range");
+    assert_eq!(empty.peak_bucket_start, None);
+    assert_eq!(empty.peak_count, 0);
+    assert_f64_close(empty.average_count, 0.0);
+
+    let now = Utc::now().with_timezone(&Shanghai).naive_local();
+    let busy_at = format_naive(now - ChronoDuration::minutes(5));
+    let quiet_at = format_naive(now - ChronoDuration::minutes(20));
+    for invoke_id in ["peak-busy-a", "peak-busy-b", "peak-busy-c"] {
+        insert_timeseries_invocation(&state.pool, invoke_id, &busy_at, "success", None).await;
+    }
+    insert_timeseries_invocation(&state.pool, "peak-quiet", &quiet_at, "success", None).await;
+
+    let Json(peak) = fetch_timeseries_peak(State(state.clone()), Query(peak_query()))
+        .await
+        .expect("fetch peak");
+    assert_eq!(peak.bucket_seconds, 60);
+    assert_eq!(peak.peak_count, 3);
+
+    let Json(timeseries) = fetch_timeseries(
+        State(state),
+        Query(TimeseriesQuery {
+            range: "1h".to_string(),
+            bucket: Some("1m".to_string()),
+            settlement_hour: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+            upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
+        }),
+    )
+    .await
+    .expect("fetch timeseries");
+    let busiest = timeseries
+        .points
+        .iter()
+        .find(|point| point.total_count == 3)
+        .expect("busy bucket");
+    assert_eq!(
+        peak.peak_bucket_start.as_deref(),
+        Some(busiest.bucket_start.as_str())
+    );
+    assert_f64_close(peak.average_count, 4.0 / timeseries.points.len() as f64);
+}
+
 #[tokio::test]
 async fn timeseries_includes_legacy_http_200_success_like_ttfb_samples() {
     let state = test_state_with_openai_base(
@@ -1159,6 +1408,11 @@ async fn timeseries_includes_legacy_http_200_success_like_ttfb_samples() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -1233,6 +1487,11 @@ async fn timeseries_and_summary_count_completed_rows_as_success() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -1351,6 +1610,11 @@ async fn timeseries_reports_snapshot_id_for_live_exact_queries() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -1365,6 +1629,81 @@ async fn timeseries_reports_snapshot_id_for_live_exact_queries() {
     assert_eq!(bucket.total_count, 2);
 }
 
+#[tokio::test]
+async fn timeseries_accepts_rfc3339_occurred_at_alongside_naive_rows() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let now = Utc::now();
+    let naive_occurred_at = format_naive(
+        (now - ChronoDuration::minutes(5))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    // Keep the RFC3339 row on the previous Shanghai day so the lexical live-range
+    // bounds still include it.
+    let rfc3339_occurred_at = (start_of_local_day(now, Shanghai) - ChronoDuration::minutes(1))
+        .with_timezone(&Shanghai)
+        .to_rfc3339_opts(SecondsFormat::Secs, false);
+    for (id, invoke_id, occurred_at) in [
+        (201_i64, "mixed-format-naive", naive_occurred_at.as_str()),
+        (
+            202_i64,
+            "mixed-format-rfc3339",
+            rfc3339_occurred_at.as_str(),
+        ),
+    ] {
+        sqlx::query(
+            r#"
+            INSERT INTO codex_invocations (
+                id,
+                invoke_id,
+                occurred_at,
+                source,
+                status,
+                total_tokens,
+                cost,
+                raw_response
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+        )
+        .bind(id)
+        .bind(invoke_id)
+        .bind(occurred_at)
+        .bind(SOURCE_PROXY)
+        .bind("success")
+        .bind(10_i64)
+        .bind(0.01_f64)
+        .bind("{}")
+        .execute(&state.pool)
+        .await
+        .expect("insert mixed-format timeseries invocation");
+    }
+
+    let Json(response) = fetch_timeseries(
+        State(state),
+        Query(TimeseriesQuery {
+            range: "1d".to_string(),
+            bucket: Some("15m".to_string()),
+            settlement_hour: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+            upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
+        }),
+    )
+    .await
+    .expect("mixed occurred_at formats should not fail the timeseries");
+
+    let total_count: i64 = response.points.iter().map(|point| point.total_count).sum();
+    assert_eq!(total_count, 2);
+}
+
 #[tokio::test]
 async fn timeseries_and_summary_do_not_treat_running_rows_with_failure_metadata_as_failures() {
     let state = test_state_with_openai_base(
@@ -1453,6 +1792,11 @@ async fn timeseries_and_summary_do_not_treat_running_rows_with_failure_metadata_
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -1532,6 +1876,11 @@ async fn timeseries_and_summary_count_http_200_rows_with_downstream_only_failure
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -1643,6 +1992,11 @@ async fn timeseries_and_summary_treat_warning_success_as_success_like() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -2718,6 +3072,11 @@ async fn previous7d_summary_matches_daily_timeseries_when_window_spans_archived_
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -3536,6 +3895,11 @@ async fn all_time_summary_skips_double_count_for_readable_materialized_archive_w
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -3743,6 +4107,11 @@ async fn all_time_summary_skips_double_count_for_readable_materialized_archive_w
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -3861,7 +4230,7 @@ async fn archived_failure_fallback_skips_already_materialized_archive_buckets()
     .bind(SOURCE_PROXY)
     .bind("service_failure")
     .bind(1_i64)
-    .bind("too_many_requests")
+    .bind("rate_limited")
     .bind(1_i64)
     .execute(&state.pool)
     .await
@@ -3961,65 +4330,905 @@ async fn archived_failure_fallback_includes_missing_rows_from_partially_material
             },
         ],
     )
-    .await;
+    .await;
+
+    let bucket_start_epoch = invocation_bucket_start_epoch(&archived_first_at)
+        .expect("partial failure count bucket start epoch should be derivable");
+    sqlx::query(
+        r#"
+        INSERT INTO invocation_failure_rollup_hourly (
+            bucket_start_epoch,
+            source,
+            failure_class,
+            is_actionable,
+            error_category,
+            failure_count,
+            updated_at
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'))
+        "#,
+    )
+    .bind(bucket_start_epoch)
+    .bind(SOURCE_PROXY)
+    .bind("service_failure")
+    .bind(1_i64)
+    .bind("rate_limited")
+    .bind(1_i64)
+    .execute(&state.pool)
+    .await
+    .expect("seed partially materialized failure count row");
+
+    let historical_range = format!("{}d", state.config.invocation_max_days + 30);
+    let Json(failure_summary) = fetch_failure_summary(
+        State(state.clone()),
+        Query(FailureSummaryQuery {
+            range: historical_range.clone(),
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch failure summary with partially materialized failure count");
+    assert_eq!(failure_summary.total_failures, 2);
+    assert_eq!(failure_summary.service_failure_count, 2);
+    assert_eq!(failure_summary.actionable_failure_count, 2);
+
+    let Json(error_distribution) = fetch_error_distribution(
+        State(state),
+        Query(ErrorQuery {
+            range: historical_range,
+            top: None,
+            scope: Some("service".to_string()),
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch error distribution with partially materialized failure count");
+    assert!(
+        error_distribution
+            .items
+            .iter()
+            .any(|item| item.reason == "too_many_requests" && item.count == 2)
+    );
+}
+
+#[tokio::test]
+async fn model_efficiency_ranks_cost_per_1k_tokens_and_guards_zero_tokens() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(
+        (Utc::now() - ChronoDuration::minutes(5))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    for (invoke_id, model, cost, tokens) in [
+        ("efficiency-cheap-a", "gpt-cheap", 0.01, 1_000),
+        ("efficiency-cheap-b", "gpt-cheap", 0.01, 3_000),
+        ("efficiency-pricey", "gpt-pricey", 0.30, 2_000),
+        ("efficiency-no-tokens", "gpt-empty", 0.0, 0),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, &occurred_at, "success", None).await;
+        sqlx::query(
+            "UPDATE codex_invocations SET model = ?1, cost = ?2, total_tokens = ?3 WHERE invoke_id = ?4",
+        )
+        .bind(model)
+        .bind(cost)
+        .bind(tokens)
+        .bind(invoke_id)
+        .execute(&state.pool)
+        .await
+        .expect("seed model usage");
+    }
+
+    let Json(response) = fetch_model_efficiency(
+        State(state.clone()),
+        Query(ModelEfficiencyQuery {
+            range: "1d".to_string(),
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch model efficiency");
+
+    let models = response
+        .items
+        .iter()
+        .map(|item| item.model.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(models, vec!["gpt-pricey", "gpt-cheap", "gpt-empty"]);
+    assert_f64_close(
+        response.items[0].cost_per_1k_tokens.expect("pricey rate"),
+        0.15,
+    );
+    assert_f64_close(
+        response.items[1].cost_per_1k_tokens.expect("cheap rate"),
+        0.005,
+    );
+    assert_eq!(response.items[1].total_tokens, 4_000);
+    assert_eq!(response.items[2].cost_per_1k_tokens, None);
+}
+
+#[tokio::test]
+async fn cache_savings_sums_cached_input_and_guards_empty_range() {
+    let mut config = test_config();
+    config.cache_discount = 0.5;
+    let state = test_state_from_config(config, true).await;
+
+    let Json(empty) = fetch_cache_savings(
+        State(state.clone()),
+        Query(CacheSavingsQuery {
+            range: "1d".to_string(),
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch cache savings for empty range");
+    assert_eq!(empty.total_input_tokens, 0);
+    assert_eq!(empty.cache_hit_rate, None);
+    assert_f64_close(empty.estimated_savings, 0.0);
+
+    let occurred_at = format_naive(
+        (Utc::now() - ChronoDuration::minutes(5))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    for (invoke_id, input_tokens, cache_input_tokens) in [
+        ("cache-savings-a", 1_000, 600),
+        ("cache-savings-b", 3_000, 200),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, &occurred_at, "success", None).await;
+        sqlx::query(
+            "UPDATE codex_invocations SET input_tokens = ?1, cache_input_tokens = ?2 WHERE invoke_id = ?3",
+        )
+        .bind(input_tokens)
+        .bind(cache_input_tokens)
+        .bind(invoke_id)
+        .execute(&state.pool)
+        .await
+        .expect("seed cache usage");
+    }
+
+    let Json(response) = fetch_cache_savings(
+        State(state.clone()),
+        Query(CacheSavingsQuery {
+            range: "1d".to_string(),
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch cache savings");
+    assert_eq!(response.cached_tokens, 800);
+    assert_eq!(response.total_input_tokens, 4_000);
+    assert_f64_close(response.cache_hit_rate.expect("hit rate"), 0.2);
+    assert_f64_close(response.estimated_savings, 400.0);
+}
+
+#[tokio::test]
+async fn hourly_profile_averages_hour_of_day_over_active_days() {
+    let state = test_state_from_config(test_config(), true).await;
+    for (invoke_id, occurred_at, cost) in [
+        ("profile-a", shanghai_local_days_ago(2, 9, 10, 0), 1.0),
+        ("profile-b", shanghai_local_days_ago(2, 9, 40, 0), 0.5),
+        ("profile-c", shanghai_local_days_ago(1, 9, 15, 0), 0.3),
+        ("profile-d", shanghai_local_days_ago(1, 14, 0, 0), 0.2),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, &occurred_at, "success", None).await;
+        sqlx::query("UPDATE codex_invocations SET cost = ?1 WHERE invoke_id = ?2")
+            .bind(cost)
+            .bind(invoke_id)
+            .execute(&state.pool)
+            .await
+            .expect("seed hourly profile cost");
+    }
+
+    let Json(response) = fetch_hourly_profile(
+        State(state.clone()),
+        Query(HourlyProfileQuery {
+            range: "7d".to_string(),
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch hourly profile");
+    assert_eq!(response.active_days, 2);
+    assert_eq!(response.items.len(), 24);
+    assert_eq!(response.items[9].hour, 9);
+    assert_f64_close(response.items[9].avg_count, 1.5);
+    assert_f64_close(response.items[9].avg_cost, 0.9);
+    assert_f64_close(response.items[14].avg_count, 0.5);
+    assert_f64_close(response.items[14].avg_cost, 0.1);
+    assert_f64_close(response.items[0].avg_count, 0.0);
+
+    let Json(utc) = fetch_hourly_profile(
+        State(state),
+        Query(HourlyProfileQuery {
+            range: "7d".to_string(),
+            time_zone: Some("UTC".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch hourly profile in utc");
+    assert_f64_close(utc.items[1].avg_count, 1.5);
+    assert_f64_close(utc.items[6].avg_count, 0.5);
+}
+
+#[tokio::test]
+async fn streak_tracks_current_run_and_longest_runs_in_order() {
+    let state = test_state_from_config(test_config(), true).await;
+    let query = || {
+        Query(StreakQuery {
+            range: "7d".to_string(),
+            time_zone: Some("Asia/Shanghai".to_string()),
+        })
+    };
+
+    let Json(empty) = fetch_streak(State(state.clone()), query())
+        .await
+        .expect("fetch empty streak");
+    assert_eq!(empty.current_streak, StreakRun::default());
+    assert_eq!((empty.longest_success, empty.longest_failure), (0, 0));
+
+    for (minute, status) in [
+        (0, "success"),
+        (1, "success"),
+        (2, "success"),
+        (3, "failed"),
+        (4, "failed"),
+        (5, "success"),
+        (6, "failed"),
+        (7, "running"),
+        (8, "success"),
+        (9, "success"),
+    ] {
+        insert_timeseries_invocation(
+            &state.pool,
+            &format!("streak-{minute}"),
+            &shanghai_local_days_ago(1, 10, minute, 0),
+            status,
+            None,
+        )
+        .await;
+    }
+
+    let Json(response) = fetch_streak(State(state), query())
+        .await
+        .expect("fetch streak");
+    assert_eq!(
+        response.current_streak,
+        StreakRun {
+            status: Some("success"),
+            count: 2,
+        }
+    );
+    assert_eq!(response.longest_success, 3);
+    assert_eq!(response.longest_failure, 2);
+}
+
+#[tokio::test]
+async fn scatter_returns_token_cost_pairs_and_downsamples_evenly() {
+    let state = test_state_from_config(test_config(), true).await;
+    for minute in 0..10_i64 {
+        sqlx::query(
+            "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, model, total_tokens, cost, raw_response) \
+             VALUES (?1, ?2, ?3, 'success', ?4, ?5, ?6, '{}')",
+        )
+        .bind(format!("scatter-{minute}"))
+        .bind(shanghai_local_days_ago(1, 10, minute as u32, 0))
+        .bind(SOURCE_PROXY)
+        .bind(format!("model-{minute}"))
+        .bind(minute * 100)
+        .bind(minute as f64 / 100.0)
+        .execute(&state.pool)
+        .await
+        .expect("insert scatter invocation");
+    }
+    sqlx::query(
+        "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, raw_response) \
+         VALUES ('scatter-unpriced', ?1, ?2, 'running', '{}')",
+    )
+    .bind(shanghai_local_days_ago(1, 10, 30, 0))
+    .bind(SOURCE_PROXY)
+    .execute(&state.pool)
+    .await
+    .expect("insert unpriced invocation");
+    let query = |limit: Option<i64>| {
+        Query(ScatterQuery {
+            range: "7d".to_string(),
+            time_zone: Some("Asia/Shanghai".to_string()),
+            limit,
+        })
+    };
+
+    let Json(all) = fetch_scatter(State(state.clone()), query(None))
+        .await
+        .expect("fetch full scatter");
+    assert_eq!(all.len(), 10);
+    assert_eq!(
+        all[3],
+        ScatterPoint {
+            total_tokens: 300,
+            cost: 0.03,
+            model: Some("model-3".to_string()),
+        }
+    );
+
+    let Json(sampled) = fetch_scatter(State(state), query(Some(4)))
+        .await
+        .expect("fetch sampled scatter");
+    assert_eq!(
+        sampled
+            .iter()
+            .map(|point| point.total_tokens)
+            .collect::<Vec<_>>(),
+        vec![0, 200, 500, 700]
+    );
+}
+
+#[tokio::test]
+async fn budget_projects_month_to_date_spend_and_reports_remaining() {
+    let mut config = test_config();
+    config.monthly_budget = Some(10.0);
+    let base_state = test_state_from_config(config, true).await;
+    // 2026-03-11 12:00 in Asia/Shanghai: 10.5 of March's 31 days have elapsed.
+    let fixed_now = Utc
+        .with_ymd_and_hms(2026, 3, 11, 4, 0, 0)
+        .single()
+        .expect("valid fixed clock instant");
+    let state = clone_state_with_clock(&base_state, Arc::new(FixedClock(fixed_now)));
+    for (invoke_id, occurred_at, cost) in [
+        ("budget-march-a", "2026-03-02 09:00:00", 1.5),
+        ("budget-march-b", "2026-03-11 11:00:00", 0.6),
+        ("budget-february", "2026-02-28 23:00:00", 5.0),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, occurred_at, "success", None).await;
+        sqlx::query("UPDATE codex_invocations SET cost = ?1 WHERE invoke_id = ?2")
+            .bind(cost)
+            .bind(invoke_id)
+            .execute(&state.pool)
+            .await
+            .expect("seed budget cost");
+    }
+
+    let Json(response) = fetch_budget(
+        State(state),
+        Query(BudgetQuery {
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch budget");
+    assert_eq!(response.period_start, "2026-02-28T16:00:00Z");
+    assert_eq!(response.period_end, "2026-03-31T16:00:00Z");
+    assert_eq!(response.budget, Some(10.0));
+    assert_f64_close(response.spent_this_period, 2.1);
+    assert_f64_close(response.remaining.expect("remaining budget"), 7.9);
+    assert_f64_close(response.projected_end_of_period, 6.2);
+}
+
+#[tokio::test]
+async fn billing_periods_follow_quota_resets_and_fall_back_to_calendar_months() {
+    let base_state = test_state_from_config(test_config(), true).await;
+    // 2026-03-11 12:00 in Asia/Shanghai.
+    let fixed_now = Utc
+        .with_ymd_and_hms(2026, 3, 11, 4, 0, 0)
+        .single()
+        .expect("valid fixed clock instant");
+    let state = clone_state_with_clock(&base_state, Arc::new(FixedClock(fixed_now)));
+    for (invoke_id, occurred_at, cost) in [
+        ("period-january", "2026-01-20 09:00:00", 1.0),
+        ("period-february-a", "2026-02-20 09:00:00", 2.0),
+        ("period-february-b", "2026-03-01 09:00:00", 0.5),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, occurred_at, "success", None).await;
+        sqlx::query("UPDATE codex_invocations SET cost = ?1 WHERE invoke_id = ?2")
+            .bind(cost)
+            .bind(invoke_id)
+            .execute(&state.pool)
+            .await
+            .expect("seed period cost");
+    }
+    let query = |periods: Option<i64>| {
+        Query(BillingPeriodQuery {
+            time_zone: Some("Asia/Shanghai".to_string()),
+            periods,
+        })
+    };
+
+    let Json(calendar) = fetch_billing_period_totals(State(state.clone()), query(Some(2)))
+        .await
+        .expect("fetch calendar periods");
+    assert_eq!(
+        calendar
+            .iter()
+            .map(|item| (item.period_start.as_str(), item.total_requests))
+            .collect::<Vec<_>>(),
+        vec![("2026-01-31T16:00:00Z", 1), ("2026-02-28T16:00:00Z", 1)]
+    );
+    assert!(
+        calendar
+            .iter()
+            .all(|item| item.source == BILLING_PERIOD_SOURCE_CALENDAR)
+    );
+
+    for reset in [
+        "2026-01-15T00:00:00Z",
+        "2026-02-15T00:00:00Z",
+        "2026-02-15T00:00:00Z",
+        "2026-03-15T00:00:00Z",
+    ] {
+        sqlx::query(
+            "INSERT INTO codex_quota_snapshots (period, period_reset_time) VALUES ('monthly', ?1)",
+        )
+        .bind(reset)
+        .execute(&state.pool)
+        .await
+        .expect("seed quota snapshot");
+    }
+    let Json(periods) = fetch_billing_period_totals(State(state), query(None))
+        .await
+        .expect("fetch billing periods");
+    assert_eq!(
+        periods
+            .iter()
+            .map(|item| (
+                item.period_start.as_str(),
+                item.period_end.as_str(),
+                item.total_requests
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("2025-12-15T00:00:00Z", "2026-01-15T00:00:00Z", 0),
+            ("2026-01-15T00:00:00Z", "2026-02-15T00:00:00Z", 1),
+            ("2026-02-15T00:00:00Z", "2026-03-15T00:00:00Z", 2),
+        ]
+    );
+    assert_f64_close(periods[1].total_cost, 1.0);
+    assert_f64_close(periods[2].total_cost, 2.5);
+    assert!(
+        periods
+            .iter()
+            .all(|item| item.source == BILLING_PERIOD_SOURCE_QUOTA_RESETS)
+    );
+}
+
+#[tokio::test]
+async fn billing_periods_project_a_trailing_period_after_the_last_reset() {
+    let base_state = test_state_from_config(test_config(), true).await;
+    // 2026-03-11 12:00 in Asia/Shanghai.
+    let fixed_now = Utc
+        .with_ymd_and_hms(2026, 3, 11, 4, 0, 0)
+        .single()
+        .expect("valid fixed clock instant");
+    let state = clone_state_with_clock(&base_state, Arc::new(FixedClock(fixed_now)));
+    for (invoke_id, occurred_at, cost) in [
+        ("trailing-before-reset", "2026-02-10 09:00:00", 1.0),
+        ("trailing-after-reset-a", "2026-02-20 09:00:00", 2.0),
+        ("trailing-after-reset-b", "2026-03-10 09:00:00", 0.5),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, occurred_at, "success", None).await;
+        sqlx::query("UPDATE codex_invocations SET cost = ?1 WHERE invoke_id = ?2")
+            .bind(cost)
+            .bind(invoke_id)
+            .execute(&state.pool)
+            .await
+            .expect("seed trailing period cost");
+    }
+    for reset in ["2026-01-15T00:00:00Z", "2026-02-15T00:00:00Z"] {
+        sqlx::query(
+            "INSERT INTO codex_quota_snapshots (period, period_reset_time) VALUES ('monthly', ?1)",
+        )
+        .bind(reset)
+        .execute(&state.pool)
+        .await
+        .expect("seed quota snapshot");
+    }
+
+    let Json(periods) = fetch_billing_period_totals(
+        State(state),
+        Query(BillingPeriodQuery {
+            time_zone: Some("Asia/Shanghai".to_string()),
+            periods: None,
+        }),
+    )
+    .await
+    .expect("fetch billing periods");
+    assert_eq!(
+        periods
+            .iter()
+            .map(|item| (
+                item.period_start.as_str(),
+                item.period_end.as_str(),
+                item.total_requests
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("2025-12-15T00:00:00Z", "2026-01-15T00:00:00Z", 0),
+            ("2026-01-15T00:00:00Z", "2026-02-15T00:00:00Z", 1),
+            ("2026-02-15T00:00:00Z", "2026-03-15T00:00:00Z", 2),
+        ]
+    );
+    assert_f64_close(periods[2].total_cost, 2.5);
+}
+
+#[tokio::test]
+async fn stats_comparison_reports_previous_period_and_guards_zero_baseline() {
+    let base_state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    // 2026-03-11 12:00 in Asia/Shanghai.
+    let fixed_now = Utc
+        .with_ymd_and_hms(2026, 3, 11, 4, 0, 0)
+        .single()
+        .expect("valid fixed clock instant");
+    let state = clone_state_with_clock(&base_state, Arc::new(FixedClock(fixed_now)));
+    for (invoke_id, occurred_at) in [
+        ("comparison-current-a", "2026-03-11 09:00:00"),
+        ("comparison-current-b", "2026-03-08 09:00:00"),
+        ("comparison-current-c", "2026-03-05 09:00:00"),
+        ("comparison-previous", "2026-03-01 09:00:00"),
+        ("comparison-too-old", "2026-02-20 09:00:00"),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, occurred_at, "success", None).await;
+    }
+    let query = |range: &str| StatsComparisonQuery {
+        range: range.to_string(),
+        time_zone: Some("Asia/Shanghai".to_string()),
+    };
+
+    let Json(weekly) = fetch_stats_comparison(State(state.clone()), Query(query("7d")))
+        .await
+        .expect("fetch weekly comparison");
+    assert_eq!(weekly.previous_range_start, "2026-02-25T04:00:00Z");
+    assert_eq!(weekly.previous_range_end, weekly.range_start);
+    assert_eq!(weekly.current.total_count, 3);
+    assert_eq!(weekly.current.total_tokens, 30);
+    assert_eq!(weekly.previous.total_count, 1);
+    assert_f64_close(weekly.count_change_pct.expect("count change"), 200.0);
+    assert_f64_close(weekly.cost_change_pct.expect("cost change"), 200.0);
+    assert_f64_close(weekly.tokens_change_pct.expect("tokens change"), 200.0);
+
+    let Json(daily) = fetch_stats_comparison(State(state), Query(query("1d")))
+        .await
+        .expect("fetch daily comparison");
+    assert_eq!(daily.current.total_count, 1);
+    assert_eq!(daily.previous.total_count, 0);
+    assert_eq!(daily.count_change_pct, None);
+    assert_eq!(daily.cost_change_pct, None);
+    assert_eq!(daily.tokens_change_pct, None);
+}
+
+#[tokio::test]
+async fn budget_reports_null_budget_when_unconfigured() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+
+    let Json(response) = fetch_budget(State(state), Query(BudgetQuery { time_zone: None }))
+        .await
+        .expect("fetch budget without configuration");
+    assert_eq!(response.budget, None);
+    assert_eq!(response.remaining, None);
+    assert_f64_close(response.spent_this_period, 0.0);
+    assert_f64_close(response.projected_end_of_period, 0.0);
+}
+
+#[tokio::test]
+async fn error_endpoints_clamp_top_and_limit_to_configured_maximums() {
+    let mut config = test_config();
+    config.error_top_max = 1;
+    config.other_errors_limit_max = 1;
+    let state = test_state_from_config(config, true).await;
+    let occurred_at = format_naive(
+        (Utc::now() - ChronoDuration::minutes(5))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    for (invoke_id, error_message) in [
+        ("clamp-502", "HTTP 502 bad gateway"),
+        ("clamp-503", "HTTP 503 service unavailable"),
+        ("clamp-503-again", "HTTP 503 service unavailable"),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, &occurred_at, "failed", None).await;
+        sqlx::query("UPDATE codex_invocations SET error_message = ?1 WHERE invoke_id = ?2")
+            .bind(error_message)
+            .bind(invoke_id)
+            .execute(&state.pool)
+            .await
+            .expect("seed failed invocation");
+    }
+
+    let Json(distribution) = fetch_error_distribution(
+        State(state.clone()),
+        Query(ErrorQuery {
+            range: "1h".to_string(),
+            top: Some(10),
+            scope: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch error distribution");
+    assert_eq!(distribution.items.len(), 1);
+    assert_eq!(
+        distribution.items[0].reason,
+        ErrorCategory::HttpServer(503).label()
+    );
+
+    let Json(others) = fetch_other_errors(
+        State(state),
+        Query(OtherErrorsQuery {
+            range: "1h".to_string(),
+            page: None,
+            limit: Some(100),
+            scope: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch other errors");
+    assert_eq!(others.limit, 1);
+}
+
+#[tokio::test]
+async fn error_distribution_prefers_stored_http_status_over_message_text() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(
+        (Utc::now() - ChronoDuration::minutes(5))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    for (invoke_id, error_message, http_status) in [
+        (
+            "http-status-stored",
+            "upstream connection reset",
+            Some(503_i64),
+        ),
+        ("http-status-parsed", "HTTP 502 bad gateway", None),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, &occurred_at, "failed", None).await;
+        sqlx::query(
+            "UPDATE codex_invocations SET error_message = ?1, http_status = ?2 WHERE invoke_id = ?3",
+        )
+        .bind(error_message)
+        .bind(http_status)
+        .bind(invoke_id)
+        .execute(&state.pool)
+        .await
+        .expect("seed failed invocation");
+    }
+
+    let Json(distribution) = fetch_error_distribution(
+        State(state.clone()),
+        Query(ErrorQuery {
+            range: "1h".to_string(),
+            top: None,
+            scope: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch error distribution");
+    let mut reasons = distribution
+        .items
+        .iter()
+        .map(|item| (item.reason.clone(), item.count))
+        .collect::<Vec<_>>();
+    reasons.sort();
+    assert_eq!(
+        reasons,
+        vec![
+            (ErrorCategory::HttpServer(502).label(), 1),
+            (ErrorCategory::HttpServer(503).label(), 1),
+        ]
+    );
+
+    let Json(samples) = fetch_error_samples(
+        State(state.clone()),
+        Query(ErrorSampleQuery {
+            category: ErrorCategory::HttpServer(503).code(),
+            range: "1h".to_string(),
+            limit: None,
+            scope: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch error samples");
+    assert_eq!(
+        samples
+            .items
+            .iter()
+            .map(|item| item.error_message.as_deref())
+            .collect::<Vec<_>>(),
+        vec![Some("upstream connection reset")]
+    );
+
+    let Json(summary) = fetch_error_summary(
+        State(state.clone()),
+        Query(ErrorQuery {
+            range: "1h".to_string(),
+            top: None,
+            scope: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch error summary");
+    let stored_bucket = summary
+        .items
+        .iter()
+        .find(|item| item.category == ErrorCategory::HttpServer(503))
+        .expect("stored http status bucket");
+    assert_eq!(
+        stored_bucket.samples,
+        vec!["upstream connection reset".to_string()]
+    );
 
-    let bucket_start_epoch = invocation_bucket_start_epoch(&archived_first_at)
-        .expect("partial failure count bucket start epoch should be derivable");
-    sqlx::query(
-        r#"
-        INSERT INTO invocation_failure_rollup_hourly (
-            bucket_start_epoch,
-            source,
-            failure_class,
-            is_actionable,
-            error_category,
-            failure_count,
-            updated_at
-        )
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'))
-        "#,
+    let Json(others) = fetch_other_errors(
+        State(state),
+        Query(OtherErrorsQuery {
+            range: "1h".to_string(),
+            page: None,
+            limit: None,
+            scope: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
     )
-    .bind(bucket_start_epoch)
-    .bind(SOURCE_PROXY)
-    .bind("service_failure")
-    .bind(1_i64)
-    .bind("too_many_requests")
-    .bind(1_i64)
-    .execute(&state.pool)
     .await
-    .expect("seed partially materialized failure count row");
+    .expect("fetch other errors");
+    assert_eq!(others.total, 0);
 
-    let historical_range = format!("{}d", state.config.invocation_max_days + 30);
-    let Json(failure_summary) = fetch_failure_summary(
+    assert_eq!(
+        resolve_invocation_http_status(Some(r#"{"statusCode":429}"#), Some("HTTP 500 boom")),
+        Some(429)
+    );
+    assert_eq!(
+        resolve_invocation_http_status(Some("{}"), Some("HTTP 500 boom")),
+        Some(500)
+    );
+    assert_eq!(
+        resolve_invocation_http_status(None, Some("stream closed")),
+        None
+    );
+}
+
+#[tokio::test]
+async fn error_samples_return_messages_matching_category_label() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(
+        (Utc::now() - ChronoDuration::minutes(5))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    for (invoke_id, error_message) in [
+        ("error-sample-429-a", "HTTP 429 too many requests"),
+        (
+            "error-sample-429-b",
+            "upstream HTTP 429: Too Many Requests, retry later",
+        ),
+        ("error-sample-502", "HTTP 502 bad gateway"),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, &occurred_at, "failed", None).await;
+        sqlx::query("UPDATE codex_invocations SET error_message = ?1 WHERE invoke_id = ?2")
+            .bind(error_message)
+            .bind(invoke_id)
+            .execute(&state.pool)
+            .await
+            .expect("seed error message");
+    }
+
+    let Json(samples) = fetch_error_samples(
         State(state.clone()),
-        Query(FailureSummaryQuery {
-            range: historical_range.clone(),
+        Query(ErrorSampleQuery {
+            category: "too_many_requests".to_string(),
+            range: "1d".to_string(),
+            limit: None,
+            scope: Some("all".to_string()),
             time_zone: Some("Asia/Shanghai".to_string()),
         }),
     )
     .await
-    .expect("fetch failure summary with partially materialized failure count");
-    assert_eq!(failure_summary.total_failures, 2);
-    assert_eq!(failure_summary.service_failure_count, 2);
-    assert_eq!(failure_summary.actionable_failure_count, 2);
+    .expect("fetch error samples");
+    assert_eq!(samples.reason, "too_many_requests");
+    assert_eq!(samples.category, ErrorCategory::RateLimited);
+    assert_eq!(samples.items.len(), 2);
+    assert!(samples.items.iter().all(|item| {
+        item.error_message
+            .as_deref()
+            .is_some_and(|message| message.contains("429"))
+    }));
 
-    let Json(error_distribution) = fetch_error_distribution(
+    let Json(limited) = fetch_error_samples(
+        State(state),
+        Query(ErrorSampleQuery {
+            category: "too_many_requests".to_string(),
+            range: "1d".to_string(),
+            limit: Some(1),
+            scope: Some("all".to_string()),
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch limited error samples");
+    assert_eq!(limited.items.len(), 1);
+}
+
+#[tokio::test]
+async fn error_summary_attaches_up_to_two_distinct_samples_per_category() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(
+        (Utc::now() - ChronoDuration::minutes(5))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    for (invoke_id, error_message) in [
+        ("error-summary-429-a", "HTTP 429 too many requests"),
+        ("error-summary-429-b", "HTTP 429 too many requests"),
+        (
+            "error-summary-429-c",
+            "upstream HTTP 429: Too Many Requests",
+        ),
+        (
+            "error-summary-429-d",
+            "HTTP 429 too many requests, slow down",
+        ),
+        ("error-summary-502", "HTTP 502 bad gateway"),
+    ] {
+        insert_timeseries_invocation(&state.pool, invoke_id, &occurred_at, "failed", None).await;
+        sqlx::query("UPDATE codex_invocations SET error_message = ?1 WHERE invoke_id = ?2")
+            .bind(error_message)
+            .bind(invoke_id)
+            .execute(&state.pool)
+            .await
+            .expect("seed error message");
+    }
+
+    let Json(summary) = fetch_error_summary(
         State(state),
         Query(ErrorQuery {
-            range: historical_range,
+            range: "1d".to_string(),
             top: None,
-            scope: Some("service".to_string()),
+            scope: Some("all".to_string()),
             time_zone: Some("Asia/Shanghai".to_string()),
         }),
     )
     .await
-    .expect("fetch error distribution with partially materialized failure count");
+    .expect("fetch error summary");
+
+    let rate_limited = summary
+        .items
+        .iter()
+        .find(|item| item.reason == "too_many_requests")
+        .expect("429 category present");
+    assert_eq!(rate_limited.count, 4);
+    assert_eq!(rate_limited.samples.len(), 2);
+    assert_ne!(rate_limited.samples[0], rate_limited.samples[1]);
     assert!(
-        error_distribution
-            .items
+        rate_limited
+            .samples
             .iter()
-            .any(|item| item.reason == "too_many_requests" && item.count == 2)
+            .all(|message| message.contains("429"))
     );
+
+    let bad_gateway = summary
+        .items
+        .iter()
+        .find(|item| item.samples.iter().any(|m| m.contains("502")))
+        .expect("502 category carries its sample");
+    assert_eq!(bad_gateway.count, 1);
+    assert_eq!(bad_gateway.samples.len(), 1);
 }
 
 #[tokio::test]
@@ -4126,7 +5335,7 @@ async fn archived_failure_fallback_aggregates_missing_rows_across_archive_parts(
     .bind(SOURCE_PROXY)
     .bind("service_failure")
     .bind(1_i64)
-    .bind("too_many_requests")
+    .bind("rate_limited")
     .bind(1_i64)
     .execute(&state.pool)
     .await
@@ -4270,7 +5479,7 @@ async fn archived_failure_fallback_keeps_unmaterialized_rows_when_sibling_archiv
     .bind(SOURCE_PROXY)
     .bind("service_failure")
     .bind(1_i64)
-    .bind("too_many_requests")
+    .bind("rate_limited")
     .bind(1_i64)
     .execute(&state.pool)
     .await
@@ -4421,7 +5630,7 @@ async fn archived_failure_fallback_keeps_unmaterialized_rows_when_materialized_s
     .bind(SOURCE_PROXY)
     .bind("service_failure")
     .bind(1_i64)
-    .bind("too_many_requests")
+    .bind("rate_limited")
     .bind(1_i64)
     .execute(&state.pool)
     .await
@@ -4594,7 +5803,7 @@ async fn archived_failure_fallback_skips_double_count_for_readable_materialized_
     .bind(SOURCE_PROXY)
     .bind("service_failure")
     .bind(1_i64)
-    .bind("too_many_requests")
+    .bind("rate_limited")
     .bind(2_i64)
     .execute(&state.pool)
     .await
@@ -4704,7 +5913,7 @@ async fn historical_failure_read_path_skips_unreadable_pending_archives() {
     .bind(SOURCE_PROXY)
     .bind("service_failure")
     .bind(1_i64)
-    .bind("too_many_requests")
+    .bind("rate_limited")
     .bind(1_i64)
     .execute(&state.pool)
     .await
@@ -4883,7 +6092,7 @@ async fn archived_failure_fallback_skips_double_count_for_readable_materialized_
     .bind(SOURCE_PROXY)
     .bind("service_failure")
     .bind(1_i64)
-    .bind("too_many_requests")
+    .bind("rate_limited")
     .bind(1_i64)
     .execute(&state.pool)
     .await
@@ -5017,7 +6226,7 @@ async fn archived_failure_fallback_includes_missing_categories_from_partially_ma
     .bind(SOURCE_PROXY)
     .bind("service_failure")
     .bind(1_i64)
-    .bind("too_many_requests")
+    .bind("rate_limited")
     .bind(1_i64)
     .execute(&state.pool)
     .await
@@ -6091,6 +7300,11 @@ async fn historical_timeseries_includes_unmaterialized_archived_hours_without_in
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -6297,6 +7511,11 @@ async fn historical_timeseries_skips_archive_fallback_rows_already_counted_in_li
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -6426,6 +7645,11 @@ async fn historical_timeseries_skip_unreadable_materialized_archives() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -6554,6 +7778,11 @@ async fn historical_timeseries_skips_unreadable_replayed_legacy_archives() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -6677,6 +7906,11 @@ async fn historical_timeseries_read_path_skips_unreadable_pending_archives() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -6824,6 +8058,11 @@ async fn timeseries_hourly_backed_repairs_stale_archived_rollup_counts_before_qu
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -6947,9 +8186,12 @@ async fn all_time_summary_missing_archive_does_not_mark_repair_complete() {
 
     fs::remove_file(&archive_path).expect("remove archived invocation batch from disk");
 
-    let repair_err = crate::stats::ensure_invocation_summary_rollups_ready(&state.pool)
-        .await
-        .expect_err("missing archive should fail background summary repair");
+    let repair_err = crate::stats::ensure_invocation_summary_rollups_ready(
+        &state.pool,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect_err("missing archive should fail background summary repair");
     assert!(
         repair_err
             .to_string()
@@ -7105,7 +8347,7 @@ async fn all_time_summary_missing_summary_markers_do_not_replay_materialized_arc
     .bind(SOURCE_PROXY)
     .bind("service_failure")
     .bind(1_i64)
-    .bind("upstream_response_failed")
+    .bind("upstream_type:upstream_response_failed")
     .bind(1_i64)
     .execute(&state.pool)
     .await
@@ -8161,9 +9403,12 @@ async fn summary_rollup_repair_best_effort_skips_unreadable_pending_archives() {
 
     fs::write(&archive_path, b"not-a-gzip-archive").expect("corrupt pending archive batch");
 
-    crate::stats::ensure_invocation_summary_rollups_ready_best_effort(&state.pool)
-        .await
-        .expect("best-effort summary repair should skip unreadable pending archive");
+    crate::stats::ensure_invocation_summary_rollups_ready_best_effort(
+        &state.pool,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("best-effort summary repair should skip unreadable pending archive");
 
     let repair_marker_cursor = sqlx::query_scalar::<_, i64>(
         "SELECT cursor_id FROM hourly_rollup_live_progress WHERE dataset = ?1",
@@ -8688,9 +9933,15 @@ async fn all_time_summary_repair_does_not_advance_shared_live_cursor_without_hou
 
     run_background_invocation_summary_rollup_repair(&state.pool).await;
 
-    let totals = query_combined_totals(&state.pool, StatsFilter::All, InvocationSourceScope::All)
-        .await
-        .expect("query all-time totals through summary repair path");
+    let totals = query_combined_totals(
+        &state.pool,
+        StatsFilter::All,
+        InvocationSourceScope::All,
+        false,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("query all-time totals through summary repair path");
 
     assert_eq!(totals.total_count, 2);
     assert_eq!(totals.success_count, 2);
@@ -8799,9 +10050,12 @@ async fn summary_rollup_repair_refreshes_stale_repair_live_cursor_from_shared_pr
     .await
     .expect("seed stale summary repair live cursor");
 
-    crate::stats::ensure_invocation_summary_rollups_ready(&state.pool)
-        .await
-        .expect("refresh stale summary repair live cursor");
+    crate::stats::ensure_invocation_summary_rollups_ready(
+        &state.pool,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("refresh stale summary repair live cursor");
 
     let repair_live_cursor: i64 =
         sqlx::query_scalar("SELECT cursor_id FROM hourly_rollup_live_progress WHERE dataset = ?1")
@@ -8926,6 +10180,11 @@ async fn timeseries_ignores_non_positive_or_missing_ttfb_samples() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -8988,6 +10247,11 @@ async fn timeseries_daily_bucket_includes_first_byte_stats() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9030,6 +10294,11 @@ async fn open_live_timeseries_range_end_stays_at_query_cutoff() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9101,6 +10370,11 @@ async fn timeseries_includes_first_response_byte_total_avg_and_p95_for_complete_
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9190,6 +10464,11 @@ async fn timeseries_ignores_incomplete_first_response_byte_total_samples() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9246,6 +10525,11 @@ async fn timeseries_includes_failed_first_response_byte_total_samples() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9303,6 +10587,11 @@ async fn timeseries_excludes_zero_ttfb_sentinel_from_first_response_byte_total_s
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9329,7 +10618,11 @@ fn bucket_aggregate_uses_histogram_for_mixed_rollup_and_exact_p95() {
     };
     add_approx_histogram_sample(&mut bucket.first_byte_histogram, 1_000.0);
 
-    bucket.record_exact_ttfb_sample(Some("success"), Some(100.0));
+    bucket.record_exact_ttfb_sample(
+        Some("success"),
+        Some(100.0),
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    );
 
     assert_eq!(bucket.first_byte_sample_count, 2);
     assert_f64_close(
@@ -9359,6 +10652,11 @@ async fn timeseries_daily_includes_archived_rollup_days_without_ttfb() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9423,6 +10721,11 @@ async fn timeseries_daily_stays_continuous_after_rollup_archive() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9508,6 +10811,11 @@ async fn timeseries_daily_combines_rollup_and_live_within_same_day() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9552,6 +10860,11 @@ async fn timeseries_hourly_rollups_rebucket_for_different_timezones() {
             settlement_hour: None,
             time_zone: Some("UTC".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9606,6 +10919,11 @@ async fn timeseries_hourly_recent_non_hour_aligned_timezones_fall_back_to_raw_ro
             settlement_hour: None,
             time_zone: Some("Asia/Kathmandu".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9659,6 +10977,11 @@ async fn timeseries_hourly_backed_ignores_missing_exact_archive_batch() {
             settlement_hour: None,
             time_zone: Some("UTC".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9902,6 +11225,11 @@ async fn timeseries_daily_backed_ignores_pruned_legacy_archive_batch_files() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9943,6 +11271,11 @@ async fn timeseries_hourly_historical_non_hour_aligned_timezones_are_rejected()
             settlement_hour: None,
             time_zone: Some("Asia/Kathmandu".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -9975,6 +11308,11 @@ async fn account_timeseries_hourly_historical_non_hour_aligned_timezones_are_rej
             settlement_hour: None,
             time_zone: Some("Asia/Kathmandu".to_string()),
             upstream_account_id: Some(42),
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -10008,6 +11346,11 @@ async fn timeseries_daily_includes_rollups_for_equivalent_day_boundaries() {
             settlement_hour: None,
             time_zone: Some("Asia/Singapore".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -10055,6 +11398,11 @@ async fn timeseries_hourly_backed_bucket_stays_available_across_archive_boundary
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -10207,9 +11555,15 @@ async fn collect_summary_snapshots_uses_hourly_backed_duration_windows() {
     )
     .await;
 
-    let summaries = collect_summary_snapshots(&state.pool, state.config.invocation_max_days)
-        .await
-        .expect("collect summary snapshots");
+    let summaries = collect_summary_snapshots(
+        &state.pool,
+        state.clock.as_ref(),
+        state.archive_db_attached,
+        state.config.invocation_max_days,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("collect summary snapshots");
 
     let month = summaries
         .iter()
@@ -10228,6 +11582,109 @@ async fn collect_summary_snapshots_uses_hourly_backed_duration_windows() {
     assert_eq!(day.summary.total_count, 0);
 }
 
+async fn insert_clock_test_invocation(pool: &Pool<Sqlite>, id: i64, occurred_at: DateTime<Utc>) {
+    sqlx::query(
+        r#"
+        INSERT INTO codex_invocations (
+            id,
+            invoke_id,
+            occurred_at,
+            source,
+            status,
+            total_tokens,
+            cost,
+            raw_response
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+        "#,
+    )
+    .bind(id)
+    .bind(format!("fixed-clock-row-{id}"))
+    .bind(format_naive(
+        occurred_at.with_timezone(&Shanghai).naive_local(),
+    ))
+    .bind(SOURCE_PROXY)
+    .bind("success")
+    .bind(10_i64)
+    .bind(0.01_f64)
+    .bind("{}")
+    .execute(pool)
+    .await
+    .expect("insert fixed clock invocation");
+}
+
+#[tokio::test]
+async fn timeseries_and_summary_snapshots_follow_injected_clock() {
+    let base_state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let fixed_now = Utc
+        .timestamp_opt(
+            (Utc::now() - ChronoDuration::hours(3)).timestamp() / 60 * 60,
+            0,
+        )
+        .single()
+        .expect("valid fixed clock instant");
+    let state = clone_state_with_clock(&base_state, Arc::new(FixedClock(fixed_now)));
+    insert_clock_test_invocation(&state.pool, 301, fixed_now - ChronoDuration::minutes(20)).await;
+    insert_clock_test_invocation(&state.pool, 302, Utc::now() - ChronoDuration::minutes(5)).await;
+
+    let Json(response) = fetch_timeseries(
+        State(state.clone()),
+        Query(TimeseriesQuery {
+            range: "1h".to_string(),
+            bucket: Some("15m".to_string()),
+            settlement_hour: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+            upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
+        }),
+    )
+    .await
+    .expect("fetch timeseries with fixed clock");
+
+    assert_eq!(response.range_end, format_utc_iso(fixed_now));
+    assert_eq!(
+        response.range_start,
+        format_utc_iso(fixed_now - ChronoDuration::hours(1))
+    );
+    let total_count: i64 = response.points.iter().map(|point| point.total_count).sum();
+    assert_eq!(total_count, 1);
+
+    let summaries = collect_summary_snapshots(
+        &state.pool,
+        state.clock.as_ref(),
+        state.archive_db_attached,
+        state.config.invocation_max_days,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("collect summary snapshots with fixed clock");
+    let hour = summaries
+        .iter()
+        .find(|summary| summary.window == "1h")
+        .expect("1h summary should be present");
+    assert_eq!(hour.summary.total_count, 1);
+
+    let Json(summary) = fetch_summary(
+        State(state),
+        Query(SummaryQuery {
+            window: Some("1h".to_string()),
+            limit: None,
+            time_zone: Some("Asia/Shanghai".to_string()),
+            upstream_account_id: None,
+        }),
+    )
+    .await
+    .expect("fetch rolling summary with fixed clock");
+    assert_eq!(summary.total_count, 1);
+}
+
 #[tokio::test]
 async fn timeseries_hourly_backed_omits_pre_cutoff_partial_archived_hours() {
     let mut config = test_config();
@@ -10311,6 +11768,11 @@ async fn timeseries_hourly_backed_omits_pre_cutoff_partial_archived_hours() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -10490,6 +11952,7 @@ async fn invocation_hourly_rollup_ignores_null_status_for_success_failure_counts
             status: None,
             detail_level: DETAIL_LEVEL_FULL.to_string(),
             model: None,
+            model_canonical: None,
             input_tokens: None,
             output_tokens: None,
             cache_input_tokens: None,
@@ -10502,6 +11965,7 @@ async fn invocation_hourly_rollup_ignores_null_status_for_success_failure_counts
             cost_output: None,
             cost_reasoning: None,
             error_message: None,
+            http_status: None,
             failure_kind: None,
             failure_class: None,
             is_actionable: None,
@@ -10516,6 +11980,7 @@ async fn invocation_hourly_rollup_ignores_null_status_for_success_failure_counts
             t_persist_ms: None,
         }],
         &INVOCATION_HOURLY_ROLLUP_TARGETS,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("upsert hourly rollup source row");
@@ -10558,6 +12023,7 @@ async fn invocation_hourly_rollup_ignores_running_and_pending_for_failure_counts
                 status: Some("success".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: None,
+                model_canonical: None,
                 input_tokens: None,
                 output_tokens: None,
                 cache_input_tokens: None,
@@ -10570,6 +12036,7 @@ async fn invocation_hourly_rollup_ignores_running_and_pending_for_failure_counts
                 cost_output: None,
                 cost_reasoning: None,
                 error_message: None,
+                http_status: None,
                 failure_kind: None,
                 failure_class: None,
                 is_actionable: None,
@@ -10590,6 +12057,7 @@ async fn invocation_hourly_rollup_ignores_running_and_pending_for_failure_counts
                 status: Some("running".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: None,
+                model_canonical: None,
                 input_tokens: None,
                 output_tokens: None,
                 cache_input_tokens: None,
@@ -10605,6 +12073,7 @@ async fn invocation_hourly_rollup_ignores_running_and_pending_for_failure_counts
                     "[upstream_response_failed] upstream response stream reported failure"
                         .to_string(),
                 ),
+                http_status: None,
                 failure_kind: Some("upstream_response_failed".to_string()),
                 failure_class: Some("service_failure".to_string()),
                 is_actionable: Some(1),
@@ -10625,6 +12094,7 @@ async fn invocation_hourly_rollup_ignores_running_and_pending_for_failure_counts
                 status: Some("pending".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: None,
+                model_canonical: None,
                 input_tokens: None,
                 output_tokens: None,
                 cache_input_tokens: None,
@@ -10640,6 +12110,7 @@ async fn invocation_hourly_rollup_ignores_running_and_pending_for_failure_counts
                     "[downstream_closed] downstream closed while streaming upstream response"
                         .to_string(),
                 ),
+                http_status: None,
                 failure_kind: Some("downstream_closed".to_string()),
                 failure_class: Some("client_abort".to_string()),
                 is_actionable: Some(0),
@@ -10660,6 +12131,7 @@ async fn invocation_hourly_rollup_ignores_running_and_pending_for_failure_counts
                 status: Some("failed".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: None,
+                model_canonical: None,
                 input_tokens: None,
                 output_tokens: None,
                 cache_input_tokens: None,
@@ -10672,6 +12144,7 @@ async fn invocation_hourly_rollup_ignores_running_and_pending_for_failure_counts
                 cost_output: None,
                 cost_reasoning: None,
                 error_message: Some("upstream stream error".to_string()),
+                http_status: None,
                 failure_kind: Some("upstream_stream_error".to_string()),
                 failure_class: None,
                 is_actionable: Some(1),
@@ -10687,6 +12160,7 @@ async fn invocation_hourly_rollup_ignores_running_and_pending_for_failure_counts
             },
         ],
         &INVOCATION_HOURLY_ROLLUP_TARGETS,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("upsert hourly rollup source rows");
@@ -10729,6 +12203,7 @@ async fn invocation_hourly_rollup_excludes_structured_legacy_http_200_failures_f
                 status: Some("http_200".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: None,
+                model_canonical: None,
                 input_tokens: None,
                 output_tokens: None,
                 cache_input_tokens: None,
@@ -10741,6 +12216,7 @@ async fn invocation_hourly_rollup_excludes_structured_legacy_http_200_failures_f
                 cost_output: None,
                 cost_reasoning: None,
                 error_message: Some("".to_string()),
+                http_status: None,
                 failure_kind: None,
                 failure_class: None,
                 is_actionable: None,
@@ -10761,6 +12237,7 @@ async fn invocation_hourly_rollup_excludes_structured_legacy_http_200_failures_f
                 status: Some("http_200".to_string()),
                 detail_level: DETAIL_LEVEL_FULL.to_string(),
                 model: None,
+                model_canonical: None,
                 input_tokens: None,
                 output_tokens: None,
                 cache_input_tokens: None,
@@ -10773,6 +12250,7 @@ async fn invocation_hourly_rollup_excludes_structured_legacy_http_200_failures_f
                 cost_output: None,
                 cost_reasoning: None,
                 error_message: Some("".to_string()),
+                http_status: None,
                 failure_kind: Some("upstream_response_failed".to_string()),
                 failure_class: Some("service_failure".to_string()),
                 is_actionable: Some(1),
@@ -10788,6 +12266,7 @@ async fn invocation_hourly_rollup_excludes_structured_legacy_http_200_failures_f
             },
         ],
         &INVOCATION_HOURLY_ROLLUP_TARGETS,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("upsert hourly rollup source rows");
@@ -10876,6 +12355,8 @@ async fn combined_totals_ignore_null_status_for_success_failure_counts() {
         &state.pool,
         StatsFilter::All,
         InvocationSourceScope::ProxyOnly,
+        false,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("query combined totals");
@@ -10996,6 +12477,11 @@ async fn account_scoped_summary_and_timeseries_filter_by_payload_upstream_accoun
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: Some(42),
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -11023,6 +12509,11 @@ async fn account_scoped_summary_and_timeseries_filter_by_payload_upstream_accoun
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: Some(42),
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -11694,6 +13185,7 @@ async fn usage_breakdown_keeps_archived_boundary_partial_hour_during_partial_arc
             status: Some("success".to_string()),
             detail_level: DETAIL_LEVEL_FULL.to_string(),
             model: None,
+            model_canonical: None,
             input_tokens: None,
             output_tokens: Some(20_i64),
             cache_input_tokens: None,
@@ -11706,6 +13198,7 @@ async fn usage_breakdown_keeps_archived_boundary_partial_hour_during_partial_arc
             cost_output: None,
             cost_reasoning: None,
             error_message: None,
+            http_status: None,
             failure_kind: None,
             failure_class: Some("none".to_string()),
             is_actionable: Some(0_i64),
@@ -11720,6 +13213,7 @@ async fn usage_breakdown_keeps_archived_boundary_partial_hour_during_partial_arc
             t_persist_ms: None,
         }],
         &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_USAGE_BREAKDOWN],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("seed boundary partial-hour usage breakdown rollup row");
@@ -11841,6 +13335,7 @@ async fn usage_breakdown_avoids_double_counting_partially_materialized_archive_r
             status: Some("success".to_string()),
             detail_level: DETAIL_LEVEL_FULL.to_string(),
             model: None,
+            model_canonical: None,
             input_tokens: None,
             output_tokens: Some(10_i64),
             cache_input_tokens: None,
@@ -11853,6 +13348,7 @@ async fn usage_breakdown_avoids_double_counting_partially_materialized_archive_r
             cost_output: None,
             cost_reasoning: None,
             error_message: None,
+            http_status: None,
             failure_kind: None,
             failure_class: Some("none".to_string()),
             is_actionable: Some(0_i64),
@@ -11867,6 +13363,7 @@ async fn usage_breakdown_avoids_double_counting_partially_materialized_archive_r
             t_persist_ms: None,
         }],
         &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_USAGE_BREAKDOWN],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("seed partially materialized usage breakdown rollup row");
@@ -12094,6 +13591,7 @@ async fn usage_breakdown_prefers_breakdown_specific_archive_progress_over_shared
             status: Some("success".to_string()),
             detail_level: DETAIL_LEVEL_FULL.to_string(),
             model: None,
+            model_canonical: None,
             input_tokens: None,
             output_tokens: Some(10_i64),
             cache_input_tokens: None,
@@ -12106,6 +13604,7 @@ async fn usage_breakdown_prefers_breakdown_specific_archive_progress_over_shared
             cost_output: None,
             cost_reasoning: None,
             error_message: None,
+            http_status: None,
             failure_kind: None,
             failure_class: Some("none".to_string()),
             is_actionable: Some(0_i64),
@@ -12120,6 +13619,7 @@ async fn usage_breakdown_prefers_breakdown_specific_archive_progress_over_shared
             t_persist_ms: None,
         }],
         &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_USAGE_BREAKDOWN],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("seed partially materialized usage breakdown rollup row");
@@ -12662,6 +14162,7 @@ async fn natural_day_summary_reports_retry_wait_and_non_success_usage() {
     recompute_invocation_hourly_rollups_for_ids_tx(
         tx.as_mut(),
         &[501, 502, 503, 504, 505, 506, 507],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("rebuild summary rollups for direct test rows");
@@ -13652,6 +15153,8 @@ async fn dashboard_activity_summary_rates_and_in_progress_are_account_sum() {
         live_phase: None,
         error_message: None,
         downstream_status_code: None,
+        http_status: None,
+        label: None,
         failure_kind: None,
         blocked_binding: None,
         blocked_binding_json: None,
@@ -14549,6 +16052,8 @@ async fn dashboard_activity_cached_snapshot_overlays_new_live_accounts() {
                 live_phase: None,
                 error_message: None,
                 downstream_status_code: None,
+                http_status: None,
+                label: None,
                 failure_kind: None,
                 blocked_binding: None,
                 blocked_binding_json: None,
@@ -14603,6 +16108,7 @@ async fn dashboard_activity_cached_snapshot_overlays_new_live_accounts() {
                 created_at: live_only_occurred_at.clone(),
             },
             Utc::now(),
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         );
 
     let Json(cached_response) = fetch_dashboard_activity(
@@ -14836,6 +16342,8 @@ async fn dashboard_activity_cached_snapshot_refreshes_terminal_recent_without_li
             live_phase: None,
             error_message: None,
             downstream_status_code: None,
+            http_status: None,
+            label: None,
             failure_kind: None,
             blocked_binding: None,
             blocked_binding_json: None,
@@ -14967,6 +16475,9 @@ fn extract_subscription_snapshot_payload(prepared: PreparedSubscriptionConnectio
         SubscriptionEventEnvelope::Snapshot { payload, .. }
         | SubscriptionEventEnvelope::Replay { payload, .. }
         | SubscriptionEventEnvelope::Live { payload, .. } => payload,
+        SubscriptionEventEnvelope::SeedIncomplete { .. } => {
+            panic!("subscription snapshot seed should not fail")
+        }
     }
 }
 
@@ -15025,6 +16536,8 @@ async fn dashboard_activity_subscription_live_overlay_updates_cached_snapshot_wi
             live_phase: Some("requesting".to_string()),
             error_message: None,
             downstream_status_code: None,
+            http_status: None,
+            label: None,
             failure_kind: None,
             blocked_binding: None,
             blocked_binding_json: None,
@@ -15107,6 +16620,8 @@ async fn dashboard_activity_subscription_live_overlay_updates_cached_snapshot_wi
                 live_phase: None,
                 error_message: None,
                 downstream_status_code: None,
+                http_status: None,
+                label: None,
                 failure_kind: None,
                 blocked_binding: None,
                 blocked_binding_json: None,
@@ -15161,6 +16676,7 @@ async fn dashboard_activity_subscription_live_overlay_updates_cached_snapshot_wi
                 created_at: occurred_at.clone(),
             },
             Utc::now(),
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         );
 
     let live_snapshot = capture_dashboard_activity_live_snapshot(state.as_ref())
@@ -15293,6 +16809,8 @@ async fn dashboard_activity_subscription_terminal_refresh_is_deferred_until_ttl(
                     live_phase: None,
                     error_message: None,
                     downstream_status_code: None,
+                    http_status: None,
+                    label: None,
                     failure_kind: None,
                     blocked_binding: None,
                     blocked_binding_json: None,
@@ -15630,15 +17148,18 @@ async fn account_activity_v2_upgrade_repair_does_not_double_count_new_live_rows(
     .expect("seed pre-v2 shared live cursor");
 
     assert_eq!(
-        replay_live_invocation_hourly_rollups(&state.pool)
+        replay_live_invocation_hourly_rollups(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
             .await
             .expect("replay new live row while v2 repair lags"),
         1
     );
     assert_eq!(
-        repair_live_invocation_account_activity_v2_once(&state.pool)
-            .await
-            .expect("repair v2 rows through shared cursor"),
+        repair_live_invocation_account_activity_v2_once(
+            &state.pool,
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS
+        )
+        .await
+        .expect("repair v2 rows through shared cursor"),
         2
     );
 
@@ -15851,6 +17372,7 @@ async fn account_activity_v2_rollup_keeps_latest_timestamp_and_value_paired() {
         tx.as_mut(),
         &rows,
         &[HOURLY_ROLLUP_TARGET_UPSTREAM_ACCOUNT_ACTIVITY_V2],
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("upsert v2 account activity");
@@ -16190,8 +17712,8 @@ async fn dashboard_activity_rollup_fallback_preserves_account_attribution_for_pa
     .await
     .expect("insert partial-hour recovery account");
 
-    let range_window =
-        resolve_range_window("1d", Shanghai).expect("1d dashboard range should resolve");
+    let range_window = resolve_range_window_at("1d", Shanghai, state.clock.now())
+        .expect("1d dashboard range should resolve");
     let full_hour_end_epoch = align_bucket_epoch(range_window.end.timestamp(), 3_600, 0);
     let full_hour_start_epoch = full_hour_end_epoch - 3_600;
     let full_hour_start = Utc
@@ -17459,6 +18981,8 @@ async fn upstream_account_activity_uses_pool_attempt_account_for_running_rows()
             live_phase: None,
             error_message: None,
             downstream_status_code: None,
+            http_status: None,
+            label: None,
             failure_kind: None,
             blocked_binding: None,
             blocked_binding_json: None,
@@ -17964,6 +19488,11 @@ async fn account_scoped_historical_stats_include_unmaterialized_archived_hours()
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: Some(account_id),
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -18035,6 +19564,8 @@ async fn combined_totals_count_legacy_null_status_failures_when_error_metadata_e
         &state.pool,
         StatsFilter::All,
         InvocationSourceScope::ProxyOnly,
+        false,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("query combined totals");
@@ -18092,6 +19623,8 @@ async fn combined_totals_count_legacy_null_status_failures_when_only_downstream_
         &state.pool,
         StatsFilter::All,
         InvocationSourceScope::ProxyOnly,
+        false,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("query combined totals");
@@ -18142,6 +19675,8 @@ async fn combined_totals_treat_legacy_http_200_without_error_as_success() {
         &state.pool,
         StatsFilter::All,
         InvocationSourceScope::ProxyOnly,
+        false,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("query combined totals");
@@ -18199,6 +19734,8 @@ async fn combined_totals_count_legacy_http_200_failures_when_only_downstream_err
         &state.pool,
         StatsFilter::All,
         InvocationSourceScope::ProxyOnly,
+        false,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("query combined totals");
@@ -18209,6 +19746,81 @@ async fn combined_totals_count_legacy_http_200_failures_when_only_downstream_err
     assert_f64_close(totals.total_cost, 0.08);
 }
 
+#[tokio::test]
+async fn empty_error_success_policy_covers_null_status_rows_only_when_enabled() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+
+    assert!(!invocation_status_is_success_like(None, None, false));
+    assert!(invocation_status_is_success_like(None, None, true));
+    assert!(invocation_status_is_success_like(
+        Some("  "),
+        Some(""),
+        true
+    ));
+    assert!(!invocation_status_is_success_like(None, Some("boom"), true));
+    assert!(!invocation_status_is_success_like(
+        Some("failed"),
+        None,
+        true
+    ));
+
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+    for (id, invoke_id, status, error_message) in [
+        (207_i64, "empty-error-null-status", None, None),
+        (208_i64, "empty-error-blank-status", Some(""), Some("")),
+        (
+            209_i64,
+            "empty-error-null-status-failed",
+            None,
+            Some("boom"),
+        ),
+        (210_i64, "empty-error-success", Some("success"), None),
+    ] {
+        sqlx::query(
+            "INSERT INTO codex_invocations (id, invoke_id, occurred_at, source, status, error_message, raw_response) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, '{}')",
+        )
+        .bind(id)
+        .bind(invoke_id)
+        .bind(&occurred_at)
+        .bind(SOURCE_PROXY)
+        .bind(status)
+        .bind(error_message)
+        .execute(&state.pool)
+        .await
+        .expect("insert status policy row");
+    }
+
+    for (enabled, expected) in [(false, 1_i64), (true, 3_i64)] {
+        let sql = format!(
+            "SELECT COUNT(*) FROM codex_invocations WHERE {}",
+            invocation_status_is_success_like_sql("status", "error_message", enabled)
+        );
+        let count: i64 = sqlx::query_scalar(&sql)
+            .fetch_one(&state.pool)
+            .await
+            .expect("count success-like rows");
+        assert_eq!(count, expected, "treat_empty_error_as_success={enabled}");
+
+        let totals = query_combined_totals(
+            &state.pool,
+            StatsFilter::All,
+            InvocationSourceScope::ProxyOnly,
+            false,
+            enabled,
+        )
+        .await
+        .expect("query combined totals");
+        assert_eq!(
+            totals.success_count, expected,
+            "treat_empty_error_as_success={enabled}"
+        );
+    }
+}
+
 #[test]
 fn resolve_failure_classification_keeps_unknown_legacy_http_200_failure_kinds_actionable() {
     let classification = resolve_failure_classification(
@@ -18254,6 +19866,7 @@ fn invocation_archive_pruned_success_details_require_empty_legacy_http_200_error
         status: Some("http_200".to_string()),
         detail_level: DETAIL_LEVEL_STRUCTURED_ONLY.to_string(),
         model: None,
+        model_canonical: None,
         input_tokens: None,
         output_tokens: None,
         cache_input_tokens: None,
@@ -18266,6 +19879,7 @@ fn invocation_archive_pruned_success_details_require_empty_legacy_http_200_error
         cost_output: None,
         cost_reasoning: None,
         error_message: Some("upstream parse failed".to_string()),
+        http_status: None,
         failure_kind: None,
         failure_class: None,
         is_actionable: None,
@@ -18280,7 +19894,10 @@ fn invocation_archive_pruned_success_details_require_empty_legacy_http_200_error
         t_persist_ms: None,
     };
     assert!(
-        !invocation_archive_has_pruned_success_details(&[failed_legacy_http_200]),
+        !invocation_archive_has_pruned_success_details(
+            &[failed_legacy_http_200],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS
+        ),
         "legacy http_200 rows with a non-empty error message must not suppress archive rollups",
     );
 
@@ -18291,6 +19908,7 @@ fn invocation_archive_pruned_success_details_require_empty_legacy_http_200_error
         status: Some("http_200".to_string()),
         detail_level: DETAIL_LEVEL_STRUCTURED_ONLY.to_string(),
         model: None,
+        model_canonical: None,
         input_tokens: None,
         output_tokens: None,
         cache_input_tokens: None,
@@ -18303,6 +19921,7 @@ fn invocation_archive_pruned_success_details_require_empty_legacy_http_200_error
         cost_output: None,
         cost_reasoning: None,
         error_message: Some("   ".to_string()),
+        http_status: None,
         failure_kind: None,
         failure_class: None,
         is_actionable: None,
@@ -18317,7 +19936,10 @@ fn invocation_archive_pruned_success_details_require_empty_legacy_http_200_error
         t_persist_ms: None,
     };
     assert!(
-        invocation_archive_has_pruned_success_details(&[success_like_legacy_http_200]),
+        invocation_archive_has_pruned_success_details(
+            &[success_like_legacy_http_200],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS
+        ),
         "legacy http_200 rows with an empty error message should still count as pruned success-like rows",
     );
 
@@ -18328,6 +19950,7 @@ fn invocation_archive_pruned_success_details_require_empty_legacy_http_200_error
         status: Some("http_200".to_string()),
         detail_level: DETAIL_LEVEL_STRUCTURED_ONLY.to_string(),
         model: None,
+        model_canonical: None,
         input_tokens: None,
         output_tokens: None,
         cache_input_tokens: None,
@@ -18340,6 +19963,7 @@ fn invocation_archive_pruned_success_details_require_empty_legacy_http_200_error
         cost_output: None,
         cost_reasoning: None,
         error_message: Some("   ".to_string()),
+        http_status: None,
         failure_kind: Some("upstream_response_failed".to_string()),
         failure_class: Some("service_failure".to_string()),
         is_actionable: Some(1),
@@ -18354,7 +19978,10 @@ fn invocation_archive_pruned_success_details_require_empty_legacy_http_200_error
         t_persist_ms: None,
     };
     assert!(
-        !invocation_archive_has_pruned_success_details(&[structured_failure_legacy_http_200]),
+        !invocation_archive_has_pruned_success_details(
+            &[structured_failure_legacy_http_200],
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS
+        ),
         "legacy http_200 rows with structured failure metadata must not be treated as pruned successes",
     );
 }
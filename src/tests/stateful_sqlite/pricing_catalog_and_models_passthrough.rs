@@ -1047,6 +1047,11 @@ async fn proxy_openai_v1_models_falls_back_when_merge_body_decode_times_out() {
         dashboard_activity_live_broadcast_seq: Arc::new(AtomicU64::new(0)),
         dashboard_activity_live_broadcast_running: Arc::new(AtomicBool::new(false)),
         process_started_at_utc: chrono::Utc::now(),
+        clock: Arc::new(SystemClock),
+        archive_db_attached: false,
+        sqlite_json1_available: true,
+        cost_spike_tracker: Arc::new(CostSpikeTracker::default()),
+        log_buffer: Arc::new(LogRingBuffer::default()),
         dashboard_network_speed_cache: Arc::new(
             crate::dashboard_network_speed::DashboardNetworkSpeedCache::new(chrono::Utc::now()),
         ),
@@ -1083,6 +1088,8 @@ async fn proxy_openai_v1_models_falls_back_when_merge_body_decode_times_out() {
         )),
         maintenance_stats_cache: Arc::new(Mutex::new(StatsMaintenanceCacheState::default())),
         system_status_cache: Arc::new(Mutex::new(SystemStatusCacheState::default())),
+        timeseries_response_cache: Arc::new(TimeseriesResponseCache::default()),
+        sse_connections: Arc::new(SseConnectionRegistry::default()),
         pool_routing_reservations: Arc::new(std::sync::Mutex::new(HashMap::new())),
         pool_routing_runtime_cache: Arc::new(Mutex::new(None)),
         pool_live_attempt_ids: Arc::new(std::sync::Mutex::new(HashSet::new())),
@@ -1395,7 +1402,10 @@ async fn proxy_capture_persist_and_broadcast_emits_records_summary_and_quota() {
             BroadcastPayload::DashboardActivityLive { .. } => {
                 saw_dashboard_live = true;
             }
-            BroadcastPayload::Version { .. } | BroadcastPayload::PoolAttempts { .. } => {}
+            BroadcastPayload::Version { .. }
+            | BroadcastPayload::PoolAttempts { .. }
+            | BroadcastPayload::Alert { .. }
+            | BroadcastPayload::Deleted { .. } => {}
         }
 
         if saw_record
@@ -1438,6 +1448,320 @@ async fn proxy_capture_persist_and_broadcast_emits_records_summary_and_quota() {
     assert!(record.failure_kind.is_none());
 }
 
+#[tokio::test]
+async fn cost_spike_alert_broadcasts_once_rolling_average_is_exceeded() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://example-upstream.invalid/").expect("valid upstream base url"),
+    )
+    .await;
+    let mut rx = state.broadcaster.subscribe();
+    let mut live_rx = state.subscription_hub.subscribe();
+    let alerts_topic = SubscriptionTopicDescriptor {
+        topic: "alerts.live".to_string(),
+        params: BTreeMap::new(),
+    };
+    let prepared = state
+        .subscription_hub
+        .prepare_connection(state.clone(), vec![alerts_topic.clone()], Vec::new())
+        .await
+        .expect("prepare alerts subscription");
+    match prepared.initial.as_slice() {
+        [SubscriptionEventEnvelope::Snapshot { payload, .. }] => assert_eq!(payload, &Value::Null),
+        other => panic!("unexpected alerts snapshot: {other:?}"),
+    }
+
+    for idx in 0..COST_SPIKE_MIN_SAMPLES {
+        broadcast_cost_spike_if_detected(
+            state.as_ref(),
+            &format!("proxy-baseline-{idx}"),
+            Some(0.01),
+        );
+    }
+    broadcast_cost_spike_if_detected(state.as_ref(), "proxy-no-cost", None);
+    broadcast_cost_spike_if_detected(state.as_ref(), "proxy-within-factor", Some(0.025));
+    assert!(
+        rx.try_recv().is_err(),
+        "costs within the spike factor should not alert"
+    );
+
+    broadcast_cost_spike_if_detected(state.as_ref(), "proxy-cost-spike", Some(0.5));
+    let alert = rx.try_recv().expect("cost spike should broadcast an alert");
+    match &alert {
+        BroadcastPayload::Alert { kind, detail } => {
+            assert_eq!(kind, ALERT_KIND_COST_SPIKE);
+            assert!(
+                detail.contains("proxy-cost-spike"),
+                "unexpected detail: {detail}"
+            );
+        }
+        other => panic!("unexpected broadcast payload: {other:?}"),
+    }
+
+    // The subscription listener fans the alert out to `alerts.live` clients.
+    state
+        .subscription_hub
+        .handle_internal_broadcast(state.clone(), alert)
+        .await;
+    let dispatch = live_rx
+        .try_recv()
+        .expect("alert should reach the alerts.live topic");
+    assert_eq!(dispatch.descriptor, alerts_topic);
+    assert_eq!(dispatch.payload["type"], json!("alert"));
+    assert_eq!(dispatch.payload["kind"], json!(ALERT_KIND_COST_SPIKE));
+}
+
+#[tokio::test]
+async fn refund_records_reduce_total_cost_and_are_counted() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://example-upstream.invalid/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+    for (invoke_id, cost) in [("refund-charge", 0.5_f64), ("refund-credit", -0.2_f64)] {
+        sqlx::query(
+            "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, cost, raw_response) \
+             VALUES (?1, ?2, ?3, 'success', ?4, '{}')",
+        )
+        .bind(invoke_id)
+        .bind(&occurred_at)
+        .bind(SOURCE_PROXY)
+        .bind(cost)
+        .execute(&state.pool)
+        .await
+        .expect("insert refund test invocation");
+    }
+
+    let Json(stats) = fetch_stats(State(state.clone()))
+        .await
+        .expect("fetch stats with a refund");
+    assert_eq!(stats.total_count, 2);
+    assert_f64_close(stats.total_cost, 0.3);
+    assert_eq!(stats.refunds, Some(1));
+
+    // The refund count follows the same source scope as the totals.
+    sqlx::query(
+        "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, cost, raw_response) \
+         VALUES ('refund-other-source', ?1, 'xy', 'success', -0.1, '{}')",
+    )
+    .bind(&occurred_at)
+    .execute(&state.pool)
+    .await
+    .expect("insert non-proxy refund invocation");
+    for (source_scope, expected) in [
+        (InvocationSourceScope::All, 2_i64),
+        (InvocationSourceScope::ProxyOnly, 1),
+    ] {
+        let count = query_all_time_refund_invocation_count(&state.pool, source_scope, false)
+            .await
+            .expect("count live refund invocations");
+        assert_eq!(count, expected, "{source_scope:?}");
+    }
+
+    // Once archives exist, archived refunds come from the materialized rollup column
+    // plus the live tail past the rollup cursor; the archive file itself is never read.
+    replay_live_invocation_hourly_rollups(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+        .await
+        .expect("materialize live refunds into hourly rollups");
+    sqlx::query("DELETE FROM codex_invocations")
+        .execute(&state.pool)
+        .await
+        .expect("drop materialized live rows as if archived");
+    sqlx::query(
+        r#"
+        INSERT INTO archive_batches (dataset, month_key, file_path, sha256, row_count, status, created_at)
+        VALUES ('codex_invocations', '2026-01', '/nonexistent/refund-archive.sqlite.gz', 'unused', 3, ?1, datetime('now'))
+        "#,
+    )
+    .bind(ARCHIVE_STATUS_COMPLETED)
+    .execute(&state.pool)
+    .await
+    .expect("insert unreadable archive batch manifest");
+    sqlx::query(
+        "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, cost, raw_response) \
+         VALUES ('refund-live-tail', ?1, ?2, 'success', -0.3, '{}')",
+    )
+    .bind(&occurred_at)
+    .bind(SOURCE_PROXY)
+    .execute(&state.pool)
+    .await
+    .expect("insert live tail refund invocation");
+    for (source_scope, expected) in [
+        (InvocationSourceScope::All, 3_i64),
+        (InvocationSourceScope::ProxyOnly, 2),
+    ] {
+        let count = query_all_time_refund_invocation_count(&state.pool, source_scope, false)
+            .await
+            .expect("count materialized refund invocations");
+        assert_eq!(count, expected, "{source_scope:?}");
+    }
+
+    // Refunds never count as spikes and don't drag the rolling average down.
+    let mut rx = state.broadcaster.subscribe();
+    for idx in 0..COST_SPIKE_MIN_SAMPLES {
+        broadcast_cost_spike_if_detected(
+            state.as_ref(),
+            &format!("refund-baseline-{idx}"),
+            Some(0.01),
+        );
+    }
+    broadcast_cost_spike_if_detected(state.as_ref(), "refund-negative", Some(-5.0));
+    broadcast_cost_spike_if_detected(state.as_ref(), "refund-within-factor", Some(0.025));
+    assert!(rx.try_recv().is_err(), "refunds should not trigger alerts");
+
+    // Rollup deltas keep their sign so a refund-only hour nets out correctly.
+    assert_f64_close(subtract_signed_cost(-0.2, 0.1), -0.3);
+}
+
+#[tokio::test]
+async fn proxy_capture_persist_rounds_costs_to_configured_precision() {
+    assert_eq!(round_cost_to_precision(0.0012300000001, 6), 0.00123);
+    assert_eq!(round_cost_to_precision(1.23456789, 4), 1.2346);
+
+    let state = test_state_with_openai_base(
+        Url::parse("https://example-upstream.invalid/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+    let invoke_id = "proxy-cost-precision";
+    let mut record = test_proxy_capture_record(invoke_id, &occurred_at);
+    record.cost = Some(0.0012300000001);
+    record.cost_breakdown = Some(ProxyCostBreakdown {
+        input: 0.0004500000002,
+        output: 0.0007799999999,
+        ..ProxyCostBreakdown::default()
+    });
+
+    persist_and_broadcast_proxy_capture(state.as_ref(), Instant::now(), record)
+        .await
+        .expect("persist should succeed");
+
+    let (cost, cost_input, cost_output): (Option<f64>, Option<f64>, Option<f64>) = sqlx::query_as(
+        "SELECT cost, cost_input, cost_output FROM codex_invocations WHERE invoke_id = ?1",
+    )
+    .bind(invoke_id)
+    .fetch_one(&state.pool)
+    .await
+    .expect("persisted invocation should exist");
+    assert_eq!(cost, Some(0.00123));
+    assert_eq!(cost_input, Some(0.00045));
+    assert_eq!(cost_output, Some(0.00078));
+}
+
+#[tokio::test]
+async fn proxy_capture_persist_maps_model_aliases_to_one_canonical_model() {
+    assert!(parse_model_aliases("gpt-4o-2024-08-06").is_err());
+    let mut config = test_config();
+    config.model_aliases =
+        parse_model_aliases(" gpt-4o-2024-08-06 = gpt-4o ,").expect("valid model aliases");
+    let state = test_state_from_config(config, true).await;
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+
+    for (invoke_id, model) in [
+        ("proxy-model-alias-dated", "gpt-4o-2024-08-06"),
+        ("proxy-model-alias-plain", "gpt-4o"),
+    ] {
+        let mut record = test_proxy_capture_record(invoke_id, &occurred_at);
+        record.model = Some(model.to_string());
+        persist_and_broadcast_proxy_capture(state.as_ref(), Instant::now(), record)
+            .await
+            .expect("persist should succeed");
+    }
+
+    let rows: Vec<(String, Option<String>)> = sqlx::query_as(
+        "SELECT model, model_canonical FROM codex_invocations \
+         WHERE invoke_id LIKE 'proxy-model-alias-%' ORDER BY invoke_id",
+    )
+    .fetch_all(&state.pool)
+    .await
+    .expect("persisted invocations should exist");
+    assert_eq!(
+        rows,
+        vec![
+            ("gpt-4o-2024-08-06".to_string(), Some("gpt-4o".to_string())),
+            ("gpt-4o".to_string(), Some("gpt-4o".to_string())),
+        ]
+    );
+
+    let Json(efficiency) = fetch_model_efficiency(
+        State(state.clone()),
+        Query(ModelEfficiencyQuery {
+            range: "1d".to_string(),
+            time_zone: Some("Asia/Shanghai".to_string()),
+        }),
+    )
+    .await
+    .expect("fetch model efficiency");
+    assert_eq!(efficiency.items.len(), 1);
+    assert_eq!(efficiency.items[0].model, "gpt-4o");
+    assert_eq!(efficiency.items[0].total_tokens, 30);
+
+    let breakdown_models: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT normalized_model, SUM(request_count) FROM upstream_account_usage_breakdown_hourly \
+         GROUP BY normalized_model",
+    )
+    .fetch_all(&state.pool)
+    .await
+    .expect("usage breakdown rollups should exist");
+    assert_eq!(breakdown_models, vec![("gpt-4o".to_string(), 2)]);
+}
+
+#[tokio::test]
+async fn proxy_capture_persist_skips_models_outside_the_allowlist() {
+    let mut config = test_config();
+    config.model_allowlist = Some(BTreeSet::from(["gpt-5.4".to_string()]));
+    let state = test_state_from_config(config, true).await;
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+
+    for (invoke_id, model) in [
+        ("proxy-allowlist-kept", "gpt-5.4"),
+        ("proxy-allowlist-skipped", "gpt-5.2-codex"),
+    ] {
+        let mut record = test_proxy_capture_record(invoke_id, &occurred_at);
+        record.model = Some(model.to_string());
+        let mut running = record.clone();
+        running.status = "running".to_string();
+        state
+            .proxy_runtime_invocations
+            .upsert(api_invocation_from_runtime_record(&running));
+        persist_and_broadcast_proxy_capture(state.as_ref(), Instant::now(), record)
+            .await
+            .expect("persist should succeed");
+    }
+
+    let invoke_ids: Vec<String> = sqlx::query_scalar(
+        "SELECT invoke_id FROM codex_invocations \
+         WHERE invoke_id LIKE 'proxy-allowlist-%' ORDER BY invoke_id",
+    )
+    .fetch_all(&state.pool)
+    .await
+    .expect("load persisted invocations");
+    assert_eq!(invoke_ids, vec!["proxy-allowlist-kept".to_string()]);
+
+    assert!(
+        state
+            .proxy_runtime_invocations
+            .snapshot()
+            .iter()
+            .all(|record| record.invoke_id != "proxy-allowlist-skipped"),
+        "filtered models should not linger in the runtime overlay"
+    );
+    let Json(response) = list_invocations(
+        State(state.clone()),
+        Query(ListQuery {
+            limit: Some(10),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("list invocations should succeed");
+    let listed = response
+        .records
+        .iter()
+        .map(|record| record.invoke_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(listed, vec!["proxy-allowlist-kept"]);
+}
+
 #[tokio::test]
 async fn proxy_capture_persist_and_broadcast_skips_duplicate_records() {
     let state = test_state_with_openai_base(
@@ -1530,6 +1854,8 @@ async fn broadcast_summary_if_changed_skips_duplicate_payloads() {
         failure_count: 0,
         total_cost: 0.5,
         total_tokens: 42,
+        avg_cost_per_request: 0.5,
+        avg_tokens_per_request: 42.0,
         usage_breakdown: None,
         in_progress_conversation_count: Some(3),
         in_progress_retry_conversation_count: Some(0),
@@ -1538,6 +1864,7 @@ async fn broadcast_summary_if_changed_skips_duplicate_payloads() {
         non_success_cost: None,
         non_success_tokens: None,
         maintenance: None,
+        refunds: None,
     };
 
     assert!(
@@ -2298,6 +2625,11 @@ async fn proxy_capture_target_compact_estimates_cost_and_flows_into_stats_withou
             settlement_hour: None,
             time_zone: None,
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -2,7 +2,7 @@ use super::*;
 use serde_json::json;
 
 async fn materialize_prompt_cache_hourly_rollups(pool: &Pool<Sqlite>) {
-    sync_hourly_rollups_from_live_tables(pool)
+    sync_hourly_rollups_from_live_tables(pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt-cache hourly rollups for read-only prompt-cache tests");
 }
@@ -1447,7 +1447,7 @@ async fn prompt_cache_conversations_count_mode_reports_all_skipped_newer_inactiv
         .await;
     }
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before legacy activity-minutes read");
 
@@ -1786,7 +1786,7 @@ async fn prompt_cache_conversations_activity_minutes_include_running_only_rows_a
     )
     .await;
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before working-conversations read");
 
@@ -2133,7 +2133,7 @@ async fn prompt_cache_conversations_activity_minutes_paginated_preserves_sort_an
     )
     .await;
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before paginated working read");
 
@@ -2300,7 +2300,7 @@ async fn prompt_cache_conversations_activity_minutes_paginated_cursor_uses_worki
     )
     .await;
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before tied paginated read");
 
@@ -5365,7 +5365,7 @@ async fn prompt_cache_conversations_cache_ignores_proxy_captures_without_prompt_
     .await
     .expect("insert prompt cache seed row");
 
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("materialize prompt cache rollups before cached read");
 
@@ -5749,9 +5749,10 @@ async fn backfill_proxy_prompt_cache_keys_updates_payload_and_is_idempotent() {
     )
     .await;
 
-    let summary_first = backfill_proxy_prompt_cache_keys(&pool, None)
-        .await
-        .expect("first prompt cache key backfill should succeed");
+    let summary_first =
+        backfill_proxy_prompt_cache_keys(&pool, None, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("first prompt cache key backfill should succeed");
     assert_eq!(summary_first.scanned, 1);
     assert_eq!(summary_first.updated, 1);
     assert_eq!(summary_first.skipped_missing_file, 0);
@@ -5771,9 +5772,10 @@ async fn backfill_proxy_prompt_cache_keys_updates_payload_and_is_idempotent() {
         "legacy codexSessionId key should be removed during backfill"
     );
 
-    let summary_second = backfill_proxy_prompt_cache_keys(&pool, None)
-        .await
-        .expect("second prompt cache key backfill should succeed");
+    let summary_second =
+        backfill_proxy_prompt_cache_keys(&pool, None, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("second prompt cache key backfill should succeed");
     assert_eq!(summary_second.scanned, 0);
     assert_eq!(summary_second.updated, 0);
 
@@ -5801,7 +5803,7 @@ async fn sync_hourly_rollups_rebuilds_after_prompt_cache_key_backfill_updates_ex
     )
     .await;
 
-    bootstrap_hourly_rollups(&pool)
+    bootstrap_hourly_rollups(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("initial hourly rollup bootstrap should succeed");
 
@@ -5814,12 +5816,13 @@ async fn sync_hourly_rollups_rebuilds_after_prompt_cache_key_backfill_updates_ex
     .expect("query initial prompt cache rollup count");
     assert_eq!(initial_count, 0);
 
-    let summary = backfill_proxy_prompt_cache_keys(&pool, None)
-        .await
-        .expect("prompt cache key backfill should succeed");
+    let summary =
+        backfill_proxy_prompt_cache_keys(&pool, None, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("prompt cache key backfill should succeed");
     assert_eq!(summary.updated, 1);
 
-    sync_hourly_rollups_from_live_tables(&pool)
+    sync_hourly_rollups_from_live_tables(&pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("hourly rollup sync should rebuild invocation-backed rollups");
 
@@ -5884,9 +5887,10 @@ async fn backfill_proxy_prompt_cache_keys_tracks_skip_counters() {
     )
     .await;
 
-    let summary = backfill_proxy_prompt_cache_keys(&pool, None)
-        .await
-        .expect("prompt cache key backfill should succeed");
+    let summary =
+        backfill_proxy_prompt_cache_keys(&pool, None, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("prompt cache key backfill should succeed");
     assert_eq!(summary.scanned, 4);
     assert_eq!(summary.updated, 1);
     assert_eq!(summary.skipped_missing_file, 1);
@@ -6185,9 +6189,13 @@ async fn backfill_proxy_prompt_cache_keys_reads_from_fallback_root_for_relative_
     )
     .await;
 
-    let summary = backfill_proxy_prompt_cache_keys(&pool, Some(&fallback_root))
-        .await
-        .expect("prompt cache key backfill with fallback root should succeed");
+    let summary = backfill_proxy_prompt_cache_keys(
+        &pool,
+        Some(&fallback_root),
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("prompt cache key backfill with fallback root should succeed");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
     assert_eq!(summary.skipped_missing_file, 0);
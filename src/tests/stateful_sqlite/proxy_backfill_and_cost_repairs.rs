@@ -259,9 +259,10 @@ async fn backfill_proxy_usage_tokens_updates_missing_tokens_idempotently() {
         .expect("insert proxy row");
     }
 
-    let summary_first = backfill_proxy_usage_tokens(&pool, None)
-        .await
-        .expect("first backfill should succeed");
+    let summary_first =
+        backfill_proxy_usage_tokens(&pool, None, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("first backfill should succeed");
     assert_eq!(summary_first.scanned, row_count as u64);
     assert_eq!(summary_first.updated, row_count as u64);
 
@@ -313,9 +314,10 @@ async fn backfill_proxy_usage_tokens_updates_missing_tokens_idempotently() {
         Some(row_count as i64)
     );
 
-    let summary_second = backfill_proxy_usage_tokens(&pool, None)
-        .await
-        .expect("second backfill should succeed");
+    let summary_second =
+        backfill_proxy_usage_tokens(&pool, None, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("second backfill should succeed");
     assert_eq!(summary_second.scanned, 0);
     assert_eq!(summary_second.updated, 0);
 
@@ -346,9 +348,14 @@ async fn backfill_proxy_usage_tokens_reads_from_fallback_root_for_relative_paths
         .await
         .expect("query fallback row id");
 
-    let summary = backfill_proxy_usage_tokens_up_to_id(&pool, row_id, Some(&fallback_root))
-        .await
-        .expect("usage backfill with fallback root should succeed");
+    let summary = backfill_proxy_usage_tokens_up_to_id(
+        &pool,
+        row_id,
+        Some(&fallback_root),
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("usage backfill with fallback root should succeed");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
     assert_eq!(summary.skipped_missing_file, 0);
@@ -396,9 +403,14 @@ async fn backfill_proxy_usage_tokens_respects_snapshot_upper_bound() {
             .await
             .expect("query second id");
 
-    let summary_first = backfill_proxy_usage_tokens_up_to_id(&pool, first_id, None)
-        .await
-        .expect("backfill up to first id should succeed");
+    let summary_first = backfill_proxy_usage_tokens_up_to_id(
+        &pool,
+        first_id,
+        None,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("backfill up to first id should succeed");
     assert_eq!(summary_first.scanned, 1);
     assert_eq!(summary_first.updated, 1);
 
@@ -417,9 +429,14 @@ async fn backfill_proxy_usage_tokens_respects_snapshot_upper_bound() {
     assert_eq!(first_total_tokens, Some(110));
     assert_eq!(second_total_tokens, None);
 
-    let summary_second = backfill_proxy_usage_tokens_up_to_id(&pool, second_id, None)
-        .await
-        .expect("backfill up to second id should succeed");
+    let summary_second = backfill_proxy_usage_tokens_up_to_id(
+        &pool,
+        second_id,
+        None,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("backfill up to second id should succeed");
     assert_eq!(summary_second.scanned, 1);
     assert_eq!(summary_second.updated, 1);
 
@@ -468,9 +485,10 @@ async fn backfill_proxy_missing_costs_updates_dated_model_alias_and_is_idempoten
         )]),
     };
 
-    let summary_first = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("first cost backfill should succeed");
+    let summary_first =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("first cost backfill should succeed");
     assert_eq!(summary_first.scanned, 1);
     assert_eq!(summary_first.updated, 1);
     assert_eq!(summary_first.skipped_unpriced_model, 0);
@@ -503,9 +521,10 @@ async fn backfill_proxy_missing_costs_updates_dated_model_alias_and_is_idempoten
         Some("unit-cost-backfill@response-tier")
     );
 
-    let summary_second = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("second cost backfill should be idempotent");
+    let summary_second =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("second cost backfill should be idempotent");
     assert_eq!(summary_second.scanned, 0);
     assert_eq!(summary_second.updated, 0);
 }
@@ -572,9 +591,10 @@ async fn backfill_proxy_missing_costs_backfills_standard_rows_with_missing_billi
         )]),
     };
 
-    let summary = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("standard missing billing tier row should be backfilled");
+    let summary =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("standard missing billing tier row should be backfilled");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
     assert_eq!(summary.skipped_unpriced_model, 0);
@@ -612,9 +632,10 @@ async fn backfill_proxy_missing_costs_backfills_standard_rows_with_missing_billi
     assert_eq!(payload_json["serviceTier"], "default");
     assert_eq!(payload_json["billingServiceTier"], "default");
 
-    let summary_second = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("standard row backfill should become idempotent");
+    let summary_second =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("standard row backfill should become idempotent");
     assert_eq!(summary_second.scanned, 0);
     assert_eq!(summary_second.updated, 0);
 }
@@ -681,9 +702,10 @@ async fn backfill_proxy_missing_costs_rewrites_stale_standard_billing_service_ti
         )]),
     };
 
-    let summary = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("stale standard billing tier row should be backfilled");
+    let summary =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("stale standard billing tier row should be backfilled");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
     assert_eq!(summary.skipped_unpriced_model, 0);
@@ -700,9 +722,10 @@ async fn backfill_proxy_missing_costs_rewrites_stale_standard_billing_service_ti
     assert_eq!(payload_json["serviceTier"], "default");
     assert_eq!(payload_json["billingServiceTier"], "default");
 
-    let summary_second = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("stale standard billing tier row should become idempotent");
+    let summary_second =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("stale standard billing tier row should become idempotent");
     assert_eq!(summary_second.scanned, 0);
     assert_eq!(summary_second.updated, 0);
 }
@@ -791,9 +814,10 @@ async fn backfill_proxy_missing_costs_reprices_api_keys_requested_tier_rows() {
         )]),
     };
 
-    let summary = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("api keys requested-tier cost backfill should succeed");
+    let summary =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("api keys requested-tier cost backfill should succeed");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
     assert_eq!(summary.skipped_unpriced_model, 0);
@@ -921,9 +945,10 @@ async fn backfill_proxy_missing_costs_reprices_failed_api_keys_requested_tier_ro
         )]),
     };
 
-    let summary = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("failed api keys requested-tier cost backfill should succeed");
+    let summary =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("failed api keys requested-tier cost backfill should succeed");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
     assert_eq!(summary.skipped_unpriced_model, 0);
@@ -1060,9 +1085,10 @@ async fn backfill_proxy_missing_costs_prefers_payload_account_kind_snapshots_ove
         )]),
     };
 
-    let summary_first = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("first api keys snapshot backfill should succeed");
+    let summary_first =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("first api keys snapshot backfill should succeed");
     assert_eq!(summary_first.scanned, 1);
     assert_eq!(summary_first.updated, 1);
 
@@ -1101,9 +1127,10 @@ async fn backfill_proxy_missing_costs_prefers_payload_account_kind_snapshots_ove
     .await
     .expect("regress api keys snapshot row");
 
-    let summary_second = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("second api keys snapshot backfill should still use payload snapshot");
+    let summary_second =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("second api keys snapshot backfill should still use payload snapshot");
     assert_eq!(summary_second.scanned, 1);
     assert_eq!(summary_second.updated, 1);
 
@@ -1232,9 +1259,10 @@ async fn backfill_proxy_missing_costs_falls_back_to_safe_live_api_key_account_ki
         )]),
     };
 
-    let summary = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("safe live api keys rows should use the requested-tier strategy");
+    let summary =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("safe live api keys rows should use the requested-tier strategy");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
 
@@ -1354,9 +1382,10 @@ async fn backfill_proxy_missing_costs_keeps_response_tier_when_live_account_crea
         )]),
     };
 
-    let summary = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("late live accounts should fall back to the response-tier strategy");
+    let summary =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("late live accounts should fall back to the response-tier strategy");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
 
@@ -1454,9 +1483,10 @@ async fn backfill_proxy_missing_costs_keeps_non_api_keys_rows_on_response_tier_s
         )]),
     };
 
-    let summary = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("non-api-keys rows should stay on the response-tier strategy");
+    let summary =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("non-api-keys rows should stay on the response-tier strategy");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
 
@@ -1550,9 +1580,10 @@ async fn backfill_proxy_missing_costs_skips_rows_already_settled_with_requested_
         )]),
     };
 
-    let summary = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("settled requested-tier rows should remain idempotent");
+    let summary =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("settled requested-tier rows should remain idempotent");
     assert_eq!(summary.scanned, 0);
     assert_eq!(summary.updated, 0);
 }
@@ -1607,9 +1638,10 @@ async fn backfill_proxy_missing_costs_skips_missing_model_or_usage_and_retries_u
         )]),
     };
 
-    let summary = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("cost backfill should succeed");
+    let summary =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("cost backfill should succeed");
     assert_eq!(summary.scanned, 1);
     assert_eq!(summary.updated, 1);
     assert_eq!(summary.skipped_unpriced_model, 1);
@@ -1642,9 +1674,10 @@ async fn backfill_proxy_missing_costs_skips_missing_model_or_usage_and_retries_u
         Some(expected_attempt_version.as_str())
     );
 
-    let summary_same_version = backfill_proxy_missing_costs(&pool, &catalog)
-        .await
-        .expect("same-version cost backfill should skip attempted unpriced rows");
+    let summary_same_version =
+        backfill_proxy_missing_costs(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+            .await
+            .expect("same-version cost backfill should skip attempted unpriced rows");
     assert_eq!(summary_same_version.scanned, 0);
     assert_eq!(summary_same_version.updated, 0);
 
@@ -1677,10 +1710,13 @@ async fn backfill_proxy_missing_costs_skips_missing_model_or_usage_and_retries_u
             ),
         ]),
     };
-    let summary_same_version_after_pricing_update =
-        backfill_proxy_missing_costs(&pool, &updated_catalog_same_version)
-            .await
-            .expect("same-version pricing update should retry previously unpriced rows");
+    let summary_same_version_after_pricing_update = backfill_proxy_missing_costs(
+        &pool,
+        &updated_catalog_same_version,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("same-version pricing update should retry previously unpriced rows");
     assert_eq!(summary_same_version_after_pricing_update.scanned, 1);
     assert_eq!(summary_same_version_after_pricing_update.updated, 1);
     assert_eq!(
@@ -1783,6 +1819,183 @@ async fn build_sqlite_connect_options_enforces_wal_and_busy_timeout_defaults() {
     let _ = fs::remove_dir_all(&temp_dir);
 }
 
+#[tokio::test]
+async fn apply_sqlite_tuning_options_sets_synchronous_and_cache_size() {
+    let temp_dir = make_temp_test_dir("sqlite-tuning-options");
+    let db_path = temp_dir.join("tuning.db");
+    let db_url = test_sqlite_url_for_path(&db_path);
+
+    let options = apply_sqlite_tuning_options(
+        build_sqlite_connect_options(
+            &db_url,
+            Duration::from_secs(DEFAULT_SQLITE_BUSY_TIMEOUT_SECS),
+        )
+        .expect("build sqlite connect options"),
+        SqliteSynchronousMode::Normal,
+        Some(32_768),
+    );
+    let mut conn = SqliteConnection::connect_with(&options)
+        .await
+        .expect("connect sqlite with tuning options");
+
+    let synchronous: i64 = sqlx::query_scalar("PRAGMA synchronous;")
+        .fetch_one(&mut conn)
+        .await
+        .expect("read pragma synchronous");
+    assert_eq!(synchronous, 1, "NORMAL maps to synchronous=1");
+
+    let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size;")
+        .fetch_one(&mut conn)
+        .await
+        .expect("read pragma cache_size");
+    assert_eq!(cache_size, -32_768);
+
+    conn.close().await.expect("close sqlite connection");
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
+#[tokio::test]
+async fn archive_db_attach_merges_historical_invocations_read_only() {
+    let temp_dir = make_temp_test_dir("archive-db-attach");
+    let archive_path = temp_dir.join("legacy history.db");
+    let archive_options = build_sqlite_connect_options(
+        &test_sqlite_url_for_path(&archive_path),
+        Duration::from_secs(DEFAULT_SQLITE_BUSY_TIMEOUT_SECS),
+    )
+    .expect("build archive connect options");
+    let mut archive_conn = SqliteConnection::connect_with(&archive_options)
+        .await
+        .expect("create archive sqlite");
+    sqlx::query(
+        r#"
+        CREATE TABLE codex_invocations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            occurred_at TEXT NOT NULL,
+            source TEXT,
+            status TEXT,
+            error_message TEXT,
+            total_tokens INTEGER,
+            cost REAL
+        )
+        "#,
+    )
+    .execute(&mut archive_conn)
+    .await
+    .expect("create archive invocations table");
+    // RFC3339 rows are normalized to Asia/Shanghai: 02:30Z is 10:30 local and
+    // 16:30Z is 00:30 local on the next day, outside the queried window.
+    for (occurred_at, total_tokens, cost) in [
+        ("2024-03-01 10:05:00", 100_i64, 0.5_f64),
+        ("2024-03-01 10:45:00", 50_i64, 0.25_f64),
+        ("2024-03-01T02:30:00Z", 20_i64, 0.125_f64),
+        ("2024-03-01T16:30:00Z", 40_i64, 1.0_f64),
+    ] {
+        sqlx::query(
+            "INSERT INTO codex_invocations (occurred_at, source, status, total_tokens, cost) VALUES (?1, ?2, 'success', ?3, ?4)",
+        )
+        .bind(occurred_at)
+        .bind(SOURCE_PROXY)
+        .bind(total_tokens)
+        .bind(cost)
+        .execute(&mut archive_conn)
+        .await
+        .expect("insert archive invocation");
+    }
+    archive_conn.close().await.expect("close archive sqlite");
+
+    assert!(resolve_archive_db_attach_uri(Some(&temp_dir.join("missing.db"))).is_none());
+    let archive_db_uri =
+        resolve_archive_db_attach_uri(Some(&archive_path)).expect("archive attach uri");
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .after_connect(move |conn, _meta| {
+            let archive_db_uri = archive_db_uri.clone();
+            Box::pin(async move { attach_archive_db(conn, &archive_db_uri).await })
+        })
+        .connect("sqlite::memory:")
+        .await
+        .expect("connect live sqlite with archive attached");
+    ensure_schema(&pool)
+        .await
+        .expect("schema should initialize");
+    assert!(
+        crate::stats::archive_db_invocations_available(&pool)
+            .await
+            .expect("probe archive database")
+    );
+    insert_timeseries_invocation(&pool, "live-1", "2026-06-22 12:00:00", "success", None).await;
+
+    let totals = query_invocation_totals(
+        &pool,
+        StatsFilter::All,
+        InvocationSourceScope::All,
+        true,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("query merged totals");
+    assert_eq!(totals.total_count, 5);
+    assert_eq!(totals.success_count, 5);
+    assert_eq!(totals.total_tokens, 220);
+    assert_f64_close(totals.total_cost, 1.885);
+    let live_only = query_invocation_totals(
+        &pool,
+        StatsFilter::All,
+        InvocationSourceScope::All,
+        false,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("query live totals");
+    assert_eq!(live_only.total_count, 1);
+
+    // Shanghai 2024-03-01 00:00 .. 2024-03-02 00:00.
+    let range_start = Utc.with_ymd_and_hms(2024, 2, 29, 16, 0, 0).unwrap();
+    let range_end = Utc.with_ymd_and_hms(2024, 3, 1, 16, 0, 0).unwrap();
+    let ranged = query_invocation_totals(
+        &pool,
+        StatsFilter::Range(range_start, range_end),
+        InvocationSourceScope::All,
+        true,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("query ranged archive totals");
+    assert_eq!(ranged.total_count, 3);
+    assert_eq!(ranged.total_tokens, 170);
+    let hourly_rows = crate::stats::query_archive_db_hourly_rollup_records(
+        &pool,
+        range_start,
+        range_end,
+        InvocationSourceScope::All,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("query archive hourly rows");
+    assert_eq!(hourly_rows.len(), 1);
+    assert_eq!(
+        hourly_rows[0].bucket_start_epoch,
+        Utc.with_ymd_and_hms(2024, 3, 1, 2, 0, 0)
+            .unwrap()
+            .timestamp()
+    );
+    assert_eq!(hourly_rows[0].total_count, 3);
+
+    let write_err = sqlx::query(
+        "INSERT INTO archive_db.codex_invocations (occurred_at, status) VALUES ('2024-03-02 00:00:00', 'success')",
+    )
+    .execute(&pool)
+    .await
+    .expect_err("archive database should be attached read-only");
+    assert!(
+        write_err.to_string().contains("readonly"),
+        "unexpected error: {write_err}"
+    );
+
+    pool.close().await;
+    let _ = fs::remove_dir_all(&temp_dir);
+}
+
 pub(crate) async fn file_backed_test_state_with_busy_timeout(
     prefix: &str,
     busy_timeout: Duration,
@@ -1838,6 +2051,11 @@ pub(crate) async fn file_backed_test_state_with_busy_timeout(
         dashboard_activity_live_broadcast_seq: Arc::new(AtomicU64::new(0)),
         dashboard_activity_live_broadcast_running: Arc::new(AtomicBool::new(false)),
         process_started_at_utc: chrono::Utc::now(),
+        clock: Arc::new(SystemClock),
+        archive_db_attached: false,
+        sqlite_json1_available: true,
+        cost_spike_tracker: Arc::new(CostSpikeTracker::default()),
+        log_buffer: Arc::new(LogRingBuffer::default()),
         dashboard_network_speed_cache: Arc::new(
             crate::dashboard_network_speed::DashboardNetworkSpeedCache::new(chrono::Utc::now()),
         ),
@@ -1866,6 +2084,8 @@ pub(crate) async fn file_backed_test_state_with_busy_timeout(
         )),
         maintenance_stats_cache: Arc::new(Mutex::new(StatsMaintenanceCacheState::default())),
         system_status_cache: Arc::new(Mutex::new(SystemStatusCacheState::default())),
+        timeseries_response_cache: Arc::new(TimeseriesResponseCache::default()),
+        sse_connections: Arc::new(SseConnectionRegistry::default()),
         hourly_rollup_sync_lock: Arc::new(Mutex::new(())),
         pool_routing_reservations: Arc::new(std::sync::Mutex::new(HashMap::new())),
         pool_routing_runtime_cache: Arc::new(Mutex::new(None)),
@@ -1900,10 +2120,11 @@ async fn dashboard_read_endpoints_stay_queryable_under_sqlite_write_lock() {
         &state.pool,
         Instant::now(),
         test_proxy_capture_record("dashboard-lock-read", &occurred_at),
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("seed dashboard lock record");
-    sync_hourly_rollups_from_live_tables(&state.pool)
+    sync_hourly_rollups_from_live_tables(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect("seed hourly rollups before lock");
     insert_parallel_work_invocation(
@@ -1946,6 +2167,11 @@ async fn dashboard_read_endpoints_stay_queryable_under_sqlite_write_lock() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -2225,8 +2451,9 @@ async fn run_backfill_with_retry_succeeds_after_lock_release() {
 
     let started = Instant::now();
     let pool_for_task = pool.clone();
-    let backfill_task =
-        tokio::spawn(async move { run_backfill_with_retry(&pool_for_task, None).await });
+    let backfill_task = tokio::spawn(async move {
+        run_backfill_with_retry(&pool_for_task, None, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS).await
+    });
 
     tokio::time::sleep(Duration::from_millis(120)).await;
     sqlx::query("COMMIT")
@@ -2287,8 +2514,9 @@ async fn run_backfill_with_retry_fails_when_lock_persists() {
 
     let started = Instant::now();
     let pool_for_task = pool.clone();
-    let backfill_task =
-        tokio::spawn(async move { run_backfill_with_retry(&pool_for_task, None).await });
+    let backfill_task = tokio::spawn(async move {
+        run_backfill_with_retry(&pool_for_task, None, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS).await
+    });
     let err = backfill_task
         .await
         .expect("join backfill task")
@@ -2333,7 +2561,7 @@ async fn run_backfill_with_retry_does_not_retry_non_lock_errors() {
         .expect("connect sqlite pool");
 
     // Intentionally skip schema initialization to force a deterministic non-lock error.
-    let err = run_backfill_with_retry(&pool, None)
+    let err = run_backfill_with_retry(&pool, None, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect_err("backfill should fail immediately on non-lock errors");
     assert!(
@@ -2404,7 +2632,12 @@ async fn run_cost_backfill_with_retry_succeeds_after_lock_release() {
     let pool_for_task = pool.clone();
     let catalog_for_task = catalog.clone();
     let backfill_task = tokio::spawn(async move {
-        run_cost_backfill_with_retry(&pool_for_task, &catalog_for_task).await
+        run_cost_backfill_with_retry(
+            &pool_for_task,
+            &catalog_for_task,
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+        )
+        .await
     });
 
     tokio::time::sleep(Duration::from_millis(120)).await;
@@ -2454,7 +2687,7 @@ async fn run_cost_backfill_with_retry_does_not_retry_non_lock_errors() {
     };
 
     // Intentionally skip schema initialization to force a deterministic non-lock error.
-    let err = run_cost_backfill_with_retry(&pool, &catalog)
+    let err = run_cost_backfill_with_retry(&pool, &catalog, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
         .await
         .expect_err("cost backfill should fail immediately on non-lock errors");
     assert!(
@@ -2503,6 +2736,11 @@ async fn quota_latest_returns_degraded_when_empty() {
         dashboard_activity_live_broadcast_seq: Arc::new(AtomicU64::new(0)),
         dashboard_activity_live_broadcast_running: Arc::new(AtomicBool::new(false)),
         process_started_at_utc: chrono::Utc::now(),
+        clock: Arc::new(SystemClock),
+        archive_db_attached: false,
+        sqlite_json1_available: true,
+        cost_spike_tracker: Arc::new(CostSpikeTracker::default()),
+        log_buffer: Arc::new(LogRingBuffer::default()),
         dashboard_network_speed_cache: Arc::new(
             crate::dashboard_network_speed::DashboardNetworkSpeedCache::new(chrono::Utc::now()),
         ),
@@ -2533,6 +2771,8 @@ async fn quota_latest_returns_degraded_when_empty() {
         )),
         maintenance_stats_cache: Arc::new(Mutex::new(StatsMaintenanceCacheState::default())),
         system_status_cache: Arc::new(Mutex::new(SystemStatusCacheState::default())),
+        timeseries_response_cache: Arc::new(TimeseriesResponseCache::default()),
+        sse_connections: Arc::new(SseConnectionRegistry::default()),
         pool_routing_reservations: Arc::new(std::sync::Mutex::new(HashMap::new())),
         pool_routing_runtime_cache: Arc::new(Mutex::new(None)),
         pool_live_attempt_ids: Arc::new(std::sync::Mutex::new(HashSet::new())),
@@ -2577,6 +2817,56 @@ async fn quota_latest_returns_seeded_historical_snapshot() {
     assert_f64_close(snapshot.total_cost, 10.0);
 }
 
+#[tokio::test]
+async fn quota_latest_returns_not_modified_for_matching_etag() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let captured_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+    seed_quota_snapshot(&state.pool, &captured_at).await;
+
+    let first_response = latest_quota_snapshot_cached(State(state.clone()), HeaderMap::new())
+        .await
+        .expect("fetch first quota snapshot response");
+    assert_eq!(first_response.status(), StatusCode::OK);
+    let etag = first_response
+        .headers()
+        .get(axum::http::header::ETAG)
+        .expect("etag header")
+        .clone();
+    assert!(
+        etag.to_str().expect("ascii etag").starts_with("W/"),
+        "quota snapshot etag should be weak"
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(axum::http::header::IF_NONE_MATCH, etag.clone());
+    let second_response = latest_quota_snapshot_cached(State(state.clone()), headers.clone())
+        .await
+        .expect("fetch revalidated quota snapshot response");
+    assert_eq!(second_response.status(), StatusCode::NOT_MODIFIED);
+    let second_body = axum::body::to_bytes(second_response.into_body(), usize::MAX)
+        .await
+        .expect("read not-modified body");
+    assert!(second_body.is_empty());
+
+    let newer_captured_at = format_naive(
+        (Utc::now() + ChronoDuration::minutes(1))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    seed_quota_snapshot(&state.pool, &newer_captured_at).await;
+    let third_response = latest_quota_snapshot_cached(State(state), headers)
+        .await
+        .expect("fetch refreshed quota snapshot response");
+    assert_eq!(third_response.status(), StatusCode::OK);
+    assert_ne!(
+        third_response.headers().get(axum::http::header::ETAG),
+        Some(&etag)
+    );
+}
+
 pub(crate) async fn insert_timeseries_invocation(
     pool: &SqlitePool,
     invoke_id: &str,
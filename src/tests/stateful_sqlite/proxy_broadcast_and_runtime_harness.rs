@@ -2457,6 +2457,11 @@ async fn forward_proxy_timeseries_keeps_hourly_attempt_history_after_retention()
             settlement_hour: None,
             time_zone: Some("UTC".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -2622,6 +2627,11 @@ async fn forward_proxy_timeseries_preserves_materialized_history_when_same_month
             settlement_hour: None,
             time_zone: Some("UTC".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -2770,6 +2780,11 @@ async fn forward_proxy_timeseries_reads_pending_archived_node_health_without_mat
             settlement_hour: None,
             time_zone: Some("UTC".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -3032,6 +3047,11 @@ async fn forward_proxy_timeseries_ignores_cached_rows_for_pending_node_health_ar
             settlement_hour: None,
             time_zone: Some("UTC".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -103,6 +103,11 @@ async fn proxy_openai_v1_allows_slow_upload_with_short_timeout() {
         dashboard_activity_live_broadcast_seq: Arc::new(AtomicU64::new(0)),
         dashboard_activity_live_broadcast_running: Arc::new(AtomicBool::new(false)),
         process_started_at_utc: chrono::Utc::now(),
+        clock: Arc::new(SystemClock),
+        archive_db_attached: false,
+        sqlite_json1_available: true,
+        cost_spike_tracker: Arc::new(CostSpikeTracker::default()),
+        log_buffer: Arc::new(LogRingBuffer::default()),
         dashboard_network_speed_cache: Arc::new(
             crate::dashboard_network_speed::DashboardNetworkSpeedCache::new(chrono::Utc::now()),
         ),
@@ -133,6 +138,8 @@ async fn proxy_openai_v1_allows_slow_upload_with_short_timeout() {
         )),
         maintenance_stats_cache: Arc::new(Mutex::new(StatsMaintenanceCacheState::default())),
         system_status_cache: Arc::new(Mutex::new(SystemStatusCacheState::default())),
+        timeseries_response_cache: Arc::new(TimeseriesResponseCache::default()),
+        sse_connections: Arc::new(SseConnectionRegistry::default()),
         pool_routing_reservations: Arc::new(std::sync::Mutex::new(HashMap::new())),
         pool_routing_runtime_cache: Arc::new(Mutex::new(None)),
         pool_live_attempt_ids: Arc::new(std::sync::Mutex::new(HashSet::new())),
@@ -1234,6 +1241,87 @@ async fn oauth_codex_capture_upstream(request: axum::extract::Request) -> Respon
         .into_response()
 }
 
+async fn gzip_json_upstream(headers: HeaderMap) -> Response {
+    let accept_encoding = headers
+        .get(http_header::ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = json!({ "acceptEncoding": accept_encoding }).to_string();
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(body.as_bytes())
+        .expect("write gzip payload");
+    (
+        StatusCode::OK,
+        [
+            (http_header::CONTENT_TYPE, "application/json"),
+            (http_header::CONTENT_ENCODING, "gzip"),
+        ],
+        encoder.finish().expect("finish gzip payload"),
+    )
+        .into_response()
+}
+
+#[tokio::test]
+async fn shared_http_client_decodes_gzip_while_proxy_client_passes_it_through() {
+    let app = Router::new().route("/usage", get(gzip_json_upstream));
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind gzip upstream");
+    let addr = listener.local_addr().expect("gzip upstream addr");
+    let server = tokio::spawn(async move {
+        axum::serve(listener, app)
+            .await
+            .expect("gzip upstream should run");
+    });
+    let url = format!("http://{addr}/usage");
+    let http_clients = HttpClients::build(&test_config()).expect("http clients");
+
+    let decoded: Value = http_clients
+        .shared
+        .get(&url)
+        .send()
+        .await
+        .expect("shared client request")
+        .json()
+        .await
+        .expect("shared client decodes gzip JSON");
+    let accept_encoding = decoded["acceptEncoding"].as_str().unwrap_or_default();
+    assert!(accept_encoding.contains("gzip"), "{accept_encoding}");
+    assert!(accept_encoding.contains("br"), "{accept_encoding}");
+
+    let relayed = http_clients
+        .proxy
+        .get(&url)
+        .header(http_header::ACCEPT_ENCODING, "gzip")
+        .send()
+        .await
+        .expect("proxy client request");
+    assert_eq!(
+        relayed
+            .headers()
+            .get(http_header::CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok()),
+        Some("gzip")
+    );
+    let raw = relayed.bytes().await.expect("proxy client body");
+    assert_eq!(&raw[..2], &[0x1f, 0x8b]);
+
+    let dedicated = http_clients
+        .client_for_parallelism(true)
+        .expect("dedicated client")
+        .get(&url)
+        .header(http_header::ACCEPT_ENCODING, "gzip")
+        .send()
+        .await
+        .expect("dedicated client request");
+    let raw = dedicated.bytes().await.expect("dedicated client body");
+    assert_eq!(&raw[..2], &[0x1f, 0x8b]);
+
+    server.abort();
+}
+
 pub(crate) async fn spawn_oauth_codex_capture_upstream() -> (String, JoinHandle<()>) {
     let app = Router::new().route(
         "/backend-api/codex/*path",
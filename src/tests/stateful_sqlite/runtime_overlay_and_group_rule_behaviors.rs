@@ -243,6 +243,7 @@ pub(crate) fn test_proxy_capture_record(invoke_id: &str, occurred_at: &str) -> P
         invoke_id: invoke_id.to_string(),
         occurred_at: occurred_at.to_string(),
         model: Some("gpt-5.2-codex".to_string()),
+        model_canonical: None,
         usage: ParsedUsage {
             input_tokens: Some(12),
             output_tokens: Some(3),
@@ -1220,6 +1221,11 @@ async fn account_timeseries_replaces_stale_db_runtime_placeholder_after_account_
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: Some(17),
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -1270,6 +1276,11 @@ async fn account_timeseries_replaces_stale_db_runtime_placeholder_after_account_
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: Some(17),
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -1292,6 +1303,11 @@ async fn account_timeseries_replaces_stale_db_runtime_placeholder_after_account_
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: Some(23),
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -1695,10 +1711,14 @@ async fn persist_proxy_capture_record_finalizes_existing_running_row_in_place()
         0.0,
     );
 
-    let running = persist_proxy_capture_runtime_record(&state.pool, running_record)
-        .await
-        .expect("persist running record")
-        .expect("running record should be inserted");
+    let running = persist_proxy_capture_runtime_record(
+        &state.pool,
+        running_record,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("persist running record")
+    .expect("running record should be inserted");
     assert!(running.id > 0);
     assert_eq!(running.status.as_deref(), Some("running"));
 
@@ -1715,10 +1735,15 @@ async fn persist_proxy_capture_record_finalizes_existing_running_row_in_place()
         truncated: false,
         truncated_reason: None,
     };
-    let finalized = persist_proxy_capture_record(&state.pool, Instant::now(), terminal_record)
-        .await
-        .expect("finalize record")
-        .expect("terminal update should reuse running row");
+    let finalized = persist_proxy_capture_record(
+        &state.pool,
+        Instant::now(),
+        terminal_record,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("finalize record")
+    .expect("terminal update should reuse running row");
 
     assert_eq!(finalized.id, running.id);
     assert_eq!(finalized.status.as_deref(), Some("success"));
@@ -1815,10 +1840,14 @@ async fn persist_proxy_capture_record_repairs_proxy_interrupted_recovery_row_wit
         120.0,
     );
 
-    let running = persist_proxy_capture_runtime_record(&state.pool, running_record)
-        .await
-        .expect("persist running record")
-        .expect("running record should be inserted");
+    let running = persist_proxy_capture_runtime_record(
+        &state.pool,
+        running_record,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("persist running record")
+    .expect("running record should be inserted");
     sqlx::query(
         r#"
         UPDATE codex_invocations
@@ -1890,6 +1919,7 @@ async fn persist_proxy_capture_record_repairs_proxy_interrupted_recovery_row_wit
         &state.pool,
         Instant::now(),
         test_proxy_capture_record(invoke_id, occurred_at),
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("finalize record")
@@ -69,6 +69,11 @@ async fn hourly_timeseries_omits_pre_cutoff_partial_hour_rollups() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         },
         Shanghai,
         InvocationSourceScope::ProxyOnly,
@@ -116,6 +121,11 @@ async fn forward_proxy_timeseries_rejects_non_hour_aligned_timezones() {
             settlement_hour: None,
             time_zone: Some("Asia/Kolkata".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -158,6 +168,11 @@ async fn timeseries_subday_bucket_stays_available_inside_live_window() {
             settlement_hour: None,
             time_zone: Some("Asia/Shanghai".to_string()),
             upstream_account_id: None,
+            min_cost: None,
+            min_tokens: None,
+            force: None,
+            ts: None,
+            resolution: None,
         }),
     )
     .await
@@ -936,6 +951,20 @@ async fn health_check_reports_starting_until_startup_is_ready() {
     state.startup_ready.store(true, Ordering::Release);
     let response = health_check(State(state)).await.into_response();
     assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get(HEALTH_BROADCAST_QUEUED_HEADER)
+            .and_then(|value| value.to_str().ok()),
+        Some("0")
+    );
+    assert_eq!(
+        response
+            .headers()
+            .get(HEALTH_BROADCAST_QUEUED_MAX_HEADER)
+            .and_then(|value| value.to_str().ok()),
+        Some("0")
+    );
     let body = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
         .expect("read health body");
@@ -1149,9 +1178,16 @@ async fn failure_classification_backfill_skips_success_rows_with_complete_defaul
     .await
     .expect("insert success row");
 
-    let outcome = backfill_failure_classification_from_cursor(&pool, 0, None, Some(10), None)
-        .await
-        .expect("run failure classification backfill");
+    let outcome = backfill_failure_classification_from_cursor(
+        &pool,
+        0,
+        None,
+        Some(10),
+        None,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("run failure classification backfill");
     assert_eq!(outcome.summary.scanned, 0);
     assert_eq!(outcome.summary.updated, 0);
     assert_eq!(outcome.next_cursor_id, 0);
@@ -1210,9 +1246,16 @@ async fn failure_classification_backfill_recovers_response_failed_records() {
     .await
     .expect("insert misrecorded success row");
 
-    let outcome = backfill_failure_classification_from_cursor(&pool, 0, None, Some(10), None)
-        .await
-        .expect("run failure classification backfill");
+    let outcome = backfill_failure_classification_from_cursor(
+        &pool,
+        0,
+        None,
+        Some(10),
+        None,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("run failure classification backfill");
     assert_eq!(outcome.summary.scanned, 1);
     assert_eq!(outcome.summary.updated, 1);
 
@@ -1327,9 +1370,16 @@ data: {{"type":"response.failed","response":{{"id":"resp_test","model":"gpt-5.4"
     .await
     .expect("insert long success row");
 
-    let outcome = backfill_failure_classification_from_cursor(&pool, 0, None, Some(10), None)
-        .await
-        .expect("run failure classification backfill");
+    let outcome = backfill_failure_classification_from_cursor(
+        &pool,
+        0,
+        None,
+        Some(10),
+        None,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("run failure classification backfill");
     assert_eq!(outcome.summary.scanned, 1);
     assert_eq!(outcome.summary.updated, 1);
 
@@ -1402,9 +1452,16 @@ async fn failure_classification_backfill_from_cursor_respects_scan_limit() {
         .expect("insert failure classification row");
     }
 
-    let first = backfill_failure_classification_from_cursor(&pool, 0, None, Some(200), None)
-        .await
-        .expect("first bounded failure classification pass");
+    let first = backfill_failure_classification_from_cursor(
+        &pool,
+        0,
+        None,
+        Some(200),
+        None,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("first bounded failure classification pass");
     assert_eq!(first.summary.scanned, 200);
     assert_eq!(first.summary.updated, 200);
     assert!(first.hit_budget);
@@ -1416,6 +1473,7 @@ async fn failure_classification_backfill_from_cursor_respects_scan_limit() {
         None,
         Some(200),
         None,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
     )
     .await
     .expect("second bounded failure classification pass");
@@ -1647,6 +1705,80 @@ async fn retention_prunes_old_legacy_http_200_success_like_invocation_details()
     cleanup_temp_test_dir(&temp_dir);
 }
 
+#[tokio::test]
+async fn retention_strips_raw_responses_older_than_raw_response_retention() {
+    let (pool, mut config, temp_dir) =
+        retention_test_pool_and_config("retention-raw-response-strip").await;
+    config.raw_response_retention_days = Some(7);
+    let raw_response = "{\"error\":{\"message\":\"upstream exploded\"}}";
+
+    for (invoke_id, days_ago) in [("old-failed-raw", 10), ("recent-failed-raw", 2)] {
+        insert_retention_invocation(
+            &pool,
+            invoke_id,
+            &shanghai_local_days_ago(days_ago, 9, 0, 0),
+            SOURCE_PROXY,
+            "failed",
+            Some("{\"endpoint\":\"/v1/responses\",\"upstreamAccountId\":7}"),
+            raw_response,
+            None,
+            None,
+            Some(321),
+            Some(0.42),
+        )
+        .await;
+    }
+
+    let summary = run_data_retention_maintenance(&pool, &config, Some(false), None)
+        .await
+        .expect("run retention raw response strip");
+    assert_eq!(summary.invocation_details_pruned, 0);
+    assert_eq!(summary.raw_response_rows_stripped, 1);
+    let stripped_payload = "{\"upstreamAccountId\":7}";
+    let expected_reclaimed = raw_response.len()
+        + "{\"endpoint\":\"/v1/responses\",\"upstreamAccountId\":7}".len()
+        - stripped_payload.len();
+    assert_eq!(
+        summary.raw_response_bytes_reclaimed,
+        expected_reclaimed as u64
+    );
+
+    let rows = sqlx::query(
+        r#"
+        SELECT invoke_id, raw_response, payload, detail_level, detail_prune_reason, total_tokens, cost
+        FROM codex_invocations
+        ORDER BY invoke_id
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("load invocations after raw response strip");
+    assert_eq!(rows[0].get::<String, _>("invoke_id"), "old-failed-raw");
+    assert_eq!(rows[0].get::<String, _>("raw_response"), "");
+    assert_eq!(
+        rows[0].get::<Option<String>, _>("payload").as_deref(),
+        Some(stripped_payload)
+    );
+    assert_eq!(
+        rows[0].get::<String, _>("detail_level"),
+        DETAIL_LEVEL_STRUCTURED_ONLY
+    );
+    assert_eq!(
+        rows[0]
+            .get::<Option<String>, _>("detail_prune_reason")
+            .as_deref(),
+        Some(DETAIL_PRUNE_REASON_RAW_RESPONSE_RETENTION)
+    );
+    assert_eq!(rows[0].get::<Option<i64>, _>("total_tokens"), Some(321));
+    assert_eq!(rows[0].get::<Option<f64>, _>("cost"), Some(0.42));
+
+    assert_eq!(rows[1].get::<String, _>("invoke_id"), "recent-failed-raw");
+    assert_eq!(rows[1].get::<String, _>("raw_response"), raw_response);
+    assert_eq!(rows[1].get::<String, _>("detail_level"), DETAIL_LEVEL_FULL);
+
+    cleanup_temp_test_dir(&temp_dir);
+}
+
 #[tokio::test]
 async fn retention_does_not_prune_legacy_http_200_rows_with_error_message() {
     let (pool, mut config, temp_dir) =
@@ -1767,15 +1899,27 @@ async fn retention_archives_old_invocations_without_changing_summary_all() {
     )
     .await;
 
-    let before = query_combined_totals(&pool, StatsFilter::All, InvocationSourceScope::All)
-        .await
-        .expect("query totals before retention");
+    let before = query_combined_totals(
+        &pool,
+        StatsFilter::All,
+        InvocationSourceScope::All,
+        false,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("query totals before retention");
     let summary = run_data_retention_maintenance(&pool, &config, Some(false), None)
         .await
         .expect("run retention archive");
-    let after = query_combined_totals(&pool, StatsFilter::All, InvocationSourceScope::All)
-        .await
-        .expect("query totals after retention");
+    let after = query_combined_totals(
+        &pool,
+        StatsFilter::All,
+        InvocationSourceScope::All,
+        false,
+        DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+    )
+    .await
+    .expect("query totals after retention");
 
     assert_eq!(summary.invocation_rows_archived, 2);
     assert_eq!(summary.archive_batches_touched, 2);
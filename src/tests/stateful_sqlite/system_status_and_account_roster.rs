@@ -257,6 +257,572 @@ async fn system_status_aggregates_counts_and_file_sizes() {
     let _ = fs::remove_dir_all(&temp_dir);
 }
 
+#[tokio::test]
+async fn admin_stats_requires_api_key_and_reports_counts() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+
+    for (invoke_id, occurred_at) in [
+        ("admin-stats-2", "2026-06-22 12:05:00"),
+        ("admin-stats-1", "2026-06-21 08:00:00"),
+        ("admin-stats-3", "2026-06-23 18:30:00"),
+    ] {
+        sqlx::query(
+            r#"
+            INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, raw_response)
+            VALUES (?1, ?2, ?3, 'success', '{}')
+            "#,
+        )
+        .bind(invoke_id)
+        .bind(occurred_at)
+        .bind(SOURCE_PROXY)
+        .execute(&state.pool)
+        .await
+        .expect("insert admin stats invocation");
+    }
+    seed_quota_snapshot(&state.pool, "2026-06-23 18:31:00").await;
+
+    let unauthorized = fetch_admin_stats(State(state.clone()), HeaderMap::new())
+        .await
+        .expect_err("admin stats should require an api key");
+    assert_eq!(unauthorized.0, StatusCode::UNAUTHORIZED);
+
+    let created = create_external_api_key_inner(
+        state.clone(),
+        CreateExternalApiKeyRequest {
+            name: "Admin Stats".to_string(),
+        },
+    )
+    .await
+    .expect("create external api key");
+    let created = serde_json::to_value(&created).expect("serialize external api key");
+    let secret = created["secret"].as_str().expect("secret present");
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::AUTHORIZATION,
+        header::HeaderValue::from_str(&format!("Bearer {secret}")).expect("valid auth header"),
+    );
+
+    let Json(stats) = fetch_admin_stats(State(state.clone()), headers)
+        .await
+        .expect("admin stats should load");
+    assert_eq!(stats.invocation_count, 3);
+    assert_eq!(stats.snapshot_count, 1);
+    assert_eq!(stats.db_file_bytes, None);
+    assert_eq!(
+        stats.oldest_invocation.as_deref(),
+        Some("2026-06-21 08:00:00")
+    );
+    assert_eq!(
+        stats.newest_invocation.as_deref(),
+        Some("2026-06-23 18:30:00")
+    );
+
+    let payload = serde_json::to_value(&stats).expect("serialize admin stats");
+    assert!(payload["dbFileBytes"].is_null());
+    assert!(payload["oldestInvocation"].as_str().is_some());
+}
+
+#[tokio::test]
+async fn admin_stats_reports_broadcast_backlog_and_its_peak() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let mut receiver = state.broadcaster.subscribe();
+    for id in [1, 2, 3] {
+        state
+            .broadcaster
+            .send(BroadcastPayload::Deleted { id })
+            .expect("queue broadcast payload");
+    }
+
+    let stats = load_admin_stats(state.as_ref())
+        .await
+        .expect("admin stats should load");
+    assert_eq!((stats.broadcast_queued, stats.broadcast_queued_max), (3, 3));
+
+    while receiver.try_recv().is_ok() {}
+    let stats = load_admin_stats(state.as_ref())
+        .await
+        .expect("admin stats should load");
+    assert_eq!((stats.broadcast_queued, stats.broadcast_queued_max), (0, 3));
+    let payload = serde_json::to_value(&stats).expect("serialize admin stats");
+    assert_eq!(payload["broadcastQueuedMax"], json!(3));
+}
+
+#[tokio::test]
+async fn admin_refresh_summaries_requires_api_key_and_rebroadcasts_changes() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+    sqlx::query(
+        "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, raw_response) \
+         VALUES ('admin-refresh-1', ?1, ?2, 'success', '{}')",
+    )
+    .bind(&occurred_at)
+    .bind(SOURCE_PROXY)
+    .execute(&state.pool)
+    .await
+    .expect("insert admin refresh invocation");
+    seed_quota_snapshot(&state.pool, &occurred_at).await;
+
+    let unauthorized = refresh_admin_summaries(State(state.clone()), HeaderMap::new())
+        .await
+        .expect_err("summary refresh should require an api key");
+    assert_eq!(unauthorized.0, StatusCode::UNAUTHORIZED);
+
+    let created = create_external_api_key_inner(
+        state.clone(),
+        CreateExternalApiKeyRequest {
+            name: "Admin Refresh".to_string(),
+        },
+    )
+    .await
+    .expect("create external api key");
+    let created = serde_json::to_value(&created).expect("serialize external api key");
+    let secret = created["secret"].as_str().expect("secret present");
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::AUTHORIZATION,
+        header::HeaderValue::from_str(&format!("Bearer {secret}")).expect("valid auth header"),
+    );
+
+    let mut receiver = state.broadcaster.subscribe();
+    let Json(first) = refresh_admin_summaries(State(state.clone()), headers.clone())
+        .await
+        .expect("summary refresh should succeed");
+    let windows = first
+        .summaries
+        .iter()
+        .map(|summary| summary.window.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(windows, vec!["all", "30m", "1h", "1d", "1mo"]);
+    assert_eq!(first.summaries[0].summary.total_count, 1);
+    assert_eq!(first.broadcast_count, 6);
+
+    let mut summary_frames = 0;
+    let mut quota_frames = 0;
+    while let Ok(payload) = receiver.try_recv() {
+        match payload {
+            BroadcastPayload::Summary { .. } => summary_frames += 1,
+            BroadcastPayload::Quota { .. } => quota_frames += 1,
+            _ => {}
+        }
+    }
+    assert_eq!((summary_frames, quota_frames), (5, 1));
+
+    let Json(second) = refresh_admin_summaries(State(state), headers)
+        .await
+        .expect("repeat summary refresh should succeed");
+    assert_eq!(second.broadcast_count, 0);
+}
+
+#[tokio::test]
+async fn bootstrap_bundles_dashboard_initial_payloads() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+    sqlx::query(
+        "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, raw_response) \
+         VALUES ('bootstrap-1', ?1, ?2, 'success', '{}')",
+    )
+    .bind(&occurred_at)
+    .bind(SOURCE_PROXY)
+    .execute(&state.pool)
+    .await
+    .expect("insert bootstrap invocation");
+    seed_quota_snapshot(&state.pool, &occurred_at).await;
+
+    let Json(bootstrap) = fetch_bootstrap(State(state.clone()))
+        .await
+        .expect("bootstrap should succeed");
+    let body = serde_json::to_value(&bootstrap).expect("serialize bootstrap");
+
+    assert!(body["version"]["backend"].is_string());
+    assert!(body["config"]["proxy"].is_object());
+    assert!(body["quota"].is_object());
+    let windows = bootstrap
+        .summaries
+        .iter()
+        .map(|summary| summary.window.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(windows, vec!["all", "30m", "1h", "1d", "1mo"]);
+    assert_eq!(bootstrap.summaries[0].summary.total_count, 1);
+    assert_eq!(
+        body["recentInvocations"][0]["invokeId"],
+        json!("bootstrap-1")
+    );
+}
+
+#[tokio::test]
+async fn delete_invocation_requires_api_key_and_broadcasts_removal() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, cost, raw_response) \
+         VALUES ('delete-me', ?1, ?2, 'success', 0.4, '{}') RETURNING id",
+    )
+    .bind(&occurred_at)
+    .bind(SOURCE_PROXY)
+    .fetch_one(&state.pool)
+    .await
+    .expect("insert invocation to delete");
+
+    let unauthorized = delete_invocation_by_id(
+        State(state.clone()),
+        HeaderMap::new(),
+        axum::extract::Path(id),
+    )
+    .await
+    .expect_err("delete should require an api key");
+    assert_eq!(unauthorized.0, StatusCode::UNAUTHORIZED);
+
+    let created = create_external_api_key_inner(
+        state.clone(),
+        CreateExternalApiKeyRequest {
+            name: "Invocation Delete".to_string(),
+        },
+    )
+    .await
+    .expect("create external api key");
+    let created = serde_json::to_value(&created).expect("serialize external api key");
+    let secret = created["secret"].as_str().expect("secret present");
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::AUTHORIZATION,
+        header::HeaderValue::from_str(&format!("Bearer {secret}")).expect("valid auth header"),
+    );
+
+    let mut receiver = state.broadcaster.subscribe();
+    let response = delete_invocation_by_id(
+        State(state.clone()),
+        headers.clone(),
+        axum::extract::Path(id),
+    )
+    .await
+    .expect("delete should succeed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    match receiver.try_recv().expect("delete should broadcast") {
+        BroadcastPayload::Deleted { id: deleted_id } => assert_eq!(deleted_id, id),
+        other => panic!("unexpected broadcast payload: {other:?}"),
+    }
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM codex_invocations")
+        .fetch_one(&state.pool)
+        .await
+        .expect("count remaining invocations");
+    assert_eq!(remaining, 0);
+
+    let missing = delete_invocation_by_id(State(state), headers, axum::extract::Path(id))
+        .await
+        .expect("repeat delete should respond");
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn delete_invocation_past_rollup_cursor_does_not_double_count_bucket() {
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+    let insert = |invoke_id: &'static str| {
+        let pool = state.pool.clone();
+        let occurred_at = occurred_at.clone();
+        async move {
+            sqlx::query_scalar::<_, i64>(
+                "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, cost, raw_response) \
+                 VALUES (?1, ?2, ?3, 'success', 0.1, '{}') RETURNING id",
+            )
+            .bind(invoke_id)
+            .bind(&occurred_at)
+            .bind(SOURCE_PROXY)
+            .fetch_one(&pool)
+            .await
+            .expect("insert cursor test invocation")
+        }
+    };
+    insert("cursor-replayed").await;
+    replay_live_invocation_hourly_rollups(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+        .await
+        .expect("replay first row into rollups");
+    let unreplayed_id = insert("cursor-unreplayed-deleted").await;
+    insert("cursor-unreplayed-kept").await;
+
+    assert!(
+        delete_persisted_invocation(
+            &state.pool,
+            unreplayed_id,
+            DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
+        )
+        .await
+        .expect("delete invocation past the rollup cursor")
+    );
+    replay_live_invocation_hourly_rollups(&state.pool, DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS)
+        .await
+        .expect("replay remaining live rows");
+
+    let bucket_epoch = invocation_bucket_start_epoch(&occurred_at).expect("bucket epoch");
+    let total_count: i64 = sqlx::query_scalar(
+        "SELECT total_count FROM invocation_rollup_hourly WHERE bucket_start_epoch = ?1 AND source = ?2",
+    )
+    .bind(bucket_epoch)
+    .bind(SOURCE_PROXY)
+    .fetch_one(&state.pool)
+    .await
+    .expect("load bucket rollup");
+    assert_eq!(total_count, 2);
+}
+
+#[tokio::test]
+async fn timeseries_route_drops_cached_body_after_invocation_delete() {
+    use tower::ServiceExt;
+
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(Utc::now().with_timezone(&Shanghai).naive_local());
+    let id = sqlx::query_scalar::<_, i64>(
+        "INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, cost, raw_response) \
+         VALUES ('delete-cached', ?1, ?2, 'success', 0.4, '{}') RETURNING id",
+    )
+    .bind(&occurred_at)
+    .bind(SOURCE_PROXY)
+    .fetch_one(&state.pool)
+    .await
+    .expect("insert invocation to delete");
+    let app = build_app_router(state.clone());
+    let timeseries_total_count = || {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .uri("/api/stats/timeseries?range=1h&bucket=1m&timeZone=UTC")
+                        .body(Body::empty())
+                        .expect("build request"),
+                )
+                .await
+                .expect("serve timeseries route");
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("read response body");
+            let payload: Value = serde_json::from_slice(&body).expect("decode timeseries");
+            payload["points"]
+                .as_array()
+                .expect("timeseries points")
+                .iter()
+                .map(|point| point["totalCount"].as_i64().expect("point totalCount"))
+                .sum::<i64>()
+        }
+    };
+    assert_eq!(timeseries_total_count().await, 1);
+
+    let created = create_external_api_key_inner(
+        state.clone(),
+        CreateExternalApiKeyRequest {
+            name: "Invocation Delete Cache".to_string(),
+        },
+    )
+    .await
+    .expect("create external api key");
+    let created = serde_json::to_value(&created).expect("serialize external api key");
+    let secret = created["secret"].as_str().expect("secret present");
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::AUTHORIZATION,
+        header::HeaderValue::from_str(&format!("Bearer {secret}")).expect("valid auth header"),
+    );
+    let generation = state.sqlite_batch_writer.write_generation();
+    let response = delete_invocation_by_id(State(state.clone()), headers, axum::extract::Path(id))
+        .await
+        .expect("delete should succeed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(state.sqlite_batch_writer.write_generation() > generation);
+
+    assert_eq!(timeseries_total_count().await, 0);
+    let stats = state.timeseries_response_cache.stats();
+    assert_eq!((stats.hits, stats.misses), (0, 2));
+}
+
+#[tokio::test]
+async fn grafana_simple_json_routes_require_api_key_and_serve_datapoints() {
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+    };
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let now = Utc::now();
+    let occurred_at = format_naive(
+        (now - ChronoDuration::minutes(10))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    sqlx::query(
+        "INSERT INTO codex_invocations \
+         (invoke_id, occurred_at, source, status, total_tokens, cost, raw_response) \
+         VALUES ('grafana-a', ?1, ?2, 'success', 1200, 0.25, '{}')",
+    )
+    .bind(&occurred_at)
+    .bind(SOURCE_PROXY)
+    .execute(&state.pool)
+    .await
+    .expect("insert grafana invocation");
+    let created = create_external_api_key_inner(
+        state.clone(),
+        CreateExternalApiKeyRequest {
+            name: "Grafana".to_string(),
+        },
+    )
+    .await
+    .expect("create external api key");
+    let created = serde_json::to_value(&created).expect("serialize external api key");
+    let authorization = format!(
+        "Bearer {}",
+        created["secret"].as_str().expect("secret present")
+    );
+    let app = build_app_router(state);
+    let send = |method: Method, uri: &'static str, body: Value, authorized: bool| {
+        let app = app.clone();
+        let authorization = authorization.clone();
+        async move {
+            let mut request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(header::CONTENT_TYPE, "application/json");
+            if authorized {
+                request = request.header(header::AUTHORIZATION, authorization);
+            }
+            let response = app
+                .oneshot(
+                    request
+                        .body(Body::from(body.to_string()))
+                        .expect("build request"),
+                )
+                .await
+                .expect("serve grafana route");
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("read response body");
+            (status, body)
+        }
+    };
+
+    let (status, _) = send(Method::GET, "/grafana/", Value::Null, false).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(Method::GET, "/grafana/", Value::Null, true).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(
+        Method::POST,
+        "/grafana/search",
+        json!({ "target": "" }),
+        true,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let metrics: Vec<String> = serde_json::from_slice(&body).expect("decode metrics");
+    assert_eq!(metrics, GRAFANA_METRICS.map(str::to_string).to_vec());
+
+    let query = json!({
+        "range": {
+            "from": format_utc_iso(now - ChronoDuration::hours(1)),
+            "to": format_utc_iso(now),
+        },
+        "intervalMs": 60_000,
+        "targets": [{ "target": "request_count" }, { "target": "total_tokens" }],
+    });
+    let (status, body) = send(Method::POST, "/grafana/query", query, true).await;
+    assert_eq!(status, StatusCode::OK);
+    let series: Value = serde_json::from_slice(&body).expect("decode series");
+    let totals = series
+        .as_array()
+        .expect("series array")
+        .iter()
+        .map(|item| {
+            let datapoints = item["datapoints"].as_array().expect("datapoints");
+            assert!(datapoints.iter().all(|pair| pair[1].is_i64()));
+            (
+                item["target"].as_str().expect("target").to_string(),
+                datapoints
+                    .iter()
+                    .map(|pair| pair[0].as_f64().expect("value"))
+                    .sum::<f64>(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        totals,
+        vec![
+            ("request_count".to_string(), 1.0),
+            ("total_tokens".to_string(), 1200.0),
+        ]
+    );
+
+    let (status, _) = send(
+        Method::POST,
+        "/grafana/query",
+        json!({
+            "range": { "from": "yesterday", "to": "now" },
+            "targets": [{ "target": "request_count" }],
+        }),
+        true,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn api_prefix_nests_api_routes_and_leaves_root_routes_alone() {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    let mut config = test_config();
+    config.api_prefix = "/v1/api".to_string();
+    let state = test_state_from_config(config, true).await;
+    let app = build_app_router(state);
+
+    for (uri, expected) in [
+        ("/v1/api/version", StatusCode::OK),
+        ("/v1/api/stats", StatusCode::OK),
+        ("/health", StatusCode::OK),
+        ("/api/version", StatusCode::NOT_FOUND),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .body(Body::empty())
+                    .expect("build request"),
+            )
+            .await
+            .expect("serve prefixed route");
+        assert_eq!(response.status(), expected, "unexpected status for {uri}");
+    }
+}
+
 #[tokio::test]
 async fn system_task_runs_filter_and_routes_serve_json() {
     use axum::{
@@ -472,6 +1038,421 @@ async fn system_task_runs_filter_and_routes_serve_json() {
     assert!(payload.get("refreshedAt").is_some());
 }
 
+#[tokio::test]
+async fn timeseries_route_serves_cached_body_until_write_generation_moves() {
+    use tower::ServiceExt;
+
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let app = build_app_router(state.clone());
+    let mut bodies = Vec::new();
+    for _ in 0..2 {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats/timeseries?range=1h&bucket=1m&timeZone=UTC")
+                    .body(Body::empty())
+                    .expect("build request"),
+            )
+            .await
+            .expect("serve timeseries route");
+        assert_eq!(response.status(), StatusCode::OK);
+        bodies.push(
+            axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("read response body"),
+        );
+    }
+    assert_eq!(bodies[0], bodies[1]);
+    let stats = state.timeseries_response_cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+
+    let params: TimeseriesQuery =
+        serde_json::from_value(json!({ "range": "1h", "bucket": "1m", "timeZone": "UTC" }))
+            .expect("decode timeseries query");
+    let key = timeseries_response_cache_key(&params);
+    let generation = state.sqlite_batch_writer.write_generation();
+    assert!(
+        state
+            .timeseries_response_cache
+            .get(&key, generation + 1, state.config.poll_interval)
+            .is_none(),
+        "a newer write generation must invalidate the cached body"
+    );
+    assert_eq!(state.timeseries_response_cache.stats().entries, 0);
+}
+
+#[test]
+fn timeseries_response_cache_evicts_least_recently_used_at_capacity() {
+    let cache = TimeseriesResponseCache::default();
+    let ttl = Duration::from_secs(60);
+    for index in 0..TIMESERIES_RESPONSE_CACHE_CAPACITY {
+        cache.insert(format!("key-{index}"), 1, Arc::new(vec![index as u8]));
+    }
+    // Touch the oldest entry so the second one becomes least recently used.
+    assert!(cache.get("key-0", 1, ttl).is_some());
+    cache.insert("overflow".to_string(), 1, Arc::new(Vec::new()));
+
+    let stats = cache.stats();
+    assert_eq!(stats.entries, TIMESERIES_RESPONSE_CACHE_CAPACITY as u64);
+    assert!(cache.get("key-0", 1, ttl).is_some());
+    assert!(cache.get("key-1", 1, ttl).is_none());
+    assert!(cache.get("overflow", 1, ttl).is_some());
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (3, 1));
+}
+
+#[tokio::test]
+async fn timeseries_route_ts_epoch_ms_switches_timestamps_to_numbers() {
+    use tower::ServiceExt;
+
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let app = build_app_router(state);
+    let fetch = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .uri(uri)
+                        .body(Body::empty())
+                        .expect("build request"),
+                )
+                .await
+                .expect("serve timeseries route");
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("read response body");
+            (status, body)
+        }
+    };
+
+    let (status, iso_body) = fetch("/api/stats/timeseries?range=1h&bucket=15m&timeZone=UTC").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, epoch_body) =
+        fetch("/api/stats/timeseries?range=1h&bucket=15m&timeZone=UTC&ts=epoch_ms").await;
+    assert_eq!(status, StatusCode::OK);
+    let iso: Value = serde_json::from_slice(&iso_body).expect("decode iso body");
+    let epoch: Value = serde_json::from_slice(&epoch_body).expect("decode epoch body");
+
+    let iso_millis = |value: &Value| {
+        DateTime::parse_from_rfc3339(value.as_str().expect("iso timestamp string"))
+            .expect("parse iso timestamp")
+            .timestamp_millis()
+    };
+    assert_eq!(epoch["rangeStart"], json!(iso_millis(&iso["rangeStart"])));
+    assert_eq!(epoch["rangeEnd"], json!(iso_millis(&iso["rangeEnd"])));
+    let iso_points = iso["points"].as_array().expect("iso points");
+    let epoch_points = epoch["points"].as_array().expect("epoch points");
+    assert!(!epoch_points.is_empty());
+    assert_eq!(iso_points.len(), epoch_points.len());
+    for (iso_point, epoch_point) in iso_points.iter().zip(epoch_points) {
+        assert_eq!(
+            epoch_point["bucketStart"],
+            json!(iso_millis(&iso_point["bucketStart"]))
+        );
+        assert_eq!(
+            epoch_point["bucketEnd"],
+            json!(iso_millis(&iso_point["bucketEnd"]))
+        );
+    }
+
+    let (status, _) = fetch("/api/stats/timeseries?range=1h&ts=unix").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn timeseries_svg_route_renders_request_count_chart() {
+    use tower::ServiceExt;
+
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let occurred_at = format_naive(
+        (Utc::now() - ChronoDuration::minutes(10))
+            .with_timezone(&Shanghai)
+            .naive_local(),
+    );
+    insert_timeseries_invocation(&state.pool, "svg-a", &occurred_at, "success", None).await;
+    let app = build_app_router(state);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/stats/timeseries.svg?range=1h&bucket=15m&timeZone=UTC")
+                .body(Body::empty())
+                .expect("build request"),
+        )
+        .await
+        .expect("serve timeseries svg route");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE),
+        Some(&header::HeaderValue::from_static("image/svg+xml"))
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    let svg = String::from_utf8(body.to_vec()).expect("utf8 svg");
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains("Requests (1h)"));
+    // One filled bar for the single bucket that has a request.
+    assert_eq!(svg.matches("fill=\"#3B82F6\"").count(), 1, "{svg}");
+}
+
+#[tokio::test]
+async fn sse_stream_rejects_new_clients_once_max_sse_clients_is_reached() {
+    let mut config = test_config();
+    config.max_sse_clients = Some(1);
+    let state = test_state_from_config(config, true).await;
+    let query = || {
+        Query(SubscriptionStreamQuery {
+            topics: None,
+            resume: None,
+            types: None,
+            attempt: None,
+            reason: None,
+        })
+    };
+
+    let existing = state.sse_connections.register();
+    let response = sse_stream(State(state.clone()), query())
+        .await
+        .expect("capped sse stream should respond");
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(state.sse_connections.len(), 1);
+
+    drop(existing);
+    assert_eq!(state.sse_connections.len(), 0);
+}
+
+#[test]
+fn stats_response_averages_per_request_and_guards_zero_count() {
+    let empty = StatsTotals::default().into_response();
+    assert_eq!(empty.avg_cost_per_request, 0.0);
+    assert_eq!(empty.avg_tokens_per_request, 0.0);
+    let payload = serde_json::to_value(&empty).expect("serialize empty stats");
+    assert_eq!(payload["avgCostPerRequest"], json!(0.0));
+    assert_eq!(payload["avgTokensPerRequest"], json!(0.0));
+
+    let stats = StatsTotals {
+        total_count: 4,
+        success_count: 3,
+        failure_count: 1,
+        total_cost: 1.0,
+        total_tokens: 10_000,
+        non_success_cost: 0.0,
+    }
+    .into_response();
+    assert_f64_close(stats.avg_cost_per_request, 0.25);
+    assert_f64_close(stats.avg_tokens_per_request, 2_500.0);
+
+    let mut payload = serde_json::to_value(&stats).expect("serialize stats");
+    scale_token_fields(&mut payload, TokenUnits::Thousands);
+    assert_eq!(payload["avgTokensPerRequest"], json!(2.5));
+    assert_eq!(payload["avgCostPerRequest"], json!(0.25));
+}
+
+#[test]
+fn materialized_archive_fallback_recomputes_stats_averages() {
+    let mut stats = StatsTotals {
+        total_count: 2,
+        success_count: 2,
+        failure_count: 0,
+        total_cost: 0.5,
+        total_tokens: 100,
+        non_success_cost: 0.0,
+    }
+    .into_response();
+    dashboard_activity_apply_materialized_archive_fallback_to_stats(
+        &mut stats,
+        StatsTotals {
+            total_count: 2,
+            success_count: 1,
+            failure_count: 1,
+            total_cost: 1.5,
+            total_tokens: 300,
+            non_success_cost: 0.5,
+        },
+    );
+    assert_eq!(stats.total_count, 4);
+    assert_f64_close(stats.total_cost, 2.0);
+    assert_eq!(stats.total_tokens, 400);
+    assert_f64_close(stats.avg_cost_per_request, 0.5);
+    assert_f64_close(stats.avg_tokens_per_request, 100.0);
+}
+
+#[test]
+fn scale_token_fields_divides_nested_token_counts_only() {
+    let mut value = json!({
+        "totalCount": 3,
+        "totalTokens": 2_500_000,
+        "totalCost": 1.25,
+        "usageBreakdown": { "outputTokens": 1_500, "models": [{ "cacheReadTokens": 500 }] },
+        "points": [{ "totalTokens": 1_000, "cacheInputTokens": 0 }],
+    });
+    let raw = value.clone();
+    scale_token_fields(&mut value, TokenUnits::Raw);
+    assert_eq!(value, raw);
+
+    scale_token_fields(&mut value, TokenUnits::Thousands);
+    assert_eq!(
+        value,
+        json!({
+            "totalCount": 3,
+            "totalTokens": 2_500.0,
+            "totalCost": 1.25,
+            "usageBreakdown": { "outputTokens": 1.5, "models": [{ "cacheReadTokens": 0.5 }] },
+            "points": [{ "totalTokens": 1.0, "cacheInputTokens": 0.0 }],
+        })
+    );
+
+    let mut value = raw;
+    scale_token_fields(&mut value, TokenUnits::Millions);
+    assert_eq!(value["totalTokens"], json!(2.5));
+}
+
+#[tokio::test]
+async fn stats_routes_units_query_scales_tokens_and_rejects_unknown_units() {
+    use tower::ServiceExt;
+
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let app = build_app_router(state);
+    let fetch = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .uri(uri)
+                        .body(Body::empty())
+                        .expect("build request"),
+                )
+                .await
+                .expect("serve stats route");
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("read response body");
+            (status, body)
+        }
+    };
+
+    for uri in [
+        "/api/stats?units=k",
+        "/api/stats/summary?window=all&units=m",
+        "/api/stats/timeseries?range=1h&bucket=15m&timeZone=UTC&units=k",
+    ] {
+        let (status, body) = fetch(uri).await;
+        assert_eq!(status, StatusCode::OK, "{uri}");
+        let value: Value = serde_json::from_slice(&body).expect("decode scaled body");
+        let total_tokens = value
+            .get("totalTokens")
+            .or_else(|| {
+                value["points"]
+                    .get(0)
+                    .and_then(|point| point.get("totalTokens"))
+            })
+            .expect("totalTokens field");
+        assert!(total_tokens.is_f64(), "{uri}: {total_tokens}");
+    }
+
+    let (status, body) = fetch("/api/stats/summary?window=all").await;
+    assert_eq!(status, StatusCode::OK);
+    let value: Value = serde_json::from_slice(&body).expect("decode raw body");
+    assert!(value["totalTokens"].is_i64());
+
+    for uri in [
+        "/api/stats?units=g",
+        "/api/stats/summary?units=g",
+        "/api/stats/timeseries?range=1h&units=g",
+    ] {
+        let (status, _) = fetch(uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn invocation_by_id_route_returns_record_or_not_found() {
+    use tower::ServiceExt;
+
+    let state = test_state_with_openai_base(
+        Url::parse("https://api.openai.com/").expect("valid upstream base url"),
+    )
+    .await;
+    let id = sqlx::query(
+        r#"
+        INSERT INTO codex_invocations (invoke_id, occurred_at, source, status, total_tokens, raw_response)
+        VALUES ('invocation-by-id', '2026-06-22 12:05:00', ?1, 'success', 42, '{}')
+        "#,
+    )
+    .bind(SOURCE_PROXY)
+    .execute(&state.pool)
+    .await
+    .expect("insert invocation")
+    .last_insert_rowid();
+
+    let app = build_app_router(state);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/invocations/{id}"))
+                .body(Body::empty())
+                .expect("build request"),
+        )
+        .await
+        .expect("serve invocation by id route");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    let payload: Value = serde_json::from_slice(&body).expect("decode invocation payload");
+    assert_eq!(payload["id"].as_i64(), Some(id));
+    assert_eq!(payload["invokeId"].as_str(), Some("invocation-by-id"));
+    assert_eq!(payload["totalTokens"].as_i64(), Some(42));
+
+    let missing = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/invocations/{}", id + 1_000))
+                .body(Body::empty())
+                .expect("build missing request"),
+        )
+        .await
+        .expect("serve missing invocation route");
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    let body = axum::body::to_bytes(missing.into_body(), usize::MAX)
+        .await
+        .expect("read missing body");
+    let payload: Value = serde_json::from_slice(&body).expect("decode missing payload");
+    assert_eq!(payload["code"].as_str(), Some("invocation_not_found"));
+
+    let summary = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/invocations/summary")
+                .body(Body::empty())
+                .expect("build summary request"),
+        )
+        .await
+        .expect("serve invocation summary route");
+    assert_eq!(summary.status(), StatusCode::OK);
+}
+
 pub(crate) fn write_backfill_response_payload_with_terminal_service_tier(
     path: &Path,
     initial_service_tier: Option<&str>,
@@ -849,6 +1830,11 @@ pub(crate) async fn test_state_from_config_with_pool_no_available_wait(
         dashboard_activity_live_broadcast_seq: Arc::new(AtomicU64::new(0)),
         dashboard_activity_live_broadcast_running: Arc::new(AtomicBool::new(false)),
         process_started_at_utc: chrono::Utc::now(),
+        clock: Arc::new(SystemClock),
+        archive_db_attached: false,
+        sqlite_json1_available: true,
+        cost_spike_tracker: Arc::new(CostSpikeTracker::default()),
+        log_buffer: Arc::new(LogRingBuffer::default()),
         dashboard_network_speed_cache: Arc::new(
             crate::dashboard_network_speed::DashboardNetworkSpeedCache::new(chrono::Utc::now()),
         ),
@@ -877,6 +1863,8 @@ pub(crate) async fn test_state_from_config_with_pool_no_available_wait(
         )),
         maintenance_stats_cache: Arc::new(Mutex::new(StatsMaintenanceCacheState::default())),
         system_status_cache: Arc::new(Mutex::new(SystemStatusCacheState::default())),
+        timeseries_response_cache: Arc::new(TimeseriesResponseCache::default()),
+        sse_connections: Arc::new(SseConnectionRegistry::default()),
         hourly_rollup_sync_lock: Arc::new(Mutex::new(())),
         pool_routing_reservations: Arc::new(std::sync::Mutex::new(HashMap::new())),
         pool_routing_runtime_cache: Arc::new(Mutex::new(None)),
@@ -916,6 +1904,11 @@ pub(crate) fn clone_state_with_upstream_accounts(
             .dashboard_activity_live_broadcast_running
             .clone(),
         process_started_at_utc: state.process_started_at_utc,
+        clock: state.clock.clone(),
+        archive_db_attached: state.archive_db_attached,
+        sqlite_json1_available: state.sqlite_json1_available,
+        cost_spike_tracker: state.cost_spike_tracker.clone(),
+        log_buffer: state.log_buffer.clone(),
         dashboard_network_speed_cache: state.dashboard_network_speed_cache.clone(),
         startup_ready: state.startup_ready.clone(),
         shutdown: state.shutdown.clone(),
@@ -936,6 +1929,8 @@ pub(crate) fn clone_state_with_upstream_accounts(
         dashboard_activity_snapshot_cache: state.dashboard_activity_snapshot_cache.clone(),
         maintenance_stats_cache: state.maintenance_stats_cache.clone(),
         system_status_cache: state.system_status_cache.clone(),
+        timeseries_response_cache: state.timeseries_response_cache.clone(),
+        sse_connections: state.sse_connections.clone(),
         pool_routing_reservations: state.pool_routing_reservations.clone(),
         pool_routing_runtime_cache: state.pool_routing_runtime_cache.clone(),
         pool_live_attempt_ids: state.pool_live_attempt_ids.clone(),
@@ -969,6 +1964,11 @@ pub(crate) fn clone_state_with_pool_group_429_retry_delay_override(
             .dashboard_activity_live_broadcast_running
             .clone(),
         process_started_at_utc: state.process_started_at_utc,
+        clock: state.clock.clone(),
+        archive_db_attached: state.archive_db_attached,
+        sqlite_json1_available: state.sqlite_json1_available,
+        cost_spike_tracker: state.cost_spike_tracker.clone(),
+        log_buffer: state.log_buffer.clone(),
         dashboard_network_speed_cache: state.dashboard_network_speed_cache.clone(),
         startup_ready: state.startup_ready.clone(),
         shutdown: state.shutdown.clone(),
@@ -989,6 +1989,8 @@ pub(crate) fn clone_state_with_pool_group_429_retry_delay_override(
         dashboard_activity_snapshot_cache: state.dashboard_activity_snapshot_cache.clone(),
         maintenance_stats_cache: state.maintenance_stats_cache.clone(),
         system_status_cache: state.system_status_cache.clone(),
+        timeseries_response_cache: state.timeseries_response_cache.clone(),
+        sse_connections: state.sse_connections.clone(),
         pool_routing_reservations: state.pool_routing_reservations.clone(),
         pool_routing_runtime_cache: state.pool_routing_runtime_cache.clone(),
         pool_live_attempt_ids: state.pool_live_attempt_ids.clone(),
@@ -998,6 +2000,66 @@ pub(crate) fn clone_state_with_pool_group_429_retry_delay_override(
     })
 }
 
+pub(crate) fn clone_state_with_clock(
+    state: &Arc<AppState>,
+    clock: Arc<dyn Clock>,
+) -> Arc<AppState> {
+    Arc::new(AppState {
+        config: state.config.clone(),
+        sqlite_batch_writer: state.sqlite_batch_writer.clone(),
+        pool_account_selection_runtime: state.pool_account_selection_runtime.clone(),
+        proxy_runtime_invocations: state.proxy_runtime_invocations.clone(),
+        pool: state.pool.clone(),
+        oauth_installation_seed: state.oauth_installation_seed,
+        hourly_rollup_sync_lock: state.hourly_rollup_sync_lock.clone(),
+        http_clients: state.http_clients.clone(),
+        broadcaster: state.broadcaster.clone(),
+        subscription_hub: state.subscription_hub.clone(),
+        broadcast_state_cache: state.broadcast_state_cache.clone(),
+        proxy_summary_quota_broadcast_seq: state.proxy_summary_quota_broadcast_seq.clone(),
+        proxy_summary_quota_broadcast_running: state.proxy_summary_quota_broadcast_running.clone(),
+        proxy_summary_quota_broadcast_handle: state.proxy_summary_quota_broadcast_handle.clone(),
+        dashboard_activity_live_broadcast_seq: state.dashboard_activity_live_broadcast_seq.clone(),
+        dashboard_activity_live_broadcast_running: state
+            .dashboard_activity_live_broadcast_running
+            .clone(),
+        process_started_at_utc: state.process_started_at_utc,
+        clock,
+        archive_db_attached: state.archive_db_attached,
+        sqlite_json1_available: state.sqlite_json1_available,
+        cost_spike_tracker: state.cost_spike_tracker.clone(),
+        log_buffer: state.log_buffer.clone(),
+        dashboard_network_speed_cache: state.dashboard_network_speed_cache.clone(),
+        startup_ready: state.startup_ready.clone(),
+        shutdown: state.shutdown.clone(),
+        semaphore: state.semaphore.clone(),
+        proxy_request_in_flight: state.proxy_request_in_flight.clone(),
+        proxy_raw_async_semaphore: state.proxy_raw_async_semaphore.clone(),
+        proxy_model_settings: state.proxy_model_settings.clone(),
+        proxy_model_settings_update_lock: state.proxy_model_settings_update_lock.clone(),
+        forward_proxy: state.forward_proxy.clone(),
+        xray_supervisor: state.xray_supervisor.clone(),
+        forward_proxy_settings_update_lock: state.forward_proxy_settings_update_lock.clone(),
+        forward_proxy_subscription_refresh_lock: state
+            .forward_proxy_subscription_refresh_lock
+            .clone(),
+        pricing_settings_update_lock: state.pricing_settings_update_lock.clone(),
+        pricing_catalog: state.pricing_catalog.clone(),
+        prompt_cache_conversation_cache: state.prompt_cache_conversation_cache.clone(),
+        dashboard_activity_snapshot_cache: state.dashboard_activity_snapshot_cache.clone(),
+        maintenance_stats_cache: state.maintenance_stats_cache.clone(),
+        system_status_cache: state.system_status_cache.clone(),
+        timeseries_response_cache: state.timeseries_response_cache.clone(),
+        sse_connections: state.sse_connections.clone(),
+        pool_routing_reservations: state.pool_routing_reservations.clone(),
+        pool_routing_runtime_cache: state.pool_routing_runtime_cache.clone(),
+        pool_live_attempt_ids: state.pool_live_attempt_ids.clone(),
+        pool_group_429_retry_delay_override: state.pool_group_429_retry_delay_override,
+        pool_no_available_wait: state.pool_no_available_wait,
+        upstream_accounts: state.upstream_accounts.clone(),
+    })
+}
+
 pub(crate) async fn test_state_from_existing_pool(
     pool: SqlitePool,
     config: AppConfig,
@@ -1031,6 +2093,11 @@ pub(crate) async fn test_state_from_existing_pool(
         dashboard_activity_live_broadcast_seq: Arc::new(AtomicU64::new(0)),
         dashboard_activity_live_broadcast_running: Arc::new(AtomicBool::new(false)),
         process_started_at_utc: chrono::Utc::now(),
+        clock: Arc::new(SystemClock),
+        archive_db_attached: false,
+        sqlite_json1_available: true,
+        cost_spike_tracker: Arc::new(CostSpikeTracker::default()),
+        log_buffer: Arc::new(LogRingBuffer::default()),
         dashboard_network_speed_cache: Arc::new(
             crate::dashboard_network_speed::DashboardNetworkSpeedCache::new(chrono::Utc::now()),
         ),
@@ -1061,6 +2128,8 @@ pub(crate) async fn test_state_from_existing_pool(
         )),
         maintenance_stats_cache: Arc::new(Mutex::new(StatsMaintenanceCacheState::default())),
         system_status_cache: Arc::new(Mutex::new(SystemStatusCacheState::default())),
+        timeseries_response_cache: Arc::new(TimeseriesResponseCache::default()),
+        sse_connections: Arc::new(SseConnectionRegistry::default()),
         hourly_rollup_sync_lock: Arc::new(Mutex::new(())),
         pool_routing_reservations: Arc::new(std::sync::Mutex::new(HashMap::new())),
         pool_routing_runtime_cache: Arc::new(Mutex::new(None)),
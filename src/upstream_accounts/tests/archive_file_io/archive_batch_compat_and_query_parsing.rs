@@ -3421,6 +3421,9 @@ pub(crate) fn usage_snapshot_test_config(base_url: &str, user_agent: &str) -> Ap
         max_parallel_polls: 2,
         shared_connection_parallelism: 1,
         http_bind: "127.0.0.1:0".parse().expect("valid socket address"),
+        tls_cert_path: None,
+        tls_key_path: None,
+        api_prefix: DEFAULT_API_PREFIX.to_string(),
         cors_allowed_origins: Vec::new(),
         list_limit_max: 100,
         user_agent: user_agent.to_string(),
@@ -3432,6 +3435,17 @@ pub(crate) fn usage_snapshot_test_config(base_url: &str, user_agent: &str) -> Ap
         retention_batch_rows: DEFAULT_RETENTION_BATCH_ROWS,
         retention_catchup_budget: Duration::from_secs(DEFAULT_RETENTION_CATCHUP_BUDGET_SECS),
         archive_dir: PathBuf::from("target/archive-tests"),
+        archive_db_path: None,
+        sqlite_synchronous: DEFAULT_SQLITE_SYNCHRONOUS,
+        sqlite_cache_size_kb: None,
+        max_timeseries_points: DEFAULT_MAX_TIMESERIES_POINTS,
+        error_top_max: DEFAULT_ERROR_TOP_MAX,
+        other_errors_limit_max: DEFAULT_OTHER_ERRORS_LIMIT_MAX,
+        max_sse_clients: None,
+        record_log_path: None,
+        record_log_max_bytes: DEFAULT_RECORD_LOG_MAX_BYTES,
+        log_buffer_lines: DEFAULT_LOG_BUFFER_LINES,
+        shutdown_timeout: Duration::from_secs(DEFAULT_SHUTDOWN_TIMEOUT_SECS),
         codex_invocation_archive_layout: DEFAULT_CODEX_INVOCATION_ARCHIVE_LAYOUT,
         codex_invocation_archive_segment_granularity:
             DEFAULT_CODEX_INVOCATION_ARCHIVE_SEGMENT_GRANULARITY,
@@ -3439,12 +3453,21 @@ pub(crate) fn usage_snapshot_test_config(base_url: &str, user_agent: &str) -> Ap
         invocation_success_full_days: DEFAULT_INVOCATION_SUCCESS_FULL_DAYS,
         invocation_max_days: DEFAULT_INVOCATION_MAX_DAYS,
         invocation_archive_ttl_days: DEFAULT_INVOCATION_ARCHIVE_TTL_DAYS,
+        raw_response_retention_days: None,
         forward_proxy_attempts_retention_days: DEFAULT_FORWARD_PROXY_ATTEMPTS_RETENTION_DAYS,
         pool_upstream_request_attempts_retention_days:
             DEFAULT_POOL_UPSTREAM_REQUEST_ATTEMPTS_RETENTION_DAYS,
         pool_upstream_request_attempts_archive_ttl_days:
             DEFAULT_POOL_UPSTREAM_REQUEST_ATTEMPTS_ARCHIVE_TTL_DAYS,
         quota_snapshot_full_days: DEFAULT_QUOTA_SNAPSHOT_FULL_DAYS,
+        cost_spike_factor: DEFAULT_COST_SPIKE_FACTOR,
+        cache_discount: DEFAULT_CACHE_DISCOUNT,
+        monthly_budget: None,
+        model_aliases: BTreeMap::new(),
+        model_allowlist: None,
+        cost_precision: DEFAULT_COST_PRECISION,
+        upstream_danger_accept_invalid_certs: DEFAULT_UPSTREAM_DANGER_ACCEPT_INVALID_CERTS,
+        treat_empty_error_as_success: DEFAULT_TREAT_EMPTY_ERROR_AS_SUCCESS,
         upstream_accounts_oauth_client_id: DEFAULT_UPSTREAM_ACCOUNTS_OAUTH_CLIENT_ID.to_string(),
         upstream_accounts_oauth_issuer: Url::parse(DEFAULT_UPSTREAM_ACCOUNTS_OAUTH_ISSUER)
             .expect("valid oauth issuer"),
@@ -4139,6 +4162,11 @@ pub(crate) async fn test_app_state_with_config_and_parallelism(
         dashboard_activity_live_broadcast_seq: Arc::new(AtomicU64::new(0)),
         dashboard_activity_live_broadcast_running: Arc::new(AtomicBool::new(false)),
         process_started_at_utc: chrono::Utc::now(),
+        clock: Arc::new(SystemClock),
+        archive_db_attached: false,
+        sqlite_json1_available: true,
+        cost_spike_tracker: Arc::new(CostSpikeTracker::default()),
+        log_buffer: Arc::new(LogRingBuffer::default()),
         dashboard_network_speed_cache: Arc::new(
             crate::dashboard_network_speed::DashboardNetworkSpeedCache::new(chrono::Utc::now()),
         ),
@@ -4171,6 +4199,8 @@ pub(crate) async fn test_app_state_with_config_and_parallelism(
         )),
         maintenance_stats_cache: Arc::new(Mutex::new(StatsMaintenanceCacheState::default())),
         system_status_cache: Arc::new(Mutex::new(SystemStatusCacheState::default())),
+        timeseries_response_cache: Arc::new(TimeseriesResponseCache::default()),
+        sse_connections: Arc::new(SseConnectionRegistry::default()),
         pool_routing_reservations: Arc::new(std::sync::Mutex::new(HashMap::new())),
         pool_routing_runtime_cache: Arc::new(Mutex::new(None)),
         pool_live_attempt_ids: Arc::new(std::sync::Mutex::new(HashSet::new())),
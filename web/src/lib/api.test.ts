@@ -1,5 +1,6 @@
 import { afterEach, describe, expect, it, vi } from "vitest";
 import {
+  applyApiPrefix,
   bulkUpdatePromptCacheConversationBindings,
   createForwardProxyNodesLatencyTestEventSource,
   createOauthMailboxSession,
@@ -2975,3 +2976,15 @@ describe("account pool frontend API helpers", () => {
     expect(fetchMock).toHaveBeenCalledTimes(1);
   });
 });
+
+describe("applyApiPrefix", () => {
+  it("rewrites /api paths onto a server-injected prefix", () => {
+    expect(applyApiPrefix("/api/invocations?limit=20", "/v1/api")).toBe(
+      "/v1/api/invocations?limit=20",
+    );
+    expect(applyApiPrefix("/api", "/v1/api")).toBe("/v1/api");
+    expect(applyApiPrefix("/apiary", "/v1/api")).toBe("/apiary");
+    expect(applyApiPrefix("/events", "/v1/api")).toBe("/events");
+    expect(applyApiPrefix("/api/settings", "/api")).toBe("/api/settings");
+  });
+});
@@ -34,7 +34,26 @@ type ZonedDateParts = {
   weekday: number;
 };
 
-export const withBase = (path: string) => `${API_BASE}${path}`;
+declare global {
+  interface Window {
+    /** Set by the server in index.html when `API_PREFIX` is not `/api`. */
+    __CVM_API_PREFIX__?: string;
+  }
+}
+
+const DEFAULT_API_PREFIX = "/api";
+const API_PREFIX =
+  (typeof window !== "undefined" ? window.__CVM_API_PREFIX__ : undefined)?.replace(/\/+$/, "") ||
+  DEFAULT_API_PREFIX;
+
+export function applyApiPrefix(path: string, prefix: string = API_PREFIX) {
+  if (prefix === DEFAULT_API_PREFIX || !path.startsWith(DEFAULT_API_PREFIX)) return path;
+  const rest = path.slice(DEFAULT_API_PREFIX.length);
+  if (rest && !rest.startsWith("/") && !rest.startsWith("?")) return path;
+  return `${prefix}${rest}`;
+}
+
+export const withBase = (path: string) => `${API_BASE}${applyApiPrefix(path)}`;
 
 export class ApiRequestError extends Error {
   readonly status: number;
@@ -1118,8 +1137,14 @@ export interface ParallelWorkStatsResponse {
   dayAll: ParallelWorkWindowResponse;
 }
 
+export interface ErrorCategory {
+  code: string;
+  label: string;
+}
+
 export interface ErrorDistributionItem {
   reason: string;
+  category?: ErrorCategory;
   count: number;
 }
 
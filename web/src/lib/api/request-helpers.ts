@@ -1,4 +1,5 @@
 export {
+  applyApiPrefix,
   createBulkUpstreamAccountSyncJobEventSource,
   createEventSource,
   createImportedOauthValidationJobEventSource,
@@ -55,6 +55,7 @@ export type {
   EffectiveRoutingRule,
   EffectiveRoutingRuleSource,
   EffectiveRoutingTimeoutFieldSources,
+  ErrorCategory,
   ErrorDistributionItem,
   ErrorDistributionResponse,
   ExternalApiKeyListResponse,